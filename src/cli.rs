@@ -4,15 +4,19 @@ use clap::CommandFactory;
 
 use clap::{Parser, Subcommand};
 use clap_complete::engine::{ArgValueCompleter, CompletionCandidate};
-use std::collections::HashSet;
 use std::ffi::OsStr;
-use std::path::PathBuf;
 
 use crate::integrations::git::{GitClient, RealGitClient};
 
 /// Git worktree management tool
 #[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
+#[command(
+    author,
+    version,
+    about,
+    long_about = None,
+    after_long_help = crate::report::vocabulary_help()
+)]
 pub struct Cli {
     /// When to use colored output
     #[arg(long, value_name = "WHEN", global = true, ignore_case = true)]
@@ -22,56 +26,336 @@ pub struct Cli {
     #[arg(long, short = 'v', global = true)]
     pub verbose: bool,
 
+    /// Suppress informational output (warnings and errors are still shown)
+    #[arg(long, short = 'q', global = true)]
+    pub quiet: bool,
+
+    /// Operate on the repository at PATH instead of the one containing the
+    /// current directory
+    ///
+    /// Only the repository changes; the working directory stays, so relative
+    /// paths given to commands still resolve from it. Combined with `-C`, a
+    /// relative PATH is resolved after changing directory.
+    #[arg(long, value_name = "PATH", global = true)]
+    pub repo: Option<std::path::PathBuf>,
+
+    /// Run as if ofsht was started in PATH (like `git -C`)
+    ///
+    /// Applied before anything else, including a relative `--repo`.
+    #[arg(short = 'C', value_name = "PATH", global = true)]
+    pub directory: Option<std::path::PathBuf>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
 
+// `--help` / man page examples for the subcommands below
+const ADD_EXAMPLES: &str = "\
+Examples:
+  ofsht add feature-login              # new branch from HEAD, then cd into it
+  ofsht add hotfix origin/main         # new branch from a start point
+  ofsht add '#123'                     # branch of PR #123 (or a branch for issue #123)
+  ofsht add --detach v1.2.0            # detached worktree at a tag
+  ofsht add --no-branch 3f2a9c1        # detached worktree at a commit (e.g. to bisect)
+  ofsht add try-it --merge main feature-x  # new branch at main with feature-x merged in
+  ofsht add feature-login --tmux       # open it in a new tmux window";
+
+const SWITCH_EXAMPLES: &str = "\
+Examples:
+  ofsht switch feature-login           # cd into its worktree, adding it if missing
+  ofsht switch hotfix origin/main      # start point is only used when creating";
+
+const CREATE_EXAMPLES: &str = "\
+Examples:
+  ofsht create feature-login           # create, stay in the current directory
+  ofsht create feature-login --porcelain
+  echo bugfix-1 | ofsht create         # branch name from stdin";
+
+const LS_EXAMPLES: &str = "\
+Examples:
+  ofsht ls                             # table on a terminal, names when piped
+  ofsht ls --show-path --sort time
+  ofsht ls --stale 30                  # worktrees without commits for 30+ days
+  ofsht ls -0 | xargs -0 -I{} ofsht exec {} -- git status -s";
+
+const RM_EXAMPLES: &str = "\
+Examples:
+  ofsht rm feature-login               # remove the worktree and its branch
+  ofsht rm .                           # remove the current worktree
+  ofsht rm feature-login --keep-branch
+  ofsht rm a b c --keep-going          # remove what can be removed, then report
+  ofsht rm                             # pick worktrees with fzf";
+
+const CD_EXAMPLES: &str = "\
+Examples:
+  ofsht cd feature-login               # exact branch or relative path
+  ofsht cd login                       # unique substring
+  ofsht cd @                           # main worktree
+  ofsht cd                             # pick with fzf";
+
+const SYNC_EXAMPLES: &str = "\
+Examples:
+  ofsht sync                           # re-run every create hook action
+  ofsht sync --copy --link             # only refresh copied and linked files";
+
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// Create a new worktree with a branch
+    #[command(after_long_help = ADD_EXAMPLES)]
     Add {
         /// Branch name for the new worktree (read from stdin when omitted and stdin is piped)
+        #[arg(add = ArgValueCompleter::new(list_addable_branches))]
         branch: Option<String>,
+        /// Create the worktree from a pull request: #123, 123 or its GitHub URL
+        #[arg(long, value_name = "PR", conflicts_with_all = ["branch", "detach"])]
+        from_pr: Option<String>,
         /// Start point (branch, tag, or commit) for the new branch.
         /// Defaults to HEAD if not specified.
         #[arg(add = ArgValueCompleter::new(list_git_refs))]
         start_point: Option<String>,
+        /// Create a detached worktree at the given commit-ish (commit, tag, branch
+        /// or stash) instead of a new branch. With a single argument, the
+        /// commit-ish also names the worktree directory.
+        #[arg(long, visible_alias = "no-branch")]
+        detach: bool,
         /// Create a new tmux window for the worktree
         #[arg(long, conflicts_with = "no_tmux")]
         tmux: bool,
         /// Skip tmux window creation (overrides config behavior)
         #[arg(long, conflicts_with = "tmux")]
         no_tmux: bool,
+        /// Print `path=`, `branch=`, `created=` and `start_point=` lines to
+        /// stdout instead of the bare path (also when tmux is used)
+        #[arg(long)]
+        porcelain: bool,
+        /// Skip create hooks (the worktree is still added, zoxide and tmux still run)
+        #[arg(long)]
+        no_hooks: bool,
+        /// Also copy files matching PATTERN from the main repository (repeatable;
+        /// runs even with --no-hooks)
+        #[arg(long = "copy", value_name = "PATTERN")]
+        copy: Vec<String>,
+        /// Also symlink files matching PATTERN from the main repository
+        /// (repeatable; runs even with --no-hooks)
+        #[arg(long = "link", value_name = "PATTERN")]
+        link: Vec<String>,
+        /// Drop the configured copy/link action with exactly this PATTERN (repeatable)
+        #[arg(long, value_name = "PATTERN")]
+        skip_pattern: Vec<String>,
+        /// Move uncommitted changes (including untracked files) of the current
+        /// worktree into the new one. Exits with status 3 if they conflict
+        #[arg(long)]
+        carry_changes: bool,
+        /// Start the new branch at the first REF and merge the others into the
+        /// worktree (`--merge main feature-x`). Exits with status 4 if a merge
+        /// conflicts, leaving the worktree conflicted
+        #[arg(
+            long,
+            value_name = "REF",
+            num_args = 2..,
+            conflicts_with_all = ["start_point", "detach", "from_pr", "carry_changes"],
+            add = ArgValueCompleter::new(list_git_refs)
+        )]
+        merge: Vec<String>,
+        /// With --merge, run create hooks even when a merge conflicts
+        #[arg(long, requires = "merge")]
+        hooks_on_conflict: bool,
+    },
+    /// Go to the worktree of a branch, creating it first if it does not exist
+    #[command(after_long_help = SWITCH_EXAMPLES)]
+    Switch {
+        /// Branch whose worktree to go to (created when missing)
+        branch: String,
+        /// Start point for the new branch when the worktree has to be created
+        #[arg(add = ArgValueCompleter::new(list_git_refs))]
+        start_point: Option<String>,
     },
     /// Create a new worktree without navigation
+    #[command(after_long_help = CREATE_EXAMPLES)]
     Create {
         /// Branch name for the new worktree (read from stdin when omitted and stdin is piped)
+        #[arg(add = ArgValueCompleter::new(list_addable_branches))]
         branch: Option<String>,
         /// Start point (branch, tag, or commit) for the new branch.
         /// Defaults to HEAD if not specified.
         #[arg(add = ArgValueCompleter::new(list_git_refs))]
         start_point: Option<String>,
+        /// Create a detached worktree at the given ref instead of a new branch.
+        /// With a single argument, the ref also names the worktree directory.
+        #[arg(long)]
+        detach: bool,
+        /// Create a new tmux window for the worktree
+        #[arg(long, conflicts_with = "no_tmux")]
+        tmux: bool,
+        /// Skip tmux window creation (overrides config behavior)
+        #[arg(long, conflicts_with = "tmux")]
+        no_tmux: bool,
+        /// Print `path=`, `branch=`, `created=` and `start_point=` lines to stdout
+        #[arg(long)]
+        porcelain: bool,
+        /// Skip create hooks (the worktree is still created, zoxide and tmux still run)
+        #[arg(long)]
+        no_hooks: bool,
+        /// Also copy files matching PATTERN from the main repository (repeatable;
+        /// runs even with --no-hooks)
+        #[arg(long = "copy", value_name = "PATTERN")]
+        copy: Vec<String>,
+        /// Also symlink files matching PATTERN from the main repository
+        /// (repeatable; runs even with --no-hooks)
+        #[arg(long = "link", value_name = "PATTERN")]
+        link: Vec<String>,
+        /// Drop the configured copy/link action with exactly this PATTERN (repeatable)
+        #[arg(long, value_name = "PATTERN")]
+        skip_pattern: Vec<String>,
     },
     /// List all worktrees
+    #[command(after_long_help = LS_EXAMPLES)]
     Ls {
         /// Show worktree paths
         #[arg(long)]
         show_path: bool,
+        /// Sort worktrees (the main worktree always stays first) [default: branch]
+        #[arg(long, value_enum, value_name = "KEY")]
+        sort: Option<crate::commands::list_display::SortKey>,
+        /// Never truncate columns to fit the terminal width
+        #[arg(long)]
+        no_truncate: bool,
+        /// Print one NUL-terminated record per worktree to stdout (for `xargs -0`),
+        /// even on a terminal. Each record is the name accepted by `cd`/`rm`
+        /// (`@`, the branch, or the path when detached); with --show-path it is
+        /// `<path><TAB><name>` instead
+        #[arg(short = '0', long)]
+        print0: bool,
+        /// Mark worktrees added (+), removed (-) or moved (~) since the previous
+        /// `ls --diff` run, then remember the current list (table output only)
+        #[arg(long, conflicts_with = "print0")]
+        diff: bool,
+        /// Only show worktrees whose directory is gone (`git worktree prune` candidates)
+        #[arg(long, conflicts_with = "stale")]
+        prunable: bool,
+        /// Only show worktrees whose last commit is at least DAYS days old
+        #[arg(long, value_name = "DAYS")]
+        stale: Option<u32>,
+        /// Also show worktrees hidden by `worktree.exclude`
+        #[arg(long)]
+        all: bool,
+        /// Show the disk usage of each worktree directory (symlinks are not
+        /// counted; can be slow on large trees)
+        #[arg(long, conflicts_with = "print0")]
+        du: bool,
+        /// Run `git fetch --prune` first and show the upstream column
+        /// (ahead/behind, `gone`); `--fetch=all` fetches every remote
+        #[arg(
+            long,
+            value_enum,
+            value_name = "SCOPE",
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "default",
+            conflicts_with = "print0"
+        )]
+        fetch: Option<crate::commands::list::FetchScope>,
+        /// Render `git worktree list --porcelain` output read from stdin instead
+        /// of running git (for debugging captured listings)
+        #[arg(long, hide = true, conflicts_with_all = ["print0", "diff"])]
+        stdin_porcelain: bool,
     },
     /// Remove a worktree
     /// When no targets are provided, fzf will be used for interactive multi-selection (if enabled)
+    #[command(after_long_help = RM_EXAMPLES)]
     Rm {
         /// Worktree name(s) to remove (optional with fzf)
         #[arg(num_args = 0.., value_name = "TARGET", add = ArgValueCompleter::new(list_git_worktrees))]
         targets: Vec<String>,
+        /// Keep the branch after removing the worktree
+        #[arg(long)]
+        keep_branch: bool,
+        /// Remove worktrees even if they have uncommitted changes or are locked
+        #[arg(short, long)]
+        force: bool,
+        /// Skip the dirty check and delete hooks and batch branch deletion
+        /// (dangerous; meant for automation)
+        #[arg(long)]
+        assume_clean: bool,
+        /// Skip delete hooks
+        #[arg(long)]
+        no_hooks: bool,
+        /// Continue with the remaining targets after a failed removal and
+        /// print a summary
+        #[arg(long)]
+        keep_going: bool,
+        /// Show which worktrees would be removed without removing anything
+        #[arg(long)]
+        dry_run: bool,
+        /// With --dry-run, measure each worktree and show the disk space that
+        /// would be reclaimed, largest first
+        #[arg(long, requires = "dry_run")]
+        stat: bool,
+    },
+    /// Lock a worktree so `git worktree prune` keeps it (e.g. on removable media)
+    Lock {
+        /// Worktree name to lock
+        #[arg(add = ArgValueCompleter::new(list_git_worktrees))]
+        target: String,
+        /// Reason for locking (shown by `git worktree list`)
+        #[arg(long)]
+        reason: Option<String>,
+    },
+    /// Unlock a previously locked worktree
+    Unlock {
+        /// Worktree name to unlock
+        #[arg(add = ArgValueCompleter::new(list_git_worktrees))]
+        target: String,
+    },
+    /// Run a command in one or more worktrees (output goes to stderr)
+    ///
+    /// Example: `ofsht exec --all -- git fetch`
+    Exec {
+        /// Worktrees to run in (branch name, relative path, or @ for main)
+        #[arg(
+            add = ArgValueCompleter::new(list_git_worktrees),
+            required_unless_present = "all",
+            conflicts_with = "all"
+        )]
+        targets: Vec<String>,
+        /// Run in every worktree, including the main one
+        #[arg(long)]
+        all: bool,
+        /// Continue with the remaining worktrees after a failure
+        #[arg(long)]
+        keep_going: bool,
+        /// Command and arguments to run (after `--`)
+        #[arg(last = true, required = true, value_name = "COMMAND")]
+        command: Vec<String>,
+    },
+    /// Open an interactive shell in a worktree (exits with the shell's exit code)
+    ///
+    /// Exports `OFSHT_WORKTREE_PATH` and `OFSHT_BRANCH` to the shell
+    Shell {
+        /// Worktree to enter (branch name, relative path, or @ for main)
+        #[arg(add = ArgValueCompleter::new(list_git_worktrees))]
+        target: String,
+        /// Command to start instead of $SHELL (run through `sh -c`)
+        #[arg(long, value_name = "CMD")]
+        shell: Option<String>,
+        /// Open the worktree in a new tmux window/pane instead
+        #[arg(long)]
+        tmux: bool,
     },
     /// Navigate to a worktree (prints path)
     /// When name is not provided, fzf will be used for interactive selection (if enabled)
+    #[command(after_long_help = CD_EXAMPLES)]
     Cd {
         /// Worktree name to navigate to (optional with fzf)
         #[arg(add = ArgValueCompleter::new(list_git_worktrees))]
         name: Option<String>,
+        /// Check the worktree out again when its directory was deleted
+        /// (otherwise such prunable worktrees are an error)
+        #[arg(long)]
+        restore: bool,
     },
+    /// Report integration availability and the effective configuration
+    Doctor,
     /// Initialize configuration files (creates both global and local configs by default)
     Init {
         /// Generate only global config
@@ -80,19 +364,46 @@ pub enum Commands {
         /// Generate only local config
         #[arg(long, conflicts_with = "global")]
         local: bool,
-        /// Overwrite existing config files
+        /// Overwrite existing config files (the old file is kept as
+        /// `<name>.bak.<timestamp>`)
         #[arg(short, long)]
         force: bool,
+        /// With --force, overwrite without keeping a backup
+        #[arg(long, requires = "force")]
+        no_backup: bool,
+        /// Print the template to stdout instead of writing files
+        /// (global by default, local with --local)
+        #[arg(long, conflicts_with = "force")]
+        stdout: bool,
+        /// Render the template with every integration enabled instead of
+        /// detecting installed tools
+        #[arg(long)]
+        no_detect: bool,
+        /// Print what would be created, overwritten or skipped, then stop
+        #[arg(long, conflicts_with = "stdout")]
+        dry_run: bool,
     },
     /// Generate shell completion script
     Completion {
-        /// Shell type (bash, zsh, fish)
-        shell: String,
+        /// Shell type
+        shell: crate::commands::completion::CompletionShell,
+        /// Write the completion script to this file (parent directories are created)
+        /// instead of printing setup instructions
+        #[arg(
+            long,
+            short,
+            value_name = "PATH",
+            conflicts_with = "check_shell_integration"
+        )]
+        output: Option<std::path::PathBuf>,
+        /// Check that completion and the shell wrapper are set up correctly
+        #[arg(long)]
+        check_shell_integration: bool,
     },
     /// Generate shell integration script
     ShellInit {
-        /// Shell type (bash, zsh, fish)
-        shell: String,
+        /// Shell type (only bash, zsh and fish have an integration script)
+        shell: crate::commands::completion::CompletionShell,
     },
     /// Open all worktrees in tmux windows or panes
     Open {
@@ -103,10 +414,16 @@ pub enum Commands {
         #[arg(long, conflicts_with = "pane")]
         window: bool,
     },
+    /// Inspect the configured hooks
+    Hook {
+        #[command(subcommand)]
+        command: HookCommand,
+    },
     /// Sync hook file operations to existing worktrees
     ///
     /// Re-applies hooks.create (run/copy/link) to all existing non-main worktrees.
     /// When no flags are specified, all actions are executed.
+    #[command(after_long_help = SYNC_EXAMPLES)]
     Sync {
         /// Only execute run commands
         #[arg(long)]
@@ -118,46 +435,238 @@ pub enum Commands {
         #[arg(long)]
         link: bool,
     },
+    /// Print the main repository root (also from inside a worktree)
+    Root {
+        /// Print the directory holding the worktrees instead (where `worktree.dir`
+        /// puts them when none exists yet)
+        #[arg(long)]
+        worktrees: bool,
+    },
+    /// Render man pages (ofsht.1, one page per subcommand, ofsht-config.5)
+    #[command(hide = true)]
+    Man {
+        /// Write every page into this directory instead of printing ofsht.1
+        #[arg(long, value_name = "DIR")]
+        out_dir: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum HookCommand {
+    /// List the effective hook actions in execution order with the config file
+    /// each comes from
+    List {
+        /// Only list hooks for this event
+        #[arg(long, value_enum)]
+        event: Option<crate::commands::hook::HookEvent>,
+        /// Fill `{branch}`, `{worktree}`, `{repo}` and `{repo_root}` in run commands
+        /// as they would be for this branch
+        #[arg(long, value_name = "NAME")]
+        branch: Option<String>,
+        /// Show how many files each copy/link pattern currently matches
+        #[arg(long)]
+        expand: bool,
+        /// Print the list as JSON to stdout
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// The `-C` directory of a command line (`words` excludes the program name)
+///
+/// Completion candidates are computed before clap parses the command line, so
+/// `main` uses this to honor `-C` while completing. Scanning stops at `--`,
+/// which leaves `-C` in the command of `ofsht exec` alone.
+#[must_use]
+pub fn directory_override<I>(words: I) -> Option<std::path::PathBuf>
+where
+    I: IntoIterator,
+    I::Item: Into<std::ffi::OsString>,
+{
+    let mut words = words.into_iter().map(Into::into);
+    let mut directory = None;
+    while let Some(word) = words.next() {
+        if word == "--" {
+            break;
+        }
+        if word == "-C" {
+            directory = words.next().map(std::path::PathBuf::from);
+        } else if let Some(path) = word.to_str().and_then(|w| w.strip_prefix("-C")) {
+            directory = Some(std::path::PathBuf::from(path.trim_start_matches('=')));
+        }
+    }
+    directory
+}
+
+/// Default cap on dynamic ref completion candidates
+const DEFAULT_COMPLETION_LIMIT: usize = 200;
+
+/// Maximum number of ref candidates to offer (`OFSHT_COMPLETION_LIMIT`, default 200)
+fn completion_limit() -> usize {
+    std::env::var("OFSHT_COMPLETION_LIMIT")
+        .ok()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or(DEFAULT_COMPLETION_LIMIT)
+}
+
+/// Build `for-each-ref` patterns matching short names under `namespace` that start with `prefix`
+///
+/// for-each-ref globs do not cross `/`, so a second `**` pattern picks up nested names
+/// (e.g. prefix `feat` must match both `feat-x` and `feat/x`).
+fn ref_patterns(namespace: &str, prefix: &str) -> Vec<String> {
+    if prefix.is_empty() {
+        return vec![namespace.to_string()];
+    }
+    let escaped: String = prefix
+        .chars()
+        .flat_map(|c| {
+            let escape = matches!(c, '*' | '?' | '[' | '\\').then_some('\\');
+            escape.into_iter().chain(std::iter::once(c))
+        })
+        .collect();
+    vec![
+        format!("{namespace}/{escaped}*"),
+        format!("{namespace}/{escaped}*/**"),
+    ]
+}
+
+/// Fetch up to `limit` short ref names from one namespace, most recent first
+///
+/// Returns empty Vec on git failure. Symbolic refs (e.g. `origin/HEAD`) are excluded.
+fn query_refs(
+    git: &impl GitClient,
+    namespace: &str,
+    sort: &str,
+    prefix: &str,
+    limit: usize,
+) -> Vec<String> {
+    let patterns = ref_patterns(namespace, prefix);
+    let patterns: Vec<&str> = patterns.iter().map(String::as_str).collect();
+    let Ok(stdout) =
+        git.for_each_ref_sorted(&patterns, "%(refname:short)%09%(symref)", sort, limit, None)
+    else {
+        return Vec::new();
+    };
+
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let (refname, symref) = line.split_once('\t').unwrap_or((line, ""));
+            // Filter out symbolic refs (symref column is non-empty)
+            if !symref.trim().is_empty() {
+                return None;
+            }
+            let refname = refname.trim();
+            refname.starts_with(prefix).then(|| refname.to_string())
+        })
+        .collect()
+}
+
+/// Assemble ref candidates by category priority: local branches, then tags,
+/// then remote branches. Duplicates are dropped and the result is truncated to `limit`.
+fn prioritize_refs(
+    local: Vec<String>,
+    tags: Vec<String>,
+    remotes: Vec<String>,
+    limit: usize,
+) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    local
+        .into_iter()
+        .chain(tags)
+        .chain(remotes)
+        .filter(|name| seen.insert(name.clone()))
+        .take(limit)
+        .collect()
 }
 
 /// List Git refs (branches and tags) for completion of start-point arguments
 ///
 /// Returns empty Vec if git command fails (e.g., not in a git repository)
-/// Includes local branches, remote branches, and tags
+/// Includes local branches, tags, and remote branches (in that priority),
+/// each category sorted most recent first and capped by `OFSHT_COMPLETION_LIMIT`
 /// Filters refs by the provided prefix
 /// Excludes symbolic refs like origin/HEAD
 #[must_use]
 pub fn list_git_refs(current: &OsStr) -> Vec<CompletionCandidate> {
     let git = RealGitClient;
-    let Ok(stdout) = git.for_each_ref(
-        &["refs/heads", "refs/remotes", "refs/tags"],
-        "%(refname:short)%09%(symref)",
-        None,
-    ) else {
-        return Vec::new();
-    };
-
     let prefix = current.to_string_lossy();
+    let limit = completion_limit();
 
-    stdout
+    let local = query_refs(&git, "refs/heads", "-committerdate", &prefix, limit);
+    let tags = query_refs(&git, "refs/tags", "-creatordate", &prefix, limit);
+    let remotes = query_refs(&git, "refs/remotes", "-committerdate", &prefix, limit);
+
+    prioritize_refs(local, tags, remotes, limit)
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect()
+}
+
+/// Branch names for a new worktree, from `git for-each-ref` output of full
+/// ref names in [`ADDABLE_REF_FORMAT`]
+///
+/// Local branches come first, then remote branches without their `<remote>/`
+/// prefix. Branches in `checked_out`, symbolic refs and duplicates are dropped.
+fn addable_branches(
+    for_each_ref_output: &str,
+    checked_out: &std::collections::HashSet<String>,
+    prefix: &str,
+) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    for_each_ref_output
         .lines()
         .filter_map(|line| {
-            let parts: Vec<&str> = line.split('\t').collect();
-            let refname = parts.first()?.trim();
-            let symref = parts.get(1).map_or("", |s| s.trim());
-
-            // Filter out symbolic refs (symref column is non-empty)
-            if !symref.is_empty() {
+            let (refname, symref) = line.split_once('\t').unwrap_or((line, ""));
+            if !symref.trim().is_empty() {
                 return None;
             }
+            let refname = refname.trim();
+            refname.strip_prefix("refs/heads/").or_else(|| {
+                refname
+                    .strip_prefix("refs/remotes/")
+                    .and_then(|name| name.split_once('/'))
+                    .map(|(_remote, branch)| branch)
+            })
+        })
+        .filter(|branch| branch.starts_with(prefix) && !checked_out.contains(*branch))
+        .filter(|branch| seen.insert(branch.to_string()))
+        .map(str::to_string)
+        .collect()
+}
 
-            // Filter by prefix
-            if !refname.starts_with(&*prefix) {
-                return None;
-            }
+const ADDABLE_REF_FORMAT: &str = "%(refname)%09%(symref)";
 
-            Some(CompletionCandidate::new(refname))
+/// List branches that can get a new worktree, for the `branch` argument of
+/// `add` and `create`
+///
+/// Local and remote branches not checked out in any worktree; remote-only
+/// branches are offered without their `<remote>/` prefix (`ofsht add` then
+/// creates a local branch tracking them). Capped by `OFSHT_COMPLETION_LIMIT`.
+/// Returns empty Vec if git command fails (e.g., not in a git repository)
+#[must_use]
+pub fn list_addable_branches(current: &OsStr) -> Vec<CompletionCandidate> {
+    let git = RealGitClient;
+    let Ok(refs) = git.for_each_ref(&["refs/heads", "refs/remotes"], ADDABLE_REF_FORMAT, None)
+    else {
+        return Vec::new();
+    };
+    let checked_out = git
+        .list_worktrees(None)
+        .map(|stdout| {
+            crate::domain::worktree::WorktreeList::parse(&stdout, None)
+                .entries()
+                .iter()
+                .filter_map(|entry| entry.branch.clone())
+                .collect()
         })
+        .unwrap_or_default();
+
+    addable_branches(&refs, &checked_out, &current.to_string_lossy())
+        .into_iter()
+        .take(completion_limit())
+        .map(CompletionCandidate::new)
         .collect()
 }
 
@@ -203,11 +712,57 @@ pub fn list_git_branches(current: &OsStr) -> Vec<CompletionCandidate> {
         .collect()
 }
 
+/// Completion values for worktree targets with the branch each one resolves to
+///
+/// Candidates follow the precedence of [`WorktreeList::find_by_name`]: `@`, branch
+/// names, paths relative to the `layout` root, then absolute paths. A value that is
+/// already taken keeps its earlier meaning, so a directory named like another
+/// worktree's branch never shadows that branch (nor gets listed twice).
+///
+/// [`WorktreeList::find_by_name`]: crate::domain::worktree::WorktreeList::find_by_name
+fn worktree_candidates(
+    list: &crate::domain::worktree::WorktreeList,
+    layout: Option<&crate::domain::worktree::WorktreeLayout>,
+    prefix: &str,
+) -> Vec<(String, Option<String>)> {
+    let mut candidates: Vec<(String, Option<String>)> = Vec::new();
+    let mut push = |value: String, branch: Option<String>| {
+        if value.starts_with(prefix) && !candidates.iter().any(|(seen, _)| *seen == value) {
+            candidates.push((value, branch));
+        }
+    };
+
+    push(
+        "@".to_string(),
+        list.main().and_then(|main| main.branch.clone()),
+    );
+    // `non_main` never includes the main worktree, even for malformed porcelain
+    for entry in list.non_main() {
+        if let Some(branch) = &entry.branch {
+            push(branch.clone(), Some(branch.clone()));
+        }
+    }
+    if let Some(layout) = layout {
+        for entry in list.non_main() {
+            if let Some(rel_path) = layout.rel_path(entry) {
+                push(rel_path, entry.branch.clone());
+            }
+        }
+    }
+    // Absolute paths cover worktrees that live outside the common root
+    for entry in list.non_main() {
+        push(entry.path.clone(), entry.branch.clone());
+    }
+    candidates
+}
+
 /// List Git worktrees for completion
 ///
 /// Returns empty Vec if git command fails
 /// Filters worktree branch names by the provided prefix
-/// Includes "@" as the main worktree
+/// Includes "@" as the main worktree, plus relative and absolute worktree paths
+/// Each candidate carries the worktree's branch name as help text
+#[must_use]
 pub fn list_git_worktrees(current: &OsStr) -> Vec<CompletionCandidate> {
     let git = RealGitClient;
     let Ok(stdout) = git.list_worktrees(None) else {
@@ -216,72 +771,313 @@ pub fn list_git_worktrees(current: &OsStr) -> Vec<CompletionCandidate> {
 
     let prefix = current.to_string_lossy();
 
-    // Use HashSet to deduplicate branch names and relative paths
-    let mut candidates_set = HashSet::new();
+    // Parse the porcelain output once via the unified WorktreeList type.
+    let list = crate::domain::worktree::WorktreeList::parse(&stdout, None);
 
-    // Always include "@" if it matches the prefix
-    if "@".starts_with(&*prefix) {
-        candidates_set.insert("@".to_string());
+    // Relative paths use the worktree.dir template root (or the common parent)
+    let layout = crate::commands::common::get_main_repo_root()
+        .ok()
+        .and_then(|repo_root| crate::config::Config::load_from_repo_root(&repo_root).ok())
+        .map(|config| {
+            crate::domain::worktree::WorktreeLayout::for_list(&list, Some(&config.worktree.dir))
+        });
+
+    worktree_candidates(&list, layout.as_ref(), &prefix)
+        .into_iter()
+        .map(|(name, branch)| {
+            let candidate = CompletionCandidate::new(name);
+            match branch {
+                Some(branch) => candidate.help(Some(branch.into())),
+                None => candidate.help(Some("detached".into())),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::worktree::{WorktreeLayout, WorktreeList};
+
+    fn names(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| (*s).to_string()).collect()
     }
 
-    // Parse the porcelain output once via the unified WorktreeList type.
-    let list = crate::domain::worktree::WorktreeList::parse(&stdout, None);
+    #[test]
+    fn test_directory_override() {
+        assert_eq!(
+            directory_override(["-C", "/repo", "ls"]),
+            Some(std::path::PathBuf::from("/repo"))
+        );
+        assert_eq!(
+            directory_override(["ls", "-C/repo"]),
+            Some(std::path::PathBuf::from("/repo"))
+        );
+        assert_eq!(
+            directory_override(["-C=/repo", "cd", "feat"]),
+            Some(std::path::PathBuf::from("/repo"))
+        );
+        assert_eq!(directory_override(["ls", "--show-path"]), None);
+        // The command of `exec` is not ours
+        assert_eq!(
+            directory_override(["exec", "--all", "--", "make", "-C", "build"]),
+            None
+        );
+        // Still typing the value
+        assert_eq!(directory_override(["-C"]), None);
+    }
 
-    // Add branch names from non-main worktrees (excludes main automatically).
-    // This naturally fixes a latent inconsistency in the legacy parser
-    // (`worktree_index > 0` vs `> 1`) where main could leak into completion candidates
-    // when entry separators were missing in malformed porcelain.
-    for entry in list.non_main() {
-        if let Some(branch) = &entry.branch {
-            candidates_set.insert(branch.clone());
-        }
+    #[test]
+    fn test_addable_branches_skips_checked_out_and_strips_remotes() {
+        let output = "refs/heads/feature-1\t\n\
+                      refs/heads/feature-2\t\n\
+                      refs/heads/main\t\n\
+                      refs/remotes/origin/HEAD\trefs/remotes/origin/main\n\
+                      refs/remotes/origin/feature-2\t\n\
+                      refs/remotes/origin/team/review\t\n\
+                      refs/remotes/upstream/team/review\t\n";
+        let checked_out = ["main", "feature-1"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(
+            addable_branches(output, &checked_out, ""),
+            names(&["feature-2", "team/review"])
+        );
+        assert_eq!(
+            addable_branches(output, &checked_out, "team"),
+            names(&["team/review"])
+        );
     }
 
-    // Try to add relative paths (new behavior)
-    if let Ok(repo_root) = crate::commands::common::get_main_repo_root() {
-        if crate::config::Config::load_from_repo_root(&repo_root).is_ok() {
-            // Collect all non-main worktree paths
-            let worktree_paths: Vec<PathBuf> = list
-                .non_main()
-                .iter()
-                .map(|entry| PathBuf::from(&entry.path))
+    fn ambiguous_list() -> WorktreeList {
+        let output = "worktree /nonexistent/repo\nHEAD a\nbranch refs/heads/main\n\n\
+                      worktree /nonexistent/wt/feature-main\nHEAD b\nbranch refs/heads/feature\n\n\
+                      worktree /nonexistent/wt/feature-2\nHEAD c\nbranch refs/heads/feature-2\n\n\
+                      worktree /nonexistent/wt/feature\nHEAD d\nbranch refs/heads/other\n\n\
+                      worktree /nonexistent/wt/feat/feature\nHEAD e\nbranch refs/heads/feat/feature\n\n";
+        WorktreeList::parse(output, None)
+    }
+
+    #[test]
+    fn test_worktree_candidates_branch_shadows_same_named_directory() {
+        let list = ambiguous_list();
+        let candidates =
+            worktree_candidates(&list, Some(&WorktreeLayout::for_list(&list, None)), "feat");
+        let values: Vec<&str> = candidates.iter().map(|(value, _)| value.as_str()).collect();
+        assert_eq!(
+            values,
+            ["feature", "feature-2", "feat/feature", "feature-main"]
+        );
+        // `feature` completes to the branch, not to the `feature` directory of `other`
+        assert_eq!(candidates[0].1.as_deref(), Some("feature"));
+        assert_eq!(candidates[3].1.as_deref(), Some("feature"));
+    }
+
+    #[test]
+    fn test_worktree_candidates_order_and_paths() {
+        let list = ambiguous_list();
+        let candidates =
+            worktree_candidates(&list, Some(&WorktreeLayout::for_list(&list, None)), "");
+        let values: Vec<&str> = candidates.iter().map(|(value, _)| value.as_str()).collect();
+        assert_eq!(
+            values,
+            [
+                "@",
+                "feature",
+                "feature-2",
+                "other",
+                "feat/feature",
+                "feature-main",
+                "/nonexistent/wt/feature-main",
+                "/nonexistent/wt/feature-2",
+                "/nonexistent/wt/feature",
+                "/nonexistent/wt/feat/feature",
+            ]
+        );
+        assert_eq!(candidates[0].1.as_deref(), Some("main"));
+        // The absolute path of the `feature` directory belongs to `other`
+        assert_eq!(candidates[8].1.as_deref(), Some("other"));
+    }
+
+    #[test]
+    fn test_worktree_candidates_without_root_skips_relative_paths() {
+        let list = ambiguous_list();
+        let candidates = worktree_candidates(&list, None, "feature-m");
+        assert!(candidates.is_empty(), "{candidates:?}");
+    }
+
+    /// Relative names of the non-main worktrees as seen by the `ls` table, by
+    /// completion, and by name resolution, in `git worktree list` order
+    fn rel_paths_by_consumer(
+        paths: &[&str],
+        template: &str,
+    ) -> (Vec<String>, Vec<String>, Vec<String>) {
+        let porcelain: String =
+            std::iter::once("worktree /src/app\nHEAD a\nbranch refs/heads/main\n\n".to_string())
+                .chain(paths.iter().enumerate().map(|(index, path)| {
+                    format!("worktree {path}\nHEAD b\nbranch refs/heads/branch-{index}\n\n")
+                }))
                 .collect();
+        let list = WorktreeList::parse(&porcelain, None);
+        let layout = WorktreeLayout::for_list(&list, Some(template));
 
-            // Calculate worktree root from all non-main worktrees
-            if let Some(worktree_root) =
-                crate::domain::worktree::calculate_worktree_root_from_paths(&worktree_paths)
-            {
-                // Add relative paths for all non-main worktrees
-                for entry in list.non_main() {
-                    let worktree_path = PathBuf::from(&entry.path);
-                    if let Some(rel_path) = crate::domain::worktree::calculate_relative_path(
-                        &worktree_path,
-                        &worktree_root,
-                    ) {
-                        candidates_set.insert(rel_path);
-                    }
-                }
-            }
+        let config = crate::config::Config {
+            worktree: crate::config::WorktreeConfig {
+                dir: template.to_string(),
+                list_columns: vec![crate::config::ListColumn::RelPath],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let table: Vec<String> = crate::commands::list_display::format_worktree_table(
+            list.entries(),
+            &vec![None; list.entries().len()],
+            false,
+            crate::color::ColorMode::Never,
+            Some(&config),
+            None,
+        )
+        .into_iter()
+        .skip(1)
+        .map(|line| line.trim().to_string())
+        .collect();
+
+        let completion: Vec<String> = worktree_candidates(&list, Some(&layout), "")
+            .into_iter()
+            .map(|(value, _)| value)
+            .filter(|value| {
+                value != "@" && !value.starts_with("branch-") && !value.starts_with('/')
+            })
+            .collect();
+
+        let resolution: Vec<String> = list
+            .non_main()
+            .iter()
+            .filter_map(|entry| {
+                let rel = layout.rel_path(entry)?;
+                let found = list.find_by_name(&rel, layout.root())?;
+                (found.path == entry.path).then_some(rel)
+            })
+            .collect();
+
+        (table, completion, resolution)
+    }
+
+    #[test]
+    fn test_layout_consumers_agree_on_relative_paths() {
+        let template = "../{repo}-worktrees/{branch}";
+        let cases: [(&[&str], &[&str]); 3] = [
+            // Nested branch names next to flat ones
+            (
+                &[
+                    "/src/app-worktrees/feature",
+                    "/src/app-worktrees/docs/tweak",
+                ],
+                &["feature", "docs/tweak"],
+            ),
+            // A single nested worktree keeps its full name via the template root
+            (&["/src/app-worktrees/feat/login"], &["feat/login"]),
+            // A worktree outside the template root falls back to the common parent
+            (
+                &["/src/app-worktrees/feature", "/src/old-worktrees/hotfix"],
+                &["app-worktrees/feature", "old-worktrees/hotfix"],
+            ),
+        ];
+        for (paths, expected) in cases {
+            let (table, completion, resolution) = rel_paths_by_consumer(paths, template);
+            assert_eq!(table, expected, "table for {paths:?}");
+            assert_eq!(completion, expected, "completion for {paths:?}");
+            assert_eq!(resolution, expected, "resolution for {paths:?}");
         }
     }
 
-    // Filter by prefix and convert to CompletionCandidate
-    candidates_set
-        .into_iter()
-        .filter(|name| name.starts_with(&*prefix))
-        .map(CompletionCandidate::new)
-        .collect()
-}
+    #[test]
+    fn test_prioritize_refs_orders_local_tags_remotes() {
+        let result = prioritize_refs(
+            names(&["main", "develop"]),
+            names(&["v1.0.0"]),
+            names(&["origin/main"]),
+            10,
+        );
+        assert_eq!(result, names(&["main", "develop", "v1.0.0", "origin/main"]));
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::domain::worktree::WorktreeList;
+    #[test]
+    fn test_prioritize_refs_truncates_lower_priority_first() {
+        let result = prioritize_refs(
+            names(&["main", "develop"]),
+            names(&["v1.0.0", "v2.0.0"]),
+            names(&["origin/main", "origin/develop"]),
+            3,
+        );
+        assert_eq!(result, names(&["main", "develop", "v1.0.0"]));
+    }
+
+    #[test]
+    fn test_prioritize_refs_drops_duplicates() {
+        let result = prioritize_refs(names(&["v1"]), names(&["v1"]), names(&[]), 10);
+        assert_eq!(result, names(&["v1"]));
+    }
+
+    #[test]
+    fn test_ref_patterns() {
+        assert_eq!(ref_patterns("refs/heads", ""), names(&["refs/heads"]));
+        assert_eq!(
+            ref_patterns("refs/heads", "feat"),
+            names(&["refs/heads/feat*", "refs/heads/feat*/**"])
+        );
+        assert_eq!(
+            ref_patterns("refs/tags", "v[1"),
+            names(&["refs/tags/v\\[1*", "refs/tags/v\\[1*/**"])
+        );
+    }
 
     #[test]
     fn verify_cli() {
         use clap::CommandFactory;
         Cli::command().debug_assert();
+
+        for shell in ["bash", "zsh", "fish", "powershell", "elvish"] {
+            assert!(
+                Cli::try_parse_from(["ofsht", "completion", shell]).is_ok(),
+                "{shell}"
+            );
+            assert!(
+                Cli::try_parse_from(["ofsht", "shell-init", shell]).is_ok(),
+                "{shell}"
+            );
+        }
+        let err = Cli::try_parse_from(["ofsht", "completion", "zshh"]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::InvalidValue);
+    }
+
+    #[test]
+    fn test_add_merge_takes_two_or_more_refs() {
+        let cli = Cli::try_parse_from(["ofsht", "add", "it", "--merge", "main", "a", "b"]).unwrap();
+        let Commands::Add {
+            branch,
+            merge,
+            start_point,
+            ..
+        } = cli.command
+        else {
+            panic!("expected add");
+        };
+        assert_eq!(branch.as_deref(), Some("it"));
+        assert_eq!(merge, ["main", "a", "b"]);
+        assert_eq!(start_point, None);
+
+        let err = Cli::try_parse_from(["ofsht", "add", "it", "--merge", "main"]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::TooFewValues);
+        for conflicting in [&["--detach"][..], &["--carry-changes"]] {
+            let mut args = vec!["ofsht", "add", "it", "--merge", "main", "a"];
+            args.extend_from_slice(conflicting);
+            let err = Cli::try_parse_from(args).unwrap_err();
+            assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
+        }
+        let err = Cli::try_parse_from(["ofsht", "add", "it", "--hooks-on-conflict"]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
     }
 
     #[test]
@@ -438,4 +1234,15 @@ branch refs/heads/feature-b
             "Completion candidates should not include @ when prefix is 'feature'"
         );
     }
+
+    #[test]
+    fn test_list_git_worktrees_attaches_branch_help() {
+        use std::ffi::OsStr;
+        let result = list_git_worktrees(OsStr::new("@"));
+        let at = result
+            .iter()
+            .find(|c| c.get_value() == "@")
+            .expect("@ should be a candidate");
+        assert!(at.get_help().is_some(), "@ should carry branch help text");
+    }
 }
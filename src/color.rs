@@ -1,11 +1,14 @@
 use std::env;
 use std::fmt;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use owo_colors::OwoColorize;
+use serde::{Deserialize, Serialize};
 
 /// Color mode for terminal output
-#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
 pub enum ColorMode {
     /// Always use colors
     Always,
@@ -82,48 +85,44 @@ impl ColorMode {
         }
     }
 
-    /// Colorize main worktree marker [@] in green
+    /// Wrap `text` in the escape sequence of `color` when colors are enabled
     #[must_use]
-    pub fn colorize_main_worktree(self, text: &str) -> String {
+    pub fn paint(self, color: TermColor, text: &str) -> String {
         if self.should_colorize() {
-            // Green: \x1b[32m
-            format!("\x1b[32m{text}\x1b[0m")
+            format!("\x1b[{}m{text}\x1b[0m", color.sgr())
         } else {
             text.to_string()
         }
     }
 
+    /// Colorize main worktree marker [@] in green
+    #[must_use]
+    pub fn colorize_main_worktree(self, text: &str) -> String {
+        self.paint(Palette::default().main, text)
+    }
+
     /// Colorize branch name in cyan
     #[must_use]
     pub fn colorize_branch(self, text: &str) -> String {
-        if self.should_colorize() {
-            // Cyan: \x1b[36m
-            format!("\x1b[36m{text}\x1b[0m")
-        } else {
-            text.to_string()
-        }
+        self.paint(Palette::default().branch, text)
     }
 
     /// Colorize detached HEAD marker in yellow
     #[must_use]
     pub fn colorize_detached(self, text: &str) -> String {
-        if self.should_colorize() {
-            // Yellow: \x1b[33m
-            format!("\x1b[33m{text}\x1b[0m")
-        } else {
-            text.to_string()
-        }
+        self.paint(TermColor::Yellow, text)
+    }
+
+    /// Colorize locked worktree indicator in yellow
+    #[must_use]
+    pub fn colorize_locked(self, text: &str) -> String {
+        self.paint(TermColor::Yellow, text)
     }
 
     /// Colorize secondary info (hash, timestamp) in dim/gray
     #[must_use]
     pub fn colorize_secondary(self, text: &str) -> String {
-        if self.should_colorize() {
-            // Bright black (gray): \x1b[90m
-            format!("\x1b[90m{text}\x1b[0m")
-        } else {
-            text.to_string()
-        }
+        self.paint(Palette::default().timestamp, text)
     }
 
     /// Colorize active worktree marker in bold magenta
@@ -138,6 +137,98 @@ impl ColorMode {
     }
 }
 
+/// Terminal color accepted by the `[color]` config table (`cyan`, `bright-black`, ...)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TermColor {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+}
+
+impl TermColor {
+    /// SGR foreground code (`30`-`37`, `90`-`97`)
+    const fn sgr(self) -> u8 {
+        match self {
+            Self::Black => 30,
+            Self::Red => 31,
+            Self::Green => 32,
+            Self::Yellow => 33,
+            Self::Blue => 34,
+            Self::Magenta => 35,
+            Self::Cyan => 36,
+            Self::White => 37,
+            Self::BrightBlack => 90,
+            Self::BrightRed => 91,
+            Self::BrightGreen => 92,
+            Self::BrightYellow => 93,
+            Self::BrightBlue => 94,
+            Self::BrightMagenta => 95,
+            Self::BrightCyan => 96,
+            Self::BrightWhite => 97,
+        }
+    }
+}
+
+/// Colors of the `ls` table, with `[color]` overrides applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    /// Branch names
+    pub branch: TermColor,
+    /// The main worktree's `[@]`
+    pub main: TermColor,
+    /// Paths relative to the worktrees root
+    pub relpath: TermColor,
+    /// Commit age and other secondary cells
+    pub timestamp: TermColor,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            branch: TermColor::Cyan,
+            main: TermColor::Green,
+            relpath: TermColor::Blue,
+            timestamp: TermColor::BrightBlack,
+        }
+    }
+}
+
+/// Process-wide quiet switch (`--quiet`), consulted by status output sinks
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Suppress success/info output for the rest of the process.
+/// Warnings, errors and stdout are never affected.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Check whether `--quiet` is active
+#[must_use]
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Print a success/info line to stderr unless `--quiet` is active
+pub fn eprint_status<D: fmt::Display>(line: D) {
+    if !is_quiet() {
+        eprintln!("{line}");
+    }
+}
+
 /// Message style for different types of output
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MessageStyle {
@@ -294,6 +385,24 @@ pub fn tree_item<D: fmt::Display>(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_paint_uses_color_code_or_plain_text() {
+        assert_eq!(
+            ColorMode::Always.paint(TermColor::BrightBlack, "x"),
+            "\x1b[90mx\x1b[0m"
+        );
+        assert_eq!(
+            ColorMode::Always.paint(TermColor::Blue, "x"),
+            "\x1b[34mx\x1b[0m"
+        );
+        assert_eq!(ColorMode::Never.paint(TermColor::Blue, "x"), "x");
+        // The fixed helpers follow the default palette
+        assert_eq!(
+            ColorMode::Always.colorize_branch("x"),
+            ColorMode::Always.paint(Palette::default().branch, "x")
+        );
+    }
+
     #[test]
     fn test_resolve_from_cli_always() {
         let mode = ColorMode::resolve(Some(ColorMode::Always));
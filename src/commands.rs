@@ -1,15 +1,47 @@
 // Command handlers module
-// This module contains all CLI command implementations
+// This module contains all CLI command implementations.
+// Only `list_display` (pure table formatting) is available without the `cli` feature.
 
+#[cfg(feature = "cli")]
 pub mod add;
+#[cfg(feature = "cli")]
+pub mod carry;
+#[cfg(feature = "cli")]
 pub mod cd;
+#[cfg(feature = "cli")]
 pub mod common;
+#[cfg(feature = "cli")]
 pub mod completion;
+#[cfg(feature = "cli")]
 pub mod create;
+#[cfg(feature = "cli")]
+pub mod doctor;
+#[cfg(feature = "cli")]
+pub mod exec;
+#[cfg(feature = "cli")]
+pub mod hook;
+#[cfg(feature = "cli")]
 pub mod init;
+#[cfg(feature = "cli")]
 pub mod list;
 pub mod list_display;
+#[cfg(feature = "cli")]
+pub mod lock;
+#[cfg(feature = "cli")]
+pub mod man;
+#[cfg(feature = "cli")]
+pub mod merge;
+#[cfg(feature = "cli")]
 pub mod open;
+#[cfg(feature = "cli")]
 pub mod rm;
+#[cfg(feature = "cli")]
+pub mod root;
+#[cfg(feature = "cli")]
+pub mod shell;
+#[cfg(feature = "cli")]
 pub mod shell_init;
+#[cfg(feature = "cli")]
+pub mod switch;
+#[cfg(feature = "cli")]
 pub mod sync;
@@ -1,35 +1,72 @@
 //! Add command - Create new worktrees with GitHub integration and tmux support
 
 use anyhow::Result;
-use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use indicatif::{ProgressBar, ProgressStyle};
+use std::cell::Cell;
 use std::time::Duration;
 
 use crate::color;
-use crate::commands::common::get_main_repo_root;
+use crate::commands::carry::{self, CarryOutcome};
+use crate::commands::common::{
+    check_worktree_location, creates_branch, effective_create_actions, effective_start_point,
+    get_main_repo_root, launch_tmux, partial_hook_failure, print_hook_summary,
+    resolve_detached_target, resolve_tmux, rollback_created_worktree, run_create_hooks,
+    verify_commitish, warn_create_hooks_skipped, HookOverrides,
+};
+use crate::commands::merge;
 use crate::config;
-use crate::hooks;
+use crate::domain;
+use crate::hooks::{self, HookSummary, HookVars};
 use crate::integrations;
+use crate::integrations::gh::meta::{GithubRef, GithubRefKind};
 use crate::integrations::git::{GitClient, RealGitClient};
-use crate::integrations::tmux::TmuxLauncher;
+use crate::integrations::resolver::{resolve_ticket_branch, RealTicketResolver};
 use crate::integrations::zoxide::{is_zoxide_available, RealZoxideClient};
 use crate::path_utils::normalize_absolute_path;
-use crate::service::{CreateWorktreeRequest, WorktreeService};
+use crate::report;
+use crate::service::{CreateOptions, CreateWorktreeRequest, WorktreeManager, WorktreeService};
+
+/// Pick the remote to fetch PRs from: `[integration.gh] remote`, else the remote
+/// pointing at the repository gh resolves, else `origin`
+fn resolve_pr_remote(
+    gh_client: &impl integrations::gh::GhClient,
+    git: &impl GitClient,
+    repo_root: &std::path::Path,
+    configured: Option<&str>,
+) -> String {
+    if let Some(remote) = configured.filter(|remote| !remote.is_empty()) {
+        return remote.to_string();
+    }
+    let remotes = git
+        .remote_list(Some(repo_root))
+        .map(|output| integrations::gh::parse_remotes(&output))
+        .unwrap_or_default();
+    let name_with_owner = gh_client.repo_name_with_owner().ok();
+    integrations::gh::select_pr_remote(&remotes, name_with_owner.as_deref(), None)
+}
 
 /// Process a PR and return branch name and start point
 fn process_pr(
+    git: &impl GitClient,
     pr: &integrations::gh::PrInfo,
     number: u32,
+    remote: &str,
     repo_root: &std::path::Path,
     color_mode: color::ColorMode,
 ) -> Result<(String, Option<String>)> {
     // Check if it's from a fork (cross-repository PR)
     let is_fork = pr.is_cross_repository;
 
-    let git = RealGitClient;
     if is_fork {
-        // Fork PR - fetch PR ref from GitHub without checking out
+        // Fork PR - fetch the PR head into an ofsht-owned ref (removed by
+        // `rm.prune_remotes`) instead of relying on FETCH_HEAD
+        let pr_ref = integrations::gh::pr_ref(number);
         git.fetch(
-            &["fetch", "origin", &format!("refs/pull/{number}/head")],
+            &[
+                "fetch",
+                remote,
+                &format!("+refs/pull/{number}/head:{pr_ref}"),
+            ],
             Some(repo_root),
         )
         .map_err(|e| anyhow::anyhow!("git fetch PR ref failed: {e}"))?;
@@ -37,13 +74,10 @@ fn process_pr(
         // Check if local branch with PR's name already exists
         let branch_exists = git.branch_exists(&pr.head_ref_name, Some(repo_root))?;
 
-        eprintln!(
-            "{}",
-            color::success(
-                color_mode,
-                &format!("Fetched PR #{}: {} (fork)", pr.number, pr.title)
-            )
-        );
+        color::eprint_status(color::success(
+            color_mode,
+            report::fetched_github_ref(GithubRefKind::Pr.label(), pr.number, &pr.title, true),
+        ));
 
         if branch_exists {
             // Conflict: local branch already exists, use unique name
@@ -61,23 +95,20 @@ fn process_pr(
                 )
             );
 
-            Ok((unique_branch, Some("FETCH_HEAD".to_string())))
+            Ok((unique_branch, Some(pr_ref)))
         } else {
             // Use PR's original branch name
-            Ok((pr.head_ref_name.clone(), Some("FETCH_HEAD".to_string())))
+            Ok((pr.head_ref_name.clone(), Some(pr_ref)))
         }
     } else {
         // Same repository - fetch the branch
-        git.fetch(&["fetch", "origin", &pr.head_ref_name], Some(repo_root))
+        git.fetch(&["fetch", remote, &pr.head_ref_name], Some(repo_root))
             .map_err(|e| anyhow::anyhow!("git fetch failed: {e}"))?;
 
-        eprintln!(
-            "{}",
-            color::success(
-                color_mode,
-                &format!("Fetched PR #{}: {}", pr.number, pr.title)
-            )
-        );
+        color::eprint_status(color::success(
+            color_mode,
+            report::fetched_github_ref(GithubRefKind::Pr.label(), pr.number, &pr.title, false),
+        ));
 
         // Check if local branch already exists
         let branch_exists = git.branch_exists(&pr.head_ref_name, Some(repo_root))?;
@@ -89,21 +120,59 @@ fn process_pr(
             // Create new branch tracking remote
             Ok((
                 pr.head_ref_name.clone(),
-                Some(format!("origin/{}", pr.head_ref_name)),
+                Some(format!("{remote}/{}", pr.head_ref_name)),
             ))
         }
     }
 }
 
-/// Resolve branch name and start point from GitHub issue/PR
+/// Resolve a ticket ID via `[integration.resolver]`, falling back to the literal input
+fn resolve_ticket_or_literal(
+    name: String,
+    resolver_config: &config::ResolverConfig,
+    color_mode: color::ColorMode,
+) -> String {
+    let resolver = RealTicketResolver::new(
+        resolver_config.command.clone(),
+        Duration::from_secs(resolver_config.timeout),
+    );
+    match resolve_ticket_branch(&resolver, resolver_config, &name) {
+        Ok(Some(ticket)) => {
+            color::eprint_status(color::success(
+                color_mode,
+                format!("Resolved {name}: {}", ticket.title),
+            ));
+            ticket.branch
+        }
+        Ok(None) => name,
+        Err(e) => {
+            eprintln!(
+                "{}",
+                color::warn(
+                    color_mode,
+                    format!("Could not resolve {name}, using it as the branch name: {e:#}")
+                )
+            );
+            name
+        }
+    }
+}
+
+/// Resolve branch name and start point from GitHub issue/PR, along with the
+/// reference to record for the new worktree
+///
+/// `url_repo` is the `owner/name` of a pasted PR/issue URL; it must match the
+/// repository gh resolves for the current checkout.
 #[allow(clippy::type_complexity)]
 fn resolve_github_ref(
     gh_client: &impl integrations::gh::GhClient,
     number: u32,
+    url_repo: Option<&str>,
     start_point: Option<&str>,
     repo_root: &std::path::Path,
+    configured_remote: Option<&str>,
     color_mode: color::ColorMode,
-) -> Result<(String, Option<String>)> {
+) -> Result<(String, Option<String>, GithubRef)> {
     if !gh_client.is_available() {
         anyhow::bail!(
             "GitHub CLI (gh) is not installed or not available.\n\
@@ -112,46 +181,153 @@ fn resolve_github_ref(
         );
     }
 
-    // Try PR first, then issue if PR fails
-    match gh_client.pr_info(number) {
-        Ok(pr) => process_pr(&pr, number, repo_root, color_mode),
-        Err(_pr_err) => match gh_client.issue_info(number) {
-            Ok(issue) => {
-                let branch_name = integrations::gh::build_issue_branch(number);
-                eprintln!(
-                    "{}",
-                    color::success(
-                        color_mode,
-                        &format!("Fetched issue #{}: {}", issue.number, issue.title)
-                    )
-                );
-                Ok((branch_name, start_point.map(String::from)))
-            }
-            Err(_issue_err) => {
+    if let Some(url_repo) = url_repo {
+        match gh_client.repo_name_with_owner() {
+            Ok(current) if !current.eq_ignore_ascii_case(url_repo) => {
                 anyhow::bail!(
-                    "#{number} is not a valid issue or pull request.\n\
-                     Please check the number and try again."
+                    "The URL points at {url_repo}, but this repository is {current}.\n\
+                     Run ofsht from a checkout of {url_repo}, or pass #{number} to use this repository."
                 );
             }
-        },
+            Ok(_) => {}
+            // Not fatal: the PR/issue lookup below reports a real problem with gh
+            Err(e) => eprintln!(
+                "{}",
+                color::warn(
+                    color_mode,
+                    format!(
+                        "Could not check that the URL belongs to this repository \
+                         (expected {url_repo}): {}",
+                        e.to_string().trim()
+                    )
+                )
+            ),
+        }
+    }
+
+    if matches!(gh_client.auth_status(), Ok(false)) {
+        anyhow::bail!(
+            "GitHub CLI is not authenticated. \
+             Run `gh auth login` or use a plain branch name instead of #{number}."
+        );
+    }
+
+    // Try PR first, then issue if no PR has that number
+    match gh_client.pr_info(number) {
+        Ok(pr) => {
+            let remote = resolve_pr_remote(gh_client, &RealGitClient, repo_root, configured_remote);
+            let (branch, start_point) =
+                process_pr(&RealGitClient, &pr, number, &remote, repo_root, color_mode)?;
+            let github_ref = GithubRef {
+                kind: GithubRefKind::Pr,
+                number: pr.number,
+                title: pr.title,
+                url: pr.url,
+                head_ref: Some(pr.head_ref_name),
+            };
+            Ok((branch, start_point, github_ref))
+        }
+        Err(pr_err) => {
+            ensure_gh_not_found(&pr_err, number)?;
+            match gh_client.issue_info(number) {
+                Ok(issue) => {
+                    let branch_name = integrations::gh::build_issue_branch(number);
+                    color::eprint_status(color::success(
+                        color_mode,
+                        report::fetched_github_ref(
+                            GithubRefKind::Issue.label(),
+                            issue.number,
+                            &issue.title,
+                            false,
+                        ),
+                    ));
+                    let github_ref = GithubRef {
+                        kind: GithubRefKind::Issue,
+                        number: issue.number,
+                        title: issue.title,
+                        url: issue.url,
+                        head_ref: None,
+                    };
+                    Ok((branch_name, start_point.map(String::from), github_ref))
+                }
+                Err(issue_err) => {
+                    ensure_gh_not_found(&issue_err, number)?;
+                    anyhow::bail!(
+                        "#{number} is not a valid issue or pull request.\n\
+                         Please check the number and try again."
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Turn a failed gh lookup into an error unless gh simply found nothing,
+/// so network or permission problems are not reported as a bad number
+fn ensure_gh_not_found(err: &anyhow::Error, number: u32) -> Result<()> {
+    match err.downcast_ref::<integrations::gh::GhError>() {
+        Some(integrations::gh::GhError::NotFound(_)) => Ok(()),
+        Some(integrations::gh::GhError::Unauthenticated(_)) => anyhow::bail!(
+            "GitHub CLI is not authenticated. \
+             Run `gh auth login` or use a plain branch name instead of #{number}."
+        ),
+        _ => anyhow::bail!("Failed to look up #{number} on GitHub: {err}"),
+    }
+}
+
+/// Machine-readable result of `add --porcelain` / `create --porcelain`
+///
+/// Printed as stable `key=value` lines so tools can pick up the branch that
+/// was actually used (e.g. `pr-123-...` after a fork branch name conflict).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddResult<'a> {
+    pub path: &'a std::path::Path,
+    /// Branch checked out in the worktree (`None` for detached worktrees)
+    pub branch: Option<&'a str>,
+    /// Whether the worktree was created by this invocation. `add` and `create`
+    /// fail when the worktree already exists, so this is currently always true.
+    pub created: bool,
+    /// Start point passed to git (`None` when defaulting to `HEAD`)
+    pub start_point: Option<&'a str>,
+}
+
+impl AddResult<'_> {
+    /// Render as `path=`, `branch=`, `created=` and `start_point=` lines
+    /// (values of missing fields are empty)
+    #[must_use]
+    pub fn to_porcelain(&self) -> String {
+        format!(
+            "path={}\nbranch={}\ncreated={}\nstart_point={}",
+            normalize_absolute_path(self.path),
+            self.branch.unwrap_or_default(),
+            self.created,
+            self.start_point.unwrap_or_default()
+        )
     }
 }
 
-/// Determine if tmux integration should be used based on flags and config
-const fn should_use_tmux(
-    behavior: config::TmuxBehavior,
-    tmux_flag: bool,
-    no_tmux_flag: bool,
-) -> bool {
-    // Priority: --no-tmux > --tmux > behavior setting
-    if no_tmux_flag {
-        return false;
-    }
-    if tmux_flag {
-        return true;
-    }
-    // behavior: Auto (default), Always, Never
-    matches!(behavior, config::TmuxBehavior::Always)
+/// Behavior switches for `ofsht add` and `ofsht create`
+#[derive(Debug, Clone, Default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct AddOptions {
+    /// Create a detached worktree instead of a branch
+    pub detach: bool,
+    pub tmux: bool,
+    pub no_tmux: bool,
+    /// Print [`AddResult`] lines instead of the bare path
+    pub porcelain: bool,
+    /// Skip configured create hooks (`hook_overrides` additions still run)
+    pub no_hooks: bool,
+    /// `--copy` / `--link` / `--skip-pattern`
+    pub hook_overrides: HookOverrides,
+    /// Move uncommitted changes of the current worktree into the new one
+    /// (`add` only)
+    pub carry_changes: bool,
+    /// Refs to start from (the first) and merge into the new worktree (the
+    /// rest); empty for a plain `add` (`add` only)
+    pub merge: Vec<String>,
+    /// Run create hooks even when a `merge` conflicts
+    pub hooks_on_conflict: bool,
 }
 
 /// Add command - Create new worktree with optional GitHub integration and tmux support
@@ -164,15 +340,35 @@ const fn should_use_tmux(
 #[allow(clippy::too_many_lines, clippy::missing_panics_doc)]
 pub fn cmd_new(
     branch: Option<&str>,
+    from_pr: Option<&str>,
     start_point: Option<&str>,
-    tmux: bool,
-    no_tmux: bool,
+    options: AddOptions,
     color_mode: color::ColorMode,
 ) -> Result<()> {
-    // Resolve branch: CLI arg > stdin (when piped) > error
-    let branch_owned = match branch {
-        Some(b) => b.to_string(),
-        None => crate::stdin::try_read_stdin_first()?.ok_or_else(|| {
+    let AddOptions {
+        detach,
+        tmux,
+        no_tmux,
+        porcelain,
+        no_hooks,
+        hook_overrides,
+        carry_changes,
+        merge: merge_refs,
+        hooks_on_conflict,
+    } = options;
+    // `--merge A B...`: the branch starts at A, then B... are merged into it
+    let start_point = merge_refs.first().map(String::as_str).or(start_point);
+    let merge_refs = merge_refs.get(1..).unwrap_or_default();
+    // Resolve branch: --from-pr > CLI arg > stdin (when piped) > error
+    let branch_owned = match (from_pr, branch) {
+        (Some(reference), _) => integrations::gh::normalize_pr_reference(reference)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Invalid --from-pr value '{reference}': expected #123, 123 or a GitHub pull request URL"
+                )
+            })?,
+        (None, Some(b)) => b.to_string(),
+        (None, None) => crate::stdin::try_read_stdin_first()?.ok_or_else(|| {
             anyhow::anyhow!("branch name required (provide as argument or via stdin)")
         })?,
     };
@@ -187,42 +383,111 @@ pub fn cmd_new(
     // Parse branch input to detect GitHub issue/PR references
     let branch_input = integrations::gh::BranchInput::parse(branch);
 
+    // Names resolved from GitHub were already accepted by git upstream, and
+    // detached worktrees take a commit-ish rather than a branch name
+    let from_github = matches!(branch_input, integrations::gh::BranchInput::Github(_))
+        && config.integrations.gh.enabled;
+    let validate_branch = !detach && !from_github;
+
     // Resolve actual branch name and optional start point from GitHub if needed
+    let mut github_ref = None;
     let (actual_branch, actual_start_point) = match branch_input {
+        integrations::gh::BranchInput::Github(number) if !merge_refs.is_empty() => {
+            anyhow::bail!(
+                "--merge cannot be combined with a GitHub reference (#{number}); \
+                 name the new branch instead"
+            );
+        }
+        integrations::gh::BranchInput::Github(number) if detach => {
+            anyhow::bail!(
+                "--detach cannot be combined with a GitHub reference (#{number}); \
+                 pass a commit, tag or branch instead"
+            );
+        }
+        // Detached worktrees take refs literally (no GitHub lookup)
+        _ if detach => {
+            let (name, commitish) = resolve_detached_target(branch, start_point);
+            (name, Some(commitish))
+        }
         integrations::gh::BranchInput::Github(number) if config.integrations.gh.enabled => {
             let gh_client = integrations::gh::RealGhClient;
-            resolve_github_ref(&gh_client, number, start_point, &repo_root, color_mode)?
+            let url_repo = integrations::gh::parse_github_url(branch).map(|(repo, _)| repo);
+            let (branch, start_point, resolved) = resolve_github_ref(
+                &gh_client,
+                number,
+                url_repo.as_deref(),
+                start_point,
+                &repo_root,
+                config.integrations.gh.remote.as_deref(),
+                color_mode,
+            )?;
+            github_ref = Some(resolved);
+            (branch, start_point)
         }
-        integrations::gh::BranchInput::Github(number) => {
+        integrations::gh::BranchInput::Github(_) => {
             // GitHub integration is disabled
             eprintln!(
                 "{}",
                 color::warn(
                     color_mode,
                     &format!(
-                        "GitHub integration is disabled. Treating '#{number}' as a literal branch name.\n\
+                        "GitHub integration is disabled. Treating '{branch}' as a literal branch name.\n\
                          To enable GitHub integration, set enabled = true in [integration.gh] in your global config."
                     )
                 )
             );
             (branch.to_string(), start_point.map(String::from))
         }
-        integrations::gh::BranchInput::Plain(name) => (name, start_point.map(String::from)),
+        integrations::gh::BranchInput::Plain(name) => (
+            resolve_ticket_or_literal(name, &config.integrations.resolver, color_mode),
+            start_point.map(String::from),
+        ),
     };
 
     let branch = &actual_branch;
-    let start_point = actual_start_point.as_deref();
 
-    // Determine if tmux should be used based on flags and config
-    let use_tmux = should_use_tmux(config.integrations.tmux.behavior, tmux, no_tmux);
+    if validate_branch {
+        domain::branch::validate(branch)?;
+    }
 
-    // Early detection if tmux integration is requested
-    if use_tmux {
-        let launcher = integrations::tmux::RealTmuxLauncher;
-        launcher.detect()?;
+    let actual_start_point = effective_start_point(
+        &RealGitClient,
+        actual_start_point.as_deref(),
+        config.worktree.default_start_point.as_deref(),
+        branch,
+        detach,
+        &repo_root,
+    );
+    let start_point = actual_start_point.as_deref();
+    if let (true, Some(commitish)) = (detach, start_point) {
+        verify_commitish(&RealGitClient, commitish, &repo_root)?;
     }
+    if !merge_refs.is_empty() {
+        if RealGitClient.branch_exists(&format!("refs/heads/{branch}"), Some(&repo_root))? {
+            anyhow::bail!(
+                "--merge creates a new branch, but '{branch}' already exists; \
+                 pick another name or remove the branch first"
+            );
+        }
+        for commitish in start_point
+            .into_iter()
+            .chain(merge_refs.iter().map(String::as_str))
+        {
+            verify_commitish(&RealGitClient, commitish, &repo_root)?;
+        }
+    }
+
+    let use_tmux = resolve_tmux(&config.integrations.tmux, tmux, no_tmux, color_mode)?;
+
+    check_worktree_location(&config.worktree, &repo_root, branch, color_mode)?;
+
+    let carried = if carry_changes {
+        carry::stash_changes(&RealGitClient, branch)?
+    } else {
+        None
+    };
 
-    let mp = MultiProgress::new();
+    let mp = hooks::new_multi_progress();
     let is_tty = color_mode.should_colorize();
 
     // Header spinner (TTY) — after GH fetch, before git worktree add
@@ -243,36 +508,82 @@ pub fn cmd_new(
     // Resolve zoxide gating before handing control to the service.
     let zoxide_enabled = config.integrations.zoxide.enabled && is_zoxide_available();
 
+    // Only probe for an existing branch when a rollback could need the answer
+    let created_branch = config.hooks.on_create_failure != config::CreateFailureAction::Keep
+        && creates_branch(&RealGitClient, branch, start_point, detach, &repo_root);
+    let rollback_path = Cell::new(None);
+    let hook_summary = Cell::new(HookSummary::default());
+    let merge_conflict = Cell::new(None);
+
     let service = WorktreeService::new(RealGitClient, RealZoxideClient);
-    let hook_actions = &config.hooks.create;
+    let manager = WorktreeManager::new(RealGitClient, &repo_root, config.worktree.clone());
+    let opts = CreateOptions {
+        start_point: start_point.map(String::from),
+        detach,
+    };
     let req = CreateWorktreeRequest {
-        branch,
-        start_point,
-        repo_root: &repo_root,
-        path_template: &config.worktree.dir,
         zoxide_enabled,
+        zoxide_score: config.integrations.zoxide.score,
+        ..manager.create_request(branch, &opts)
     };
 
+    let create_hooks = config::Hooks {
+        create: effective_create_actions(&config.hooks.create, &hook_overrides, no_hooks),
+        ..config.hooks.clone()
+    };
     let result = service.create(&req, |path| {
         // non-TTY: print header before hooks (rm/sync pattern)
         if !is_tty {
-            eprintln!("{}", color::success(color_mode, format!("Added {branch}")));
+            color::eprint_status(color::success(
+                color_mode,
+                report::created_worktree(branch, path),
+            ));
         }
 
-        if !hook_actions.run.is_empty()
-            || !hook_actions.copy.is_empty()
-            || !hook_actions.link.is_empty()
-        {
-            hooks::execute_hooks_lenient_with_mp(
-                hook_actions,
-                path,
-                &repo_root,
-                color_mode,
-                "  ",
-                &mp,
-            );
+        if !merge_refs.is_empty() {
+            let outcome = merge::merge_refs(&RealGitClient, merge_refs, path)
+                .inspect_err(|_| rollback_path.set(Some(path.to_path_buf())))?;
+            for rev in &outcome.merged {
+                hooks::emit_line(
+                    &mp,
+                    is_tty,
+                    format!("  {}", color::success(color_mode, format!("Merged {rev}"))),
+                );
+            }
+            let conflicted = outcome.conflict.is_some();
+            merge_conflict.set(outcome.conflict);
+            if conflicted && !hooks_on_conflict {
+                hooks::emit_warning(
+                    &mp,
+                    is_tty,
+                    format!(
+                        "  {}",
+                        color::warn(
+                            color_mode,
+                            "Skipped create hooks: the merge conflicts (--hooks-on-conflict runs them)"
+                        )
+                    ),
+                );
+                return Ok(());
+            }
         }
 
+        if no_hooks {
+            warn_create_hooks_skipped(&config.hooks, color_mode, &mp);
+        }
+
+        let summary = run_create_hooks(
+            &create_hooks,
+            path,
+            &repo_root,
+            &HookVars::new((!detach).then_some(branch), path, &repo_root)
+                .with_env(github_ref.iter().flat_map(GithubRef::hook_env)),
+            branch,
+            color_mode,
+            &mp,
+        )
+        .inspect_err(|_| rollback_path.set(Some(path.to_path_buf())))?;
+        hook_summary.set(summary);
         Ok(())
     });
 
@@ -281,82 +592,199 @@ pub fn cmd_new(
             if let Some(pb) = header_pb {
                 pb.finish_and_clear();
             }
+            if let Some(path) = rollback_path.take() {
+                rollback_created_worktree(
+                    &RealGitClient,
+                    &path,
+                    created_branch.then_some(branch.as_str()),
+                    &repo_root,
+                    color_mode,
+                );
+            }
+            if let Some(carried) = &carried {
+                carried.restore(&RealGitClient, color_mode);
+            }
             return Err(e);
         }
         Ok(path) => path,
     };
 
-    // Finish header: Adding → Added
+    // Finish header: Adding → Created
     if let Some(pb) = header_pb {
         pb.set_style(ProgressStyle::with_template("{msg}").unwrap());
         pb.finish_with_message(format!(
             "{}",
-            color::success(color_mode, format!("Added {branch}"))
+            color::success(color_mode, report::created_worktree(branch, &worktree_path))
         ));
     }
+    let hook_summary = hook_summary.take();
+    print_hook_summary(branch, &hook_summary, color_mode);
+
+    // Remember the PR/issue so listings can show it next to the branch
+    if let Some(github_ref) = &github_ref {
+        if let Err(e) = integrations::gh::meta::save(&worktree_path, github_ref) {
+            eprintln!(
+                "{}",
+                color::warn(
+                    color_mode,
+                    format!(
+                        "Could not record #{} for the worktree: {e:#}",
+                        github_ref.number
+                    )
+                )
+            );
+        }
+    }
+
+    let carry_conflict = match &carried {
+        Some(carried) => match carried.pop_into(&RealGitClient, &worktree_path)? {
+            CarryOutcome::Applied => None,
+            CarryOutcome::Conflicts(conflict) => {
+                conflict.report(&worktree_path, color_mode);
+                Some(conflict)
+            }
+        },
+        None => None,
+    };
+
+    let merge_conflict = merge_conflict.take();
+    if let Some(conflict) = &merge_conflict {
+        conflict.report(&worktree_path, color_mode);
+    }
 
-    // Create tmux window or pane if enabled
     if use_tmux {
-        let launcher = integrations::tmux::RealTmuxLauncher;
-        let result = match config.integrations.tmux.create.as_str() {
-            "pane" => launcher.create_pane(&worktree_path),
-            _ => launcher.create_window(&worktree_path, branch),
+        launch_tmux(
+            &config.integrations.tmux,
+            &worktree_path,
+            branch,
+            github_ref
+                .as_ref()
+                .map(|github_ref| github_ref.title.as_str()),
+        );
+    }
+
+    if porcelain {
+        let result = AddResult {
+            path: &worktree_path,
+            branch: (!detach).then_some(branch.as_str()),
+            created: true,
+            start_point,
         };
-        if let Err(e) = result {
-            eprintln!("Warning: tmux creation failed: {e}");
-        }
-        // Don't print path to stdout when using tmux
-        // (prevents shell integration from cd'ing in the calling shell)
-    } else {
-        // Print normalized absolute path to STDOUT for shell wrapper integration
+        println!("{}", result.to_porcelain());
+    } else if !use_tmux {
+        // Print normalized absolute path to STDOUT for shell wrapper integration.
+        // Not with tmux, so shell integration doesn't cd the calling shell.
         println!("{}", normalize_absolute_path(&worktree_path));
     }
 
+    if let Some(conflict) = carry_conflict {
+        return Err(conflict.into());
+    }
+    if let Some(conflict) = merge_conflict {
+        return Err(conflict.into());
+    }
+    if hook_summary.has_errors() && config.hooks.create.fail_on_error {
+        return Err(partial_hook_failure(branch, &worktree_path));
+    }
+
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::integrations::git::tests::MockGitClient;
+    use std::collections::HashMap;
+    use std::path::Path;
 
-    #[test]
-    fn test_should_use_tmux_no_tmux_flag_priority() {
-        use config::TmuxBehavior;
-        // --no-tmux has highest priority
-        assert!(!should_use_tmux(TmuxBehavior::Always, true, true));
-        assert!(!should_use_tmux(TmuxBehavior::Always, false, true));
-        assert!(!should_use_tmux(TmuxBehavior::Auto, true, true));
-        assert!(!should_use_tmux(TmuxBehavior::Auto, false, true));
+    fn parse_porcelain(output: &str) -> HashMap<&str, &str> {
+        output
+            .lines()
+            .map(|line| line.split_once('=').expect("key=value line"))
+            .collect()
+    }
+
+    fn fork_pr() -> integrations::gh::PrInfo {
+        integrations::gh::PrInfo {
+            number: 123,
+            title: "Fix login".to_string(),
+            url: "https://github.com/owner/repo/pull/123".to_string(),
+            head_ref_name: "feature/login".to_string(),
+            is_cross_repository: true,
+        }
     }
 
     #[test]
-    fn test_should_use_tmux_tmux_flag_priority() {
-        use config::TmuxBehavior;
-        // --tmux has second priority
-        assert!(should_use_tmux(TmuxBehavior::Never, true, false));
-        assert!(should_use_tmux(TmuxBehavior::Auto, true, false));
-        assert!(should_use_tmux(TmuxBehavior::Always, true, false));
+    fn test_add_result_porcelain_keys() {
+        let result = AddResult {
+            path: Path::new("/repo-worktrees/feature"),
+            branch: Some("feature"),
+            created: true,
+            start_point: None,
+        };
+        let output = result.to_porcelain();
+        let fields = parse_porcelain(&output);
+        assert_eq!(fields.len(), 4);
+        assert_eq!(fields["path"], "/repo-worktrees/feature");
+        assert_eq!(fields["branch"], "feature");
+        assert_eq!(fields["created"], "true");
+        assert_eq!(fields["start_point"], "");
     }
 
     #[test]
-    fn test_should_use_tmux_behavior_auto() {
-        use config::TmuxBehavior;
-        // behavior=Auto defaults to false
-        assert!(!should_use_tmux(TmuxBehavior::Auto, false, false));
+    fn test_add_result_porcelain_detached() {
+        let result = AddResult {
+            path: Path::new("/repo-worktrees/v1.0"),
+            branch: None,
+            created: true,
+            start_point: Some("v1.0"),
+        };
+        let output = result.to_porcelain();
+        let fields = parse_porcelain(&output);
+        assert_eq!(fields["branch"], "");
+        assert_eq!(fields["start_point"], "v1.0");
     }
 
     #[test]
-    fn test_should_use_tmux_behavior_always() {
-        use config::TmuxBehavior;
-        // behavior=Always enables tmux
-        assert!(should_use_tmux(TmuxBehavior::Always, false, false));
+    fn test_fork_pr_branch_conflict_reports_renamed_branch() {
+        let git = MockGitClient {
+            branch_exists_value: true,
+            ..Default::default()
+        };
+        let (branch, start_point) = process_pr(
+            &git,
+            &fork_pr(),
+            123,
+            "origin",
+            Path::new("/repo"),
+            color::ColorMode::Never,
+        )
+        .unwrap();
+
+        let result = AddResult {
+            path: Path::new("/repo-worktrees/pr-123-feature-login"),
+            branch: Some(&branch),
+            created: true,
+            start_point: start_point.as_deref(),
+        };
+        let output = result.to_porcelain();
+        let fields = parse_porcelain(&output);
+        assert_eq!(fields["branch"], "pr-123-feature-login");
+        assert_eq!(fields["start_point"], "refs/ofsht/pr/123");
     }
 
     #[test]
-    fn test_should_use_tmux_behavior_never() {
-        use config::TmuxBehavior;
-        // behavior=Never disables tmux (unless --tmux is specified)
-        assert!(!should_use_tmux(TmuxBehavior::Never, false, false));
+    fn test_fork_pr_without_conflict_keeps_branch_name() {
+        let (branch, _) = process_pr(
+            &MockGitClient::default(),
+            &fork_pr(),
+            123,
+            "origin",
+            Path::new("/repo"),
+            color::ColorMode::Never,
+        )
+        .unwrap();
+        assert_eq!(branch, "feature/login");
     }
 
     #[test]
@@ -373,13 +801,70 @@ mod tests {
             &mock,
             33,
             None,
+            None,
             std::path::Path::new("/tmp"),
+            None,
             color::ColorMode::Never,
         );
 
-        let (branch, start_point) = result.unwrap();
+        let (branch, start_point, github_ref) = result.unwrap();
         assert_eq!(branch, "issue-33");
         assert!(start_point.is_none());
+        assert_eq!(github_ref.kind, GithubRefKind::Issue);
+        assert_eq!(github_ref.number, 33);
+        assert_eq!(github_ref.url, "https://github.com/owner/repo/issues/33");
+    }
+
+    #[test]
+    fn test_github_ref_is_exposed_to_create_hooks() {
+        let mock = integrations::gh::MockGhClient::new()
+            .with_pr_error("not found")
+            .with_issue(integrations::gh::IssueInfo {
+                number: 33,
+                title: "Crash on \"save\"".to_string(),
+                url: "https://github.com/owner/repo/issues/33".to_string(),
+            });
+        let (branch, _, github_ref) = resolve_github_ref(
+            &mock,
+            33,
+            None,
+            None,
+            Path::new("/tmp"),
+            None,
+            color::ColorMode::Never,
+        )
+        .unwrap();
+
+        let temp = tempfile::tempdir().unwrap();
+        let hooks = config::Hooks {
+            create: config::HookActions {
+                run: vec![
+                    "printf '%s|%s|%s|%s' \"$OFSHT_GH_KIND\" \"$OFSHT_GH_NUMBER\" \
+                     \"$OFSHT_GH_TITLE\" \"$OFSHT_GH_URL\" > gh-env"
+                        .into(),
+                ],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let vars =
+            HookVars::new(Some(&branch), temp.path(), temp.path()).with_env(github_ref.hook_env());
+        let summary = run_create_hooks(
+            &hooks,
+            temp.path(),
+            temp.path(),
+            &vars,
+            &branch,
+            color::ColorMode::Never,
+            &hooks::new_multi_progress(),
+        )
+        .unwrap();
+
+        assert_eq!(summary.commands_run, 1);
+        assert_eq!(
+            std::fs::read_to_string(temp.path().join("gh-env")).unwrap(),
+            "issue|33|Crash on \"save\"|https://github.com/owner/repo/issues/33"
+        );
     }
 
     #[test]
@@ -395,12 +880,14 @@ mod tests {
         let result = resolve_github_ref(
             &mock,
             33,
+            None,
             Some("develop"),
             std::path::Path::new("/tmp"),
+            None,
             color::ColorMode::Never,
         );
 
-        let (branch, start_point) = result.unwrap();
+        let (branch, start_point, _) = result.unwrap();
         assert_eq!(branch, "issue-33");
         assert_eq!(start_point.as_deref(), Some("develop"));
     }
@@ -408,14 +895,18 @@ mod tests {
     #[test]
     fn test_resolve_github_ref_both_fail() {
         let mock = integrations::gh::MockGhClient::new()
-            .with_pr_error("no pr")
-            .with_issue_error("no issue");
+            .with_pr_error("no pull requests found for branch \"999\"")
+            .with_issue_error(
+                "GraphQL: Could not resolve to an issue or pull request with the number of 999.",
+            );
 
         let result = resolve_github_ref(
             &mock,
             999,
             None,
+            None,
             std::path::Path::new("/tmp"),
+            None,
             color::ColorMode::Never,
         );
 
@@ -426,4 +917,171 @@ mod tests {
             "unexpected error: {err}"
         );
     }
+
+    #[test]
+    fn test_resolve_github_ref_unauthenticated() {
+        let mock = integrations::gh::MockGhClient::new().unauthenticated();
+
+        let err = resolve_github_ref(
+            &mock,
+            123,
+            None,
+            None,
+            std::path::Path::new("/tmp"),
+            None,
+            color::ColorMode::Never,
+        )
+        .unwrap_err()
+        .to_string();
+        assert_eq!(
+            err,
+            "GitHub CLI is not authenticated. \
+             Run `gh auth login` or use a plain branch name instead of #123."
+        );
+    }
+
+    #[test]
+    fn test_resolve_github_ref_network_error_is_not_reported_as_invalid_number() {
+        let mock = integrations::gh::MockGhClient::new()
+            .with_pr_error("error connecting to api.github.com")
+            .with_issue(integrations::gh::IssueInfo {
+                number: 33,
+                title: "Test issue".to_string(),
+                url: "https://github.com/owner/repo/issues/33".to_string(),
+            });
+
+        let err = resolve_github_ref(
+            &mock,
+            33,
+            None,
+            None,
+            std::path::Path::new("/tmp"),
+            None,
+            color::ColorMode::Never,
+        )
+        .unwrap_err()
+        .to_string();
+        assert_eq!(
+            err,
+            "Failed to look up #33 on GitHub: error connecting to api.github.com"
+        );
+
+        // Same for the issue lookup after the PR lookup found nothing
+        let mock = integrations::gh::MockGhClient::new()
+            .with_pr_error("no pull requests found")
+            .with_issue_error("HTTP 502: Bad Gateway");
+        let err = resolve_github_ref(
+            &mock,
+            33,
+            None,
+            None,
+            std::path::Path::new("/tmp"),
+            None,
+            color::ColorMode::Never,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("HTTP 502"), "{err}");
+        assert!(!err.contains("not a valid"), "{err}");
+    }
+
+    #[test]
+    fn test_resolve_github_ref_url_for_another_repo_is_rejected() {
+        let mock = integrations::gh::MockGhClient::new()
+            .with_repo("owner/repo")
+            .with_issue(integrations::gh::IssueInfo {
+                number: 33,
+                title: "Test issue".to_string(),
+                url: "https://github.com/owner/repo/issues/33".to_string(),
+            });
+
+        let err = resolve_github_ref(
+            &mock,
+            33,
+            Some("someone/else"),
+            None,
+            std::path::Path::new("/tmp"),
+            None,
+            color::ColorMode::Never,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(
+            err.contains("points at someone/else, but this repository is owner/repo"),
+            "{err}"
+        );
+
+        // Owner and name compare case-insensitively, like GitHub itself
+        let (branch, _, _) = resolve_github_ref(
+            &mock,
+            33,
+            Some("Owner/Repo"),
+            None,
+            std::path::Path::new("/tmp"),
+            None,
+            color::ColorMode::Never,
+        )
+        .unwrap();
+        assert_eq!(branch, "issue-33");
+    }
+
+    #[test]
+    fn test_resolve_github_ref_url_proceeds_when_repository_is_unknown() {
+        // No `with_repo`: gh cannot resolve the current repository
+        let mock = integrations::gh::MockGhClient::new().with_issue(integrations::gh::IssueInfo {
+            number: 33,
+            title: "Test issue".to_string(),
+            url: "https://github.com/owner/repo/issues/33".to_string(),
+        });
+
+        let (branch, _, _) = resolve_github_ref(
+            &mock,
+            33,
+            Some("owner/repo"),
+            None,
+            std::path::Path::new("/tmp"),
+            None,
+            color::ColorMode::Never,
+        )
+        .unwrap();
+        assert_eq!(branch, "issue-33");
+    }
+
+    const REMOTES: &str = "origin\tgit@github.com:me/repo.git (fetch)\n\
+                           origin\tgit@github.com:me/repo.git (push)\n\
+                           upstream\thttps://github.com/owner/repo.git (fetch)\n\
+                           upstream\thttps://github.com/owner/repo.git (push)\n";
+
+    #[test]
+    fn test_resolve_pr_remote_matches_gh_repository() {
+        let gh = integrations::gh::MockGhClient::new().with_repo("owner/repo");
+        let git = crate::integrations::git::tests::MockGitClient {
+            remote_output: REMOTES.to_string(),
+            ..Default::default()
+        };
+        let remote = resolve_pr_remote(&gh, &git, std::path::Path::new("/tmp"), None);
+        assert_eq!(remote, "upstream");
+    }
+
+    #[test]
+    fn test_resolve_pr_remote_config_override() {
+        let gh = integrations::gh::MockGhClient::new().with_repo("owner/repo");
+        let git = crate::integrations::git::tests::MockGitClient {
+            remote_output: REMOTES.to_string(),
+            ..Default::default()
+        };
+        let remote = resolve_pr_remote(&gh, &git, std::path::Path::new("/tmp"), Some("origin"));
+        assert_eq!(remote, "origin");
+    }
+
+    #[test]
+    fn test_resolve_pr_remote_falls_back_to_origin_without_gh_repo() {
+        let gh = integrations::gh::MockGhClient::new();
+        let git = crate::integrations::git::tests::MockGitClient {
+            remote_output: REMOTES.to_string(),
+            ..Default::default()
+        };
+        let remote = resolve_pr_remote(&gh, &git, std::path::Path::new("/tmp"), None);
+        assert_eq!(remote, "origin");
+    }
 }
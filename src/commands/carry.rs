@@ -0,0 +1,256 @@
+//! `add --carry-changes` - Move uncommitted changes into the new worktree
+//!
+//! Changes (including untracked files) are stashed in the current worktree
+//! before the new one is created, then popped there. When the pop conflicts,
+//! git keeps the stash entry; the conflicted files are listed and ofsht exits
+//! with [`CARRY_CONFLICT_EXIT_CODE`] so wrappers can tell this apart from a
+//! failed `add`.
+
+use anyhow::{Context, Result};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::color;
+use crate::domain::status::unmerged_paths;
+use crate::integrations::git::GitClient;
+use crate::path_utils::display_path;
+
+/// Exit status of `ofsht add --carry-changes` when the carried changes conflict
+pub const CARRY_CONFLICT_EXIT_CODE: i32 = 3;
+
+/// Stash entry holding the carried changes
+///
+/// The entry is tracked by commit rather than by `stash@{n}`, which shifts
+/// whenever another stash is pushed in the meantime.
+#[derive(Debug)]
+pub struct CarriedStash {
+    commit: String,
+    source: PathBuf,
+}
+
+/// Stash the uncommitted changes of the worktree containing the current directory
+///
+/// Returns `None` when there is nothing to carry.
+///
+/// # Errors
+/// Returns an error if the current directory is not inside a worktree or the
+/// stash cannot be created
+pub fn stash_changes(git: &impl GitClient, branch: &str) -> Result<Option<CarriedStash>> {
+    let source = git
+        .rev_parse(&["rev-parse", "--show-toplevel"], None)
+        .context("--carry-changes must be run inside a worktree")?;
+    let source = PathBuf::from(source.trim());
+    if git.status_porcelain(&source)?.trim().is_empty() {
+        return Ok(None);
+    }
+    git.stash_push(&format!("ofsht: carry to {branch}"), Some(&source))?;
+    let commit = git.rev_parse(&["rev-parse", "refs/stash"], Some(&source))?;
+    Ok(Some(CarriedStash {
+        commit: commit.trim().to_string(),
+        source,
+    }))
+}
+
+/// Carried changes that conflicted in the new worktree
+#[derive(Debug)]
+pub struct CarryConflict {
+    /// `stash@{n}` still holding the changes
+    pub stash_ref: String,
+    /// Commit of the stash entry
+    pub commit: String,
+    /// Paths left with conflict markers, relative to the worktree
+    pub files: Vec<String>,
+}
+
+impl CarryConflict {
+    /// Print the conflicted files as a tree and where the changes are kept
+    pub fn report(&self, worktree_path: &Path, color_mode: color::ColorMode) {
+        report_conflicts(
+            &format!(
+                "Carried changes conflict in {}:",
+                display_path(worktree_path)
+            ),
+            &self.files,
+            &format!(
+                "Your changes are still in `git stash list` as {} ({}). \
+                 Resolve the conflicts, then run `git stash drop {}`.",
+                self.stash_ref,
+                short_commit(&self.commit),
+                self.stash_ref
+            ),
+            color_mode,
+        );
+    }
+}
+
+/// Print a conflict report to stderr: a warning `header`, the conflicted
+/// `files` as a tree, then `hint` on how to continue
+pub fn report_conflicts(header: &str, files: &[String], hint: &str, color_mode: color::ColorMode) {
+    eprintln!("{}", color::warn(color_mode, header));
+    for (i, file) in files.iter().enumerate() {
+        eprintln!(
+            "{}",
+            color::tree_item(color_mode, file, i + 1 == files.len(), 1)
+        );
+    }
+    eprintln!("{}", color::info(color_mode, hint));
+}
+
+impl fmt::Display for CarryConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "carried changes conflict in {} file(s); they are kept in {}",
+            self.files.len(),
+            self.stash_ref
+        )
+    }
+}
+
+impl std::error::Error for CarryConflict {}
+
+/// Outcome of popping the carried changes into the new worktree
+#[derive(Debug)]
+pub enum CarryOutcome {
+    Applied,
+    Conflicts(CarryConflict),
+}
+
+impl CarriedStash {
+    /// Pop the carried changes into `worktree_path`
+    ///
+    /// # Errors
+    /// Returns an error if the stash entry is gone or the pop fails for a
+    /// reason other than conflicts
+    pub fn pop_into(&self, git: &impl GitClient, worktree_path: &Path) -> Result<CarryOutcome> {
+        let stash_ref = self.stash_ref(git)?;
+        let Err(e) = git.stash_pop(&stash_ref, Some(worktree_path)) else {
+            return Ok(CarryOutcome::Applied);
+        };
+        let files = unmerged_paths(&git.status_porcelain(worktree_path).unwrap_or_default());
+        if files.is_empty() {
+            return Err(e.context(format!(
+                "Failed to apply carried changes; they are still in {stash_ref} ({})",
+                short_commit(&self.commit)
+            )));
+        }
+        Ok(CarryOutcome::Conflicts(CarryConflict {
+            stash_ref,
+            commit: self.commit.clone(),
+            files,
+        }))
+    }
+
+    /// Put the changes back into the worktree they came from (the new
+    /// worktree was not created). Failures are reported as warnings.
+    pub fn restore(&self, git: &impl GitClient, color_mode: color::ColorMode) {
+        let restored = self
+            .stash_ref(git)
+            .and_then(|stash_ref| git.stash_pop(&stash_ref, Some(&self.source)));
+        if let Err(e) = restored {
+            eprintln!(
+                "{}",
+                color::warn(
+                    color_mode,
+                    format!(
+                        "Could not restore carried changes to {}; they are still in `git stash list` ({}): {}",
+                        display_path(&self.source),
+                        short_commit(&self.commit),
+                        e.to_string().trim()
+                    )
+                )
+            );
+        }
+    }
+
+    /// Current `stash@{n}` of the carried entry
+    fn stash_ref(&self, git: &impl GitClient) -> Result<String> {
+        git.stash_list(Some(&self.source))?
+            .lines()
+            .position(|line| line.trim() == self.commit)
+            .map(|n| format!("stash@{{{n}}}"))
+            .with_context(|| {
+                format!(
+                    "Carried changes ({}) are no longer in `git stash list`",
+                    short_commit(&self.commit)
+                )
+            })
+    }
+}
+
+fn short_commit(commit: &str) -> &str {
+    commit.get(..7).unwrap_or(commit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::git::tests::MockGitClient;
+
+    const STASH: &str = "1111111111111111111111111111111111111111";
+
+    fn carried() -> CarriedStash {
+        CarriedStash {
+            commit: STASH.to_string(),
+            source: PathBuf::from("/repo"),
+        }
+    }
+
+    #[test]
+    fn test_stash_changes_skips_clean_worktree() {
+        let git = MockGitClient {
+            rev_parse_output: "/repo\n".to_string(),
+            ..Default::default()
+        };
+        assert!(stash_changes(&git, "feature").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_pop_into_applies_cleanly() {
+        let git = MockGitClient {
+            stash_list_output: format!("{STASH}\n"),
+            ..Default::default()
+        };
+        let outcome = carried().pop_into(&git, Path::new("/wt")).unwrap();
+        assert!(matches!(outcome, CarryOutcome::Applied));
+    }
+
+    #[test]
+    fn test_pop_into_reports_conflicts_with_current_stash_ref() {
+        let git = MockGitClient {
+            stash_list_output: format!("2222222222222222222222222222222222222222\n{STASH}\n"),
+            stash_pop_should_fail: true,
+            status_output: "UU src/main.rs\nAA new.txt\n M other.rs\n".to_string(),
+            ..Default::default()
+        };
+        let CarryOutcome::Conflicts(conflict) = carried().pop_into(&git, Path::new("/wt")).unwrap()
+        else {
+            panic!("expected conflicts");
+        };
+        assert_eq!(conflict.stash_ref, "stash@{1}");
+        assert_eq!(conflict.files, ["src/main.rs", "new.txt"]);
+        assert_eq!(
+            conflict.to_string(),
+            "carried changes conflict in 2 file(s); they are kept in stash@{1}"
+        );
+    }
+
+    #[test]
+    fn test_pop_into_failure_without_conflicts_is_an_error() {
+        let git = MockGitClient {
+            stash_list_output: format!("{STASH}\n"),
+            stash_pop_should_fail: true,
+            ..Default::default()
+        };
+        let err = carried().pop_into(&git, Path::new("/wt")).unwrap_err();
+        assert!(format!("{err:#}").contains("still in stash@{0} (1111111)"));
+    }
+
+    #[test]
+    fn test_pop_into_missing_stash_entry() {
+        let err = carried()
+            .pop_into(&MockGitClient::default(), Path::new("/wt"))
+            .unwrap_err();
+        assert!(err.to_string().contains("no longer in `git stash list`"));
+    }
+}
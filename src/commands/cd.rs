@@ -1,15 +1,147 @@
 //! Cd command - Navigate to a worktree by branch name
 
 use anyhow::{Context, Result};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use crate::color;
 use crate::commands::common::get_main_repo_root;
+use crate::commands::list_display::{format_worktree_table_with_layout, sort_rows, SortKey};
 use crate::config;
-use crate::domain::worktree::WorktreeList;
+use crate::domain::exclude::WorktreeExclude;
+use crate::domain::worktree::{NameMatch, WorktreeEntry, WorktreeLayout, WorktreeList};
 use crate::integrations;
 use crate::integrations::fzf::FzfPicker;
 use crate::integrations::git::{GitClient, RealGitClient};
-use crate::path_utils::normalize_absolute_path;
+use crate::integrations::zoxide::{is_zoxide_available, RealZoxideClient, ZoxideClient};
+use crate::path_utils::{display_path, normalize_absolute_path};
+
+/// Record a visit to `path` in zoxide so ofsht navigation bumps its frecency
+/// even when the user's shell has no zoxide hook. Failures only warn.
+fn record_zoxide_visit<Z: ZoxideClient>(
+    zoxide: &Z,
+    path: &Path,
+    enabled: bool,
+    color_mode: color::ColorMode,
+) {
+    if !enabled {
+        return;
+    }
+    if let Err(e) = zoxide.add(path, None) {
+        eprintln!(
+            "{}",
+            color::warn(color_mode, format!("Failed to record visit in zoxide: {e}"))
+        );
+    }
+}
+
+/// Record the destination in zoxide, then print it for the shell wrapper
+///
+/// The visit is recorded first so it is in the database by the time the
+/// wrapper `cd`s; a zoxide failure only warns and the path is still printed.
+fn emit_destination(path: &str, zoxide_enabled: bool, color_mode: color::ColorMode) {
+    let target = normalize_absolute_path(&PathBuf::from(path));
+    record_zoxide_visit(
+        &RealZoxideClient,
+        Path::new(&target),
+        zoxide_enabled,
+        color_mode,
+    );
+    println!("{target}");
+}
+
+/// Make sure the directory of a resolved worktree exists before `cd` prints it
+///
+/// A prunable worktree (directory deleted by hand) is an error naming the
+/// worktree, unless `restore` is set: the stale registration is then removed
+/// and the worktree checked out again on the same branch (or commit).
+fn ensure_worktree_dir(
+    git: &impl GitClient,
+    entry: &WorktreeEntry,
+    restore: bool,
+    repo_root: &Path,
+    color_mode: color::ColorMode,
+) -> Result<()> {
+    let path = Path::new(&entry.path);
+    if path.exists() {
+        return Ok(());
+    }
+    let name = entry.branch.as_deref().unwrap_or(&entry.path);
+    if !restore {
+        anyhow::bail!(
+            "Worktree '{name}' is prunable: {} no longer exists\n\
+             Run `ofsht rm {name}` to clean it up, `git worktree repair` if it was moved, \
+             or `ofsht cd --restore {name}` to check it out again",
+            entry.path
+        );
+    }
+
+    git.remove_worktree(path, false, Some(repo_root))
+        .with_context(|| format!("Failed to restore '{name}'"))?;
+    let recreated = entry.branch.as_deref().map_or_else(
+        || {
+            let commitish = entry.hash.as_deref().unwrap_or("HEAD");
+            git.create_detached_worktree(path, commitish, Some(repo_root))
+        },
+        |branch| git.create_worktree(branch, path, None, Some(repo_root)),
+    );
+    recreated.with_context(|| format!("Failed to restore '{name}'"))?;
+    color::eprint_status(color::success(
+        color_mode,
+        format!("Restored {name} at {}", display_path(path)),
+    ));
+    Ok(())
+}
+
+/// Outcome of the numbered worktree menu
+enum MenuChoice {
+    Picked(WorktreeEntry),
+    /// Empty answer or EOF
+    Cancelled,
+    /// stdin or stderr is not a TTY, so no menu was shown
+    NotInteractive,
+}
+
+/// Pick a worktree from a numbered menu when fzf is disabled or not installed
+///
+/// Rows are the `ofsht ls` table (branch, relative path, last commit, ...) in
+/// its default order, without the worktrees hidden by `worktree.exclude`.
+fn select_from_menu<G: GitClient + Sync>(
+    git: &G,
+    list: &WorktreeList,
+    config: &config::Config,
+    color_mode: color::ColorMode,
+) -> Result<MenuChoice> {
+    let layout = WorktreeLayout::for_list(list, Some(&config.worktree.dir));
+    let entries =
+        WorktreeExclude::new(&config.worktree.exclude)?.filter_entries(list.entries(), &layout);
+    if entries.is_empty() {
+        anyhow::bail!("No worktrees found");
+    }
+    let paths: Vec<PathBuf> = entries.iter().map(|e| PathBuf::from(&e.path)).collect();
+    let mut rows: Vec<_> = entries
+        .into_iter()
+        .zip(git.last_commit_times_parallel(&paths))
+        .collect();
+    // Same order as `ofsht ls`, so menu numbers stay put between runs
+    sort_rows(&mut rows, SortKey::default());
+    let (entries, commit_times): (Vec<_>, Vec<_>) = rows.into_iter().unzip();
+    let labels = format_worktree_table_with_layout(
+        &entries,
+        &commit_times,
+        false,
+        color_mode,
+        Some(config),
+        Some(&layout),
+        None,
+    );
+
+    let Some(selected) = crate::ui::prompt_select("Select a worktree", &labels, false)? else {
+        return Ok(MenuChoice::NotInteractive);
+    };
+    Ok(selected.first().map_or(MenuChoice::Cancelled, |&index| {
+        MenuChoice::Picked(entries[index].clone())
+    }))
+}
 
 /// Navigate to a worktree by branch name
 ///
@@ -17,11 +149,13 @@ use crate::path_utils::normalize_absolute_path;
 /// Returns an error if:
 /// - Git worktree list command fails
 /// - Worktree not found
-/// - Fzf is required but not available
-pub fn cmd_goto(name: Option<&str>, _color_mode: crate::color::ColorMode) -> Result<()> {
-    // Get worktree list
+/// - No name is given, fzf is disabled or not installed, and stdin or stderr
+///   is not a TTY (otherwise a numbered menu is shown)
+#[allow(clippy::too_many_lines)]
+pub fn cmd_goto(name: Option<&str>, restore: bool, color_mode: color::ColorMode) -> Result<()> {
+    // Get worktree list (from the `--repo` repository, if any)
     let git = RealGitClient;
-    let stdout = git.list_worktrees(None)?;
+    let stdout = git.list_worktrees(get_main_repo_root().ok().as_deref())?;
 
     // Resolve name: CLI arg > stdin (when piped) > fzf
     let resolved_name: Option<String> = match name {
@@ -33,16 +167,35 @@ pub fn cmd_goto(name: Option<&str>, _color_mode: crate::color::ColorMode) -> Res
         let repo_root = get_main_repo_root()?;
         let config = config::Config::load_from_repo_root(&repo_root)?;
 
-        if !config.integrations.fzf.enabled {
-            anyhow::bail!("Provide a worktree name or enable fzf in config");
-        }
-
-        if !integrations::fzf::is_fzf_available() {
-            anyhow::bail!("fzf is not installed. Install it or provide a worktree name");
+        let fzf_enabled = config.integrations.fzf.enabled;
+        if !fzf_enabled || !integrations::fzf::is_fzf_available() {
+            let list = WorktreeList::parse(&stdout, None);
+            match select_from_menu(&git, &list, &config, color_mode)? {
+                MenuChoice::Picked(entry) => {
+                    ensure_worktree_dir(&git, &entry, restore, &repo_root, color_mode)?;
+                    let zoxide_enabled =
+                        config.integrations.zoxide.enabled && is_zoxide_available();
+                    emit_destination(&entry.path, zoxide_enabled, color_mode);
+                    return Ok(());
+                }
+                MenuChoice::Cancelled => return Ok(()),
+                MenuChoice::NotInteractive if !fzf_enabled => {
+                    anyhow::bail!("Provide a worktree name or enable fzf in config");
+                }
+                MenuChoice::NotInteractive => {
+                    anyhow::bail!("fzf is not installed. Install it or provide a worktree name");
+                }
+            }
         }
 
         // Build items for fzf
-        let items = integrations::fzf::build_worktree_items(&stdout);
+        let exclude = WorktreeExclude::new(&config.worktree.exclude)?;
+        let items = integrations::fzf::build_worktree_items(
+            &stdout,
+            &exclude,
+            Some(&config.worktree.dir),
+            color_mode,
+        );
 
         if items.is_empty() {
             anyhow::bail!("No worktrees found");
@@ -57,60 +210,219 @@ pub fn cmd_goto(name: Option<&str>, _color_mode: crate::color::ColorMode) -> Res
             return Ok(());
         }
 
-        println!("{}", normalize_absolute_path(&PathBuf::from(&selected[0])));
+        let list = WorktreeList::parse(&stdout, None);
+        if let Some(entry) = list.entries().iter().find(|e| e.path == selected[0]) {
+            ensure_worktree_dir(&git, entry, restore, &repo_root, color_mode)?;
+        }
+        let zoxide_enabled = config.integrations.zoxide.enabled && is_zoxide_available();
+        emit_destination(&selected[0], zoxide_enabled, color_mode);
         return Ok(());
     };
     let name = name.as_str();
 
-    // Parse the porcelain output once and reuse the WorktreeList for all 3
-    // resolution passes (`@`, branch name, relative path, absolute path).
+    // Parse the porcelain output once and reuse the WorktreeList for every
+    // resolution pass (`@`, then `WorktreeList::match_name`).
     let list = WorktreeList::parse(&stdout, None);
 
+    // Load config to get worktree template (for relative path resolution)
+    // and the zoxide integration switch
+    let repo_root = get_main_repo_root()?;
+    let config = config::Config::load_from_repo_root(&repo_root).ok();
+    let zoxide_enabled = config
+        .as_ref()
+        .is_some_and(|c| c.integrations.zoxide.enabled)
+        && is_zoxide_available();
+
     // Special handling for "@" (main worktree)
     if name == "@" {
         let main_path = list
             .main()
             .map(|m| m.path.as_str())
             .context("git worktree list returned no entries")?;
-        println!("{}", normalize_absolute_path(&PathBuf::from(main_path)));
+        emit_destination(main_path, zoxide_enabled, color_mode);
         return Ok(());
     }
 
-    // Load config to get worktree template (for relative path resolution)
-    let repo_root = get_main_repo_root()?;
-    let config = config::Config::load_from_repo_root(&repo_root).ok();
+    // Branch name, then relative path (if config is available), then absolute
+    // path, then a case-insensitive substring of a branch or relative path
+    let layout = config
+        .as_ref()
+        .map(|config| WorktreeLayout::for_list(&list, Some(&config.worktree.dir)));
+    let candidates = match list.match_name(name, layout.as_ref().and_then(WorktreeLayout::root)) {
+        NameMatch::Unique(entry) => {
+            ensure_worktree_dir(&git, entry, restore, &repo_root, color_mode)?;
+            emit_destination(&entry.path, zoxide_enabled, color_mode);
+            return Ok(());
+        }
+        NameMatch::NotFound => anyhow::bail!("Worktree not found: {name}"),
+        NameMatch::Ambiguous(candidates) => candidates,
+    };
 
-    // Priority 1: Try to find by branch name
-    if let Some(entry) = list.find_by_branch(name) {
-        println!("{}", normalize_absolute_path(&PathBuf::from(&entry.path)));
+    // Several worktrees contain the query: let fzf narrow them down when it
+    // is available, otherwise list them and fail
+    let fzf_options = config
+        .as_ref()
+        .filter(|c| c.integrations.fzf.enabled && integrations::fzf::is_fzf_available())
+        .map(|c| c.integrations.fzf.options.clone());
+    if let Some(options) = fzf_options {
+        let items: Vec<_> = integrations::fzf::build_worktree_items(
+            &stdout,
+            &WorktreeExclude::default(),
+            config.as_ref().map(|c| c.worktree.dir.as_str()),
+            color_mode,
+        )
+        .into_iter()
+        .filter(|item| candidates.iter().any(|entry| entry.path == item.value))
+        .collect();
+        let selected = integrations::fzf::RealFzfPicker::new(options).pick(&items, false)?;
+        if let Some(entry) = selected
+            .first()
+            .and_then(|path| candidates.iter().find(|entry| &entry.path == path))
+        {
+            ensure_worktree_dir(&git, entry, restore, &repo_root, color_mode)?;
+            emit_destination(&entry.path, zoxide_enabled, color_mode);
+        }
         return Ok(());
     }
 
-    // Priority 2: Try to resolve as relative path (if config is available)
-    if config.is_some() {
-        let worktree_paths: Vec<PathBuf> = list
-            .non_main()
-            .iter()
-            .map(|e| PathBuf::from(&e.path))
-            .collect();
+    eprintln!("'{name}' matches {} worktrees:", candidates.len());
+    for entry in &candidates {
+        eprintln!(
+            "  {}",
+            entry.branch.as_deref().map_or_else(
+                || entry.path.clone(),
+                |branch| format!("{branch} ({})", entry.path)
+            )
+        );
+    }
+    anyhow::bail!("Ambiguous worktree name: {name}")
+}
 
-        if let Some(worktree_root) =
-            crate::domain::worktree::calculate_worktree_root_from_paths(&worktree_paths)
-        {
-            let abs_path = worktree_root.join(name);
-            if let Some(entry) = list.find_by_path(&abs_path) {
-                println!("{}", normalize_absolute_path(&PathBuf::from(&entry.path)));
-                return Ok(());
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// Zoxide mock that records every `add` call
+    #[derive(Default)]
+    struct RecordingZoxideClient {
+        added: RefCell<Vec<PathBuf>>,
+        should_fail: bool,
+    }
+
+    impl ZoxideClient for RecordingZoxideClient {
+        fn add(&self, path: &Path, _score: Option<f64>) -> Result<()> {
+            self.added.borrow_mut().push(path.to_path_buf());
+            if self.should_fail {
+                anyhow::bail!("Mock zoxide failure");
             }
+            Ok(())
         }
     }
 
-    // Priority 3: Try to resolve as absolute path (fallback)
-    let input_path = PathBuf::from(name);
-    if let Some(entry) = list.find_by_path(&input_path) {
-        println!("{}", normalize_absolute_path(&PathBuf::from(&entry.path)));
-        return Ok(());
+    #[test]
+    fn test_record_zoxide_visit_when_enabled() {
+        let zoxide = RecordingZoxideClient::default();
+        record_zoxide_visit(
+            &zoxide,
+            Path::new("/repo-worktrees/feature"),
+            true,
+            color::ColorMode::Never,
+        );
+        assert_eq!(
+            *zoxide.added.borrow(),
+            vec![PathBuf::from("/repo-worktrees/feature")]
+        );
+    }
+
+    #[test]
+    fn test_record_zoxide_visit_skipped_when_disabled() {
+        let zoxide = RecordingZoxideClient::default();
+        record_zoxide_visit(
+            &zoxide,
+            Path::new("/repo-worktrees/feature"),
+            false,
+            color::ColorMode::Never,
+        );
+        assert!(zoxide.added.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_record_zoxide_visit_failure_is_not_fatal() {
+        let zoxide = RecordingZoxideClient {
+            should_fail: true,
+            ..Default::default()
+        };
+        // Must not panic; the failure is reported as a warning only
+        record_zoxide_visit(
+            &zoxide,
+            Path::new("/repo-worktrees/feature"),
+            true,
+            color::ColorMode::Never,
+        );
+        assert_eq!(zoxide.added.borrow().len(), 1);
+    }
+
+    fn missing_entry(branch: Option<&str>) -> WorktreeEntry {
+        WorktreeEntry {
+            path: "/nonexistent/repo-worktrees/feature".to_string(),
+            branch: branch.map(String::from),
+            hash: Some("abc12345".to_string()),
+            ..WorktreeEntry::default()
+        }
+    }
+
+    #[test]
+    fn test_ensure_worktree_dir_existing_is_ok() {
+        let temp = tempfile::tempdir().unwrap();
+        let entry = WorktreeEntry {
+            path: temp.path().to_string_lossy().into_owned(),
+            ..WorktreeEntry::default()
+        };
+        let git = crate::integrations::git::tests::MockGitClient {
+            remove_worktree_should_fail: true, // would fail if reached
+            ..Default::default()
+        };
+        assert!(ensure_worktree_dir(
+            &git,
+            &entry,
+            false,
+            Path::new("/repo"),
+            color::ColorMode::Never
+        )
+        .is_ok());
     }
 
-    anyhow::bail!("Worktree not found: {name}");
+    #[test]
+    fn test_ensure_worktree_dir_missing_names_branch() {
+        let err = ensure_worktree_dir(
+            &crate::integrations::git::tests::MockGitClient::default(),
+            &missing_entry(Some("feature")),
+            false,
+            Path::new("/repo"),
+            color::ColorMode::Never,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("Worktree 'feature' is prunable"), "{err}");
+        assert!(err.contains("ofsht rm feature"), "{err}");
+        assert!(err.contains("git worktree repair"), "{err}");
+    }
+
+    #[test]
+    fn test_ensure_worktree_dir_restore_propagates_git_failure() {
+        let git = crate::integrations::git::tests::MockGitClient {
+            create_should_fail: true,
+            ..Default::default()
+        };
+        let err = ensure_worktree_dir(
+            &git,
+            &missing_entry(Some("feature")),
+            true,
+            Path::new("/repo"),
+            color::ColorMode::Never,
+        )
+        .unwrap_err();
+        assert!(format!("{err:#}").contains("Failed to restore 'feature'"));
+    }
 }
@@ -4,46 +4,190 @@
 
 use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
 
-use crate::domain::worktree::WorktreeList;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+
+use crate::color;
+use crate::config::{
+    integration_disabled_by_env, CreateFailureAction, HookActions, Hooks, TmuxBehavior, TmuxConfig,
+    WorktreeConfig, NO_TMUX_ENV,
+};
+use crate::domain::worktree::{
+    directory_sizes, expand_path_template, validate_worktree_location, WorktreeList,
+    WorktreeLocation,
+};
+use crate::hooks::{self, HookSummary, HookVars};
 use crate::integrations::git::{GitClient, RealGitClient};
-use crate::path_utils::canonicalize_allow_missing;
+use crate::integrations::tmux::{self, RealTmuxLauncher, TmuxLauncher};
+use crate::path_utils::{canonicalize_allow_missing, display_path};
+use crate::service::WorktreeManager;
+
+/// Repository selected with the global `--repo` flag
+///
+/// Process-wide so that every command's [`get_main_repo_root`] honors it
+/// without threading a base path through each of them. It is only consulted
+/// there: unlike `-C` (a real `set_current_dir` in `main`, done first so a
+/// relative `--repo` resolves from it), the working directory is unchanged,
+/// so relative paths passed to commands still resolve from where ofsht runs.
+static REPO_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Make [`get_main_repo_root`] resolve from `path` instead of the current directory
+///
+/// Only the first call has an effect; it is made once from `main` for `--repo`.
+pub fn set_repo_override(path: PathBuf) {
+    REPO_OVERRIDE.set(path).ok();
+}
 
 /// Get the main repository root path
 ///
+/// Resolves from the `--repo` path when one was given, otherwise from the
+/// current directory.
+///
 /// # Errors
 /// Returns an error if:
 /// - Not in a git repository
 /// - Git command fails
 /// - Path canonicalization fails
 pub fn get_main_repo_root() -> Result<PathBuf> {
+    get_main_repo_root_from(REPO_OVERRIDE.get().map(PathBuf::as_path))
+}
+
+/// Get the main repository root path of the repository containing `base`
+/// (or the current directory when `base` is `None`)
+///
+/// # Errors
+/// Returns an error if `base` is not inside a git repository or git fails
+pub fn get_main_repo_root_from(base: Option<&Path>) -> Result<PathBuf> {
     let git = RealGitClient;
     let stdout = git
-        .rev_parse(&["rev-parse", "--git-common-dir"], None)
+        .rev_parse(&["rev-parse", "--git-common-dir"], base)
         .map_err(|e| {
-            anyhow::anyhow!(
-                "Not in a git repository. Please run ofsht from within a git repository.\nGit error: {e}"
+            base.map_or_else(
+                || {
+                    anyhow::anyhow!(
+                        "Not in a git repository. Please run ofsht from within a git repository.\nGit error: {e}"
+                    )
+                },
+                |base| {
+                    anyhow::anyhow!(
+                        "Not a git repository: {}\nGit error: {e}",
+                        display_path(base)
+                    )
+                },
             )
         })?;
 
-    let git_dir = stdout.trim().to_string();
-    let git_path = PathBuf::from(&git_dir);
+    let common_dir = absolute_git_path(stdout.trim(), base)?;
 
-    // Convert relative path to absolute
-    let abs_git_path = if git_path.is_absolute() {
-        git_path
-    } else {
-        std::env::current_dir()?.join(git_path).canonicalize()?
-    };
+    // Bare repository: there is no main worktree, the repository itself is the root
+    let common_dir_arg = common_dir.to_string_lossy();
+    let is_bare = git
+        .rev_parse(
+            &[
+                "--git-dir",
+                &common_dir_arg,
+                "rev-parse",
+                "--is-bare-repository",
+            ],
+            base,
+        )
+        .is_ok_and(|out| out.trim() == "true");
+    if is_bare {
+        return Ok(common_dir);
+    }
+
+    // In the main worktree (or with an external GIT_DIR / GIT_WORK_TREE) the
+    // git dir is the common dir and git knows the top level directly
+    let in_main = git
+        .rev_parse(&["rev-parse", "--git-dir"], base)
+        .ok()
+        .and_then(|out| absolute_git_path(out.trim(), base).ok())
+        .is_some_and(|git_dir| same_path(&git_dir, &common_dir));
+    if in_main {
+        if let Ok(toplevel) = git.rev_parse(&["rev-parse", "--show-toplevel"], base) {
+            let toplevel = toplevel.trim();
+            if !toplevel.is_empty() {
+                return Ok(PathBuf::from(toplevel));
+            }
+        }
+    }
 
-    // Parent of .git directory is the repository root
-    // For bare repositories, git_dir itself might be the root
-    let repo_root = abs_git_path
+    // Linked worktree: the main repository root is the parent of the common dir
+    Ok(common_dir
         .parent()
-        .map(PathBuf::from)
-        .unwrap_or(abs_git_path);
+        .map_or_else(|| common_dir.clone(), PathBuf::from))
+}
+
+/// Make a path printed by `git rev-parse` (relative to where git ran) absolute
+fn absolute_git_path(path: &str, base: Option<&Path>) -> Result<PathBuf> {
+    let path = PathBuf::from(path);
+    if path.is_absolute() {
+        return Ok(path);
+    }
+    let cwd = std::env::current_dir()?;
+    Ok(base
+        .map_or_else(|| cwd.clone(), |b| cwd.join(b))
+        .join(path)
+        .canonicalize()?)
+}
+
+/// Whether two paths name the same directory (symlinks resolved when possible)
+fn same_path(a: &Path, b: &Path) -> bool {
+    a == b || matches!((a.canonicalize(), b.canonicalize()), (Ok(a), Ok(b)) if a == b)
+}
+
+/// Resolve the `{branch}` name and commit-ish for a detached worktree
+///
+/// `ofsht add --detach <ref>` names the worktree after the ref (sanitized by
+/// [`commitish_dir_name`]), while `ofsht add --detach <name> <ref>` uses
+/// `<name>` as given.
+///
+/// Returns: (`name`, `commitish`)
+#[must_use]
+pub fn resolve_detached_target(name: &str, start_point: Option<&str>) -> (String, String) {
+    start_point.map_or_else(
+        || (commitish_dir_name(name), name.to_string()),
+        |start| (name.to_string(), start.to_string()),
+    )
+}
+
+/// Directory-safe name for a commit-ish such as `release/1.0` or `stash@{0}`:
+/// path separators and revision syntax (`@`, `{}`, `^`, `:` ...) become `-`,
+/// collapsed and trimmed (`release-1.0`, `stash-0`)
+fn commitish_dir_name(commitish: &str) -> String {
+    let mut name = String::with_capacity(commitish.len());
+    for c in commitish.chars() {
+        let c = if c.is_control() || "/\\:@{}^?*[] ".contains(c) {
+            '-'
+        } else {
+            c
+        };
+        if c != '-' || !name.ends_with('-') {
+            name.push(c);
+        }
+    }
+    let name = name.trim_matches('-');
+    if name.is_empty() {
+        "detached".to_string()
+    } else {
+        name.to_string()
+    }
+}
 
-    Ok(repo_root)
+/// Check that `commitish` names a commit before creating a detached worktree at it
+///
+/// # Errors
+/// Returns an error when `git rev-parse --verify <commitish>^{commit}` fails
+pub fn verify_commitish(git: &impl GitClient, commitish: &str, repo_root: &Path) -> Result<()> {
+    if git.branch_exists(&format!("{commitish}^{{commit}}"), Some(repo_root))? {
+        return Ok(());
+    }
+    anyhow::bail!(
+        "'{commitish}' does not resolve to a commit; \
+         pass a commit, tag, branch or stash (e.g. stash@{{0}})"
+    )
 }
 
 /// Resolve a worktree target to its canonical path and metadata
@@ -56,7 +200,7 @@ pub fn get_main_repo_root() -> Result<PathBuf> {
 pub fn resolve_worktree_target(
     name: &str,
     list_stdout: &str,
-    _repo_root: &Path,
+    repo_root: &Path,
 ) -> Result<(PathBuf, PathBuf, Option<String>, bool)> {
     let is_current_worktree_removal = name == ".";
 
@@ -83,12 +227,9 @@ pub fn resolve_worktree_target(
         anyhow::bail!("Cannot remove main worktree");
     }
 
-    let worktree_path: PathBuf;
-    let branch_name: Option<String>;
-    let canonical_path: PathBuf;
-
     // Special handling for "." (current worktree)
-    if let Some(current_path) = current_path_opt {
+    let (worktree_path, branch_name, canonical_path) = if let Some(current_path) = current_path_opt
+    {
         let current_path_buf = PathBuf::from(&current_path);
         let canonical_current = canonicalize_allow_missing(&current_path_buf);
         let main_path_buf = PathBuf::from(&main_path);
@@ -105,55 +246,29 @@ pub fn resolve_worktree_target(
             .find(|e| canonicalize_allow_missing(&PathBuf::from(&e.path)) == canonical_current)
             .and_then(|e| e.branch.clone());
 
-        worktree_path = PathBuf::from(current_path);
-        branch_name = current_branch;
-        canonical_path = canonical_current;
-    } else if let Some(entry) = list.find_by_branch(name) {
-        // Found by branch name (excludes main automatically)
-        worktree_path = PathBuf::from(&entry.path);
-        branch_name = Some(name.to_string());
-        canonical_path = canonicalize_allow_missing(&worktree_path);
-    } else {
-        // Try to resolve as relative path from worktree root
-        let worktree_paths: Vec<PathBuf> = list
-            .non_main()
-            .iter()
-            .map(|e| PathBuf::from(&e.path))
-            .collect();
-
-        let relative_match = crate::domain::worktree::calculate_worktree_root_from_paths(
-            &worktree_paths,
+        (
+            PathBuf::from(current_path),
+            current_branch,
+            canonical_current,
         )
-        .and_then(|root| {
-            let abs_path = root.join(name);
-            list.find_by_path(&abs_path).cloned()
-        });
-
-        if let Some(matched) = relative_match {
-            worktree_path = PathBuf::from(&matched.path);
-            branch_name = matched.branch;
-            canonical_path = canonicalize_allow_missing(&worktree_path);
-        } else {
-            // Fallback: try to resolve as an absolute path
-            let input_path_buf = PathBuf::from(name);
-            let canonical_input = canonicalize_allow_missing(&input_path_buf);
-
-            // Check if it's the main worktree
-            let main_path_buf = PathBuf::from(&main_path);
-            let canonical_main = canonicalize_allow_missing(&main_path_buf);
-            if canonical_input == canonical_main {
+    } else {
+        // Branch name, then relative path from the worktree root, then absolute path
+        let worktree = crate::config::Config::load_from_repo_root(repo_root)
+            .map(|config| config.worktree)
+            .unwrap_or_default();
+        let manager = WorktreeManager::new(RealGitClient, repo_root, worktree);
+        let Some(entry) = manager.find_in(&list, name) else {
+            // The main worktree is never matched; name it explicitly when targeted by path
+            let canonical_main = canonicalize_allow_missing(&PathBuf::from(&main_path));
+            if canonicalize_allow_missing(&PathBuf::from(name)) == canonical_main {
                 anyhow::bail!("Cannot remove main worktree");
             }
-
-            if let Some(entry) = list.find_by_path(&input_path_buf) {
-                worktree_path = PathBuf::from(&entry.path);
-                branch_name = entry.branch.clone();
-                canonical_path = canonical_input;
-            } else {
-                anyhow::bail!("Worktree not found: {name}");
-            }
-        }
-    }
+            anyhow::bail!("Worktree not found: {name}");
+        };
+        let worktree_path = PathBuf::from(&entry.path);
+        let canonical_path = canonicalize_allow_missing(&worktree_path);
+        (worktree_path, entry.branch.clone(), canonical_path)
+    };
 
     Ok((
         canonical_path,
@@ -162,3 +277,638 @@ pub fn resolve_worktree_target(
         is_current_worktree_removal,
     ))
 }
+
+/// Resolve a target like [`resolve_worktree_target`], also accepting `@` for the
+/// main worktree
+///
+/// Returns: (`worktree_path`, `branch_name`)
+///
+/// # Errors
+/// Returns an error if the target cannot be found
+pub fn resolve_worktree_or_main(
+    target: &str,
+    list_stdout: &str,
+    repo_root: &Path,
+) -> Result<(PathBuf, Option<String>)> {
+    if target == "@" {
+        let list = WorktreeList::parse(list_stdout, None);
+        let main = list
+            .main()
+            .context("git worktree list returned no entries")?;
+        return Ok((PathBuf::from(&main.path), main.branch.clone()));
+    }
+    let (_, path, branch, _) = resolve_worktree_target(target, list_stdout, repo_root)?;
+    Ok((path, branch))
+}
+
+/// Per-invocation create hook changes from `--copy`, `--link` and `--skip-pattern`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HookOverrides {
+    /// Extra `copy` patterns
+    pub copy: Vec<String>,
+    /// Extra `link` patterns
+    pub link: Vec<String>,
+    /// Configured `copy` / `link` patterns to drop (exact match)
+    pub skip: Vec<String>,
+}
+
+/// Create hook actions for one `add` / `create` invocation
+///
+/// `--skip-pattern` drops configured copy/link patterns, then the `--copy` /
+/// `--link` patterns are appended. With `no_hooks` every configured action is
+/// dropped, but the command-line ones still run.
+#[must_use]
+pub fn effective_create_actions(
+    configured: &HookActions,
+    overrides: &HookOverrides,
+    no_hooks: bool,
+) -> HookActions {
+    let keep = |patterns: &[String], extra: &[String]| -> Vec<String> {
+        patterns
+            .iter()
+            .filter(|_| !no_hooks)
+            .filter(|p| !overrides.skip.contains(p))
+            .chain(extra)
+            .cloned()
+            .collect()
+    };
+    HookActions {
+        run: if no_hooks {
+            Vec::new()
+        } else {
+            configured.run.clone()
+        },
+        copy: keep(&configured.copy, &overrides.copy),
+        link: keep(&configured.link, &overrides.link),
+        ..configured.clone()
+    }
+}
+
+/// Report that `--no-hooks` skipped the configured create hooks (silent when
+/// there are none)
+pub fn warn_create_hooks_skipped(config: &Hooks, color_mode: color::ColorMode, mp: &MultiProgress) {
+    let actions = &config.create;
+    if actions.run.is_empty() && actions.copy.is_empty() && actions.link.is_empty() {
+        return;
+    }
+    hooks::emit_warning(
+        mp,
+        color_mode.should_colorize(),
+        format!(
+            "  {}",
+            color::warn(color_mode, "Skipped create hooks (--no-hooks)")
+        ),
+    );
+}
+
+/// Check where `worktree.dir` puts the worktree for `branch` before creating it
+///
+/// Warns when it would be nested inside the main repository (unless
+/// `worktree.allow_inside_repo` is set), where every file of the worktree
+/// shows up in the main repository's `git status`.
+///
+/// # Errors
+/// Returns an error when the path cannot be expanded or is the repository root
+pub fn check_worktree_location(
+    config: &WorktreeConfig,
+    repo_root: &Path,
+    branch: &str,
+    color_mode: color::ColorMode,
+) -> Result<()> {
+    let worktree_path = expand_path_template(&config.dir, repo_root, branch)?;
+    let location = validate_worktree_location(&worktree_path, repo_root)?;
+    if location == WorktreeLocation::InsideRepo && !config.allow_inside_repo {
+        eprintln!(
+            "{}",
+            color::warn(
+                color_mode,
+                format!(
+                    "{} is inside the main repository (worktree.dir = \"{}\"); \
+                     add it to .gitignore or use a \"../\" template. \
+                     Set worktree.allow_inside_repo = true to silence this warning.",
+                    display_path(&worktree_path),
+                    config.dir
+                )
+            )
+        );
+    }
+    Ok(())
+}
+
+/// Run create hooks for a freshly added worktree, applying `[hooks] on_create_failure`
+///
+/// Returns the hook error only when the worktree should be rolled back; in every
+/// other case the failure is reported as a warning with an `ofsht rm` hint and the
+/// returned summary (with [`HookSummary::has_errors`]) tells the caller that the
+/// kept worktree had failing hooks.
+///
+/// # Errors
+/// Returns the hook error when `on_create_failure` is `remove`, or `prompt` and
+/// the user agreed to remove the worktree
+pub fn run_create_hooks(
+    config: &Hooks,
+    worktree_path: &Path,
+    repo_root: &Path,
+    vars: &HookVars,
+    label: &str,
+    color_mode: color::ColorMode,
+    mp: &MultiProgress,
+) -> Result<HookSummary> {
+    let actions = &config.create;
+    if actions.run.is_empty() && actions.copy.is_empty() && actions.link.is_empty() {
+        return Ok(HookSummary::default());
+    }
+
+    let actions = hooks::with_placeholders(actions, vars);
+    let summary = hooks::execute_hooks_collect_with_mp(
+        &actions,
+        worktree_path,
+        repo_root,
+        &vars.env,
+        color_mode,
+        config.raw_output,
+        "  ",
+        mp,
+    );
+    if !summary.has_errors() {
+        return Ok(summary);
+    }
+    let err = anyhow::anyhow!("{}", summary.errors.join("; "));
+    // The caller rolls the worktree back on error, taking the hook log with it
+    let rollback_err = || {
+        let errors: Vec<_> = summary
+            .errors
+            .iter()
+            .map(|e| hooks::without_log_note(e))
+            .collect();
+        anyhow::anyhow!("{}", errors.join("; "))
+    };
+
+    if config.on_create_failure == CreateFailureAction::Remove {
+        return Err(rollback_err());
+    }
+
+    let is_tty = color_mode.should_colorize();
+    hooks::emit_warning(
+        mp,
+        is_tty,
+        format!(
+            "  {}",
+            color::warn(color_mode, format!("Hook error: {err}"))
+        ),
+    );
+
+    if config.on_create_failure == CreateFailureAction::Prompt {
+        let question = format!("Remove the new worktree {label}?");
+        if mp
+            .suspend(|| crate::stdin::confirm(&question))?
+            .unwrap_or(false)
+        {
+            return Err(rollback_err());
+        }
+    }
+
+    hooks::emit_warning(
+        mp,
+        is_tty,
+        format!(
+            "  {}",
+            color::warn(
+                color_mode,
+                format!("Kept {label}; run `ofsht rm {label}` to discard it")
+            )
+        ),
+    );
+    Ok(summary)
+}
+
+/// Print the post-create summary line, e.g.
+/// `✓ Created feature (2 copied, 1 linked, 3 commands run)`
+///
+/// Nothing is printed when the hooks did not do anything.
+pub fn print_hook_summary(label: &str, summary: &HookSummary, color_mode: color::ColorMode) {
+    if let Some(description) = summary.describe() {
+        color::eprint_status(color::success(
+            color_mode,
+            format!("Created {label} ({description})"),
+        ));
+    }
+    if let Some(timing) = summary.timing_summary() {
+        color::eprint_status(color::info(color_mode, timing));
+    }
+}
+
+/// Emit the hook timing line of `summary` beneath the current header
+pub fn emit_hook_timing(summary: &HookSummary, color_mode: color::ColorMode, mp: &MultiProgress) {
+    if let Some(timing) = summary.timing_summary() {
+        hooks::emit_line(
+            mp,
+            color_mode.should_colorize(),
+            format!("  {}", color::info(color_mode, timing)),
+        );
+    }
+}
+
+/// Disk usage of every path (`ls --du`, `rm --dry-run --stat`), in input order
+///
+/// Sizes are computed in parallel behind a progress bar on stderr, shown only
+/// when output is colorized (a TTY) and `--quiet` is not set, like hook spinners.
+#[must_use]
+pub fn measure_directory_sizes(paths: &[PathBuf], color_mode: color::ColorMode) -> Vec<u64> {
+    let pb = (color_mode.should_colorize() && !color::is_quiet()).then(|| {
+        let pb = ProgressBar::new(paths.len() as u64);
+        pb.set_style(
+            ProgressStyle::with_template("{spinner:.cyan} Measuring disk usage {pos}/{len}")
+                .unwrap(),
+        );
+        pb.enable_steady_tick(Duration::from_millis(100));
+        pb
+    });
+    let sizes = directory_sizes(paths, || {
+        if let Some(pb) = &pb {
+            pb.inc(1);
+        }
+    });
+    if let Some(pb) = pb {
+        pb.finish_and_clear();
+    }
+    sizes
+}
+
+/// Error reported after a kept worktree's create hooks failed with
+/// `fail_on_error` set, so the exit code reflects the partial failure
+#[must_use]
+pub fn partial_hook_failure(label: &str, worktree_path: &Path) -> anyhow::Error {
+    anyhow::anyhow!(
+        "Created {label} at {}, but some create hooks failed (see warnings above)",
+        display_path(worktree_path)
+    )
+}
+
+/// Whether `git worktree add` for `branch` will create a new branch
+/// (as opposed to checking out an existing one or detaching)
+pub fn creates_branch(
+    git: &impl GitClient,
+    branch: &str,
+    start_point: Option<&str>,
+    detach: bool,
+    repo_root: &Path,
+) -> bool {
+    !detach
+        && (start_point.is_some() || !git.branch_exists(branch, Some(repo_root)).unwrap_or(true))
+}
+
+/// Start point passed to `git worktree add`: the explicit one, otherwise
+/// `worktree.default_start_point` when `branch` is about to be created
+///
+/// A branch only one remote has gets no start point, so it tracks that remote.
+pub fn effective_start_point(
+    git: &impl GitClient,
+    explicit: Option<&str>,
+    configured: Option<&str>,
+    branch: &str,
+    detach: bool,
+    repo_root: &Path,
+) -> Option<String> {
+    explicit
+        .or_else(|| {
+            configured.filter(|_| {
+                !detach
+                    && matches!(git.branch_exists(branch, Some(repo_root)), Ok(false))
+                    && !git.unique_remote_branch(branch, Some(repo_root))
+            })
+        })
+        .map(String::from)
+}
+
+/// Determine if tmux integration should be used based on flags and config
+const fn should_use_tmux(
+    behavior: TmuxBehavior,
+    tmux_flag: bool,
+    no_tmux_flag: bool,
+    env_disabled: bool,
+) -> bool {
+    // Priority: OFSHT_NO_TMUX > --no-tmux > --tmux > behavior setting
+    if env_disabled || no_tmux_flag {
+        return false;
+    }
+    if tmux_flag {
+        return true;
+    }
+    // behavior: Auto (default), Always, Never
+    matches!(behavior, TmuxBehavior::Always)
+}
+
+/// Resolve `--tmux` / `--no-tmux` for `add`, `create` and `shell`, checking
+/// that tmux is usable before anything is created
+///
+/// # Errors
+/// Returns an error if tmux is requested but not available
+pub fn resolve_tmux(
+    config: &TmuxConfig,
+    tmux: bool,
+    no_tmux: bool,
+    color_mode: color::ColorMode,
+) -> Result<bool> {
+    let env_disabled = integration_disabled_by_env(NO_TMUX_ENV);
+    if tmux && env_disabled {
+        eprintln!(
+            "{}",
+            color::warn(
+                color_mode,
+                "Ignoring --tmux: tmux integration is disabled by the environment (OFSHT_NO_TMUX / OFSHT_NO_INTEGRATIONS)"
+            )
+        );
+    }
+    let use_tmux = should_use_tmux(config.behavior, tmux, no_tmux, env_disabled);
+
+    if use_tmux {
+        let launcher = RealTmuxLauncher;
+        // Sessions can be created and attached from outside tmux
+        if config.create == "session" {
+            launcher.detect_binary()?;
+        } else {
+            launcher.detect()?;
+        }
+    }
+    Ok(use_tmux)
+}
+
+/// Open a new tmux window, pane or session for a worktree
+///
+/// Windows and sessions are named by `integration.tmux.name`, where `title`
+/// (of the GitHub PR/issue, if any) fills `{title_slug}`. Returns that name.
+///
+/// # Errors
+/// Returns an error if tmux fails to create the window, pane or session
+pub fn open_tmux(
+    config: &TmuxConfig,
+    worktree_path: &Path,
+    branch: &str,
+    title: Option<&str>,
+) -> Result<String> {
+    let launcher = RealTmuxLauncher;
+    let name = tmux::expand_name_template(config.name.as_deref(), branch, title);
+    match config.create.as_str() {
+        "pane" => launcher.create_pane(worktree_path)?,
+        "session" => launcher.create_session(worktree_path, &name)?,
+        _ => launcher.create_window(worktree_path, &name)?,
+    }
+    Ok(name)
+}
+
+/// [`open_tmux`] for a created worktree
+///
+/// Failures are only warned about: the worktree itself already exists.
+pub fn launch_tmux(config: &TmuxConfig, worktree_path: &Path, branch: &str, title: Option<&str>) {
+    if let Err(e) = open_tmux(config, worktree_path, branch, title) {
+        eprintln!("Warning: tmux creation failed: {e}");
+    }
+}
+
+/// Remove a worktree whose create hooks failed, along with the branch it created
+///
+/// Failures are reported as warnings so the caller can still surface the
+/// original hook error.
+pub fn rollback_created_worktree(
+    git: &impl GitClient,
+    worktree_path: &Path,
+    created_branch: Option<&str>,
+    repo_root: &Path,
+    color_mode: color::ColorMode,
+) {
+    if let Err(e) = git.remove_worktree(worktree_path, true, Some(repo_root)) {
+        eprintln!(
+            "{}",
+            color::warn(
+                color_mode,
+                format!(
+                    "Failed to roll back {}: {}",
+                    display_path(worktree_path),
+                    e.to_string().trim()
+                )
+            )
+        );
+        return;
+    }
+    if let Some(branch) = created_branch {
+        git.remove_branch(branch, Some(repo_root)).ok();
+    }
+    color::eprint_status(color::info(
+        color_mode,
+        format!("Removed {} after hook failure", display_path(worktree_path)),
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn configured_actions() -> HookActions {
+        HookActions {
+            run: vec!["pnpm install".into()],
+            copy: vec![".env".to_string(), "node_modules".to_string()],
+            link: vec![".vscode".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_effective_create_actions_without_overrides() {
+        let actions =
+            effective_create_actions(&configured_actions(), &HookOverrides::default(), false);
+        assert_eq!(actions.run.len(), 1);
+        assert_eq!(actions.copy, [".env", "node_modules"]);
+        assert_eq!(actions.link, [".vscode"]);
+    }
+
+    #[test]
+    fn test_effective_create_actions_skip_and_append() {
+        let overrides = HookOverrides {
+            copy: vec!["extra.txt".to_string()],
+            link: vec!["node_modules".to_string()],
+            skip: vec!["node_modules".to_string(), "node".to_string()],
+        };
+        let actions = effective_create_actions(&configured_actions(), &overrides, false);
+        // Exact matches only: "node" does not drop "node_modules"; the skip
+        // does not apply to the --link added on the command line
+        assert_eq!(actions.copy, [".env", "extra.txt"]);
+        assert_eq!(actions.link, [".vscode", "node_modules"]);
+        assert_eq!(actions.run.len(), 1);
+    }
+
+    #[test]
+    fn test_effective_create_actions_no_hooks_keeps_cli_actions() {
+        let overrides = HookOverrides {
+            link: vec!["data".to_string()],
+            ..Default::default()
+        };
+        let actions = effective_create_actions(&configured_actions(), &overrides, true);
+        assert!(actions.run.is_empty());
+        assert!(actions.copy.is_empty());
+        assert_eq!(actions.link, ["data"]);
+
+        let none = effective_create_actions(&configured_actions(), &HookOverrides::default(), true);
+        assert!(none.run.is_empty() && none.copy.is_empty() && none.link.is_empty());
+    }
+
+    #[test]
+    fn test_creates_branch() {
+        use crate::integrations::git::tests::MockGitClient;
+        let root = Path::new("/repo");
+        let missing = MockGitClient::default();
+        let existing = MockGitClient {
+            branch_exists_value: true,
+            ..Default::default()
+        };
+
+        assert!(creates_branch(&missing, "feature", None, false, root));
+        assert!(!creates_branch(&existing, "feature", None, false, root));
+        // An explicit start point always means `git worktree add -b`
+        assert!(creates_branch(
+            &existing,
+            "feature",
+            Some("main"),
+            false,
+            root
+        ));
+        assert!(!creates_branch(&missing, "feature", None, true, root));
+    }
+
+    #[test]
+    fn test_effective_start_point() {
+        use crate::integrations::git::tests::MockGitClient;
+        let root = Path::new("/repo");
+        let missing = MockGitClient::default();
+        let existing = MockGitClient {
+            branch_exists_value: true,
+            ..Default::default()
+        };
+
+        // New branches start from the configured point
+        assert_eq!(
+            effective_start_point(&missing, None, Some("develop"), "feature", false, root)
+                .as_deref(),
+            Some("develop")
+        );
+        // An explicit start point wins
+        assert_eq!(
+            effective_start_point(
+                &missing,
+                Some("main"),
+                Some("develop"),
+                "feature",
+                false,
+                root
+            )
+            .as_deref(),
+            Some("main")
+        );
+        // Existing branches and detached worktrees ignore the setting
+        assert_eq!(
+            effective_start_point(&existing, None, Some("develop"), "feature", false, root),
+            None
+        );
+        assert_eq!(
+            effective_start_point(&missing, None, Some("develop"), "feature", true, root),
+            None
+        );
+        assert_eq!(
+            effective_start_point(&missing, None, None, "feature", false, root),
+            None
+        );
+    }
+
+    #[test]
+    fn test_resolve_detached_target_single_ref_sanitizes_slashes() {
+        assert_eq!(
+            resolve_detached_target("release/1.0", None),
+            ("release-1.0".to_string(), "release/1.0".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_detached_target_single_ref_sanitizes_revision_syntax() {
+        for (commitish, name) in [
+            ("stash@{0}", "stash-0"),
+            ("HEAD~3", "HEAD~3"),
+            ("v1.2.0^{commit}", "v1.2.0-commit"),
+            ("0a1b2c3", "0a1b2c3"),
+            ("@{-1}", "1"),
+            ("@", "detached"),
+        ] {
+            assert_eq!(
+                resolve_detached_target(commitish, None),
+                (name.to_string(), commitish.to_string()),
+                "{commitish}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify_commitish() {
+        use crate::integrations::git::tests::MockGitClient;
+
+        let mut git = MockGitClient {
+            branch_exists_value: true,
+            ..Default::default()
+        };
+        assert!(verify_commitish(&git, "v1.0.0", Path::new("/repo")).is_ok());
+
+        git.branch_exists_value = false;
+        let err = verify_commitish(&git, "nope", Path::new("/repo"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("'nope' does not resolve to a commit"), "{err}");
+    }
+
+    #[test]
+    fn test_resolve_detached_target_explicit_name() {
+        assert_eq!(
+            resolve_detached_target("repro", Some("v1.2.3")),
+            ("repro".to_string(), "v1.2.3".to_string())
+        );
+    }
+
+    #[test]
+    fn test_should_use_tmux_no_tmux_flag_priority() {
+        // --no-tmux has highest priority
+        assert!(!should_use_tmux(TmuxBehavior::Always, true, true, false));
+        assert!(!should_use_tmux(TmuxBehavior::Always, false, true, false));
+        assert!(!should_use_tmux(TmuxBehavior::Auto, true, true, false));
+        assert!(!should_use_tmux(TmuxBehavior::Auto, false, true, false));
+    }
+
+    #[test]
+    fn test_should_use_tmux_tmux_flag_priority() {
+        // --tmux has second priority
+        assert!(should_use_tmux(TmuxBehavior::Never, true, false, false));
+        assert!(should_use_tmux(TmuxBehavior::Auto, true, false, false));
+        assert!(should_use_tmux(TmuxBehavior::Always, true, false, false));
+    }
+
+    #[test]
+    fn test_should_use_tmux_env_override_beats_flag() {
+        assert!(!should_use_tmux(TmuxBehavior::Always, true, false, true));
+        assert!(!should_use_tmux(TmuxBehavior::Auto, true, false, true));
+        assert!(!should_use_tmux(TmuxBehavior::Always, false, false, true));
+    }
+
+    #[test]
+    fn test_should_use_tmux_behavior_auto() {
+        // behavior=Auto defaults to false
+        assert!(!should_use_tmux(TmuxBehavior::Auto, false, false, false));
+    }
+
+    #[test]
+    fn test_should_use_tmux_behavior_always() {
+        // behavior=Always enables tmux
+        assert!(should_use_tmux(TmuxBehavior::Always, false, false, false));
+    }
+
+    #[test]
+    fn test_should_use_tmux_behavior_never() {
+        // behavior=Never disables tmux (unless --tmux is specified)
+        assert!(!should_use_tmux(TmuxBehavior::Never, false, false, false));
+    }
+}
@@ -1,45 +1,472 @@
 //! Completion command - Generate shell completion setup instructions
 
-use anyhow::Result;
-use clap_complete::Shell;
+use anyhow::{Context, Result};
+use clap::CommandFactory;
+use clap_complete::env::{Bash, EnvCompleter, Fish, Zsh};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::color;
+use crate::path_utils::display_path;
+
+/// Environment variable exported by the `ofsht shell-init` wrapper function
+pub const SHELL_INTEGRATION_ENV: &str = "OFSHT_SHELL_INTEGRATION";
+
+/// Shell accepted by `ofsht completion` and `ofsht shell-init`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+    Powershell,
+    Elvish,
+}
+
+impl CompletionShell {
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Bash => "bash",
+            Self::Zsh => "zsh",
+            Self::Fish => "fish",
+            Self::Powershell => "powershell",
+            Self::Elvish => "elvish",
+        }
+    }
+
+    /// Static `clap_complete` generator for shells without dynamic completion
+    /// support in ofsht
+    const fn static_generator(self) -> Option<clap_complete::Shell> {
+        match self {
+            Self::Powershell => Some(clap_complete::Shell::PowerShell),
+            Self::Elvish => Some(clap_complete::Shell::Elvish),
+            Self::Bash | Self::Zsh | Self::Fish => None,
+        }
+    }
+}
+
+/// Static completion script for `generator`, generated from the CLI definition
+fn static_script(generator: clap_complete::Shell) -> Vec<u8> {
+    let mut script = Vec::new();
+    clap_complete::generate(
+        generator,
+        &mut crate::cli::Cli::command(),
+        "ofsht",
+        &mut script,
+    );
+    script
+}
+
+/// Dynamic completion setup line for `shell`
+fn registration_line(shell: &str) -> Option<&'static str> {
+    match shell {
+        "bash" => Some("source <(COMPLETE=bash ofsht)"),
+        "zsh" => Some("source <(COMPLETE=zsh ofsht)"),
+        "fish" => Some("source (COMPLETE=fish ofsht | psub)"),
+        _ => None,
+    }
+}
+
+/// The dynamic registration script for `shell`, as printed by `COMPLETE=<shell> ofsht`
+///
+/// The script calls back into the `ofsht` on PATH, so it stays valid across upgrades.
+fn registration_script(shell: &str) -> Result<Vec<u8>> {
+    let completer: &dyn EnvCompleter = match shell {
+        "bash" => &Bash,
+        "zsh" => &Zsh,
+        "fish" => &Fish,
+        _ => anyhow::bail!("Unsupported shell: {shell}"),
+    };
+    let mut script = Vec::new();
+    completer
+        .write_registration("COMPLETE", "ofsht", "ofsht", "ofsht", &mut script)
+        .context("Failed to generate the completion script")?;
+    Ok(script)
+}
+
+/// Write the completion script for `shell` to `output`, creating parent directories
+fn write_script(shell: CompletionShell, output: &Path, color_mode: color::ColorMode) -> Result<()> {
+    let script = match shell.static_generator() {
+        Some(generator) => static_script(generator),
+        None => registration_script(shell.name())?,
+    };
+    let shell = shell.name();
+    if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    std::fs::write(output, script)
+        .with_context(|| format!("Failed to write {}", output.display()))?;
+    color::eprint_status(color::success(
+        color_mode,
+        format!(
+            "Wrote {shell} completion script to {}",
+            display_path(output)
+        ),
+    ));
+    Ok(())
+}
+
+/// Shell rc file where the setup lines belong
+fn rc_file(shell: &str) -> &'static str {
+    match shell {
+        "zsh" => "~/.zshrc",
+        "fish" => "~/.config/fish/config.fish",
+        _ => "~/.bashrc",
+    }
+}
 
 /// Generate shell completion setup instructions
 ///
+/// With `output`, write the completion script itself to that file instead (for
+/// packaging); with `check_shell_integration`, run the self-test checklist.
+/// PowerShell and elvish get a static script generated from the CLI definition,
+/// printed to stdout when no `output` is given.
+///
 /// # Errors
 /// Returns an error if:
-/// - Invalid shell specified
-pub fn cmd_completion(shell: &str) -> Result<()> {
-    // Validate shell type
-    let _ = shell.parse::<Shell>().map_err(|_| {
-        anyhow::anyhow!("Invalid shell: {shell}. Supported shells: bash, zsh, fish")
-    })?;
+/// - The shell integration check is requested for PowerShell or elvish
+/// - The script cannot be written to `output`
+/// - Any shell integration check fails
+pub fn cmd_completion(
+    shell: CompletionShell,
+    output: Option<&Path>,
+    check_shell_integration: bool,
+    color_mode: color::ColorMode,
+) -> Result<()> {
+    if check_shell_integration {
+        if shell.static_generator().is_some() {
+            anyhow::bail!(
+                "--check-shell-integration supports bash, zsh and fish, not {}",
+                shell.name()
+            );
+        }
+        return check_integration(shell.name(), color_mode);
+    }
+    if let Some(output) = output {
+        return write_script(shell, output, color_mode);
+    }
+    if let Some(generator) = shell.static_generator() {
+        std::io::stdout()
+            .write_all(&static_script(generator))
+            .context("Failed to write the completion script")?;
+        return Ok(());
+    }
+
+    let shell = shell.name();
+    let line = registration_line(shell).unwrap_or_default();
 
     // Print dynamic completion setup instructions
-    let instructions = match shell {
-        "bash" => {
-            r"# ofsht shell completion setup for Bash
-# Add this to your ~/.bashrc:
-source <(COMPLETE=bash ofsht)
-"
-        }
-        "zsh" => {
-            r"# ofsht shell completion setup for Zsh
-# Add this to your ~/.zshrc:
-source <(COMPLETE=zsh ofsht)
-"
-        }
-        "fish" => {
-            r"# ofsht shell completion setup for Fish
-# Add this to your ~/.config/fish/config.fish:
-source (COMPLETE=fish ofsht | psub)
-"
-        }
-        _ => {
-            anyhow::bail!("Unsupported shell: {shell}");
-        }
+    let shell_name = match shell {
+        "bash" => "Bash",
+        "zsh" => "Zsh",
+        _ => "Fish",
     };
+    print!(
+        "# ofsht shell completion setup for {shell_name}\n# Add this to your {}:\n{line}\n",
+        rc_file(shell)
+    );
+
+    Ok(())
+}
+
+/// One item of the shell integration checklist
+#[derive(Debug, PartialEq, Eq)]
+struct Check {
+    label: String,
+    /// `None` when the check passed, otherwise how to fix it
+    fix: Option<String>,
+}
+
+impl Check {
+    fn pass(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            fix: None,
+        }
+    }
+
+    fn fail(label: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+/// Run every check, print the checklist to stderr and fail if anything failed
+fn check_integration(shell: &str, color_mode: color::ColorMode) -> Result<()> {
+    let exe = std::env::current_exe().context("Failed to locate the ofsht executable")?;
+    let home = dirs::home_dir().unwrap_or_default();
+    let xdg_config = std::env::var_os("XDG_CONFIG_HOME").map(PathBuf::from);
 
-    print!("{instructions}");
+    let mut checks = vec![
+        check_wrapper(shell, std::env::var(SHELL_INTEGRATION_ENV).ok().as_deref()),
+        check_registration(shell, &exe),
+        check_candidates(shell, &exe),
+    ];
+    checks.extend(check_static_scripts(
+        shell,
+        &static_completion_paths(shell, &home, xdg_config.as_deref()),
+    ));
+
+    eprintln!("Shell integration ({shell}):");
+    for check in &checks {
+        for line in format_check(check, color_mode) {
+            eprintln!("  {line}");
+        }
+    }
 
+    let failed = checks.iter().filter(|check| check.fix.is_some()).count();
+    if failed > 0 {
+        anyhow::bail!("{failed} shell integration check(s) failed");
+    }
     Ok(())
 }
+
+/// Format a checklist item: `✓ label`, or `✗ label` followed by its fix
+fn format_check(check: &Check, color_mode: color::ColorMode) -> Vec<String> {
+    check.fix.as_ref().map_or_else(
+        || vec![color::success(color_mode, &check.label).to_string()],
+        |fix| {
+            vec![
+                color::error(color_mode, &check.label).to_string(),
+                format!("  {}", color::dim(color_mode, format!("fix: {fix}"))),
+            ]
+        },
+    )
+}
+
+/// The wrapper function exports `OFSHT_SHELL_INTEGRATION` (see `ofsht shell-init`)
+fn check_wrapper(shell: &str, env_value: Option<&str>) -> Check {
+    let label = "Shell wrapper function is loaded";
+    if env_value.is_some_and(|value| !value.is_empty()) {
+        return Check::pass(label);
+    }
+    let setup = if shell == "fish" {
+        "ofsht shell-init fish | source".to_string()
+    } else {
+        format!("eval \"$(ofsht shell-init {shell})\"")
+    };
+    Check::fail(
+        label,
+        format!(
+            "add `{setup}` to {} and restart the shell (run this check through the `ofsht` function)",
+            rc_file(shell)
+        ),
+    )
+}
+
+/// `COMPLETE=<shell> ofsht` must print a registration script
+fn check_registration(shell: &str, exe: &Path) -> Check {
+    let label = "Dynamic completion registration script is generated";
+    let output = Command::new(exe).env("COMPLETE", shell).output();
+    match output {
+        Ok(output) if output.status.success() && !output.stdout.is_empty() => Check::pass(label),
+        Ok(output) => Check::fail(
+            label,
+            format!(
+                "`COMPLETE={shell} ofsht` exited with {}; reinstall ofsht",
+                output.status
+            ),
+        ),
+        Err(e) => Check::fail(label, format!("failed to run {}: {e}", exe.display())),
+    }
+}
+
+/// Completing `ofsht <TAB>` through the completion engine must yield subcommands
+fn check_candidates(shell: &str, exe: &Path) -> Check {
+    let label = "Completion engine returns candidates for `ofsht <TAB>`";
+    let output = Command::new(exe)
+        .env("COMPLETE", shell)
+        .env("_CLAP_COMPLETE_INDEX", "1")
+        .env("_CLAP_IFS", "\n")
+        .args(["--", "ofsht", ""])
+        .output();
+    match output {
+        Ok(output)
+            if output.status.success()
+                && String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .any(|line| line.starts_with("add")) =>
+        {
+            Check::pass(label)
+        }
+        Ok(_) => Check::fail(
+            label,
+            "no candidates were produced; make sure the `ofsht` on PATH is this binary",
+        ),
+        Err(e) => Check::fail(label, format!("failed to run {}: {e}", exe.display())),
+    }
+}
+
+/// Standard locations where a static completion script for `ofsht` would be picked up
+fn static_completion_paths(shell: &str, home: &Path, xdg_config: Option<&Path>) -> Vec<PathBuf> {
+    let (user, system): (Vec<PathBuf>, &[&str]) = match shell {
+        "bash" => (
+            vec![
+                home.join(".local/share/bash-completion/completions/ofsht"),
+                home.join(".bash_completion.d/ofsht"),
+            ],
+            &[
+                "/usr/share/bash-completion/completions/ofsht",
+                "/usr/local/share/bash-completion/completions/ofsht",
+                "/etc/bash_completion.d/ofsht",
+                "/usr/local/etc/bash_completion.d/ofsht",
+                "/opt/homebrew/etc/bash_completion.d/ofsht",
+            ],
+        ),
+        "zsh" => (
+            vec![
+                home.join(".zfunc/_ofsht"),
+                home.join(".zsh/completions/_ofsht"),
+                home.join(".oh-my-zsh/completions/_ofsht"),
+            ],
+            &[
+                "/usr/share/zsh/site-functions/_ofsht",
+                "/usr/share/zsh/vendor-completions/_ofsht",
+                "/usr/local/share/zsh/site-functions/_ofsht",
+                "/opt/homebrew/share/zsh/site-functions/_ofsht",
+            ],
+        ),
+        "fish" => (
+            vec![xdg_config
+                .map_or_else(|| home.join(".config"), Path::to_path_buf)
+                .join("fish/completions/ofsht.fish")],
+            &[
+                "/usr/share/fish/vendor_completions.d/ofsht.fish",
+                "/usr/local/share/fish/vendor_completions.d/ofsht.fish",
+                "/opt/homebrew/share/fish/vendor_completions.d/ofsht.fish",
+            ],
+        ),
+        _ => (Vec::new(), &[]),
+    };
+    user.into_iter()
+        .chain(system.iter().map(PathBuf::from))
+        .collect()
+}
+
+/// A saved copy of the dynamic registration script still defers to `COMPLETE=`;
+/// anything else is a static script from an older ofsht
+fn is_static_script(content: &str) -> bool {
+    !content.contains("COMPLETE=")
+}
+
+/// Flag static completion scripts that would shadow dynamic completion
+fn check_static_scripts(shell: &str, paths: &[PathBuf]) -> Vec<Check> {
+    let shadowing: Vec<&PathBuf> = paths
+        .iter()
+        .filter(|path| {
+            std::fs::read_to_string(path).is_ok_and(|content| is_static_script(&content))
+        })
+        .collect();
+    if shadowing.is_empty() {
+        return vec![Check::pass(
+            "No static completion script shadows dynamic completion",
+        )];
+    }
+    shadowing
+        .into_iter()
+        .map(|path| {
+            Check::fail(
+                format!("Static completion script found: {}", path.display()),
+                format!(
+                    "remove it and use `{}` in {} instead",
+                    registration_line(shell).unwrap_or_default(),
+                    rc_file(shell)
+                ),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registration_script_defers_to_complete_env() {
+        for shell in ["bash", "zsh", "fish"] {
+            let script = String::from_utf8(registration_script(shell).unwrap()).unwrap();
+            assert!(script.contains("COMPLETE="), "{shell}: {script}");
+            assert!(!is_static_script(&script));
+        }
+        assert!(registration_script("powershell").is_err());
+    }
+
+    #[test]
+    fn test_static_script_for_powershell_and_elvish() {
+        for shell in [CompletionShell::Powershell, CompletionShell::Elvish] {
+            let generator = shell.static_generator().unwrap();
+            let script = String::from_utf8(static_script(generator)).unwrap();
+            assert!(script.contains("ofsht"), "{}: {script}", shell.name());
+            assert!(script.contains("completion"), "{}", shell.name());
+        }
+        assert!(CompletionShell::Bash.static_generator().is_none());
+    }
+
+    #[test]
+    fn test_check_wrapper() {
+        assert_eq!(check_wrapper("bash", Some("bash")).fix, None);
+        let failed = check_wrapper("zsh", None);
+        assert!(failed.fix.unwrap().contains("ofsht shell-init zsh"));
+        let failed = check_wrapper("fish", Some(""));
+        assert!(failed
+            .fix
+            .unwrap()
+            .contains("ofsht shell-init fish | source"));
+    }
+
+    #[test]
+    fn test_static_completion_paths_use_home_and_xdg() {
+        let home = Path::new("/home/me");
+        let bash = static_completion_paths("bash", home, None);
+        assert!(bash.contains(&PathBuf::from(
+            "/home/me/.local/share/bash-completion/completions/ofsht"
+        )));
+        let zsh = static_completion_paths("zsh", home, None);
+        assert!(zsh.contains(&PathBuf::from("/home/me/.zfunc/_ofsht")));
+        let fish = static_completion_paths("fish", home, Some(Path::new("/xdg")));
+        assert_eq!(fish[0], PathBuf::from("/xdg/fish/completions/ofsht.fish"));
+    }
+
+    #[test]
+    fn test_is_static_script() {
+        assert!(is_static_script("_ofsht() {\n  COMPREPLY=()\n}\n"));
+        assert!(!is_static_script(
+            "_clap_complete_ofsht() {\n  COMPLETE=bash ofsht -- \"${COMP_WORDS[@]}\"\n}\n"
+        ));
+    }
+
+    #[test]
+    fn test_check_static_scripts() {
+        let temp = tempfile::tempdir().unwrap();
+        let static_file = temp.path().join("ofsht");
+        let dynamic_file = temp.path().join("ofsht-dynamic");
+        let missing = temp.path().join("missing");
+        std::fs::write(&static_file, "complete -F _ofsht ofsht\n").unwrap();
+        std::fs::write(&dynamic_file, "COMPLETE=bash ofsht\n").unwrap();
+
+        let checks = check_static_scripts("bash", &[dynamic_file.clone(), missing.clone()]);
+        assert_eq!(checks.len(), 1);
+        assert!(checks[0].fix.is_none());
+
+        let checks = check_static_scripts("bash", &[static_file.clone(), dynamic_file, missing]);
+        assert_eq!(checks.len(), 1);
+        assert!(checks[0].label.contains(&static_file.display().to_string()));
+        assert!(checks[0]
+            .fix
+            .as_ref()
+            .unwrap()
+            .contains("source <(COMPLETE=bash ofsht)"));
+    }
+
+    #[test]
+    fn test_format_check() {
+        let lines = format_check(&Check::pass("ok"), color::ColorMode::Never);
+        assert_eq!(lines, ["✓ ok"]);
+        let lines = format_check(&Check::fail("broken", "do x"), color::ColorMode::Never);
+        assert_eq!(lines, ["✗ broken", "  fix: do x"]);
+    }
+}
@@ -1,31 +1,52 @@
 //! Create command - Simple worktree creation without extra features
 
 use anyhow::Result;
-use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use indicatif::{ProgressBar, ProgressStyle};
+use std::cell::Cell;
 use std::time::Duration;
 
 use crate::color;
-use crate::commands::common::get_main_repo_root;
+use crate::commands::add::{AddOptions, AddResult};
+use crate::commands::common::{
+    check_worktree_location, creates_branch, effective_create_actions, effective_start_point,
+    get_main_repo_root, launch_tmux, partial_hook_failure, print_hook_summary,
+    resolve_detached_target, resolve_tmux, rollback_created_worktree, run_create_hooks,
+    verify_commitish, warn_create_hooks_skipped,
+};
 use crate::config;
-use crate::hooks;
+use crate::domain;
+use crate::hooks::{self, HookSummary, HookVars};
 use crate::integrations::git::RealGitClient;
 use crate::integrations::zoxide::{is_zoxide_available, RealZoxideClient};
-use crate::path_utils::display_path;
-use crate::service::{CreateWorktreeRequest, WorktreeService};
+use crate::report;
+use crate::service::{CreateOptions, CreateWorktreeRequest, WorktreeManager, WorktreeService};
 
-/// Create a new worktree (simple version without tmux/GitHub integration)
+/// Create a new worktree without GitHub integration or printing its path
+///
+/// Like `add`, the worktree is registered with zoxide and opened in tmux when
+/// enabled; only `--porcelain` writes to stdout.
 ///
 /// # Errors
 /// Returns an error if:
 /// - Not in a git repository
 /// - Git worktree creation fails
 /// - Zoxide registration fails
-#[allow(clippy::missing_panics_doc)]
+#[allow(clippy::too_many_lines, clippy::missing_panics_doc)]
 pub fn cmd_create(
     branch: Option<&str>,
     start_point: Option<&str>,
+    options: &AddOptions,
     color_mode: color::ColorMode,
 ) -> Result<()> {
+    let AddOptions {
+        detach,
+        tmux,
+        no_tmux,
+        porcelain,
+        no_hooks,
+        ref hook_overrides,
+        ..
+    } = *options;
     // Resolve branch: CLI arg > stdin (when piped) > error
     let branch_owned = match branch {
         Some(b) => b.to_string(),
@@ -33,6 +54,13 @@ pub fn cmd_create(
             anyhow::anyhow!("branch name required (provide as argument or via stdin)")
         })?,
     };
+    let (branch_owned, start_point_owned) = if detach {
+        let (name, commitish) = resolve_detached_target(&branch_owned, start_point);
+        (name, Some(commitish))
+    } else {
+        domain::branch::validate(&branch_owned)?;
+        (branch_owned, start_point.map(String::from))
+    };
     let branch = branch_owned.as_str();
 
     // Get main repository root
@@ -41,7 +69,24 @@ pub fn cmd_create(
     // Load configuration from repo root
     let config = config::Config::load_from_repo_root(&repo_root)?;
 
-    let mp = MultiProgress::new();
+    let start_point_owned = effective_start_point(
+        &RealGitClient,
+        start_point_owned.as_deref(),
+        config.worktree.default_start_point.as_deref(),
+        branch,
+        detach,
+        &repo_root,
+    );
+    let start_point = start_point_owned.as_deref();
+    if let (true, Some(commitish)) = (detach, start_point) {
+        verify_commitish(&RealGitClient, commitish, &repo_root)?;
+    }
+
+    let use_tmux = resolve_tmux(&config.integrations.tmux, tmux, no_tmux, color_mode)?;
+
+    check_worktree_location(&config.worktree, &repo_root, branch, color_mode)?;
+
+    let mp = hooks::new_multi_progress();
     let is_tty = color_mode.should_colorize();
 
     // Header spinner (TTY) or deferred header (non-TTY)
@@ -63,43 +108,52 @@ pub fn cmd_create(
     // service does not need to know about zoxide-availability detection.
     let zoxide_enabled = config.integrations.zoxide.enabled && is_zoxide_available();
 
-    let service = WorktreeService::new(RealGitClient, RealZoxideClient);
+    // Only probe for an existing branch when a rollback could need the answer
+    let created_branch = config.hooks.on_create_failure != config::CreateFailureAction::Keep
+        && creates_branch(&RealGitClient, branch, start_point, detach, &repo_root);
+    let rollback_path = Cell::new(None);
+    let hook_summary = Cell::new(HookSummary::default());
 
-    let hook_actions = &config.hooks.create;
+    let service = WorktreeService::new(RealGitClient, RealZoxideClient);
+    let manager = WorktreeManager::new(RealGitClient, &repo_root, config.worktree.clone());
+    let opts = CreateOptions {
+        start_point: start_point.map(String::from),
+        detach,
+    };
     let req = CreateWorktreeRequest {
-        branch,
-        start_point,
-        repo_root: &repo_root,
-        path_template: &config.worktree.dir,
         zoxide_enabled,
+        zoxide_score: config.integrations.zoxide.score,
+        ..manager.create_request(branch, &opts)
     };
 
+    let create_hooks = config::Hooks {
+        create: effective_create_actions(&config.hooks.create, hook_overrides, no_hooks),
+        ..config.hooks.clone()
+    };
     let result = service.create(&req, |path| {
         // non-TTY: print "Created..." header before hooks (matches rm/sync pattern)
         if !is_tty {
-            eprintln!(
-                "{}",
-                color::success(
-                    color_mode,
-                    format!("Created worktree at: {}", display_path(path))
-                )
-            );
+            color::eprint_status(color::success(
+                color_mode,
+                report::created_worktree(branch, path),
+            ));
         }
 
-        if !hook_actions.run.is_empty()
-            || !hook_actions.copy.is_empty()
-            || !hook_actions.link.is_empty()
-        {
-            hooks::execute_hooks_lenient_with_mp(
-                hook_actions,
-                path,
-                &repo_root,
-                color_mode,
-                "  ",
-                &mp,
-            );
+        if no_hooks {
+            warn_create_hooks_skipped(&config.hooks, color_mode, &mp);
         }
 
+        let summary = run_create_hooks(
+            &create_hooks,
+            path,
+            &repo_root,
+            &HookVars::new((!detach).then_some(branch), path, &repo_root),
+            branch,
+            color_mode,
+            &mp,
+        )
+        .inspect_err(|_| rollback_path.set(Some(path.to_path_buf())))?;
+        hook_summary.set(summary);
         Ok(())
     });
 
@@ -108,6 +162,15 @@ pub fn cmd_create(
             if let Some(pb) = header_pb {
                 pb.finish_and_clear();
             }
+            if let Some(path) = rollback_path.take() {
+                rollback_created_worktree(
+                    &RealGitClient,
+                    &path,
+                    created_branch.then_some(branch),
+                    &repo_root,
+                    color_mode,
+                );
+            }
             Err(e)
         }
         Ok(path) => {
@@ -115,12 +178,26 @@ pub fn cmd_create(
                 pb.set_style(ProgressStyle::with_template("{msg}").unwrap());
                 pb.finish_with_message(format!(
                     "{}",
-                    color::success(
-                        color_mode,
-                        format!("Created worktree at: {}", display_path(&path))
-                    )
+                    color::success(color_mode, report::created_worktree(branch, &path))
                 ));
             }
+            let hook_summary = hook_summary.take();
+            print_hook_summary(branch, &hook_summary, color_mode);
+            if use_tmux {
+                launch_tmux(&config.integrations.tmux, &path, branch, None);
+            }
+            if porcelain {
+                let result = AddResult {
+                    path: &path,
+                    branch: (!detach).then_some(branch),
+                    created: true,
+                    start_point,
+                };
+                println!("{}", result.to_porcelain());
+            }
+            if hook_summary.has_errors() && config.hooks.create.fail_on_error {
+                return Err(partial_hook_failure(branch, &path));
+            }
             Ok(())
         }
     }
@@ -0,0 +1,189 @@
+//! Doctor command - Report integration availability and effective configuration
+
+use std::path::Path;
+
+use crate::color;
+use crate::commands::common::get_main_repo_root;
+use crate::config;
+use crate::config::template_generator::TemplateContext;
+use crate::domain::worktree::worktree_root_from_template;
+use crate::path_utils::display_path;
+
+/// Availability of one external tool
+struct ToolCheck {
+    name: &'static str,
+    available: bool,
+    install_url: &'static str,
+}
+
+/// Build the tool checks from detected availability
+const fn tool_checks(ctx: &TemplateContext) -> [ToolCheck; 4] {
+    [
+        ToolCheck {
+            name: "gh",
+            available: ctx.gh_available,
+            install_url: "https://cli.github.com/",
+        },
+        ToolCheck {
+            name: "zoxide",
+            available: ctx.zoxide_available,
+            install_url: "https://github.com/ajeetdsouza/zoxide",
+        },
+        ToolCheck {
+            name: "fzf",
+            available: ctx.fzf_available,
+            install_url: "https://github.com/junegunn/fzf",
+        },
+        ToolCheck {
+            name: "tmux",
+            available: ctx.tmux_available,
+            install_url: "https://github.com/tmux/tmux",
+        },
+    ]
+}
+
+/// Format a tool line: success when installed, warning with install URL when missing
+fn format_tool_line(check: &ToolCheck, color_mode: color::ColorMode) -> String {
+    if check.available {
+        color::success(color_mode, format!("{}: installed", check.name)).to_string()
+    } else {
+        color::warn(
+            color_mode,
+            format!(
+                "{}: not found (install from {})",
+                check.name, check.install_url
+            ),
+        )
+        .to_string()
+    }
+}
+
+/// Format a config file line, noting whether the file exists
+fn format_config_line(label: &str, path: &Path, color_mode: color::ColorMode) -> String {
+    let state = if path.exists() { "" } else { " (not found)" };
+    color::info(
+        color_mode,
+        format!("{label} config: {}{state}", display_path(path)),
+    )
+    .to_string()
+}
+
+/// Report integration availability, config paths, repository and `worktree.dir`
+///
+/// Diagnostics only: always succeeds, problems are reported as warnings.
+/// Output goes to stderr like every other human-readable report.
+pub fn cmd_doctor(color_mode: color::ColorMode) {
+    let ctx = TemplateContext::detect();
+    let repo_root = get_main_repo_root().ok();
+
+    eprintln!("Integrations:");
+    for check in &tool_checks(&ctx) {
+        eprintln!("  {}", format_tool_line(check, color_mode));
+    }
+
+    eprintln!("Configuration:");
+    match config::Config::global_config_path() {
+        Some(path) => eprintln!("  {}", format_config_line("Global", &path, color_mode)),
+        None => eprintln!(
+            "  {}",
+            color::warn(
+                color_mode,
+                "Global config: path unknown (set HOME or XDG_CONFIG_HOME)"
+            )
+        ),
+    }
+    let local_path = repo_root.as_deref().map_or_else(
+        config::Config::local_config_path,
+        config::Config::local_config_path_from,
+    );
+    eprintln!("  {}", format_config_line("Local", &local_path, color_mode));
+
+    eprintln!("Repository:");
+    match &repo_root {
+        Some(root) => eprintln!(
+            "  {}",
+            color::success(
+                color_mode,
+                format!("Git repository: {}", display_path(root))
+            )
+        ),
+        None => eprintln!(
+            "  {}",
+            color::warn(color_mode, "Not inside a git repository")
+        ),
+    }
+
+    let loaded = repo_root
+        .as_deref()
+        .map_or_else(config::Config::load, config::Config::load_from_repo_root);
+    match loaded {
+        Ok(cfg) => {
+            eprintln!(
+                "  {}",
+                color::info(color_mode, format!("worktree.dir: {}", cfg.worktree.dir))
+            );
+            if let Some(root) = &repo_root {
+                match worktree_root_from_template(&cfg.worktree.dir, root) {
+                    Ok(wt_root) => eprintln!(
+                        "  {}",
+                        color::info(
+                            color_mode,
+                            format!("Worktrees root: {}", display_path(&wt_root))
+                        )
+                    ),
+                    Err(e) => eprintln!(
+                        "  {}",
+                        color::warn(color_mode, format!("worktree.dir cannot be expanded: {e}"))
+                    ),
+                }
+            }
+        }
+        Err(e) => eprintln!(
+            "  {}",
+            color::warn(color_mode, format!("Failed to load config: {e:#}"))
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tool_checks_cover_all_integrations() {
+        let ctx = TemplateContext {
+            gh_available: true,
+            zoxide_available: false,
+            fzf_available: true,
+            tmux_available: false,
+        };
+        let names: Vec<&str> = tool_checks(&ctx).iter().map(|c| c.name).collect();
+        assert_eq!(names, vec!["gh", "zoxide", "fzf", "tmux"]);
+    }
+
+    #[test]
+    fn test_format_tool_line_available() {
+        let check = ToolCheck {
+            name: "fzf",
+            available: true,
+            install_url: "https://github.com/junegunn/fzf",
+        };
+        assert_eq!(
+            format_tool_line(&check, color::ColorMode::Never),
+            "✓ fzf: installed"
+        );
+    }
+
+    #[test]
+    fn test_format_tool_line_missing_includes_url() {
+        let check = ToolCheck {
+            name: "tmux",
+            available: false,
+            install_url: "https://github.com/tmux/tmux",
+        };
+        assert_eq!(
+            format_tool_line(&check, color::ColorMode::Never),
+            "⚠ tmux: not found (install from https://github.com/tmux/tmux)"
+        );
+    }
+}
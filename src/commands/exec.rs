@@ -0,0 +1,230 @@
+//! Exec command - Run a command in one or more worktrees
+
+use anyhow::{Context, Result};
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::color;
+use crate::commands::common::{get_main_repo_root, resolve_worktree_or_main};
+use crate::domain::worktree::WorktreeList;
+use crate::integrations::git::{GitClient, RealGitClient};
+use crate::path_utils::display_path;
+
+/// A worktree to run the command in: (`label`, `path`)
+type ExecTarget = (String, PathBuf);
+
+/// Resolve exec targets from `git worktree list --porcelain` output
+///
+/// `--all` selects every worktree (main first). Otherwise each target is resolved
+/// like `rm`/`cd`, with `@` naming the main worktree. Duplicate targets run once.
+fn resolve_targets(
+    list_stdout: &str,
+    targets: &[String],
+    all: bool,
+    repo_root: &Path,
+) -> Result<Vec<ExecTarget>> {
+    let list = WorktreeList::parse(list_stdout, None);
+    let label = |path: &str, branch: Option<&String>| {
+        branch
+            .cloned()
+            .unwrap_or_else(|| display_path(Path::new(path)))
+    };
+
+    let mut resolved: Vec<ExecTarget> = Vec::new();
+    if all {
+        for (index, entry) in list.entries().iter().enumerate() {
+            let name = if index == 0 {
+                "@".to_string()
+            } else {
+                label(&entry.path, entry.branch.as_ref())
+            };
+            resolved.push((name, PathBuf::from(&entry.path)));
+        }
+        return Ok(resolved);
+    }
+
+    for target in targets {
+        let (path, branch) = resolve_worktree_or_main(target, list_stdout, repo_root)?;
+        let name = if target == "@" {
+            "@".to_string()
+        } else {
+            branch.unwrap_or_else(|| display_path(&path))
+        };
+        if !resolved.iter().any(|(_, existing)| existing == &path) {
+            resolved.push((name, path));
+        }
+    }
+    Ok(resolved)
+}
+
+/// Forward every line of `reader` to stderr, prefixed with the worktree label
+fn forward_lines<R: Read>(reader: R, prefix: &str) {
+    for line in BufReader::new(reader).lines().map_while(Result::ok) {
+        eprintln!("{prefix} {line}");
+    }
+}
+
+/// Run `command` in `dir`, streaming its stdout and stderr to stderr
+///
+/// Returns `Ok(None)` on success, `Ok(Some(reason))` when the command exits non-zero.
+fn run_in_worktree(
+    command: &[String],
+    dir: &Path,
+    label: &str,
+    color_mode: color::ColorMode,
+) -> Result<Option<String>> {
+    let (program, args) = command.split_first().context("No command given")?;
+    let mut child = Command::new(program)
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to execute command: {program}"))?;
+
+    let prefix = color::tree_item(
+        color_mode,
+        color::dim(color_mode, format!("[{label}]")),
+        false,
+        0,
+    )
+    .to_string();
+    let stdout = child.stdout.take().expect("stdout was piped");
+    let stderr = child.stderr.take().expect("stderr was piped");
+    let stdout_prefix = prefix.clone();
+    let stdout_handle = std::thread::spawn(move || forward_lines(stdout, &stdout_prefix));
+    let stderr_handle = std::thread::spawn(move || forward_lines(stderr, &prefix));
+
+    let status = child
+        .wait()
+        .with_context(|| format!("Failed to wait for command: {program}"))?;
+    stdout_handle.join().ok();
+    stderr_handle.join().ok();
+
+    if status.success() {
+        return Ok(None);
+    }
+    Ok(Some(status.code().map_or_else(
+        || "signal".to_string(),
+        |code| format!("exit {code}"),
+    )))
+}
+
+/// Run a command in the selected worktrees
+///
+/// Stdout stays clean: the command's output is forwarded to stderr line by line.
+///
+/// # Errors
+/// Returns an error if:
+/// - Not in a git repository
+/// - Target resolution fails
+/// - The command fails or cannot be spawned in any worktree (after the first
+///   failure unless `keep_going`)
+pub fn cmd_exec(
+    targets: &[String],
+    all: bool,
+    keep_going: bool,
+    command: &[String],
+    color_mode: color::ColorMode,
+) -> Result<()> {
+    let repo_root = get_main_repo_root()?;
+    let git = RealGitClient;
+    let list_stdout = git.list_worktrees(Some(&repo_root))?;
+    let resolved = resolve_targets(&list_stdout, targets, all, &repo_root)?;
+
+    let display_command = command.join(" ");
+    let mut failed: Vec<String> = Vec::new();
+    for (label, path) in &resolved {
+        color::eprint_status(color::info(
+            color_mode,
+            format!("Running `{display_command}` in {label}"),
+        ));
+        // A command that cannot even be started (e.g. the worktree directory
+        // is gone) counts as a failure in that worktree, like a non-zero exit
+        let failure = match run_in_worktree(command, path, label, color_mode) {
+            Ok(None) => {
+                color::eprint_status(color::success(color_mode, label));
+                continue;
+            }
+            Ok(Some(reason)) => format!("command failed ({reason})"),
+            Err(e) => format!("{e:#}"),
+        };
+        eprintln!("{}", color::warn(color_mode, format!("{label}: {failure}")));
+        failed.push(label.clone());
+        if !keep_going {
+            break;
+        }
+    }
+
+    if !failed.is_empty() {
+        anyhow::bail!(
+            "Command failed in {} worktree(s): {}",
+            failed.len(),
+            failed.join(", ")
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PORCELAIN: &str = "worktree /repo\nHEAD abc\nbranch refs/heads/main\n\n\
+                             worktree /repo-worktrees/feature-a\nHEAD def\nbranch refs/heads/feature-a\n\n\
+                             worktree /repo-worktrees/feature-b\nHEAD 012\nbranch refs/heads/feature-b\n\n";
+
+    #[test]
+    fn test_resolve_targets_all_includes_main_first() {
+        let targets = resolve_targets(PORCELAIN, &[], true, Path::new("/repo")).unwrap();
+        let labels: Vec<&str> = targets.iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(labels, ["@", "feature-a", "feature-b"]);
+        assert_eq!(targets[0].1, PathBuf::from("/repo"));
+    }
+
+    #[test]
+    fn test_resolve_targets_named_and_main() {
+        let targets = resolve_targets(
+            PORCELAIN,
+            &["feature-b".to_string(), "@".to_string()],
+            false,
+            Path::new("/repo"),
+        )
+        .unwrap();
+        assert_eq!(
+            targets,
+            [
+                (
+                    "feature-b".to_string(),
+                    PathBuf::from("/repo-worktrees/feature-b")
+                ),
+                ("@".to_string(), PathBuf::from("/repo")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_targets_deduplicates() {
+        let targets = resolve_targets(
+            PORCELAIN,
+            &["feature-a".to_string(), "feature-a".to_string()],
+            false,
+            Path::new("/repo"),
+        )
+        .unwrap();
+        assert_eq!(targets.len(), 1);
+    }
+
+    #[test]
+    fn test_resolve_targets_unknown_target_errors() {
+        let result = resolve_targets(
+            PORCELAIN,
+            &["missing".to_string()],
+            false,
+            Path::new("/repo"),
+        );
+        assert!(result.is_err());
+    }
+}
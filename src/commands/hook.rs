@@ -0,0 +1,292 @@
+//! Hook command - Inspect the effective hook configuration
+
+use anyhow::Result;
+use serde::Serialize;
+use std::path::Path;
+
+use crate::color;
+use crate::commands::common::get_main_repo_root;
+use crate::config::{Config, HookActions, Hooks};
+use crate::domain::worktree::expand_path_template;
+use crate::hooks::{self, HookVars};
+use crate::path_utils::display_path;
+
+/// Hook event selectable with `ofsht hook list --event`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum HookEvent {
+    Create,
+    Delete,
+}
+
+impl HookEvent {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Create => "create",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+/// One resolved hook action with the config file it came from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct HookEntry {
+    event: &'static str,
+    kind: &'static str,
+    value: String,
+    source: String,
+    /// Current match count for copy/link patterns (`--expand` only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    matches: Option<usize>,
+}
+
+/// Flatten `actions` into entries in execution order (run, copy, link)
+fn annotate_actions(event: HookEvent, actions: &HookActions, source: &str) -> Vec<HookEntry> {
+    let entry = |kind: &'static str, value: String| HookEntry {
+        event: event.name(),
+        kind,
+        value,
+        source: source.to_string(),
+        matches: None,
+    };
+    actions
+        .run
+        .iter()
+        .map(|v| entry("run", v.to_string()))
+        .chain(actions.copy.iter().map(|v| entry("copy", v.clone())))
+        .chain(actions.link.iter().map(|v| entry("link", v.clone())))
+        .collect()
+}
+
+/// Resolve the hooks for `events`, each entry labelled with `source`
+fn annotate_hooks(hooks: &Hooks, source: &str, events: &[HookEvent]) -> Vec<HookEntry> {
+    events
+        .iter()
+        .flat_map(|&event| {
+            let actions = match event {
+                HookEvent::Create => &hooks.create,
+                HookEvent::Delete => &hooks.delete,
+            };
+            annotate_actions(event, actions, source)
+        })
+        .collect()
+}
+
+/// Copy of `hooks` with the run commands' placeholders filled in for `branch`,
+/// whose worktree is assumed at the path `ofsht add` would give it
+fn hooks_for_branch(
+    hooks: &Hooks,
+    branch: &str,
+    path_template: &str,
+    repo_root: &Path,
+) -> Result<Hooks> {
+    let worktree_path = expand_path_template(path_template, repo_root, branch)?;
+    let vars = HookVars::new(Some(branch), &worktree_path, repo_root);
+    Ok(Hooks {
+        create: hooks::with_placeholders(&hooks.create, &vars),
+        delete: hooks::with_placeholders(&hooks.delete, &vars),
+        ..hooks.clone()
+    })
+}
+
+/// Fill in current match counts for copy/link patterns, relative to `repo_root`
+fn expand_matches(entries: &mut [HookEntry], repo_root: &Path) {
+    for entry in entries.iter_mut().filter(|e| e.kind != "run") {
+        entry.matches = hooks::count_pattern_matches(&entry.value, repo_root).ok();
+    }
+}
+
+/// Human-readable listing, grouped by event
+fn format_entries(
+    entries: &[HookEntry],
+    events: &[HookEvent],
+    color_mode: color::ColorMode,
+) -> Vec<String> {
+    let value_width = entries
+        .iter()
+        .map(|e| e.value.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut lines = Vec::new();
+    for event in events {
+        lines.push(format!("{}:", event.name()));
+        let mut any = false;
+        for entry in entries.iter().filter(|e| e.event == event.name()) {
+            any = true;
+            let matches = entry.matches.map_or_else(String::new, |n| {
+                let noun = if n == 1 { "match" } else { "matches" };
+                format!(" ({n} {noun})")
+            });
+            lines.push(format!(
+                "  {:<4}  {:<value_width$}  {}{}",
+                entry.kind,
+                entry.value,
+                color::dim(color_mode, &entry.source),
+                matches
+            ));
+        }
+        if !any {
+            lines.push(format!("  {}", color::dim(color_mode, "(none)")));
+        }
+    }
+    lines
+}
+
+/// List the effective hook actions and the config file each one comes from
+///
+/// Hooks come from one file: a local `.ofsht.toml` replaces the global config,
+/// which is then reported as unused. The human-readable listing goes to stderr;
+/// `--json` prints an array to stdout.
+/// With `branch`, run commands are shown with their placeholders filled in.
+///
+/// # Errors
+/// Returns an error if the configuration cannot be loaded or `branch` gives
+/// no valid worktree path
+pub fn cmd_hook_list(
+    event: Option<HookEvent>,
+    branch: Option<&str>,
+    expand: bool,
+    json: bool,
+    color_mode: color::ColorMode,
+) -> Result<()> {
+    let repo_root = get_main_repo_root()?;
+    let (config, source) = Config::load_from_repo_root_with_source(&repo_root)?;
+    if let (Some(local), Some(global)) = (&source.path, &source.shadowed_global) {
+        // Hooks are never merged across files; say so instead of silently
+        // leaving the global ones out
+        color::eprint_status(color::info(
+            color_mode,
+            format!(
+                "{} replaces {}: its hooks are not used",
+                display_path(local),
+                display_path(global)
+            ),
+        ));
+    }
+    let source = source
+        .path
+        .map_or_else(|| "default".to_string(), |path| display_path(&path));
+
+    let events = event.map_or_else(|| vec![HookEvent::Create, HookEvent::Delete], |e| vec![e]);
+    let hooks = match branch {
+        Some(branch) => hooks_for_branch(&config.hooks, branch, &config.worktree.dir, &repo_root)?,
+        None => config.hooks,
+    };
+    let mut entries = annotate_hooks(&hooks, &source, &events);
+    if expand {
+        expand_matches(&mut entries, &repo_root);
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+    } else {
+        for line in format_entries(&entries, &events, color_mode) {
+            eprintln!("{line}");
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_hooks() -> Hooks {
+        Hooks {
+            create: HookActions {
+                run: vec!["pnpm install".into()],
+                copy: vec![".env".to_string()],
+                link: vec![".vscode".to_string()],
+                ..Default::default()
+            },
+            delete: HookActions {
+                run: vec!["echo bye".into()],
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_annotate_hooks_orders_and_labels_entries() {
+        let entries = annotate_hooks(
+            &sample_hooks(),
+            "~/repo/.ofsht.toml",
+            &[HookEvent::Create, HookEvent::Delete],
+        );
+        let summary: Vec<(&str, &str, &str)> = entries
+            .iter()
+            .map(|e| (e.event, e.kind, e.value.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("create", "run", "pnpm install"),
+                ("create", "copy", ".env"),
+                ("create", "link", ".vscode"),
+                ("delete", "run", "echo bye"),
+            ]
+        );
+        assert!(entries.iter().all(|e| e.source == "~/repo/.ofsht.toml"));
+    }
+
+    #[test]
+    fn test_hooks_for_branch_fills_run_placeholders() {
+        let hooks = Hooks {
+            create: HookActions {
+                run: vec!["createdb app_{branch}".into(), "echo {worktree}".into()],
+                copy: vec!["{branch}.env".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let hooks = hooks_for_branch(
+            &hooks,
+            "feat/x",
+            "../{repo}-worktrees/{branch}",
+            Path::new("/src/app"),
+        )
+        .unwrap();
+        let entries = annotate_hooks(&hooks, "default", &[HookEvent::Create]);
+        let values: Vec<&str> = entries.iter().map(|e| e.value.as_str()).collect();
+        assert_eq!(
+            values,
+            [
+                "createdb app_feat/x",
+                "echo /src/app-worktrees/feat/x",
+                "{branch}.env"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_annotate_hooks_filters_event() {
+        let entries = annotate_hooks(&sample_hooks(), "default", &[HookEvent::Delete]);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].event, "delete");
+    }
+
+    #[test]
+    fn test_expand_matches_counts_copy_and_link_only() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".env"), "").unwrap();
+        let mut entries = annotate_hooks(&sample_hooks(), "default", &[HookEvent::Create]);
+        expand_matches(&mut entries, dir.path());
+        let matches: Vec<Option<usize>> = entries.iter().map(|e| e.matches).collect();
+        assert_eq!(matches, vec![None, Some(1), Some(0)]);
+    }
+
+    #[test]
+    fn test_format_entries_marks_empty_events() {
+        let entries = annotate_hooks(&sample_hooks(), "default", &[HookEvent::Create]);
+        let lines = format_entries(
+            &entries,
+            &[HookEvent::Create, HookEvent::Delete],
+            color::ColorMode::Never,
+        );
+        assert_eq!(lines[0], "create:");
+        assert!(lines[1].starts_with("  run   pnpm install"));
+        assert!(lines[1].ends_with("default"));
+        assert_eq!(lines[4], "delete:");
+        assert_eq!(lines[5], "  (none)");
+    }
+}
@@ -1,32 +1,126 @@
 //! Init command - Initialize configuration files
 
 use anyhow::{Context, Result};
+use std::path::Path;
 
 use crate::color;
 use crate::commands::common::get_main_repo_root;
 use crate::config;
 use crate::config::template_generator::TemplateContext;
-use crate::path_utils::display_path;
+use crate::path_utils::{backup_path, display_path};
 
-/// Write config file if it doesn't exist (or force overwrite)
-fn write_config_if_needed(
-    path: &std::path::Path,
+/// What `ofsht init` does with one config file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileAction {
+    /// The file does not exist yet and will be written
+    Create,
+    /// The file exists and `--force` replaces it
+    Overwrite,
+    /// The file exists and is left alone
+    Skip,
+}
+
+impl FileAction {
+    const fn for_file(exists: bool, force: bool) -> Self {
+        match (exists, force) {
+            (false, _) => Self::Create,
+            (true, true) => Self::Overwrite,
+            (true, false) => Self::Skip,
+        }
+    }
+
+    const fn writes(self) -> bool {
+        !matches!(self, Self::Skip)
+    }
+}
+
+/// Per-file actions for one `ofsht init` run (`None` = file not requested)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct InitPlan {
+    global: Option<FileAction>,
+    local: Option<FileAction>,
+    /// Exactly one file was asked for with `--global` / `--local`
+    explicit: bool,
+}
+
+impl InitPlan {
+    /// Decide what happens to each file
+    ///
+    /// Without `--global` / `--local` both files are planned.
+    const fn new(global_exists: bool, local_exists: bool, options: InitOptions) -> Self {
+        let want_global = options.global || !options.local;
+        let want_local = options.local || !options.global;
+        Self {
+            global: if want_global {
+                Some(FileAction::for_file(global_exists, options.force))
+            } else {
+                None
+            },
+            local: if want_local {
+                Some(FileAction::for_file(local_exists, options.force))
+            } else {
+                None
+            },
+            explicit: options.global != options.local,
+        }
+    }
+
+    /// Whether any file will be written
+    fn writes_anything(self) -> bool {
+        [self.global, self.local]
+            .into_iter()
+            .flatten()
+            .any(FileAction::writes)
+    }
+
+    /// Fail only when a specific file was requested and nothing gets written;
+    /// the default both-files mode succeeds even if both already exist
+    fn should_fail(self) -> bool {
+        self.explicit && !self.writes_anything()
+    }
+}
+
+/// Describe the planned action for one file
+fn plan_line(label: &str, action: FileAction, path: &Path, no_backup: bool) -> String {
+    let path = display_path(path);
+    match action {
+        FileAction::Create => format!("{label}: will create at {path}"),
+        FileAction::Overwrite if no_backup => {
+            format!("{label}: exists at {path}, will overwrite (no backup)")
+        }
+        FileAction::Overwrite => {
+            format!("{label}: exists at {path}, will overwrite (keeping a backup)")
+        }
+        FileAction::Skip => format!("{label}: exists at {path}, skipping (use --force)"),
+    }
+}
+
+/// Write a config file, backing up an existing one first unless `no_backup`
+fn write_config(
+    path: &Path,
     template: &str,
-    force: bool,
+    no_backup: bool,
     label: &str,
     color_mode: color::ColorMode,
 ) -> Result<()> {
-    // Check if already exists
-    if path.exists() && !force {
-        eprintln!(
-            "{}",
-            color::warn(
-                color_mode,
-                format!("{label} config already exists: {}", display_path(path))
+    // Keep the previous file around before overwriting it
+    if path.exists() && !no_backup {
+        let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+        let backup = backup_path(path, &format!("bak.{timestamp}"), Path::exists);
+        std::fs::rename(path, &backup).with_context(|| {
+            format!(
+                "Failed to back up {} to {}",
+                path.display(),
+                backup.display()
             )
-        );
-        eprintln!("Use --force to overwrite");
-        return Ok(());
+        })?;
+        color::eprint_status(color::info(
+            color_mode,
+            format!(
+                "Backed up existing {label} config to {}",
+                display_path(&backup)
+            ),
+        ));
     }
 
     // Create parent directory if needed
@@ -39,63 +133,211 @@ fn write_config_if_needed(
     std::fs::write(path, template)
         .with_context(|| format!("Failed to write config file: {}", path.display()))?;
 
-    eprintln!(
-        "{}",
-        color::success(
-            color_mode,
-            format!("Created {label} config: {}", display_path(path))
-        )
-    );
+    color::eprint_status(color::success(
+        color_mode,
+        format!("Created {label} config: {}", display_path(path)),
+    ));
     Ok(())
 }
 
+/// Behavior switches for `ofsht init`
+#[derive(Debug, Clone, Copy, Default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct InitOptions {
+    /// Generate only the global config
+    pub global: bool,
+    /// Generate only the local config
+    pub local: bool,
+    /// Overwrite existing config files
+    pub force: bool,
+    /// With `force`, overwrite without keeping a `.bak.<timestamp>` copy
+    pub no_backup: bool,
+    /// Print one template (global unless `local`) to stdout instead of writing files
+    pub stdout: bool,
+    /// Render the all-enabled template instead of detecting installed tools
+    pub no_detect: bool,
+    /// Print the per-file plan and stop
+    pub dry_run: bool,
+}
+
 /// Initialize configuration files
 ///
+/// Prints a per-file plan to stderr first, then writes the planned files
+/// (unless `dry_run`).
+///
 /// # Errors
 /// Returns an error if:
 /// - Global config path cannot be determined
 /// - File write fails
-pub fn cmd_init(
-    scope_global: bool,
-    scope_local: bool,
-    force: bool,
-    color_mode: color::ColorMode,
-) -> Result<()> {
-    // Determine what to generate
-    // Default (no flags): create both configs
-    let generate_global = scope_global || !scope_local;
-    let generate_local = scope_local || !scope_global;
+/// - `--global` / `--local` was given and that file exists without `--force`
+pub fn cmd_init(options: InitOptions, color_mode: color::ColorMode) -> Result<()> {
+    let ctx = if options.no_detect {
+        TemplateContext::all_enabled()
+    } else {
+        TemplateContext::detect()
+    };
 
-    // Detect tool availability
-    let ctx = TemplateContext::detect();
+    if options.stdout {
+        let template = if options.local {
+            ctx.generate_local()
+        } else {
+            ctx.generate_global()
+        };
+        print!("{template}");
+        return Ok(());
+    }
 
-    // Generate global config
-    if generate_global {
+    let global_path = if options.global || !options.local {
         let Some(path) = config::Config::global_config_path() else {
             anyhow::bail!(
                 "Could not determine global config path (HOME directory not found). \
                  Please set the HOME environment variable or XDG_CONFIG_HOME."
             );
         };
-        write_config_if_needed(&path, &ctx.generate_global(), force, "Global", color_mode)?;
+        Some(path)
+    } else {
+        None
+    };
+    // Local config lives in the main repository root when inside a git repository
+    let local_path = get_main_repo_root().map_or_else(
+        |_| config::Config::local_config_path(),
+        |repo_root| config::Config::local_config_path_from(&repo_root),
+    );
+
+    let plan = InitPlan::new(
+        global_path.as_deref().is_some_and(Path::exists),
+        local_path.exists(),
+        options,
+    );
+    // (action, path, plan name, message label, template)
+    let generate_global: fn(&TemplateContext) -> String = TemplateContext::generate_global;
+    let generate_local: fn(&TemplateContext) -> String = TemplateContext::generate_local;
+    let files = [
+        (
+            plan.global,
+            global_path.as_deref(),
+            "global",
+            "Global",
+            generate_global,
+        ),
+        (
+            plan.local,
+            Some(local_path.as_path()),
+            "local",
+            "Local",
+            generate_local,
+        ),
+    ];
+
+    for (action, path, name, _, _) in files {
+        if let (Some(action), Some(path)) = (action, path) {
+            eprintln!("{}", plan_line(name, action, path, options.no_backup));
+        }
+    }
+    if options.dry_run {
+        return Ok(());
+    }
+
+    for (action, path, _, label, generate) in files {
+        if let (Some(action), Some(path)) = (action, path) {
+            if action.writes() {
+                write_config(path, &generate(&ctx), options.no_backup, label, color_mode)?;
+            }
+        }
     }
 
-    // Generate local config
-    if generate_local {
-        // Get repo root if we're in a git repository
-        let config_path = get_main_repo_root().map_or_else(
-            |_| config::Config::local_config_path(),
-            |repo_root| config::Config::local_config_path_from(&repo_root),
+    if plan.should_fail() {
+        anyhow::bail!(
+            "Nothing was written: the requested config already exists (use --force to overwrite)"
         );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        write_config_if_needed(
-            &config_path,
-            &ctx.generate_local(),
+    fn options(global: bool, local: bool, force: bool) -> InitOptions {
+        InitOptions {
+            global,
+            local,
             force,
-            "Local",
-            color_mode,
-        )?;
+            ..Default::default()
+        }
     }
 
-    Ok(())
+    #[test]
+    fn test_plan_default_mode_never_fails() {
+        use FileAction::{Create, Skip};
+        let default = options(false, false, false);
+        for (global_exists, local_exists, global, local) in [
+            (false, false, Create, Create),
+            (true, false, Skip, Create),
+            (false, true, Create, Skip),
+            (true, true, Skip, Skip),
+        ] {
+            let plan = InitPlan::new(global_exists, local_exists, default);
+            assert_eq!(plan.global, Some(global));
+            assert_eq!(plan.local, Some(local));
+            assert!(!plan.should_fail(), "{global_exists} {local_exists}");
+        }
+    }
+
+    #[test]
+    fn test_plan_global_only() {
+        let global_only = options(true, false, false);
+        for (global_exists, local_exists, action, fails) in [
+            (false, false, FileAction::Create, false),
+            (false, true, FileAction::Create, false),
+            (true, false, FileAction::Skip, true),
+            (true, true, FileAction::Skip, true),
+        ] {
+            let plan = InitPlan::new(global_exists, local_exists, global_only);
+            assert_eq!(plan.global, Some(action));
+            assert_eq!(plan.local, None);
+            assert_eq!(plan.should_fail(), fails, "{global_exists} {local_exists}");
+        }
+    }
+
+    #[test]
+    fn test_plan_local_only() {
+        let local_only = options(false, true, false);
+        for (global_exists, local_exists, action, fails) in [
+            (false, false, FileAction::Create, false),
+            (true, false, FileAction::Create, false),
+            (false, true, FileAction::Skip, true),
+            (true, true, FileAction::Skip, true),
+        ] {
+            let plan = InitPlan::new(global_exists, local_exists, local_only);
+            assert_eq!(plan.global, None);
+            assert_eq!(plan.local, Some(action));
+            assert_eq!(plan.should_fail(), fails, "{global_exists} {local_exists}");
+        }
+    }
+
+    #[test]
+    fn test_plan_force_overwrites_existing() {
+        let plan = InitPlan::new(true, false, options(false, false, true));
+        assert_eq!(plan.global, Some(FileAction::Overwrite));
+        assert_eq!(plan.local, Some(FileAction::Create));
+
+        let plan = InitPlan::new(true, true, options(false, true, true));
+        assert_eq!(plan.local, Some(FileAction::Overwrite));
+        assert!(!plan.should_fail());
+    }
+
+    #[test]
+    fn test_plan_line_wording() {
+        let path = Path::new("/repo/.ofsht.toml");
+        assert_eq!(
+            plan_line("local", FileAction::Create, path, false),
+            "local: will create at /repo/.ofsht.toml"
+        );
+        assert_eq!(
+            plan_line("local", FileAction::Skip, path, false),
+            "local: exists at /repo/.ofsht.toml, skipping (use --force)"
+        );
+        assert!(plan_line("local", FileAction::Overwrite, path, true).ends_with("(no backup)"));
+    }
 }
@@ -2,16 +2,404 @@
 
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use std::io::IsTerminal;
-use std::path::PathBuf;
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::{Deserialize, Serialize};
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
 use crate::color;
-use crate::commands::common::get_main_repo_root;
-use crate::commands::list_display::format_worktree_table;
-use crate::config::Config;
-use crate::domain::worktree::WorktreeList;
+use crate::commands::common::{get_main_repo_root, measure_directory_sizes};
+use crate::commands::list_display::{format_worktree_table_with_layout, sort_rows, SortKey};
+use crate::config::{AutoFetch, Config, ListColumn};
+use crate::domain::exclude::WorktreeExclude;
+use crate::domain::status::{upstream_states, UPSTREAM_FORMAT};
+use crate::domain::worktree::{
+    diff_entries, EntryChange, WorktreeEntry, WorktreeLayout, WorktreeList,
+};
+use crate::integrations::gh;
 use crate::integrations::git::{GitClient, RealGitClient};
 use crate::path_utils::normalize_absolute_path;
+use crate::service::WorktreeManager;
+use crate::state;
+
+/// Pair each worktree with its last commit time (when `with_times` is set) and apply `sort`
+/// (the default [`SortKey`] when `None`)
+///
+/// With `with_dirty`, each entry's `dirty` count is filled from `git status --porcelain`.
+/// The PR/issue number recorded by `ofsht add '#123'` is attached to every entry.
+fn collect_rows<G: GitClient + Sync>(
+    git: &G,
+    entries: &[WorktreeEntry],
+    with_times: bool,
+    with_dirty: bool,
+    sort: Option<SortKey>,
+) -> (Vec<WorktreeEntry>, Vec<Option<DateTime<Utc>>>) {
+    let paths: Vec<PathBuf> = entries.iter().map(|e| PathBuf::from(&e.path)).collect();
+    let times = if with_times {
+        git.last_commit_times_parallel(&paths)
+    } else {
+        vec![None; entries.len()]
+    };
+    let mut rows: Vec<(WorktreeEntry, Option<DateTime<Utc>>)> = entries
+        .iter()
+        .zip(&paths)
+        .zip(times)
+        .map(|((entry, path), time)| {
+            let mut entry = entry.clone();
+            entry.github_number = gh::meta::load(path).map(|github_ref| github_ref.number);
+            if with_dirty {
+                entry.dirty = git
+                    .status_porcelain(path)
+                    .ok()
+                    .map(|status| status.lines().count());
+            }
+            (entry, time)
+        })
+        .collect();
+    sort_rows(&mut rows, sort.unwrap_or_default());
+    rows.into_iter().unzip()
+}
+
+/// Fill `disk_size` for every entry (`ls --du`)
+fn fill_disk_sizes(entries: &mut [WorktreeEntry], color_mode: color::ColorMode) {
+    let paths: Vec<PathBuf> = entries.iter().map(|e| PathBuf::from(&e.path)).collect();
+    let sizes = measure_directory_sizes(&paths, color_mode);
+    for (entry, size) in entries.iter_mut().zip(sizes) {
+        entry.disk_size = Some(size);
+    }
+}
+
+/// Remotes fetched by `ls --fetch`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum FetchScope {
+    /// `origin`, or the first remote when there is no `origin`
+    Default,
+    /// Every remote (`git fetch --all`)
+    All,
+}
+
+/// How long a fetch counts as fresh for `ls.auto_fetch = "if-stale"`
+// `Duration::from_mins` needs Rust 1.91
+#[allow(clippy::duration_suboptimal_units)]
+const AUTO_FETCH_MAX_AGE: Duration = Duration::from_secs(15 * 60);
+
+/// Whether remote-tracking state is older than `max_age`, judged by the
+/// modification time of `FETCH_HEAD` (a repository never fetched is stale)
+fn fetch_is_stale(
+    fetch_head_modified: Option<SystemTime>,
+    now: SystemTime,
+    max_age: Duration,
+) -> bool {
+    fetch_head_modified
+        .is_none_or(|modified| now.duration_since(modified).is_ok_and(|age| age > max_age))
+}
+
+/// Modification time of the main repository's `FETCH_HEAD`
+fn fetch_head_modified(git: &impl GitClient, repo_root: &Path) -> Option<SystemTime> {
+    let path = git
+        .rev_parse(&["rev-parse", "--git-path", "FETCH_HEAD"], Some(repo_root))
+        .ok()?;
+    std::fs::metadata(repo_root.join(path.trim()))
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Run one `git fetch --prune --quiet` for `scope` behind a spinner on stderr
+///
+/// Failures only warn: the listing goes on with the remote-tracking state it has.
+fn fetch_remotes(
+    git: &impl GitClient,
+    scope: FetchScope,
+    repo_root: &Path,
+    color_mode: color::ColorMode,
+) {
+    let target = match scope {
+        FetchScope::All => "--all".to_string(),
+        FetchScope::Default => {
+            let remotes = git.remote_list(Some(repo_root)).unwrap_or_default();
+            let mut names = remotes.lines().filter_map(|line| line.split('\t').next());
+            let Some(first) = names.next() else {
+                // Nothing to fetch from
+                return;
+            };
+            if first == "origin" || names.any(|name| name == "origin") {
+                "origin".to_string()
+            } else {
+                first.to_string()
+            }
+        }
+    };
+    let label = if scope == FetchScope::All {
+        "all remotes"
+    } else {
+        target.as_str()
+    };
+    let pb = (color_mode.should_colorize() && !color::is_quiet()).then(|| {
+        let pb = ProgressBar::new_spinner();
+        pb.set_style(ProgressStyle::with_template("{spinner:.cyan} {msg}").unwrap());
+        pb.set_message(format!("Fetching {label}"));
+        pb.enable_steady_tick(Duration::from_millis(100));
+        pb
+    });
+    let result = git.fetch(&["fetch", "--prune", "--quiet", &target], Some(repo_root));
+    if let Some(pb) = pb {
+        pb.finish_and_clear();
+    }
+    if let Err(e) = result {
+        eprintln!(
+            "{}",
+            color::warn(
+                color_mode,
+                format!(
+                    "Failed to fetch {label}; upstream state may be stale: {}",
+                    e.to_string().trim()
+                )
+            )
+        );
+    }
+}
+
+/// Fill `upstream` for every entry with a branch that tracks one
+fn fill_upstream(git: &impl GitClient, entries: &mut [WorktreeEntry], repo_root: &Path) {
+    let output = git
+        .for_each_ref(&["refs/heads"], UPSTREAM_FORMAT, Some(repo_root))
+        .unwrap_or_default();
+    let states = upstream_states(&output);
+    for entry in entries {
+        entry.upstream = entry
+            .branch
+            .as_ref()
+            .map(|branch| states.get(branch).cloned().unwrap_or_default());
+    }
+}
+
+/// Rows for `ls --stdin-porcelain`: the parsed entries, none of them active and
+/// without commit times, sorted by `sort` (the default [`SortKey`] when `None`)
+fn porcelain_rows(
+    porcelain: &str,
+    sort: Option<SortKey>,
+) -> (Vec<WorktreeEntry>, Vec<Option<DateTime<Utc>>>) {
+    let list = WorktreeList::parse(porcelain, None);
+    let mut rows: Vec<(WorktreeEntry, Option<DateTime<Utc>>)> = list
+        .entries()
+        .iter()
+        .map(|entry| (entry.clone(), None))
+        .collect();
+    sort_rows(&mut rows, sort.unwrap_or_default());
+    rows.into_iter().unzip()
+}
+
+/// Width available for table rows, or `None` when it cannot be determined
+///
+/// `COLUMNS` takes precedence so the width can be forced (e.g. in scripts and tests);
+/// otherwise the size of the terminal behind the output stream is used.
+fn output_width(term: &console::Term) -> Option<usize> {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse::<usize>().ok())
+        .filter(|&columns| columns > 0)
+        .or_else(|| term.size_checked().map(|(_rows, cols)| usize::from(cols)))
+}
+
+/// Name printed for a worktree in pipe mode, actionable by `cd` and `rm`:
+/// `@` for the main worktree, the branch, or the absolute path when detached
+fn pipe_name(index: usize, entry: &WorktreeEntry) -> String {
+    if index == 0 {
+        "@".to_string()
+    } else if let Some(branch) = &entry.branch {
+        branch.clone()
+    } else {
+        normalize_absolute_path(&PathBuf::from(&entry.path))
+    }
+}
+
+/// NUL-terminated records for `ls -0`: the pipe-mode name, or `path<TAB>name` with `show_path`
+///
+/// `rows` pairs each entry with its position in the full list (0 = main worktree).
+fn print0_records(rows: &[(usize, &WorktreeEntry)], show_path: bool) -> Vec<u8> {
+    let mut out = Vec::new();
+    for &(index, entry) in rows {
+        if show_path {
+            out.extend_from_slice(normalize_absolute_path(&PathBuf::from(&entry.path)).as_bytes());
+            out.push(b'\t');
+        }
+        out.extend_from_slice(pipe_name(index, entry).as_bytes());
+        out.push(0);
+    }
+    out
+}
+
+/// Row filter for `ls --prunable` / `ls --stale <days>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListFilter {
+    /// Worktrees git reports as prunable (their directory is gone)
+    Prunable,
+    /// Worktrees whose last commit is older than this many days
+    Stale(u32),
+}
+
+impl ListFilter {
+    /// Whether a row passes the filter; rows without a commit time are never stale
+    fn matches(
+        self,
+        entry: &WorktreeEntry,
+        commit_time: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> bool {
+        match self {
+            Self::Prunable => entry.prunable,
+            Self::Stale(days) => commit_time
+                .is_some_and(|time| now.signed_duration_since(time).num_days() >= i64::from(days)),
+        }
+    }
+}
+
+/// Apply `filter` to the rows, keeping each row's position in the full list
+///
+/// The main worktree (position 0) is kept when `keep_main` is set, so tables stay
+/// anchored on it the same way sorting keeps it first.
+fn filter_rows(
+    entries: &[WorktreeEntry],
+    commit_times: &[Option<DateTime<Utc>>],
+    filter: Option<ListFilter>,
+    keep_main: bool,
+    now: DateTime<Utc>,
+) -> Vec<usize> {
+    (0..entries.len())
+        .filter(|&index| {
+            filter.is_none_or(|filter| {
+                (keep_main && index == 0)
+                    || filter.matches(
+                        &entries[index],
+                        commit_times.get(index).copied().flatten(),
+                        now,
+                    )
+            })
+        })
+        .collect()
+}
+
+/// Worktree fields remembered between `ls --diff` runs
+#[derive(Debug, Serialize, Deserialize)]
+struct SnapshotEntry {
+    path: String,
+    branch: Option<String>,
+    hash: Option<String>,
+}
+
+/// Snapshot file for `ls --diff`:
+/// `$XDG_STATE_HOME/ofsht/ls/<repo>.json` (fallback: `~/.local/state`)
+fn snapshot_path(repo_root: &Path) -> Option<PathBuf> {
+    let state_home = std::env::var_os("XDG_STATE_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| dirs::home_dir().map(|home| home.join(".local").join("state")))?;
+    let key: String = normalize_absolute_path(repo_root)
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    Some(
+        state_home
+            .join("ofsht")
+            .join("ls")
+            .join(format!("{key}.json")),
+    )
+}
+
+/// Entries stored by the previous `ls --diff` run (best-effort; `None` when missing or unreadable)
+fn load_snapshot(path: &Path) -> Option<Vec<WorktreeEntry>> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let snapshot: Vec<SnapshotEntry> = serde_json::from_str(&content).ok()?;
+    Some(
+        snapshot
+            .into_iter()
+            .map(|entry| WorktreeEntry {
+                path: entry.path,
+                branch: entry.branch,
+                hash: entry.hash,
+                ..Default::default()
+            })
+            .collect(),
+    )
+}
+
+/// Store the current entries for the next `ls --diff` run (best-effort)
+fn save_snapshot(path: &Path, entries: &[WorktreeEntry], color_mode: color::ColorMode) {
+    let snapshot: Vec<SnapshotEntry> = entries
+        .iter()
+        .map(|entry| SnapshotEntry {
+            path: entry.path.clone(),
+            branch: entry.branch.clone(),
+            hash: entry.hash.clone(),
+        })
+        .collect();
+    if let Ok(json) = serde_json::to_string(&snapshot) {
+        state::write_best_effort(path, json, color_mode);
+    }
+}
+
+/// Format the table with a change marker in front of every row
+///
+/// Worktrees gone since `previous` are appended as dimmed rows built from the snapshot.
+/// `format` renders the combined rows like a regular table.
+fn format_diff_table(
+    entries: &[WorktreeEntry],
+    commit_times: &[Option<DateTime<Utc>>],
+    previous: &[WorktreeEntry],
+    color_mode: color::ColorMode,
+    max_width: Option<usize>,
+    format: impl Fn(&[WorktreeEntry], &[Option<DateTime<Utc>>], Option<usize>) -> Vec<String>,
+) -> Vec<String> {
+    let diff = diff_entries(previous, entries);
+    let changes: Vec<EntryChange> = diff.iter().map(|(change, _)| *change).collect();
+    let all_entries: Vec<WorktreeEntry> = diff.into_iter().map(|(_, entry)| entry).collect();
+    let mut all_times = commit_times.to_vec();
+    all_times.resize(all_entries.len(), None);
+
+    let lines = format(
+        &all_entries,
+        &all_times,
+        max_width.map(|width| width.saturating_sub(2)),
+    );
+    lines
+        .into_iter()
+        .zip(changes)
+        .map(|(line, change)| {
+            if change == EntryChange::Removed {
+                format!("{} {}", change.marker(), color::dim(color_mode, line))
+            } else {
+                format!("{} {line}", change.marker())
+            }
+        })
+        .collect()
+}
+
+/// Options for [`cmd_list`]
+#[derive(Debug, Default, Clone, Copy)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct ListOptions {
+    /// Show worktree paths (table output in pipe mode)
+    pub show_path: bool,
+    pub sort: Option<SortKey>,
+    /// Never truncate columns to fit the terminal width
+    pub no_truncate: bool,
+    /// NUL-delimited records on stdout
+    pub print0: bool,
+    /// Mark rows changed since the previous `--diff` run
+    pub diff: bool,
+    /// Only show prunable or stale worktrees
+    pub filter: Option<ListFilter>,
+    /// Ignore `worktree.exclude`
+    pub all: bool,
+    /// Read the porcelain listing from stdin instead of running git
+    pub stdin_porcelain: bool,
+    /// Add a disk usage column (table output only)
+    pub du: bool,
+    /// Fetch before filling the `upstream` column, which is shown even when
+    /// not configured (table output only)
+    pub fetch: Option<FetchScope>,
+}
 
 /// List all worktrees
 ///
@@ -19,18 +407,169 @@ use crate::path_utils::normalize_absolute_path;
 /// Returns an error if:
 /// - Git worktree list command fails
 /// - Output parsing fails
-pub fn cmd_list(show_path: bool, color_mode: color::ColorMode) -> Result<()> {
-    // Get worktree list in porcelain format
+#[allow(clippy::too_many_lines)]
+pub fn cmd_list(options: ListOptions, color_mode: color::ColorMode) -> Result<()> {
+    let ListOptions {
+        show_path,
+        sort,
+        no_truncate,
+        print0,
+        diff,
+        filter,
+        all,
+        stdin_porcelain,
+        du,
+        fetch,
+    } = options;
+    let now = Utc::now();
+    // Stale filtering needs commit times even where they are not displayed
+    let needs_times = sort == Some(SortKey::Time) || matches!(filter, Some(ListFilter::Stale(_)));
+    // Table rows keep the main worktree; pipe output only lists matching rows
+    let filter_table = |entries: Vec<WorktreeEntry>, commit_times: Vec<Option<DateTime<Utc>>>| {
+        if filter.is_none() {
+            return (entries, commit_times);
+        }
+        filter_rows(&entries, &commit_times, filter, true, now)
+            .into_iter()
+            .map(|index| (entries[index].clone(), commit_times[index]))
+            .unzip()
+    };
+
+    if stdin_porcelain {
+        let porcelain = std::io::read_to_string(std::io::stdin())?;
+        let (entries, commit_times) = porcelain_rows(&porcelain, sort);
+        let config = Config::default();
+        let layout = WorktreeLayout::new(&entries, Some(&config.worktree.dir));
+        let (entries, commit_times) = filter_table(entries, commit_times);
+        let max_width = if no_truncate {
+            None
+        } else {
+            output_width(&console::Term::stdout())
+        };
+        let lines = format_worktree_table_with_layout(
+            &entries,
+            &commit_times,
+            show_path,
+            color_mode,
+            Some(&config),
+            Some(&layout),
+            max_width,
+        );
+        for line in lines {
+            println!("{line}");
+        }
+        return Ok(());
+    }
+
     let git = RealGitClient;
-    let stdout = git.list_worktrees(None)?;
+    let repo_root = get_main_repo_root()?;
+
+    // Load config from main repository root
+    let config = Config::load_from_repo_root(&repo_root).ok();
 
-    // Get current directory for active worktree detection
+    // The worktree containing the current directory is marked active for the tables
+    let manager = WorktreeManager::new(
+        RealGitClient,
+        &repo_root,
+        config
+            .as_ref()
+            .map(|cfg| cfg.worktree.clone())
+            .unwrap_or_default(),
+    );
     let current_dir = std::env::current_dir().ok();
+    let list = manager.worktree_list(current_dir.as_deref())?;
 
-    // Load config from main repository root
-    let config = get_main_repo_root()
-        .ok()
-        .and_then(|repo_root| Config::load_from_repo_root(&repo_root).ok());
+    // Worktrees matching `worktree.exclude` are hidden unless `--all` is given
+    let exclude = match config.as_ref().filter(|_| !all) {
+        Some(cfg) => WorktreeExclude::new(&cfg.worktree.exclude)?,
+        None => WorktreeExclude::default(),
+    };
+
+    // Relative paths come from the complete listing, before any rows are hidden
+    let layout =
+        WorktreeLayout::for_list(&list, config.as_ref().map(|cfg| cfg.worktree.dir.as_str()));
+    // The relative path column is only filled when a config was loaded
+    let table_layout = config.as_ref().map(|_| &layout);
+    let visible = exclude.filter_entries(list.entries(), &layout);
+
+    // NUL-delimited records are plain data for scripts: always stdout, never the table
+    if print0 {
+        let (entries, commit_times) = collect_rows(&git, &visible, needs_times, false, sort);
+        let rows: Vec<(usize, &WorktreeEntry)> =
+            filter_rows(&entries, &commit_times, filter, false, now)
+                .into_iter()
+                .map(|index| (index, &entries[index]))
+                .collect();
+        let mut out = std::io::stdout().lock();
+        out.write_all(&print0_records(&rows, show_path))?;
+        out.flush()?;
+        return Ok(());
+    }
+
+    // Uncommitted-change counts are only collected when the `dirty` column is shown
+    let with_dirty = config
+        .as_ref()
+        .is_some_and(|cfg| cfg.worktree.list_columns.contains(&ListColumn::Dirty));
+
+    // Upstream state is filled for the `upstream` column or `--fetch`; the
+    // fetch itself runs once, before the first table is built
+    let with_upstream = fetch.is_some()
+        || config
+            .as_ref()
+            .is_some_and(|cfg| cfg.worktree.list_columns.contains(&ListColumn::Upstream));
+    let fill_remote_state = |entries: &mut [WorktreeEntry]| {
+        if !with_upstream {
+            return;
+        }
+        let auto_fetch = config
+            .as_ref()
+            .is_some_and(|cfg| cfg.ls.auto_fetch == AutoFetch::IfStale)
+            && fetch_is_stale(
+                fetch_head_modified(&git, &repo_root),
+                SystemTime::now(),
+                AUTO_FETCH_MAX_AGE,
+            );
+        if let Some(scope) = fetch.or_else(|| auto_fetch.then_some(FetchScope::Default)) {
+            fetch_remotes(&git, scope, &repo_root, color_mode);
+        }
+        fill_upstream(&git, entries, &repo_root);
+    };
+
+    // `--diff`: compare against the previous run's snapshot (missing snapshot = no changes)
+    let snapshot = diff.then(|| snapshot_path(&repo_root)).flatten();
+    let previous = snapshot.as_deref().map(load_snapshot);
+    let format_rows = |entries: &[WorktreeEntry],
+                       commit_times: &[Option<DateTime<Utc>>],
+                       max_width: Option<usize>| {
+        format_worktree_table_with_layout(
+            entries,
+            commit_times,
+            show_path,
+            color_mode,
+            config.as_ref(),
+            table_layout,
+            max_width,
+        )
+    };
+    let format_table = |entries: &[WorktreeEntry],
+                        commit_times: &[Option<DateTime<Utc>>],
+                        max_width: Option<usize>| {
+        match (&previous, &snapshot) {
+            (Some(previous), Some(path)) => {
+                let lines = format_diff_table(
+                    entries,
+                    commit_times,
+                    previous.as_deref().unwrap_or(entries),
+                    color_mode,
+                    max_width,
+                    format_rows,
+                );
+                save_snapshot(path, entries, color_mode);
+                lines
+            }
+            _ => format_rows(entries, commit_times, max_width),
+        }
+    };
 
     // Determine stream/format based ONLY on TTY status
     // Color mode only affects ANSI emission, not which stream or format
@@ -38,23 +577,22 @@ pub fn cmd_list(show_path: bool, color_mode: color::ColorMode) -> Result<()> {
 
     if is_interactive {
         // Interactive mode: enhanced table to stderr (with colors if enabled)
-        let list = WorktreeList::parse(&stdout, current_dir.as_deref());
-        let entries = list.entries();
 
-        // Get commit times for all worktrees
-        let commit_times: Vec<Option<DateTime<Utc>>> = entries
-            .iter()
-            .map(|entry| git.last_commit_time(&std::path::PathBuf::from(&entry.path)))
-            .collect();
+        // Get commit times for all worktrees (main worktree stays first when sorting)
+        let (entries, commit_times) = collect_rows(&git, &visible, true, with_dirty, sort);
+        let (mut entries, commit_times) = filter_table(entries, commit_times);
+        if du {
+            fill_disk_sizes(&mut entries, color_mode);
+        }
+        fill_remote_state(&mut entries);
 
         // Format and print table to stderr (color_mode controls ANSI emission)
-        let lines = format_worktree_table(
-            entries,
-            &commit_times,
-            show_path,
-            color_mode,
-            config.as_ref(),
-        );
+        let max_width = if no_truncate {
+            None
+        } else {
+            output_width(&console::Term::stderr())
+        };
+        let lines = format_table(&entries, &commit_times, max_width);
         for line in lines {
             eprintln!("{line}");
         }
@@ -62,44 +600,157 @@ pub fn cmd_list(show_path: bool, color_mode: color::ColorMode) -> Result<()> {
         // Pipe mode: output to stdout (color_mode still controls ANSI emission)
         if show_path {
             // Full table output to stdout
-            let list = WorktreeList::parse(&stdout, current_dir.as_deref());
-            let entries = list.entries();
-
-            let commit_times: Vec<Option<DateTime<Utc>>> = entries
-                .iter()
-                .map(|entry| git.last_commit_time(&std::path::PathBuf::from(&entry.path)))
-                .collect();
+            let (entries, commit_times) = collect_rows(&git, &visible, true, with_dirty, sort);
+            let (mut entries, commit_times) = filter_table(entries, commit_times);
+            if du {
+                fill_disk_sizes(&mut entries, color_mode);
+            }
+            fill_remote_state(&mut entries);
 
             // Format and print table to stdout
             // color_mode determines whether ANSI codes are included
-            let lines = format_worktree_table(
-                entries,
-                &commit_times,
-                show_path,
-                color_mode,
-                config.as_ref(),
-            );
+            let max_width = if no_truncate {
+                None
+            } else {
+                output_width(&console::Term::stdout())
+            };
+            let lines = format_table(&entries, &commit_times, max_width);
             for line in lines {
                 println!("{line}");
             }
         } else {
-            // Simple mode: branch names only — pipe-mode parse without active_path
-            let list = WorktreeList::parse(&stdout, None);
-
-            for (index, entry) in list.entries().iter().enumerate() {
-                if index == 0 {
-                    // Main worktree
-                    println!("@");
-                } else if let Some(branch) = &entry.branch {
-                    // Output branch name (actionable by cd and rm)
-                    println!("{branch}");
-                } else {
-                    // Detached HEAD: output normalized absolute path to make it actionable by cd and rm
-                    println!("{}", normalize_absolute_path(&PathBuf::from(&entry.path)));
-                }
+            // Simple mode: branch names only
+            // Commit times are only needed to honor `--sort time` and `--stale`
+            let (entries, commit_times) = collect_rows(&git, &visible, needs_times, false, sort);
+
+            for index in filter_rows(&entries, &commit_times, filter, false, now) {
+                println!("{}", pipe_name(index, &entries[index]));
             }
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::git::tests::MockGitClient;
+    use chrono::Duration;
+
+    fn entry(path: &str, prunable: bool) -> WorktreeEntry {
+        WorktreeEntry {
+            path: path.to_string(),
+            branch: Some(path.trim_start_matches('/').to_string()),
+            prunable,
+            ..Default::default()
+        }
+    }
+
+    fn rows() -> (
+        Vec<WorktreeEntry>,
+        Vec<Option<DateTime<Utc>>>,
+        DateTime<Utc>,
+    ) {
+        let now = Utc::now();
+        let entries = vec![
+            entry("/main", false),
+            entry("/fresh", false),
+            entry("/old", false),
+            entry("/gone", true),
+            entry("/unknown", false),
+        ];
+        let times = vec![
+            Some(now - Duration::days(90)),
+            Some(now - Duration::days(2)),
+            Some(now - Duration::days(45)),
+            Some(now - Duration::days(30)),
+            None,
+        ];
+        (entries, times, now)
+    }
+
+    #[test]
+    fn test_collect_rows_counts_dirty_files() {
+        let git = MockGitClient {
+            status_output: " M src/main.rs\n?? notes.txt\n".to_string(),
+            ..Default::default()
+        };
+        let entries = [entry("/nonexistent/ofsht-dirty", false)];
+
+        let (rows, _) = collect_rows(&git, &entries, false, true, None);
+        assert_eq!(rows[0].dirty, Some(2));
+
+        // Without `--dirty` the status is never consulted
+        let (rows, _) = collect_rows(&git, &entries, false, false, None);
+        assert_eq!(rows[0].dirty, None);
+    }
+
+    #[test]
+    fn test_filter_rows_stale() {
+        let (entries, times, now) = rows();
+        let filter = Some(ListFilter::Stale(30));
+        // Pipe output: main is listed only because it is stale itself
+        assert_eq!(filter_rows(&entries, &times, filter, false, now), [0, 2, 3]);
+        assert_eq!(
+            filter_rows(&entries, &times, Some(ListFilter::Stale(60)), false, now),
+            [0]
+        );
+    }
+
+    #[test]
+    fn test_filter_rows_prunable_keeps_main_for_tables() {
+        let (entries, times, now) = rows();
+        let filter = Some(ListFilter::Prunable);
+        assert_eq!(filter_rows(&entries, &times, filter, false, now), [3]);
+        assert_eq!(filter_rows(&entries, &times, filter, true, now), [0, 3]);
+    }
+
+    #[test]
+    fn test_filter_rows_without_filter_keeps_everything() {
+        let (entries, times, now) = rows();
+        assert_eq!(
+            filter_rows(&entries, &times, None, false, now),
+            [0, 1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    #[allow(clippy::duration_suboptimal_units)] // see AUTO_FETCH_MAX_AGE
+    fn test_fetch_is_stale() {
+        let now = SystemTime::now();
+        let max_age = std::time::Duration::from_secs(15 * 60);
+        let ago = |mins: u64| Some(now - std::time::Duration::from_secs(mins * 60));
+        assert!(fetch_is_stale(None, now, max_age));
+        assert!(fetch_is_stale(ago(16), now, max_age));
+        assert!(!fetch_is_stale(ago(1), now, max_age));
+        // A FETCH_HEAD from the future (clock skew) counts as fresh
+        assert!(!fetch_is_stale(
+            Some(now + std::time::Duration::from_secs(60)),
+            now,
+            max_age
+        ));
+    }
+
+    #[test]
+    fn test_fill_upstream_marks_tracked_branches() {
+        let git = crate::integrations::git::tests::MockGitClient {
+            for_each_ref_output: "main\trefs/remotes/origin/main\t\n\
+                                  gone\trefs/remotes/origin/gone\t[gone]\n"
+                .to_string(),
+            ..Default::default()
+        };
+        let mut entries = vec![
+            entry("/main", false),
+            entry("/gone", true),
+            entry("/local", false),
+        ];
+        entries.push(WorktreeEntry {
+            branch: None,
+            ..entry("/detached", false)
+        });
+        fill_upstream(&git, &mut entries, Path::new("/repo"));
+        let states: Vec<Option<&str>> = entries.iter().map(|e| e.upstream.as_deref()).collect();
+        assert_eq!(states, [Some("="), Some("gone"), Some(""), None]);
+    }
+}
@@ -5,40 +5,368 @@
 
 use chrono::{DateTime, Utc};
 use chrono_humanize::{Accuracy, HumanTime, Tense};
+use std::cmp::Ordering;
 
 use crate::color;
-use crate::domain::worktree::{
-    calculate_relative_path, calculate_worktree_root_from_paths, WorktreeEntry,
-};
+use crate::config::{ListColumn, DEFAULT_LIST_COLUMNS};
+use crate::domain::worktree::{WorktreeEntry, WorktreeLayout};
 use crate::path_utils::display_path;
 
-/// Worktree display information including commit time
+/// Sort order for `ofsht ls --sort`
+///
+/// Without `--sort`, rows are ordered by [`SortKey::Branch`] so the listing
+/// does not depend on git's porcelain order (which differs between versions).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum SortKey {
+    /// Most recent commit first; worktrees without a commit time go last
+    Time,
+    /// Branch name in natural order (`feature-2` before `feature-10`);
+    /// detached worktrees go last, by path
+    #[default]
+    Branch,
+    /// Worktree path, alphabetically
+    Path,
+}
+
+/// Compare two worktree rows (entry + last commit time) by `key`
+///
+/// Ties fall back to the worktree path so the order is deterministic.
+#[must_use]
+pub fn compare_rows(
+    a: &(WorktreeEntry, Option<DateTime<Utc>>),
+    b: &(WorktreeEntry, Option<DateTime<Utc>>),
+    key: SortKey,
+) -> Ordering {
+    let primary = match key {
+        // Newest first
+        SortKey::Time => match (a.1, b.1) {
+            (Some(ta), Some(tb)) => tb.cmp(&ta),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        },
+        SortKey::Branch => match (&a.0.branch, &b.0.branch) {
+            (Some(ba), Some(bb)) => natural_cmp(ba, bb),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        },
+        SortKey::Path => Ordering::Equal,
+    };
+    primary.then_with(|| a.0.path.cmp(&b.0.path))
+}
+
+/// Compare strings in natural order: case-insensitively, with runs of ASCII
+/// digits compared by numeric value (`feature-2` < `feature-10`)
+///
+/// Strings that only differ in case or leading zeros fall back to a plain
+/// comparison, so distinct strings never compare equal.
+#[must_use]
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a_rest, mut b_rest) = (a, b);
+    loop {
+        let (ca, cb) = match (a_rest.chars().next(), b_rest.chars().next()) {
+            (None, None) => return a.cmp(b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(ca), Some(cb)) => (ca, cb),
+        };
+        let ordering = if ca.is_ascii_digit() && cb.is_ascii_digit() {
+            let (a_digits, a_tail) = split_digits(a_rest);
+            let (b_digits, b_tail) = split_digits(b_rest);
+            (a_rest, b_rest) = (a_tail, b_tail);
+            let a_digits = a_digits.trim_start_matches('0');
+            let b_digits = b_digits.trim_start_matches('0');
+            // Equal-length digit runs compare like their values
+            a_digits
+                .len()
+                .cmp(&b_digits.len())
+                .then_with(|| a_digits.cmp(b_digits))
+        } else {
+            a_rest = &a_rest[ca.len_utf8()..];
+            b_rest = &b_rest[cb.len_utf8()..];
+            ca.to_lowercase().cmp(cb.to_lowercase())
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+/// Split `s` into its leading run of ASCII digits and the rest
+fn split_digits(s: &str) -> (&str, &str) {
+    s.split_at(s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len()))
+}
+
+/// Sort worktree rows in place by `key`, keeping the main worktree (first row) pinned
+pub fn sort_rows(rows: &mut [(WorktreeEntry, Option<DateTime<Utc>>)], key: SortKey) {
+    if let Some((_main, rest)) = rows.split_first_mut() {
+        rest.sort_by(|a, b| compare_rows(a, b, key));
+    }
+}
+
+/// Narrowest the path column is truncated to before other columns give way
+const MIN_PATH_WIDTH: usize = 16;
+
+/// Trailing marker of locked worktrees
+const LOCKED_INDICATOR: &str = "🔒";
+
+/// Terminal columns taken by [`LOCKED_INDICATOR`] (a wide character)
+const LOCKED_INDICATOR_WIDTH: usize = 2;
+
+/// Natural (untruncated) widths of a worktree table row, in characters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ColumnWidths {
+    /// Everything that is never degraded: other columns, separators and the lock indicator
+    pub fixed: usize,
+    /// `None` when paths are not shown
+    pub path: Option<usize>,
+    /// Width the relative path column adds, separator included
+    /// (`None` when the column is not shown)
+    pub rel_path: Option<usize>,
+    /// 0 when the time column is not shown
+    pub timestamp: usize,
+    pub compact_timestamp: usize,
+}
+
+/// How to render a worktree table so that each row fits on one line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderPlan {
+    /// Maximum width of the path column (`None` = untruncated)
+    pub path_width: Option<usize>,
+    pub show_rel_path: bool,
+    pub compact_time: bool,
+}
+
+impl RenderPlan {
+    /// Render every column at its natural width
+    #[must_use]
+    pub const fn full() -> Self {
+        Self {
+            path_width: None,
+            show_rel_path: true,
+            compact_time: false,
+        }
+    }
+}
+
+/// Total row width for `widths` rendered with `plan`
+const fn row_width(widths: &ColumnWidths, plan: &RenderPlan) -> usize {
+    let mut width = widths.fixed;
+    if let Some(path) = widths.path {
+        width += match plan.path_width {
+            Some(max) if max < path => max,
+            _ => path,
+        };
+    }
+    if let (true, Some(rel_path)) = (plan.show_rel_path, widths.rel_path) {
+        width += rel_path;
+    }
+    width += if plan.compact_time {
+        widths.compact_timestamp
+    } else {
+        widths.timestamp
+    };
+    width
+}
+
+/// Decide how to degrade the table so rows fit within `term_width`
+///
+/// Degradations are applied in order until the row fits: truncate the path
+/// column (down to a minimum), drop the relative path column, then switch to
+/// compact timestamps. If nothing is enough, the most compact plan is returned.
+#[must_use]
+pub fn plan_table_layout(widths: &ColumnWidths, term_width: usize) -> RenderPlan {
+    let steps: [fn(&mut RenderPlan); 3] = [
+        |_| {},
+        |plan| plan.show_rel_path = false,
+        |plan| plan.compact_time = true,
+    ];
+    let mut plan = RenderPlan::full();
+    for step in steps {
+        step(&mut plan);
+        if let Some(path) = widths.path {
+            let fixed = row_width(
+                widths,
+                &RenderPlan {
+                    path_width: Some(0),
+                    ..plan
+                },
+            );
+            let room = term_width.saturating_sub(fixed);
+            plan.path_width = (room < path).then(|| room.max(path.min(MIN_PATH_WIDTH)));
+        }
+        if row_width(widths, &plan) <= term_width {
+            break;
+        }
+    }
+    plan
+}
+
+/// Truncate `path` to at most `max_width` characters, keeping its last components
+///
+/// The cut happens at a `/` boundary when possible and is marked with a leading `…`.
+#[must_use]
+pub fn truncate_path_left(path: &str, max_width: usize) -> String {
+    let len = path.chars().count();
+    if len <= max_width {
+        return path.to_string();
+    }
+    let keep = max_width.saturating_sub(1);
+    let tail: String = path.chars().skip(len - keep).collect();
+    let tail = tail.find('/').map_or(tail.as_str(), |slash| &tail[slash..]);
+    format!("…{tail}")
+}
+
+/// Compact age such as `5m`, `3h`, `2d`, `4mo`
+fn compact_age(duration: chrono::Duration) -> String {
+    const UNITS: [(i64, &str); 6] = [
+        (365 * 86_400, "y"),
+        (30 * 86_400, "mo"),
+        (7 * 86_400, "w"),
+        (86_400, "d"),
+        (3_600, "h"),
+        (60, "m"),
+    ];
+    let secs = duration.num_seconds().max(0);
+    UNITS.iter().find(|(unit, _)| secs >= *unit).map_or_else(
+        || "now".to_string(),
+        |(unit, suffix)| format!("{}{suffix}", secs / unit),
+    )
+}
+
+/// Human-readable byte count such as `512 B`, `1.4 GB` or `312 MB`
+/// (binary units, one decimal below 10)
+#[must_use]
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else if value < 10.0 {
+        format!("{value:.1} {}", UNITS[unit])
+    } else {
+        format!("{value:.0} {}", UNITS[unit])
+    }
+}
+
+/// Resolve the columns to render: the configured list (or the default layout),
+/// with `path` injected after the marker when `show_path` is set
+#[must_use]
+pub fn resolve_columns(show_path: bool, config: Option<&crate::config::Config>) -> Vec<ListColumn> {
+    let mut columns = config.map_or_else(
+        || DEFAULT_LIST_COLUMNS.to_vec(),
+        |cfg| cfg.worktree.list_columns.clone(),
+    );
+    if show_path && !columns.contains(&ListColumn::Path) {
+        let at = columns
+            .iter()
+            .position(|column| *column == ListColumn::Marker)
+            .map_or(0, |marker| marker + 1);
+        columns.insert(at, ListColumn::Path);
+    }
+    columns
+}
+
+/// Separator rendered after `column` (the marker hugs the next column)
+const fn separator_after(column: ListColumn) -> &'static str {
+    match column {
+        ListColumn::Marker => " ",
+        _ => "  ",
+    }
+}
+
+/// Plain-text cell content for one row
 struct WorktreeDisplay {
-    path: Option<String>,
-    hash: String,
-    rel_path: Option<String>,
-    branch: String,
-    timestamp: String,
-    is_active: bool,
+    cells: Vec<String>,
+    /// Compact alternative for the time cell
+    compact_timestamp: String,
+    locked: bool,
+}
+
+/// Color a branch cell (cyan by default), dimming the trailing `#123` of
+/// worktrees created from a PR or issue
+fn colorize_branch_cell(
+    text: &str,
+    github_number: Option<u32>,
+    color_mode: color::ColorMode,
+    branch_color: color::TermColor,
+) -> String {
+    github_number.map_or_else(
+        || color_mode.paint(branch_color, text),
+        |number| {
+            let suffix = format!(" #{number}");
+            format!(
+                "{} {}",
+                color_mode.paint(branch_color, text.strip_suffix(&suffix).unwrap_or(text)),
+                color::dim(color_mode, format!("#{number}"))
+            )
+        },
+    )
 }
 
 /// Format worktree entries as a table with aligned columns
 ///
-/// Returns formatted lines ready for display
-/// If `show_path` is false and `config` is None: hash • branch • time
-/// If `show_path` is false and `config` is Some: hash • `rel_path` • branch • time
-/// If `show_path` is true: path • hash • `rel_path` • branch • time
+/// Returns formatted lines ready for display. Columns come from
+/// `worktree.list_columns` (default: marker • hash • `rel_path` • branch • time);
+/// `show_path` injects the `path` column after the marker when it is missing.
+/// The relative path column is only filled when `config` is Some, and columns
+/// that are empty for every row (`relpath`, `dirty`, `size`, `upstream`) are omitted.
+/// Entries with a `disk_size` or `upstream` state add a trailing `size` or
+/// `upstream` column when none is configured.
+/// Locked worktrees get a trailing [`LOCKED_INDICATOR`].
+///
+/// When `max_width` is given and the table is wider, it is degraded according to
+/// [`plan_table_layout`] so each row stays on one line.
 ///
 /// # Panics
 /// Panics if entries and `commit_times` have different lengths
 #[must_use]
-#[allow(clippy::too_many_lines)]
+#[allow(dead_code)] // Library API (see the crate docs); `ls` uses the layout variant
 pub fn format_worktree_table(
     entries: &[WorktreeEntry],
     commit_times: &[Option<DateTime<Utc>>],
     show_path: bool,
     color_mode: color::ColorMode,
     config: Option<&crate::config::Config>,
+    max_width: Option<usize>,
+) -> Vec<String> {
+    let layout = config.map(|cfg| WorktreeLayout::new(entries, Some(&cfg.worktree.dir)));
+    format_worktree_table_with_layout(
+        entries,
+        commit_times,
+        show_path,
+        color_mode,
+        config,
+        layout.as_ref(),
+        max_width,
+    )
+}
+
+/// [`format_worktree_table`] with relative paths taken from `layout`
+///
+/// `ls` builds the layout from the complete worktree list, so hiding rows
+/// (`--prunable`, `worktree.exclude`) never changes the names of the others.
+///
+/// # Panics
+/// Panics if entries and `commit_times` have different lengths
+#[must_use]
+#[allow(clippy::too_many_lines)]
+pub fn format_worktree_table_with_layout(
+    entries: &[WorktreeEntry],
+    commit_times: &[Option<DateTime<Utc>>],
+    show_path: bool,
+    color_mode: color::ColorMode,
+    config: Option<&crate::config::Config>,
+    layout: Option<&WorktreeLayout>,
+    max_width: Option<usize>,
 ) -> Vec<String> {
     assert_eq!(
         entries.len(),
@@ -47,137 +375,228 @@ pub fn format_worktree_table(
     );
 
     let now = Utc::now();
-    let mut displays: Vec<WorktreeDisplay> = Vec::new();
-
-    // Calculate worktree root if config is provided
-    // Collect all non-main worktree paths (skip index 0 which is main worktree)
-    let worktree_root = config.and_then(|_cfg| {
-        let non_main_paths: Vec<std::path::PathBuf> = entries
-            .iter()
-            .skip(1)
-            .map(|entry| std::path::PathBuf::from(&entry.path))
-            .collect();
-
-        calculate_worktree_root_from_paths(&non_main_paths)
-    });
+    let palette = config.map_or_else(color::Palette::default, |cfg| cfg.color.palette());
+    let mut columns = resolve_columns(show_path, config);
+    // `ls --du` sizes go in a trailing column unless one is configured
+    if entries.iter().any(|e| e.disk_size.is_some()) && !columns.contains(&ListColumn::Size) {
+        columns.push(ListColumn::Size);
+    }
+    // So does the state filled by `ls --fetch`
+    if entries.iter().any(|e| e.upstream.is_some()) && !columns.contains(&ListColumn::Upstream) {
+        columns.push(ListColumn::Upstream);
+    }
 
     // Build display data
-    for (index, (entry, commit_time)) in entries.iter().zip(commit_times.iter()).enumerate() {
-        let path = if show_path {
-            Some(display_path(&std::path::PathBuf::from(&entry.path)))
-        } else {
-            None
-        };
-        let hash = entry
-            .hash
-            .clone()
-            .unwrap_or_else(|| "(unknown)".to_string());
-
-        // Calculate relative path for non-main worktrees
-        let rel_path = if index != 0 {
-            worktree_root.as_ref().and_then(|root| {
-                calculate_relative_path(&std::path::PathBuf::from(&entry.path), root)
-            })
-        } else {
-            None
-        };
-
-        // Main worktree (index 0) is always displayed as "@"
-        let branch = if index == 0 {
-            "[@]".to_string()
-        } else {
-            entry
-                .branch
-                .as_ref()
-                .map_or_else(|| "[detached]".to_string(), |b| format!("[{b}]"))
-        };
-        let timestamp = commit_time.as_ref().map_or_else(
-            || "–".to_string(),
-            |dt| {
-                let duration = now.signed_duration_since(*dt);
-                HumanTime::from(duration).to_text_en(Accuracy::Rough, Tense::Past)
-            },
-        );
+    let displays: Vec<WorktreeDisplay> = entries
+        .iter()
+        .zip(commit_times.iter())
+        .enumerate()
+        .map(|(index, (entry, commit_time))| {
+            let (timestamp, compact_timestamp) = commit_time.as_ref().map_or_else(
+                || ("–".to_string(), "–".to_string()),
+                |dt| {
+                    let duration = now.signed_duration_since(*dt);
+                    (
+                        HumanTime::from(duration).to_text_en(Accuracy::Rough, Tense::Past),
+                        compact_age(duration),
+                    )
+                },
+            );
+            let cells = columns
+                .iter()
+                .map(|column| match column {
+                    ListColumn::Marker => if entry.is_active { "*" } else { " " }.to_string(),
+                    ListColumn::Hash => entry
+                        .hash
+                        .clone()
+                        .unwrap_or_else(|| "(unknown)".to_string()),
+                    // Relative path for non-main worktrees
+                    ListColumn::RelPath if index != 0 => layout
+                        .and_then(|layout| layout.rel_path(entry))
+                        .unwrap_or_default(),
+                    ListColumn::RelPath => String::new(),
+                    ListColumn::Path => display_path(&std::path::PathBuf::from(&entry.path)),
+                    // Main worktree (index 0) is always displayed as "@"
+                    ListColumn::Branch if index == 0 => "[@]".to_string(),
+                    ListColumn::Branch => {
+                        let branch = entry
+                            .branch
+                            .as_ref()
+                            .map_or_else(|| "[detached]".to_string(), |b| format!("[{b}]"));
+                        match entry.github_number {
+                            Some(number) => format!("{branch} #{number}"),
+                            None => branch,
+                        }
+                    }
+                    ListColumn::Time => timestamp.clone(),
+                    ListColumn::Dirty => entry
+                        .dirty
+                        .filter(|count| *count > 0)
+                        .map(|count| format!("!{count}"))
+                        .unwrap_or_default(),
+                    ListColumn::Size => entry.disk_size.map(format_size).unwrap_or_default(),
+                    ListColumn::Upstream => entry.upstream.clone().unwrap_or_default(),
+                })
+                .collect();
+            WorktreeDisplay {
+                cells,
+                compact_timestamp,
+                locked: entry.locked,
+            }
+        })
+        .collect();
 
-        displays.push(WorktreeDisplay {
-            path,
-            hash,
-            rel_path,
-            branch,
-            timestamp,
-            is_active: entry.is_active,
-        });
-    }
+    // Omit optional columns that are empty for every row
+    let visible: Vec<usize> = (0..columns.len())
+        .filter(|&i| {
+            !matches!(
+                columns[i],
+                ListColumn::RelPath | ListColumn::Dirty | ListColumn::Size | ListColumn::Upstream
+            ) || displays.iter().any(|d| !d.cells[i].is_empty())
+        })
+        .collect();
 
     // Calculate column widths
-    let max_path_width = if show_path {
-        displays
-            .iter()
-            .filter_map(|d| d.path.as_ref().map(std::string::String::len))
-            .max()
-            .unwrap_or(0)
+    let natural_widths: Vec<usize> = (0..columns.len())
+        .map(|i| {
+            displays
+                .iter()
+                .map(|d| d.cells[i].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let lock_width = if displays.iter().any(|d| d.locked) {
+        2 + LOCKED_INDICATOR_WIDTH
     } else {
         0
     };
-    let max_hash_width = displays.iter().map(|d| d.hash.len()).max().unwrap_or(0);
-    let max_rel_path_width = displays
-        .iter()
-        .filter_map(|d| d.rel_path.as_ref().map(std::string::String::len))
-        .max()
-        .unwrap_or(0);
-    let max_branch_width = displays.iter().map(|d| d.branch.len()).max().unwrap_or(0);
+
+    // Total row width of the visible columns, with `width_of` choosing each column's width
+    let layout_width = |indices: &[usize], width_of: &dyn Fn(usize) -> usize| -> usize {
+        indices
+            .iter()
+            .enumerate()
+            .map(|(pos, &i)| {
+                let separator = if pos + 1 < indices.len() {
+                    separator_after(columns[i]).len()
+                } else {
+                    0
+                };
+                width_of(i) + separator
+            })
+            .sum::<usize>()
+            + lock_width
+    };
+
+    // Decide how to degrade the table when it is wider than the terminal
+    let find = |wanted: ListColumn| visible.iter().copied().find(|&i| columns[i] == wanted);
+    let path_index = find(ListColumn::Path);
+    let rel_path_index = find(ListColumn::RelPath);
+    let time_index = find(ListColumn::Time);
+    let plan = max_width.map_or_else(RenderPlan::full, |term_width| {
+        let degradable = |i: usize| Some(i) == path_index || Some(i) == time_index;
+        let fixed_width = |i: usize| if degradable(i) { 0 } else { natural_widths[i] };
+        let without_rel: Vec<usize> = visible
+            .iter()
+            .copied()
+            .filter(|&i| Some(i) != rel_path_index)
+            .collect();
+        let fixed = layout_width(&without_rel, &fixed_width);
+        let widths = ColumnWidths {
+            fixed,
+            path: path_index.map(|i| natural_widths[i]),
+            rel_path: rel_path_index.map(|_| layout_width(&visible, &fixed_width) - fixed),
+            timestamp: time_index.map_or(0, |i| natural_widths[i]),
+            compact_timestamp: time_index.map_or(0, |_| {
+                displays
+                    .iter()
+                    .map(|d| d.compact_timestamp.chars().count())
+                    .max()
+                    .unwrap_or(0)
+            }),
+        };
+        plan_table_layout(&widths, term_width)
+    });
+
+    let rendered: Vec<usize> = visible
+        .into_iter()
+        .filter(|&i| plan.show_rel_path || Some(i) != rel_path_index)
+        .collect();
+    let column_width = |i: usize| -> usize {
+        if Some(i) == path_index {
+            plan.path_width
+                .map_or(natural_widths[i], |width| width.min(natural_widths[i]))
+        } else if Some(i) == time_index && plan.compact_time {
+            displays
+                .iter()
+                .map(|d| d.compact_timestamp.chars().count())
+                .max()
+                .unwrap_or(0)
+        } else {
+            natural_widths[i]
+        }
+    };
 
     // Format lines with padding and colors
     displays
         .iter()
         .enumerate()
         .map(|(index, d)| {
-            // Create active marker
-            let marker = if d.is_active {
-                color_mode.colorize_active_marker("*")
-            } else {
-                " ".to_string()
-            };
-
-            // Apply colors to each component
-            let colored_branch = if index == 0 {
-                // Main worktree [@] in green
-                color_mode.colorize_main_worktree(&d.branch)
-            } else if d.branch == "[detached]" {
-                // Detached HEAD in yellow
-                color_mode.colorize_detached(&d.branch)
-            } else {
-                // Regular branch in cyan
-                color_mode.colorize_branch(&d.branch)
-            };
-            let colored_timestamp = color_mode.colorize_secondary(&d.timestamp);
-
-            // Manual padding (format! doesn't work correctly with ANSI codes)
-            let hash_padding = " ".repeat(max_hash_width.saturating_sub(d.hash.len()));
-            let branch_padding = " ".repeat(max_branch_width.saturating_sub(d.branch.len()));
-
-            if show_path {
-                let colored_path = d.path.as_ref().unwrap();
-                let path_padding =
-                    " ".repeat(max_path_width.saturating_sub(colored_path.len()));
-
-                // Format relative path with padding
-                let rel_path_str = d.rel_path.as_deref().unwrap_or("");
-                let rel_path_padding =
-                    " ".repeat(max_rel_path_width.saturating_sub(rel_path_str.len()));
-
-                format!("{marker} {colored_path}{path_padding}  {}{hash_padding}  {rel_path_str}{rel_path_padding}  {colored_branch}{branch_padding}  {colored_timestamp}", d.hash)
-            } else if max_rel_path_width > 0 {
-                // Show relative path column when config is provided
-                let rel_path_str = d.rel_path.as_deref().unwrap_or("");
-                let rel_path_padding =
-                    " ".repeat(max_rel_path_width.saturating_sub(rel_path_str.len()));
-
-                format!("{marker} {}{hash_padding}  {rel_path_str}{rel_path_padding}  {colored_branch}{branch_padding}  {colored_timestamp}", d.hash)
-            } else {
-                // Original format without relative path
-                format!("{marker} {}{hash_padding}  {colored_branch}{branch_padding}  {colored_timestamp}", d.hash)
+            let mut line = String::new();
+            // Trailing empty cells are dropped so rows never end in padding
+            let cells = rendered
+                .iter()
+                .rposition(|&i| !d.cells[i].is_empty())
+                .map_or(&rendered[..0], |last| &rendered[..=last]);
+            for (pos, &i) in cells.iter().enumerate() {
+                let text = match columns[i] {
+                    ListColumn::Path => truncate_path_left(&d.cells[i], column_width(i)),
+                    ListColumn::Time if plan.compact_time => d.compact_timestamp.clone(),
+                    _ => d.cells[i].clone(),
+                };
+
+                // Apply colors to each component
+                let colored = match columns[i] {
+                    ListColumn::Marker if text == "*" => color_mode.colorize_active_marker(&text),
+                    // Main worktree [@] in green
+                    ListColumn::Branch if index == 0 => color_mode.paint(palette.main, &text),
+                    // Detached HEAD in yellow
+                    ListColumn::Branch if text == "[detached]" => {
+                        color_mode.colorize_detached(&text)
+                    }
+                    // Regular branch in cyan, followed by the dimmed `#123` it came from
+                    ListColumn::Branch => colorize_branch_cell(
+                        &text,
+                        entries[index].github_number,
+                        color_mode,
+                        palette.branch,
+                    ),
+                    ListColumn::RelPath => color_mode.paint(palette.relpath, &text),
+                    ListColumn::Upstream if text == "gone" => color_mode.colorize_detached(&text),
+                    ListColumn::Time | ListColumn::Upstream => {
+                        color_mode.paint(palette.timestamp, &text)
+                    }
+                    ListColumn::Dirty => color_mode.colorize_detached(&text),
+                    _ => text.clone(),
+                };
+                line.push_str(&colored);
+
+                // Manual padding (format! doesn't work correctly with ANSI codes);
+                // the last column is not padded to avoid trailing whitespace
+                if pos + 1 < cells.len() {
+                    let padding = column_width(i).saturating_sub(text.chars().count());
+                    line.push_str(&" ".repeat(padding));
+                    line.push_str(separator_after(columns[i]));
+                }
+            }
+
+            // Lock indicator trails the last column so it never shifts other columns
+            if d.locked {
+                line.push_str("  ");
+                line.push_str(&color_mode.colorize_locked(LOCKED_INDICATOR));
             }
+            line
         })
         .collect()
 }
@@ -193,6 +612,7 @@ mod tests {
             branch: Some("main".to_string()),
             hash: Some("a1b2c3d4".to_string()),
             is_active: false,
+            ..Default::default()
         }];
         let commit_times = vec![Some(
             DateTime::from_timestamp(Utc::now().timestamp() - 3600, 0).unwrap(),
@@ -204,6 +624,7 @@ mod tests {
             false,
             crate::color::ColorMode::Never,
             None,
+            None,
         );
         assert_eq!(result.len(), 1);
         // Line should contain hash, branch, and timestamp (no path)
@@ -220,6 +641,7 @@ mod tests {
             branch: Some("main".to_string()),
             hash: Some("a1b2c3d4".to_string()),
             is_active: false,
+            ..Default::default()
         }];
         let commit_times = vec![Some(
             DateTime::from_timestamp(Utc::now().timestamp() - 3600, 0).unwrap(),
@@ -231,6 +653,7 @@ mod tests {
             true,
             crate::color::ColorMode::Never,
             None,
+            None,
         );
         assert_eq!(result.len(), 1);
         // Line should contain path, hash, branch, and timestamp
@@ -248,12 +671,14 @@ mod tests {
                 branch: Some("main".to_string()),
                 hash: Some("a1b2c3d4".to_string()),
                 is_active: false,
+                ..Default::default()
             },
             WorktreeEntry {
                 path: "/path/to/feature-branch".to_string(),
                 branch: Some("feature".to_string()),
                 hash: Some("e5f6g7h8".to_string()),
                 is_active: false,
+                ..Default::default()
             },
         ];
         let commit_times = vec![
@@ -267,6 +692,7 @@ mod tests {
             false,
             crate::color::ColorMode::Never,
             None,
+            None,
         );
         assert_eq!(result.len(), 2);
         // Both lines should have same structure (no paths)
@@ -288,12 +714,14 @@ mod tests {
                 branch: Some("a".to_string()),
                 hash: Some("12345678".to_string()),
                 is_active: false,
+                ..Default::default()
             },
             WorktreeEntry {
                 path: "/very/long/path/to/worktree".to_string(),
                 branch: Some("feature-branch".to_string()),
                 hash: Some("abcdefgh".to_string()),
                 is_active: false,
+                ..Default::default()
             },
         ];
         let commit_times = vec![None, None];
@@ -304,6 +732,7 @@ mod tests {
             true,
             crate::color::ColorMode::Never,
             None,
+            None,
         );
         assert_eq!(result.len(), 2);
 
@@ -328,6 +757,7 @@ mod tests {
             branch: None,
             hash: Some("deadbeef".to_string()),
             is_active: false,
+            ..Default::default()
         }];
         let commit_times = vec![None];
 
@@ -337,6 +767,7 @@ mod tests {
             false,
             crate::color::ColorMode::Never,
             None,
+            None,
         );
         assert_eq!(result.len(), 1);
         // Main worktree (first entry) is always [@], even if detached
@@ -353,12 +784,14 @@ mod tests {
                 branch: Some("main".to_string()),
                 hash: Some("a1b2c3d4".to_string()),
                 is_active: false,
+                ..Default::default()
             },
             WorktreeEntry {
                 path: "/path/to/feature".to_string(),
                 branch: Some("feature".to_string()),
                 hash: Some("e5f6g7h8".to_string()),
                 is_active: true,
+                ..Default::default()
             },
         ];
         let commit_times = vec![None, None];
@@ -369,6 +802,7 @@ mod tests {
             false,
             crate::color::ColorMode::Never,
             None,
+            None,
         );
         assert_eq!(result.len(), 2);
         // First entry (inactive) should have space prefix
@@ -386,12 +820,14 @@ mod tests {
                 branch: Some("main".to_string()),
                 hash: Some("a1b2c3d4".to_string()),
                 is_active: false,
+                ..Default::default()
             },
             WorktreeEntry {
                 path: "/path/to/feature".to_string(),
                 branch: Some("feature".to_string()),
                 hash: Some("e5f6g7h8".to_string()),
                 is_active: true,
+                ..Default::default()
             },
         ];
         let commit_times = vec![None, None];
@@ -402,6 +838,7 @@ mod tests {
             true,
             crate::color::ColorMode::Never,
             None,
+            None,
         );
         assert_eq!(result.len(), 2);
         // Both entries should have marker prefix (space or *)
@@ -420,18 +857,21 @@ mod tests {
                 branch: Some("main".to_string()),
                 hash: Some("a1b2c3d4".to_string()),
                 is_active: false,
+                ..Default::default()
             },
             WorktreeEntry {
                 path: "/Users/test/repo-worktrees/feature".to_string(),
                 branch: Some("feature".to_string()),
                 hash: Some("e5f6g7h8".to_string()),
                 is_active: false,
+                ..Default::default()
             },
             WorktreeEntry {
                 path: "/Users/test/repo-worktrees/docs/tweak".to_string(),
                 branch: Some("docs/tweak".to_string()),
                 hash: Some("i9j0k1l2".to_string()),
                 is_active: true,
+                ..Default::default()
             },
         ];
         let commit_times = vec![None, None, None];
@@ -439,9 +879,11 @@ mod tests {
         let config = Config {
             worktree: WorktreeConfig {
                 dir: "../{repo}-worktrees/{branch}".to_string(),
+                ..Default::default()
             },
             hooks: Hooks::default(),
             integrations: IntegrationsConfig::default(),
+            ..Default::default()
         };
 
         let result = format_worktree_table(
@@ -450,6 +892,7 @@ mod tests {
             false,
             crate::color::ColorMode::Never,
             Some(&config),
+            None,
         );
 
         assert_eq!(result.len(), 3);
@@ -475,4 +918,560 @@ mod tests {
         assert!(nested_line.contains("[docs/tweak]"));
         assert!(nested_line.starts_with("* ")); // Active marker
     }
+
+    #[test]
+    fn test_format_worktree_table_locked_indicator() {
+        let entries = vec![
+            WorktreeEntry {
+                path: "/path/to/main".to_string(),
+                branch: Some("main".to_string()),
+                hash: Some("a1b2c3d4".to_string()),
+                ..Default::default()
+            },
+            WorktreeEntry {
+                path: "/path/to/feature".to_string(),
+                branch: Some("feature".to_string()),
+                hash: Some("e5f6g7h8".to_string()),
+                locked: true,
+                ..Default::default()
+            },
+        ];
+        let commit_times = vec![None, None];
+
+        let result = format_worktree_table(
+            &entries,
+            &commit_times,
+            false,
+            crate::color::ColorMode::Never,
+            None,
+            None,
+        );
+        assert!(!result[0].contains(LOCKED_INDICATOR));
+        assert!(result[1].ends_with("  🔒"));
+    }
+
+    #[test]
+    fn test_format_worktree_table_github_number() {
+        let entries = vec![
+            WorktreeEntry {
+                path: "/path/to/main".to_string(),
+                branch: Some("main".to_string()),
+                hash: Some("a1b2c3d4".to_string()),
+                ..Default::default()
+            },
+            WorktreeEntry {
+                path: "/path/to/feature".to_string(),
+                branch: Some("feature".to_string()),
+                hash: Some("e5f6g7h8".to_string()),
+                github_number: Some(456),
+                ..Default::default()
+            },
+        ];
+        let result = format_worktree_table(
+            &entries,
+            &[None, None],
+            false,
+            crate::color::ColorMode::Never,
+            None,
+            None,
+        );
+        assert!(result[1].contains("[feature] #456"), "{}", result[1]);
+
+        let colored = colorize_branch_cell(
+            "[feature] #456",
+            Some(456),
+            crate::color::ColorMode::Always,
+            crate::color::TermColor::Cyan,
+        );
+        assert!(
+            colored.starts_with("\x1b[36m[feature]\x1b[0m "),
+            "{colored:?}"
+        );
+        assert!(colored.contains("#456"));
+    }
+
+    fn row(
+        path: &str,
+        branch: Option<&str>,
+        secs: Option<i64>,
+    ) -> (WorktreeEntry, Option<DateTime<Utc>>) {
+        (
+            WorktreeEntry {
+                path: path.to_string(),
+                branch: branch.map(str::to_string),
+                ..Default::default()
+            },
+            secs.and_then(|s| DateTime::from_timestamp(s, 0)),
+        )
+    }
+
+    fn paths(rows: &[(WorktreeEntry, Option<DateTime<Utc>>)]) -> Vec<&str> {
+        rows.iter().map(|(entry, _)| entry.path.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sort_rows_time_newest_first_none_last() {
+        let mut rows = vec![
+            row("/main", Some("main"), Some(0)),
+            row("/old", Some("old"), Some(100)),
+            row("/unknown", Some("unknown"), None),
+            row("/new", Some("new"), Some(300)),
+        ];
+        sort_rows(&mut rows, SortKey::Time);
+        assert_eq!(paths(&rows), ["/main", "/new", "/old", "/unknown"]);
+    }
+
+    #[test]
+    fn test_sort_rows_branch_alphabetical_detached_last() {
+        let mut rows = vec![
+            row("/main", Some("main"), None),
+            row("/wt/zeta", Some("zeta"), None),
+            row("/wt/detached", None, None),
+            row("/wt/alpha", Some("alpha"), None),
+        ];
+        sort_rows(&mut rows, SortKey::Branch);
+        assert_eq!(
+            paths(&rows),
+            ["/main", "/wt/alpha", "/wt/zeta", "/wt/detached"]
+        );
+    }
+
+    #[test]
+    fn test_sort_rows_path_keeps_main_pinned() {
+        let mut rows = vec![
+            row("/z/main", Some("main"), None),
+            row("/b", Some("b"), None),
+            row("/a", Some("a"), None),
+        ];
+        sort_rows(&mut rows, SortKey::Path);
+        assert_eq!(paths(&rows), ["/z/main", "/a", "/b"]);
+    }
+
+    #[test]
+    fn test_compare_rows_time_ties_fall_back_to_path() {
+        let a = row("/a", Some("a"), None);
+        let b = row("/b", Some("b"), None);
+        assert_eq!(compare_rows(&a, &b, SortKey::Time), Ordering::Less);
+        assert_eq!(compare_rows(&b, &a, SortKey::Time), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_natural_cmp_numeric_runs() {
+        assert_eq!(natural_cmp("feature-2", "feature-10"), Ordering::Less);
+        assert_eq!(natural_cmp("feature-10", "feature-9"), Ordering::Greater);
+        assert_eq!(natural_cmp("v1.10.0", "v1.9.3"), Ordering::Greater);
+        assert_eq!(natural_cmp("issue-007", "issue-7"), Ordering::Less);
+        assert_eq!(natural_cmp("issue-7", "issue-007"), Ordering::Greater);
+        assert_eq!(
+            natural_cmp(
+                "build-99999999999999999999999",
+                "build-100000000000000000000000"
+            ),
+            Ordering::Less
+        );
+        assert_eq!(natural_cmp("2", "a"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_natural_cmp_folds_case_then_breaks_ties() {
+        assert_eq!(natural_cmp("Beta", "alpha"), Ordering::Greater);
+        assert_eq!(natural_cmp("alpha", "Beta"), Ordering::Less);
+        assert_eq!(natural_cmp("Alpha", "alpha"), Ordering::Less);
+        assert_eq!(natural_cmp("alpha", "alpha"), Ordering::Equal);
+        assert_eq!(natural_cmp("feat", "feature"), Ordering::Less);
+        assert_eq!(natural_cmp("", "a"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_natural_cmp_unicode() {
+        assert_eq!(natural_cmp("Émoji-🚀-2", "émoji-🚀-10"), Ordering::Less);
+        assert_eq!(natural_cmp("日本-3", "日本-12"), Ordering::Less);
+        // Non-ASCII digits are ordinary characters, not numbers
+        assert_eq!(natural_cmp("x-٣", "x-٣"), Ordering::Equal);
+        assert_eq!(natural_cmp("İ", "i"), natural_cmp("i", "İ").reverse());
+    }
+
+    #[test]
+    fn test_sort_rows_default_is_branch_natural_order() {
+        let mut rows = vec![
+            row("/main", Some("main"), Some(100)),
+            row("/wt/detached-b", None, Some(100)),
+            row("/wt/feature-10", Some("feature-10"), Some(100)),
+            row("/wt/detached-a", None, Some(100)),
+            row("/wt/Feature-9", Some("Feature-9"), Some(100)),
+            row("/wt/feature-2", Some("feature-2"), Some(100)),
+        ];
+        sort_rows(&mut rows, SortKey::default());
+        assert_eq!(
+            paths(&rows),
+            [
+                "/main",
+                "/wt/feature-2",
+                "/wt/Feature-9",
+                "/wt/feature-10",
+                "/wt/detached-a",
+                "/wt/detached-b"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sort_rows_empty() {
+        let mut rows: Vec<(WorktreeEntry, Option<DateTime<Utc>>)> = Vec::new();
+        sort_rows(&mut rows, SortKey::Time);
+        assert!(rows.is_empty());
+    }
+
+    fn wide_widths() -> ColumnWidths {
+        // marker(2) + path sep(2) + hash(8+2) + branch(15+2) = 31
+        ColumnWidths {
+            fixed: 31,
+            path: Some(60),
+            rel_path: Some(22),
+            timestamp: 14,
+            compact_timestamp: 3,
+        }
+    }
+
+    #[test]
+    fn test_plan_table_layout_fits_without_changes() {
+        // 31 + 60 + 22 + 14 = 127
+        assert_eq!(plan_table_layout(&wide_widths(), 127), RenderPlan::full());
+        assert_eq!(plan_table_layout(&wide_widths(), 200), RenderPlan::full());
+    }
+
+    #[test]
+    fn test_plan_table_layout_truncates_path_first() {
+        let plan = plan_table_layout(&wide_widths(), 100);
+        assert_eq!(
+            plan,
+            RenderPlan {
+                path_width: Some(33),
+                show_rel_path: true,
+                compact_time: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_plan_table_layout_drops_rel_path_second() {
+        // Path at minimum (16) + rel path needs 83 columns; without rel path 61
+        let plan = plan_table_layout(&wide_widths(), 70);
+        assert_eq!(
+            plan,
+            RenderPlan {
+                path_width: Some(25),
+                show_rel_path: false,
+                compact_time: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_plan_table_layout_compacts_time_last() {
+        let plan = plan_table_layout(&wide_widths(), 50);
+        assert_eq!(
+            plan,
+            RenderPlan {
+                path_width: Some(16),
+                show_rel_path: false,
+                compact_time: true,
+            }
+        );
+    }
+
+    #[test]
+    fn test_plan_table_layout_without_path_column() {
+        let widths = ColumnWidths {
+            fixed: 29,
+            path: None,
+            ..wide_widths()
+        };
+        // 29 + 22 + 14 = 65
+        assert_eq!(plan_table_layout(&widths, 65), RenderPlan::full());
+        let plan = plan_table_layout(&widths, 50);
+        assert!(!plan.show_rel_path);
+        assert!(!plan.compact_time);
+        assert_eq!(plan.path_width, None);
+        let plan = plan_table_layout(&widths, 10);
+        assert!(!plan.show_rel_path && plan.compact_time);
+    }
+
+    #[test]
+    fn test_truncate_path_left_keeps_last_components() {
+        assert_eq!(truncate_path_left("/short", 16), "/short");
+        assert_eq!(
+            truncate_path_left("/home/user/projects/repo-worktrees/feature", 20),
+            "…/feature"
+        );
+        assert_eq!(
+            truncate_path_left("~/repo-worktrees/feat/login", 18),
+            "…/feat/login"
+        );
+        // No slash in the kept tail: cut mid-component
+        assert_eq!(
+            truncate_path_left("/a/very-long-directory-name", 8),
+            "…ry-name"
+        );
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1024), "1.0 KB");
+        assert_eq!(format_size(15 * 1024 + 300), "15 KB");
+        assert_eq!(format_size(312 * 1024 * 1024), "312 MB");
+        assert_eq!(format_size(1_503_238_553), "1.4 GB");
+        assert_eq!(format_size(3 * 1024_u64.pow(4)), "3.0 TB");
+        assert_eq!(format_size(5000 * 1024_u64.pow(4)), "5000 TB");
+    }
+
+    #[test]
+    fn test_compact_age() {
+        assert_eq!(compact_age(chrono::Duration::seconds(30)), "now");
+        assert_eq!(compact_age(chrono::Duration::minutes(5)), "5m");
+        assert_eq!(compact_age(chrono::Duration::hours(3)), "3h");
+        assert_eq!(compact_age(chrono::Duration::days(2)), "2d");
+        assert_eq!(compact_age(chrono::Duration::days(14)), "2w");
+        assert_eq!(compact_age(chrono::Duration::days(70)), "2mo");
+        assert_eq!(compact_age(chrono::Duration::days(800)), "2y");
+    }
+
+    #[test]
+    fn test_format_worktree_table_fitted_rows_fit_width() {
+        let entries = vec![
+            WorktreeEntry {
+                path: "/home/user/projects/some-long-repository-name".to_string(),
+                branch: Some("main".to_string()),
+                hash: Some("a1b2c3d4".to_string()),
+                ..Default::default()
+            },
+            WorktreeEntry {
+                path: "/home/user/projects/some-long-repository-name-worktrees/feature/deep"
+                    .to_string(),
+                branch: Some("feature/deep".to_string()),
+                hash: Some("e5f6a7b8".to_string()),
+                ..Default::default()
+            },
+        ];
+        let commit_times = vec![None, None];
+        let lines = format_worktree_table(
+            &entries,
+            &commit_times,
+            true,
+            crate::color::ColorMode::Never,
+            None,
+            Some(60),
+        );
+        for line in &lines {
+            assert!(line.chars().count() <= 60, "too wide: {line:?}");
+        }
+        assert!(lines[1].contains('…') && lines[1].contains("/deep"));
+    }
+
+    fn config_with_columns(columns: &[ListColumn]) -> crate::config::Config {
+        let mut config = crate::config::Config::default();
+        config.worktree.list_columns = columns.to_vec();
+        config
+    }
+
+    fn two_entries() -> Vec<WorktreeEntry> {
+        vec![
+            WorktreeEntry {
+                path: "/repo".to_string(),
+                branch: Some("main".to_string()),
+                hash: Some("a1b2c3d4".to_string()),
+                ..Default::default()
+            },
+            WorktreeEntry {
+                path: "/repo-worktrees/feature".to_string(),
+                branch: Some("feature".to_string()),
+                hash: Some("e5f6a7b8".to_string()),
+                is_active: true,
+                dirty: Some(3),
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_format_worktree_table_custom_column_order() {
+        let config =
+            config_with_columns(&[ListColumn::Branch, ListColumn::Hash, ListColumn::Marker]);
+        let result = format_worktree_table(
+            &two_entries(),
+            &[None, None],
+            false,
+            crate::color::ColorMode::Never,
+            Some(&config),
+            None,
+        );
+        assert_eq!(result, ["[@]        a1b2c3d4   ", "[feature]  e5f6a7b8  *"]);
+    }
+
+    #[test]
+    fn test_format_worktree_table_dirty_and_relpath_columns() {
+        let config =
+            config_with_columns(&[ListColumn::RelPath, ListColumn::Dirty, ListColumn::Branch]);
+        let result = format_worktree_table(
+            &two_entries(),
+            &[None, None],
+            false,
+            crate::color::ColorMode::Never,
+            Some(&config),
+            None,
+        );
+        assert_eq!(result, ["             [@]", "feature  !3  [feature]"]);
+    }
+
+    #[test]
+    fn test_format_worktree_table_applies_configured_colors() {
+        let mut config =
+            config_with_columns(&[ListColumn::RelPath, ListColumn::Branch, ListColumn::Time]);
+        let render = |config: &crate::config::Config, mode| {
+            format_worktree_table(
+                &two_entries(),
+                &[None, None],
+                false,
+                mode,
+                Some(config),
+                None,
+            )
+        };
+
+        // Defaults: blue relative path, cyan branch, green main marker
+        let result = render(&config, crate::color::ColorMode::Always);
+        assert!(
+            result[1].starts_with("\x1b[34mfeature\x1b[0m"),
+            "{result:?}"
+        );
+        assert!(result[1].contains("\x1b[36m[feature]\x1b[0m"), "{result:?}");
+        assert!(result[0].contains("\x1b[32m[@]\x1b[0m"), "{result:?}");
+
+        config.color = crate::config::ColorConfig {
+            branch: Some(crate::color::TermColor::Green),
+            main: Some(crate::color::TermColor::Cyan),
+            relpath: Some(crate::color::TermColor::Magenta),
+            timestamp: Some(crate::color::TermColor::White),
+        };
+        let result = render(&config, crate::color::ColorMode::Always);
+        assert!(
+            result[1].starts_with("\x1b[35mfeature\x1b[0m"),
+            "{result:?}"
+        );
+        assert!(result[1].contains("\x1b[32m[feature]\x1b[0m"), "{result:?}");
+        assert!(result[1].contains("\x1b[37m–\x1b[0m"), "{result:?}");
+        assert!(result[0].contains("\x1b[36m[@]\x1b[0m"), "{result:?}");
+
+        // --color=never strips everything regardless of the palette
+        let result = render(&config, crate::color::ColorMode::Never);
+        assert!(
+            result.iter().all(|line| !line.contains('\x1b')),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn test_format_worktree_table_hides_all_clean_dirty_column() {
+        let mut entries = two_entries();
+        entries[1].dirty = Some(0);
+        let config =
+            config_with_columns(&[ListColumn::Hash, ListColumn::Dirty, ListColumn::Branch]);
+        let result = format_worktree_table(
+            &entries,
+            &[None, None],
+            false,
+            crate::color::ColorMode::Never,
+            Some(&config),
+            None,
+        );
+        assert_eq!(result, ["a1b2c3d4  [@]", "e5f6a7b8  [feature]"]);
+    }
+
+    #[test]
+    fn test_format_worktree_table_appends_size_column() {
+        let mut entries = two_entries();
+        entries[0].disk_size = Some(1_500_000_000);
+        entries[1].disk_size = Some(312 * 1024 * 1024);
+        let config = config_with_columns(&[ListColumn::Hash, ListColumn::Branch]);
+        let result = format_worktree_table(
+            &entries,
+            &[None, None],
+            false,
+            crate::color::ColorMode::Never,
+            Some(&config),
+            None,
+        );
+        assert_eq!(
+            result,
+            ["a1b2c3d4  [@]        1.4 GB", "e5f6a7b8  [feature]  312 MB"]
+        );
+
+        // Configured but not computed: the column is hidden
+        let config = config_with_columns(&[ListColumn::Hash, ListColumn::Size]);
+        let result = format_worktree_table(
+            &two_entries(),
+            &[None, None],
+            false,
+            crate::color::ColorMode::Never,
+            Some(&config),
+            None,
+        );
+        assert_eq!(result, ["a1b2c3d4", "e5f6a7b8"]);
+    }
+
+    #[test]
+    fn test_format_worktree_table_upstream_column() {
+        let mut entries = two_entries();
+        entries[1].upstream = Some("gone".to_string());
+        let config =
+            config_with_columns(&[ListColumn::Hash, ListColumn::Upstream, ListColumn::Branch]);
+        let result = format_worktree_table(
+            &entries,
+            &[None, None],
+            false,
+            crate::color::ColorMode::Never,
+            Some(&config),
+            None,
+        );
+        assert_eq!(result, ["a1b2c3d4        [@]", "e5f6a7b8  gone  [feature]"]);
+
+        // Filled without being configured: appended at the end
+        let config = config_with_columns(&[ListColumn::Hash]);
+        let result = format_worktree_table(
+            &entries,
+            &[None, None],
+            false,
+            crate::color::ColorMode::Never,
+            Some(&config),
+            None,
+        );
+        assert_eq!(result, ["a1b2c3d4", "e5f6a7b8  gone"]);
+    }
+
+    #[test]
+    fn test_resolve_columns_show_path_injects_after_marker() {
+        assert_eq!(
+            resolve_columns(true, None),
+            [
+                ListColumn::Marker,
+                ListColumn::Path,
+                ListColumn::Hash,
+                ListColumn::RelPath,
+                ListColumn::Branch,
+                ListColumn::Time,
+            ]
+        );
+        let config = config_with_columns(&[ListColumn::Branch, ListColumn::Path]);
+        assert_eq!(
+            resolve_columns(true, Some(&config)),
+            [ListColumn::Branch, ListColumn::Path]
+        );
+        let config = config_with_columns(&[ListColumn::Branch]);
+        assert_eq!(
+            resolve_columns(true, Some(&config)),
+            [ListColumn::Path, ListColumn::Branch]
+        );
+    }
 }
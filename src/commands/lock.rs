@@ -0,0 +1,105 @@
+//! Lock / unlock commands - Protect worktrees from `git worktree prune`
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+use crate::color;
+use crate::commands::common::{get_main_repo_root, resolve_worktree_or_main};
+use crate::domain::worktree::WorktreeList;
+use crate::integrations::git::{GitClient, RealGitClient};
+use crate::path_utils::{canonicalize_allow_missing, display_path};
+
+/// Resolve a lock/unlock target, refusing the main worktree
+///
+/// Returns: (`worktree_path`, `branch_name`)
+fn resolve_lock_target(
+    target: &str,
+    list_stdout: &str,
+    repo_root: &Path,
+    action: &str,
+) -> Result<(PathBuf, Option<String>)> {
+    let list = WorktreeList::parse(list_stdout, None);
+    let main = list
+        .main()
+        .context("git worktree list returned no entries")?;
+    let (worktree_path, branch_name) = resolve_worktree_or_main(target, list_stdout, repo_root)?;
+    if canonicalize_allow_missing(&worktree_path)
+        == canonicalize_allow_missing(Path::new(&main.path))
+    {
+        anyhow::bail!("Cannot {action} the main worktree");
+    }
+    Ok((worktree_path, branch_name))
+}
+
+/// Lock a worktree via `git worktree lock`
+///
+/// # Errors
+/// Returns an error if:
+/// - Not in a git repository
+/// - Target resolution fails or refers to the main worktree
+/// - Git worktree lock command fails (e.g. already locked)
+pub fn cmd_lock(target: &str, reason: Option<&str>, color_mode: color::ColorMode) -> Result<()> {
+    let repo_root = get_main_repo_root()?;
+    let git = RealGitClient;
+    let list_stdout = git.list_worktrees(Some(&repo_root))?;
+
+    let (worktree_path, branch_name) =
+        resolve_lock_target(target, &list_stdout, &repo_root, "lock")?;
+
+    git.lock_worktree(&worktree_path, reason, Some(&repo_root))?;
+
+    let label = branch_name.unwrap_or_else(|| display_path(&worktree_path));
+    color::eprint_status(color::success(color_mode, format!("Locked {label}")));
+    Ok(())
+}
+
+/// Unlock a worktree via `git worktree unlock`
+///
+/// # Errors
+/// Returns an error if:
+/// - Not in a git repository
+/// - Target resolution fails or refers to the main worktree
+/// - Git worktree unlock command fails (e.g. not locked)
+pub fn cmd_unlock(target: &str, color_mode: color::ColorMode) -> Result<()> {
+    let repo_root = get_main_repo_root()?;
+    let git = RealGitClient;
+    let list_stdout = git.list_worktrees(Some(&repo_root))?;
+
+    let (worktree_path, branch_name) =
+        resolve_lock_target(target, &list_stdout, &repo_root, "unlock")?;
+
+    git.unlock_worktree(&worktree_path, Some(&repo_root))?;
+
+    let label = branch_name.unwrap_or_else(|| display_path(&worktree_path));
+    color::eprint_status(color::success(color_mode, format!("Unlocked {label}")));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PORCELAIN: &str = "worktree /repo\nHEAD abc\nbranch refs/heads/main\n\n\
+                             worktree /repo-worktrees/feature-a\nHEAD def\nbranch refs/heads/feature-a\n\n";
+
+    #[test]
+    fn test_resolve_lock_target_branch() {
+        let (path, branch) =
+            resolve_lock_target("feature-a", PORCELAIN, Path::new("/repo"), "lock").unwrap();
+        assert_eq!(path, PathBuf::from("/repo-worktrees/feature-a"));
+        assert_eq!(branch.as_deref(), Some("feature-a"));
+    }
+
+    #[test]
+    fn test_resolve_lock_target_refuses_main() {
+        let err = resolve_lock_target("@", PORCELAIN, Path::new("/repo"), "lock")
+            .unwrap_err()
+            .to_string();
+        assert_eq!(err, "Cannot lock the main worktree");
+
+        let err = resolve_lock_target("@", PORCELAIN, Path::new("/repo"), "unlock")
+            .unwrap_err()
+            .to_string();
+        assert_eq!(err, "Cannot unlock the main worktree");
+    }
+}
@@ -0,0 +1,106 @@
+//! Man command - Render man pages from the CLI definition
+//!
+//! `ofsht.1` and one page per subcommand (`ofsht-add.1`, ...) come from the
+//! clap definitions, so they never drift from `--help`; `ofsht-config.5` is
+//! built from [`crate::config::reference`].
+
+use anyhow::{Context, Result};
+use clap::CommandFactory;
+use std::io::Write;
+use std::path::Path;
+
+use crate::config::reference::config_man_page;
+use crate::path_utils::display_path;
+
+/// File name and roff source of every man page
+///
+/// # Errors
+/// Returns an error if a page cannot be rendered
+pub fn man_pages() -> Result<Vec<(String, Vec<u8>)>> {
+    fn collect(cmd: clap::Command, pages: &mut Vec<(String, Vec<u8>)>) -> Result<()> {
+        let subcommands: Vec<_> = cmd
+            .get_subcommands()
+            .filter(|sub| !sub.is_hide_set())
+            .cloned()
+            .collect();
+        let man = clap_mangen::Man::new(cmd);
+        let mut page = Vec::new();
+        man.render(&mut page)
+            .with_context(|| format!("Failed to render {}", man.get_filename()))?;
+        pages.push((man.get_filename(), page));
+        for sub in subcommands {
+            collect(sub, pages)?;
+        }
+        Ok(())
+    }
+
+    let mut cmd = crate::cli::Cli::command().disable_help_subcommand(true);
+    cmd.build();
+    let mut pages = Vec::new();
+    collect(cmd, &mut pages)?;
+    pages.push((
+        "ofsht-config.5".to_string(),
+        config_man_page(env!("CARGO_PKG_VERSION")).into_bytes(),
+    ));
+    Ok(pages)
+}
+
+/// Print `ofsht.1` to stdout, or write every page into `out_dir`
+///
+/// # Errors
+/// Returns an error if a page cannot be rendered or written
+pub fn cmd_man(out_dir: Option<&Path>) -> Result<()> {
+    let pages = man_pages()?;
+    let Some(out_dir) = out_dir else {
+        let (_, page) = &pages[0];
+        std::io::stdout()
+            .write_all(page)
+            .context("Failed to write man page")?;
+        return Ok(());
+    };
+
+    std::fs::create_dir_all(out_dir)
+        .with_context(|| format!("Failed to create {}", display_path(out_dir)))?;
+    for (name, page) in &pages {
+        let path = out_dir.join(name);
+        std::fs::write(&path, page)
+            .with_context(|| format!("Failed to write {}", display_path(&path)))?;
+        println!("{}", display_path(&path));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_man_pages_cover_every_visible_subcommand() {
+        let pages = man_pages().unwrap();
+        let names: Vec<&str> = pages.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names[0], "ofsht.1");
+        assert_eq!(names.last(), Some(&"ofsht-config.5"));
+
+        for sub in crate::cli::Cli::command().get_subcommands() {
+            let name = format!("ofsht-{}.1", sub.get_name());
+            assert_eq!(
+                names.contains(&name.as_str()),
+                !sub.is_hide_set(),
+                "{name} in {names:?}"
+            );
+        }
+        assert!(pages.iter().all(|(_, page)| !page.is_empty()));
+    }
+
+    #[test]
+    fn test_subcommand_page_includes_examples() {
+        let pages = man_pages().unwrap();
+        let (_, add) = pages
+            .iter()
+            .find(|(name, _)| name == "ofsht-add.1")
+            .unwrap();
+        let add = String::from_utf8_lossy(add);
+        assert!(add.contains(".TH ofsht-add 1"), "{add}");
+        assert!(add.contains("Examples:"), "{add}");
+    }
+}
@@ -0,0 +1,164 @@
+//! `add --merge` - Create a worktree at a merge of several refs
+//!
+//! The new branch starts at the first ref; the remaining refs are merged into
+//! the worktree one by one with `git merge --no-edit`. The first conflicting
+//! merge stops the sequence and leaves the worktree in its conflicted state;
+//! the conflicted files are listed and ofsht exits with
+//! [`MERGE_CONFLICT_EXIT_CODE`] so wrappers can tell this apart from a failed
+//! `add`.
+
+use anyhow::Result;
+use std::fmt;
+use std::path::Path;
+
+use crate::color;
+use crate::commands::carry::report_conflicts;
+use crate::domain::status::unmerged_paths;
+use crate::integrations::git::GitClient;
+use crate::path_utils::display_path;
+
+/// Exit status of `ofsht add --merge` when a merge conflicts
+pub const MERGE_CONFLICT_EXIT_CODE: i32 = 4;
+
+/// A merge of `add --merge` that stopped with conflicts
+#[derive(Debug)]
+pub struct MergeConflict {
+    /// Ref whose merge conflicted
+    pub rev: String,
+    /// Paths left with conflict markers, relative to the worktree
+    pub files: Vec<String>,
+    /// Refs after `rev` that were not merged
+    pub pending: Vec<String>,
+}
+
+impl MergeConflict {
+    /// Print the conflicted files as a tree and how to continue
+    pub fn report(&self, worktree_path: &Path, color_mode: color::ColorMode) {
+        let hint = "Resolve the conflicts and commit, or run `git merge --abort`.";
+        let hint = if self.pending.is_empty() {
+            hint.to_string()
+        } else {
+            format!("{hint} Not merged yet: {}.", self.pending.join(", "))
+        };
+        report_conflicts(
+            &format!(
+                "Merging {} conflicts in {}:",
+                self.rev,
+                display_path(worktree_path)
+            ),
+            &self.files,
+            &hint,
+            color_mode,
+        );
+    }
+}
+
+impl fmt::Display for MergeConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "merging {} conflicts in {} file(s)",
+            self.rev,
+            self.files.len()
+        )
+    }
+}
+
+impl std::error::Error for MergeConflict {}
+
+/// Result of merging refs into a new worktree
+#[derive(Debug, Default)]
+pub struct MergeOutcome {
+    /// Refs merged cleanly, in order
+    pub merged: Vec<String>,
+    /// The merge that stopped the sequence, if any
+    pub conflict: Option<MergeConflict>,
+}
+
+/// Merge `refs` into the worktree at `worktree_path`, in order
+///
+/// Stops at the first conflicting merge.
+///
+/// # Errors
+/// Returns an error when a merge fails without leaving conflicts (e.g. an
+/// unrelated history or a missing identity)
+pub fn merge_refs(
+    git: &impl GitClient,
+    refs: &[String],
+    worktree_path: &Path,
+) -> Result<MergeOutcome> {
+    let mut outcome = MergeOutcome::default();
+    for (i, rev) in refs.iter().enumerate() {
+        let Err(e) = git.merge(rev, Some(worktree_path)) else {
+            outcome.merged.push(rev.clone());
+            continue;
+        };
+        let files = unmerged_paths(&git.status_porcelain(worktree_path).unwrap_or_default());
+        if files.is_empty() {
+            return Err(e.context(format!("Failed to merge {rev}")));
+        }
+        outcome.conflict = Some(MergeConflict {
+            rev: rev.clone(),
+            files,
+            pending: refs[i + 1..].to_vec(),
+        });
+        break;
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::git::tests::MockGitClient;
+
+    fn refs(names: &[&str]) -> Vec<String> {
+        names.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_merge_refs_merges_all_in_order() {
+        let outcome = merge_refs(
+            &MockGitClient::default(),
+            &refs(&["feature-x", "feature-y"]),
+            Path::new("/wt"),
+        )
+        .unwrap();
+        assert_eq!(outcome.merged, ["feature-x", "feature-y"]);
+        assert!(outcome.conflict.is_none());
+    }
+
+    #[test]
+    fn test_merge_refs_stops_at_first_conflict() {
+        let git = MockGitClient {
+            merge_fails_for: refs(&["feature-y"]),
+            status_output: "UU src/lib.rs\n M README.md\nAA new.txt\n".to_string(),
+            ..Default::default()
+        };
+        let outcome = merge_refs(
+            &git,
+            &refs(&["feature-x", "feature-y", "feature-z"]),
+            Path::new("/wt"),
+        )
+        .unwrap();
+        assert_eq!(outcome.merged, ["feature-x"]);
+        let conflict = outcome.conflict.unwrap();
+        assert_eq!(conflict.rev, "feature-y");
+        assert_eq!(conflict.files, ["src/lib.rs", "new.txt"]);
+        assert_eq!(conflict.pending, ["feature-z"]);
+        assert_eq!(
+            conflict.to_string(),
+            "merging feature-y conflicts in 2 file(s)"
+        );
+    }
+
+    #[test]
+    fn test_merge_refs_failure_without_conflicts_is_an_error() {
+        let git = MockGitClient {
+            merge_fails_for: refs(&["feature-x"]),
+            ..Default::default()
+        };
+        let err = merge_refs(&git, &refs(&["feature-x"]), Path::new("/wt")).unwrap_err();
+        assert!(format!("{err:#}").contains("Failed to merge feature-x"));
+    }
+}
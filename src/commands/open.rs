@@ -7,9 +7,7 @@ use std::process::Command;
 use crate::color;
 use crate::commands::common::get_main_repo_root;
 use crate::config;
-use crate::domain::worktree::{
-    calculate_relative_path, calculate_worktree_root_from_paths, WorktreeList,
-};
+use crate::domain::worktree::{WorktreeLayout, WorktreeList};
 use crate::integrations::git::{GitClient, RealGitClient};
 use crate::integrations::tmux::{sanitize_window_name, RealTmuxLauncher, TmuxLauncher};
 use crate::path_utils::canonicalize_allow_missing;
@@ -45,39 +43,22 @@ fn get_current_worktree_path() -> Result<PathBuf> {
 
 /// Build worktree list with names, skipping the current worktree
 fn build_worktree_list(
-    main_path: &str,
-    main_branch: Option<&str>,
-    worktrees: &[(String, Option<String>)],
+    list: &WorktreeList,
+    layout: &WorktreeLayout,
     current_path: &Path,
 ) -> (Vec<OpenWorktree>, Option<String>) {
-    // Build full list: main first, then non-main worktrees
-    let mut all_entries: Vec<(String, Option<String>)> = Vec::with_capacity(worktrees.len() + 1);
-    all_entries.push((main_path.to_string(), main_branch.map(String::from)));
-    all_entries.extend(worktrees.iter().cloned());
-
-    // Calculate worktree root from non-main paths for name calculation
-    let non_main_paths: Vec<PathBuf> = worktrees.iter().map(|(p, _)| PathBuf::from(p)).collect();
-    let worktree_root = calculate_worktree_root_from_paths(&non_main_paths);
-
     let canonical_current = canonicalize_allow_missing(current_path);
     let mut skipped_name: Option<String> = None;
     let mut result = Vec::new();
 
-    for (index, (path, _branch)) in all_entries.iter().enumerate() {
-        let canonical_path = canonicalize_allow_missing(&PathBuf::from(path));
+    for (index, entry) in list.entries().iter().enumerate() {
+        let canonical_path = canonicalize_allow_missing(&PathBuf::from(&entry.path));
 
         // Calculate name
         let name = if index == 0 {
             "@".to_string()
         } else {
-            worktree_root
-                .as_ref()
-                .and_then(|root| calculate_relative_path(&PathBuf::from(path), root))
-                .unwrap_or_else(|| {
-                    PathBuf::from(path)
-                        .file_name()
-                        .map_or_else(|| path.clone(), |n| n.to_string_lossy().to_string())
-                })
+            layout.display_name(entry)
         };
 
         // Skip current worktree
@@ -87,7 +68,7 @@ fn build_worktree_list(
         }
 
         result.push(OpenWorktree {
-            path: path.clone(),
+            path: entry.path.clone(),
             name,
         });
     }
@@ -104,6 +85,12 @@ pub fn cmd_open(pane: bool, window: bool, color_mode: color::ColorMode) -> Resul
     let repo_root = get_main_repo_root()?;
     let cfg = config::Config::load_from_repo_root(&repo_root)?;
 
+    if config::integration_disabled_by_env(config::NO_TMUX_ENV) {
+        anyhow::bail!(
+            "tmux integration is disabled by the environment (OFSHT_NO_TMUX / OFSHT_NO_INTEGRATIONS)"
+        );
+    }
+
     // Detect tmux — hard error if not available
     let launcher = RealTmuxLauncher;
     launcher.detect()?;
@@ -112,26 +99,21 @@ pub fn cmd_open(pane: bool, window: bool, color_mode: color::ColorMode) -> Resul
     let git = RealGitClient;
     let list_stdout = git.list_worktrees(Some(&repo_root))?;
     let list = WorktreeList::parse(&list_stdout, None);
-    let main_entry = list
-        .main()
-        .context("git worktree list returned no entries")?;
-    let main_path = main_entry.path.clone();
-    let main_branch = main_entry.branch.as_deref();
-    let worktrees: Vec<(String, Option<String>)> = list
-        .non_main()
-        .iter()
-        .map(|e| (e.path.clone(), e.branch.clone()))
-        .collect();
+    if list.main().is_none() {
+        anyhow::bail!("git worktree list returned no entries");
+    }
+    let layout = WorktreeLayout::for_list(&list, Some(&cfg.worktree.dir));
 
     // Detect current worktree
     let current_path = get_current_worktree_path()?;
 
     // Build list, skipping current worktree
-    let (open_list, skipped_name) =
-        build_worktree_list(&main_path, main_branch, &worktrees, &current_path);
+    let (open_list, skipped_name) = build_worktree_list(&list, &layout, &current_path);
 
     if open_list.is_empty() {
-        eprintln!("No worktrees to open (all worktrees are already in the current session).");
+        color::eprint_status(
+            "No worktrees to open (all worktrees are already in the current session).",
+        );
         return Ok(());
     }
 
@@ -146,17 +128,14 @@ pub fn cmd_open(pane: bool, window: bool, color_mode: color::ColorMode) -> Resul
         .as_ref()
         .map_or(String::new(), |name| format!(" (skipped current: {name})"));
 
-    eprintln!(
-        "{}",
-        color::info(
-            color_mode,
-            format!(
-                "Opened {} worktree(s) as {}s{skip_msg}",
-                open_list.len(),
-                mode
-            )
-        )
-    );
+    color::eprint_status(color::info(
+        color_mode,
+        format!(
+            "Opened {} worktree(s) as {}s{skip_msg}",
+            open_list.len(),
+            mode
+        ),
+    ));
 
     Ok(())
 }
@@ -171,10 +150,7 @@ fn open_as_windows(
         if let Err(e) = launcher.create_window(Path::new(&wt.path), &window_name) {
             anyhow::bail!("Failed to create window for {}: {e}", wt.name);
         }
-        eprintln!(
-            "{}",
-            color::info(color_mode, format!("  + window: {}", wt.name))
-        );
+        color::eprint_status(color::info(color_mode, format!("  + window: {}", wt.name)));
     }
     Ok(())
 }
@@ -194,10 +170,7 @@ fn open_as_panes(worktrees: &[OpenWorktree], color_mode: color::ColorMode) -> Re
 
         if output.status.success() {
             success_count += 1;
-            eprintln!(
-                "{}",
-                color::info(color_mode, format!("  + split: {}", wt.name))
-            );
+            color::eprint_status(color::info(color_mode, format!("  + split: {}", wt.name)));
         } else {
             let stderr = String::from_utf8_lossy(&output.stderr);
             errors.push(format!("{}: {}", wt.name, stderr.trim()));
@@ -297,19 +270,24 @@ mod tests {
         assert!(list.main().is_none());
     }
 
+    fn open_list(non_main: &[(&str, &str)]) -> WorktreeList {
+        let porcelain: String = std::iter::once(
+            "worktree /path/to/main\nHEAD abc123\nbranch refs/heads/main\n\n".to_string(),
+        )
+        .chain(non_main.iter().map(|(path, branch)| {
+            format!("worktree {path}\nHEAD def456\nbranch refs/heads/{branch}\n\n")
+        }))
+        .collect();
+        WorktreeList::parse(&porcelain, None)
+    }
+
     #[test]
     fn test_build_worktree_list_skips_current() {
-        let main_path = "/path/to/main";
-        let worktrees = vec![
-            (
-                "/worktrees/feature".to_string(),
-                Some("feature".to_string()),
-            ),
-            ("/worktrees/fix".to_string(), Some("fix".to_string())),
-        ];
+        let list = open_list(&[("/worktrees/feature", "feature"), ("/worktrees/fix", "fix")]);
+        let layout = WorktreeLayout::for_list(&list, None);
         let current = PathBuf::from("/path/to/main");
 
-        let (list, skipped) = build_worktree_list(main_path, Some("main"), &worktrees, &current);
+        let (list, skipped) = build_worktree_list(&list, &layout, &current);
 
         assert_eq!(list.len(), 2);
         assert_eq!(list[0].name, "feature");
@@ -319,17 +297,11 @@ mod tests {
 
     #[test]
     fn test_build_worktree_list_skips_non_main_current() {
-        let main_path = "/path/to/main";
-        let worktrees = vec![
-            (
-                "/worktrees/feature".to_string(),
-                Some("feature".to_string()),
-            ),
-            ("/worktrees/fix".to_string(), Some("fix".to_string())),
-        ];
+        let list = open_list(&[("/worktrees/feature", "feature"), ("/worktrees/fix", "fix")]);
+        let layout = WorktreeLayout::for_list(&list, None);
         let current = PathBuf::from("/worktrees/feature");
 
-        let (list, skipped) = build_worktree_list(main_path, Some("main"), &worktrees, &current);
+        let (list, skipped) = build_worktree_list(&list, &layout, &current);
 
         assert_eq!(list.len(), 2);
         assert_eq!(list[0].name, "@");
@@ -340,11 +312,11 @@ mod tests {
 
     #[test]
     fn test_build_worktree_list_all_empty_after_skip() {
-        let main_path = "/path/to/main";
-        let worktrees: Vec<(String, Option<String>)> = vec![];
+        let list = open_list(&[]);
+        let layout = WorktreeLayout::for_list(&list, None);
         let current = PathBuf::from("/path/to/main");
 
-        let (list, skipped) = build_worktree_list(main_path, Some("main"), &worktrees, &current);
+        let (list, skipped) = build_worktree_list(&list, &layout, &current);
 
         assert!(list.is_empty());
         assert_eq!(skipped, Some("@".to_string()));
@@ -352,20 +324,14 @@ mod tests {
 
     #[test]
     fn test_build_worktree_list_worktree_names_use_relative_paths() {
-        let main_path = "/path/to/main";
-        let worktrees = vec![
-            (
-                "/worktrees/feat/foo".to_string(),
-                Some("feat/foo".to_string()),
-            ),
-            (
-                "/worktrees/fix/bar".to_string(),
-                Some("fix/bar".to_string()),
-            ),
-        ];
+        let list = open_list(&[
+            ("/worktrees/feat/foo", "feat/foo"),
+            ("/worktrees/fix/bar", "fix/bar"),
+        ]);
+        let layout = WorktreeLayout::for_list(&list, None);
         let current = PathBuf::from("/path/to/main");
 
-        let (list, _) = build_worktree_list(main_path, Some("main"), &worktrees, &current);
+        let (list, _) = build_worktree_list(&list, &layout, &current);
 
         assert_eq!(list.len(), 2);
         assert_eq!(list[0].name, "feat/foo");
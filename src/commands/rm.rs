@@ -6,30 +6,316 @@ use std::collections::HashSet;
 use std::time::Duration;
 
 use crate::color;
-use crate::commands::common::{get_main_repo_root, resolve_worktree_target};
+use crate::commands::common::{
+    emit_hook_timing, get_main_repo_root, measure_directory_sizes, resolve_worktree_target,
+};
+use crate::commands::list_display::format_size;
 use crate::config;
-use crate::domain::worktree::WorktreeList;
-use crate::hooks;
+use crate::domain::exclude::WorktreeExclude;
+use crate::domain::worktree::{worktree_root_from_template, WorktreeList};
+use crate::hooks::{self, HookVars};
 use crate::integrations;
 use crate::integrations::fzf::FzfPicker;
 use crate::integrations::git::{GitClient, RealGitClient};
-use crate::path_utils::display_path;
+use crate::path_utils::{display_path, ensure_within_root, normalize_absolute_path};
+use crate::report;
+use crate::service::{RemoveOptions, RemoveOutcome, WorktreeManager};
+
+/// Behavior switches for `ofsht rm`
+#[derive(Debug, Clone, Copy, Default)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct RmOptions {
+    /// Keep the branch after removing its worktree (skip `git branch -D`)
+    pub keep_branch: bool,
+    /// Remove worktrees even when they have uncommitted changes or are locked
+    /// (`git worktree remove --force --force`)
+    pub force: bool,
+    /// Automation fast path: skip the dirty check and delete hooks, batch the
+    /// branch deletion and print a single summary line
+    pub assume_clean: bool,
+    /// Skip delete hooks
+    pub no_hooks: bool,
+    /// Report a failed removal and continue with the remaining targets
+    /// instead of stopping at the first failure
+    pub keep_going: bool,
+    /// Resolve the targets and report them without removing anything
+    pub dry_run: bool,
+    /// With `dry_run`, measure the worktrees and report the space to reclaim
+    pub stat: bool,
+}
+
+/// Result of a single worktree removal attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RemovalOutcome {
+    Removed,
+    /// Worktree had uncommitted changes and `--force` was not given
+    SkippedDirty,
+}
+
+/// Counts of the regular removal loop, summarized by `--keep-going`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct RemovalTally {
+    removed: usize,
+    skipped: usize,
+    failed: usize,
+}
+
+impl RemovalTally {
+    /// `Removed 3, failed 1` (skipped dirty worktrees are only mentioned when present)
+    fn summary(self) -> String {
+        if self.skipped > 0 {
+            format!(
+                "Removed {}, skipped {}, failed {}",
+                self.removed, self.skipped, self.failed
+            )
+        } else {
+            format!("Removed {}, failed {}", self.removed, self.failed)
+        }
+    }
+}
+
+/// One worktree of the `rm --dry-run --stat` report
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ReclaimCandidate {
+    label: String,
+    /// Bytes on disk (0 when the directory is missing)
+    size: u64,
+    /// The directory is already gone (prunable)
+    missing: bool,
+}
+
+/// `rm --dry-run --stat` report lines: candidates largest first, then the total
+fn reclaim_report(mut candidates: Vec<ReclaimCandidate>) -> Vec<String> {
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.size));
+    let sizes: Vec<String> = candidates.iter().map(|c| format_size(c.size)).collect();
+    let width = sizes.iter().map(String::len).max().unwrap_or(0);
+    let mut lines: Vec<String> = candidates
+        .iter()
+        .zip(&sizes)
+        .map(|(candidate, size)| {
+            let missing = if candidate.missing { " (missing)" } else { "" };
+            format!("{size:>width$}  {}{missing}", candidate.label)
+        })
+        .collect();
+    let total: u64 = candidates.iter().map(|c| c.size).sum();
+    let noun = if candidates.len() == 1 {
+        "worktree"
+    } else {
+        "worktrees"
+    };
+    lines.push(format!(
+        "Would reclaim ~{} across {} {noun}",
+        format_size(total),
+        candidates.len()
+    ));
+    lines
+}
+
+/// Report what `rm --dry-run` would remove (`--stat` adds disk usage)
+fn print_dry_run(
+    queue: &[(&std::path::Path, Option<&str>, bool)],
+    list: &WorktreeList,
+    stat: bool,
+    color_mode: color::ColorMode,
+) {
+    if !stat {
+        for &(path, branch, _) in queue {
+            eprintln!("Would remove {}", removal_label(path, branch));
+        }
+        return;
+    }
+    // Prunable worktrees have no directory left to measure
+    let missing: Vec<bool> = queue
+        .iter()
+        .map(|&(path, _, _)| list.find_by_path(path).is_some_and(|e| e.prunable) || !path.is_dir())
+        .collect();
+    let paths: Vec<std::path::PathBuf> = queue
+        .iter()
+        .zip(&missing)
+        .filter(|(_, &missing)| !missing)
+        .map(|(&(path, _, _), _)| path.to_path_buf())
+        .collect();
+    let mut sizes = measure_directory_sizes(&paths, color_mode).into_iter();
+    let candidates = queue
+        .iter()
+        .zip(missing)
+        .map(|(&(path, branch, _), missing)| ReclaimCandidate {
+            label: removal_label(path, branch),
+            size: if missing {
+                0
+            } else {
+                sizes.next().unwrap_or(0)
+            },
+            missing,
+        })
+        .collect();
+    for line in reclaim_report(candidates) {
+        eprintln!("{line}");
+    }
+}
+
+/// Branch of a worktree, or its path when detached
+fn removal_label(worktree_path: &std::path::Path, branch_name: Option<&str>) -> String {
+    branch_name.map_or_else(|| display_path(worktree_path), str::to_string)
+}
 
 /// Remove a worktree and optionally delete its branch
 /// This is a shared helper function used by both `cmd_rm_many` and `cmd_finish`
 fn remove_worktree_internal(
     worktree_path: &std::path::Path,
     branch_name: Option<&str>,
-    label: &str,
     config: &config::Config,
     repo_root: &std::path::Path,
+    options: RmOptions,
     color_mode: color::ColorMode,
     mp: &MultiProgress,
-) -> Result<()> {
+) -> Result<RemovalOutcome> {
     let is_tty = color_mode.should_colorize();
+    let label = &removal_label(worktree_path, branch_name);
+
+    let manager = WorktreeManager::new(RealGitClient, repo_root, config.worktree.clone());
+    let mut header_pb = None;
+    let result = manager.remove_path(
+        worktree_path,
+        branch_name,
+        RemoveOptions {
+            force: options.force,
+            keep_branch: options.keep_branch,
+        },
+        |worktree_path| {
+            header_pb = start_removal_header(label, color_mode, mp);
 
-    // Header spinner (TTY) or pre-printed header (non-TTY)
-    let header_pb = if is_tty {
+            // Execute delete hooks before removing the worktree (indent 4sp for nesting)
+            let delete_hooks = &config.hooks.delete;
+            let has_delete_hooks = !delete_hooks.run.is_empty()
+                || !delete_hooks.copy.is_empty()
+                || !delete_hooks.link.is_empty();
+            if has_delete_hooks && options.no_hooks {
+                hooks::emit_warning(
+                    mp,
+                    is_tty,
+                    format!(
+                        "  {}",
+                        color::warn(color_mode, "Skipped delete hooks (--no-hooks)")
+                    ),
+                );
+            } else if has_delete_hooks && worktree_path.exists() {
+                let summary = hooks::execute_hooks_lenient_with_mp(
+                    &hooks::with_placeholders(
+                        delete_hooks,
+                        &HookVars::new(branch_name, worktree_path, repo_root),
+                    ),
+                    worktree_path,
+                    repo_root,
+                    color_mode,
+                    config.hooks.raw_output,
+                    "  ",
+                    mp,
+                );
+                emit_hook_timing(&summary, color_mode, mp);
+            } else if has_delete_hooks {
+                run_delete_hooks_for_missing_worktree(
+                    worktree_path,
+                    branch_name,
+                    config,
+                    repo_root,
+                    color_mode,
+                    mp,
+                );
+            }
+        },
+    );
+
+    let (deleted_branch, branch_error) = match result {
+        Err(e) => {
+            // Clear header spinner on error
+            if let Some(pb) = header_pb {
+                pb.finish_and_clear();
+            }
+            return Err(e);
+        }
+        Ok(RemoveOutcome::SkippedDirty { changes, .. }) => {
+            let noun = if changes == 1 { "file" } else { "files" };
+            hooks::emit_warning(
+                mp,
+                is_tty,
+                format!(
+                    "{}",
+                    color::warn(
+                        color_mode,
+                        format!(
+                            "Skipped {label}: {changes} uncommitted {noun} (use --force to remove anyway)"
+                        )
+                    )
+                ),
+            );
+            return Ok(RemovalOutcome::SkippedDirty);
+        }
+        Ok(RemoveOutcome::Removed {
+            deleted_branch,
+            branch_error,
+            ..
+        }) => (deleted_branch, branch_error),
+    };
+
+    // Finish header: Removing → Removed
+    if let Some(pb) = header_pb {
+        pb.set_style(ProgressStyle::with_template("{msg}").unwrap());
+        pb.finish_with_message(format!(
+            "{}",
+            color::success(color_mode, report::removed_worktree(label))
+        ));
+    }
+
+    emit_branch_deletion(
+        branch_name,
+        deleted_branch.as_deref(),
+        branch_error.as_deref(),
+        color_mode,
+        mp,
+    );
+
+    Ok(RemovalOutcome::Removed)
+}
+
+/// Report the branch deleted with a worktree, or git's reason it was not
+fn emit_branch_deletion(
+    branch_name: Option<&str>,
+    deleted_branch: Option<&str>,
+    branch_error: Option<&str>,
+    color_mode: color::ColorMode,
+    mp: &MultiProgress,
+) {
+    let is_tty = color_mode.should_colorize();
+    if let Some(branch) = deleted_branch {
+        hooks::emit_line(
+            mp,
+            is_tty,
+            format!(
+                "  {}",
+                color::success(color_mode, report::deleted_branch(branch))
+            ),
+        );
+    }
+    if let (Some(branch), Some(reason)) = (branch_name, branch_error) {
+        hooks::emit_warning(
+            mp,
+            is_tty,
+            format!(
+                "  {}",
+                color::warn(color_mode, report::failed_to_delete_branch(branch, reason))
+            ),
+        );
+    }
+}
+
+/// Header spinner (TTY) or pre-printed header (non-TTY) for a removal
+fn start_removal_header(
+    label: &str,
+    color_mode: color::ColorMode,
+    mp: &MultiProgress,
+) -> Option<ProgressBar> {
+    if color_mode.should_colorize() {
         let pb = mp.add(ProgressBar::new_spinner());
         pb.set_style(
             ProgressStyle::default_spinner()
@@ -41,60 +327,353 @@ fn remove_worktree_internal(
         Some(pb)
     } else {
         // non-TTY: print header before hooks (sync pattern)
-        eprintln!("{}", color::success(color_mode, format!("Removed {label}")));
+        color::eprint_status(color::success(color_mode, report::removed_worktree(label)));
         None
-    };
+    }
+}
 
-    // Execute delete hooks before removing the worktree (indent 4sp for nesting)
-    if worktree_path.exists()
-        && (!config.hooks.delete.run.is_empty()
-            || !config.hooks.delete.copy.is_empty()
-            || !config.hooks.delete.link.is_empty())
-    {
-        hooks::execute_hooks_lenient_with_mp(
-            &config.hooks.delete,
-            worktree_path,
-            repo_root,
-            color_mode,
-            "  ",
+/// Delete hooks of a worktree whose directory is already gone (prunable)
+///
+/// Skipped with a warning unless `hooks.run_delete_hooks_in_repo_root_when_missing`
+/// is set, in which case only the `run` commands execute, from the repository
+/// root with `OFSHT_WORKTREE_PATH` naming the missing directory.
+fn run_delete_hooks_for_missing_worktree(
+    worktree_path: &std::path::Path,
+    branch_name: Option<&str>,
+    config: &config::Config,
+    repo_root: &std::path::Path,
+    color_mode: color::ColorMode,
+    mp: &MultiProgress,
+) {
+    let is_tty = color_mode.should_colorize();
+    let delete_hooks = &config.hooks.delete;
+    if !config.hooks.run_delete_hooks_in_repo_root_when_missing {
+        hooks::emit_warning(
             mp,
+            is_tty,
+            format!(
+                "  {}",
+                color::warn(
+                    color_mode,
+                    "Skipped delete hooks: worktree directory is missing \
+                     (set hooks.run_delete_hooks_in_repo_root_when_missing to run them from the repository root)"
+                )
+            ),
         );
+        return;
     }
+    if delete_hooks.run.is_empty() {
+        return;
+    }
+    if !delete_hooks.copy.is_empty() || !delete_hooks.link.is_empty() {
+        hooks::emit_warning(
+            mp,
+            is_tty,
+            format!(
+                "  {}",
+                color::warn(
+                    color_mode,
+                    "Skipped delete hook copy/link actions: worktree directory is missing"
+                )
+            ),
+        );
+    }
+    let summary = hooks::execute_run_hooks_lenient_with_mp(
+        &hooks::with_placeholders(
+            delete_hooks,
+            &HookVars::new(branch_name, worktree_path, repo_root),
+        ),
+        repo_root,
+        &[(
+            crate::commands::shell::WORKTREE_PATH_ENV,
+            normalize_absolute_path(worktree_path),
+        )],
+        color_mode,
+        config.hooks.raw_output,
+        "  ",
+        mp,
+    );
+    emit_hook_timing(&summary, color_mode, mp);
+}
 
-    // Remove worktree using git worktree remove
-    let git = RealGitClient;
-    if let Err(e) = git.remove_worktree(worktree_path, Some(repo_root)) {
-        // Clear header spinner on error
-        if let Some(pb) = header_pb {
-            pb.finish_and_clear();
+/// `--assume-clean` fast path: one `git worktree remove` per target, then a
+/// single `git branch -D` for every branch, with no dirty check and no hooks.
+///
+/// Git itself still refuses to remove a dirty worktree unless `--force` is given.
+fn remove_worktrees_assume_clean(
+    git: &impl GitClient,
+    removals: &[(std::path::PathBuf, std::path::PathBuf, Option<String>)],
+    config: &config::Config,
+    repo_root: &std::path::Path,
+    options: RmOptions,
+    color_mode: color::ColorMode,
+) -> Result<()> {
+    let root = if config.worktree.strict_paths {
+        Some(worktree_root_from_template(
+            &config.worktree.dir,
+            repo_root,
+        )?)
+    } else {
+        None
+    };
+
+    let mut removed = 0usize;
+    let mut failed = 0usize;
+    let mut branches = Vec::new();
+    for (_, worktree_path, branch_name) in removals {
+        let result = root
+            .as_deref()
+            .map_or(Ok(()), |root| ensure_within_root(worktree_path, root))
+            .and_then(|()| git.remove_worktree(worktree_path, options.force, Some(repo_root)));
+        match result {
+            Ok(()) => {
+                removed += 1;
+                if let Some(branch) = branch_name.as_deref().filter(|_| !options.keep_branch) {
+                    branches.push(branch);
+                }
+            }
+            Err(e) => {
+                failed += 1;
+                eprintln!(
+                    "{}",
+                    color::warn(
+                        color_mode,
+                        format!(
+                            "Failed to remove {}: {}",
+                            display_path(worktree_path),
+                            e.to_string().trim()
+                        )
+                    )
+                );
+            }
         }
-        return Err(e);
     }
 
-    // Finish header: Removing → Removed
-    if let Some(pb) = header_pb {
-        pb.set_style(ProgressStyle::with_template("{msg}").unwrap());
-        pb.finish_with_message(format!(
+    let branch_result = git.remove_branches(&branches, Some(repo_root))?;
+    for (branch, reason) in &branch_result.failed {
+        eprintln!(
             "{}",
-            color::success(color_mode, format!("Removed {label}"))
-        ));
+            color::warn(color_mode, report::failed_to_delete_branch(branch, reason))
+        );
     }
+    let deleted = branch_result.deleted.len();
+    let summary = if branch_result.failed.is_empty() {
+        format!("Removed {removed} worktree(s), deleted {deleted} branch(es)")
+    } else {
+        format!(
+            "Removed {removed} worktree(s), deleted {deleted} branch(es), {} branch(es) could not be deleted",
+            branch_result.failed.len()
+        )
+    };
+    color::eprint_status(color::success(color_mode, summary));
 
-    // Try to delete the branch (optional, may fail if branch doesn't exist)
-    if let Some(branch) = branch_name {
-        if git.remove_branch(branch, Some(repo_root)).unwrap_or(false) {
-            hooks::emit_line(
-                mp,
-                is_tty,
-                format!(
-                    "  {}",
-                    color::success(color_mode, format!("Deleted branch: {branch}"))
-                ),
+    if failed > 0 {
+        anyhow::bail!("{failed} worktree(s) could not be removed");
+    }
+    Ok(())
+}
+
+/// Upstream remotes of `branches` (unique, in first-seen order)
+fn upstream_remotes<'a>(
+    git: &impl GitClient,
+    branches: impl Iterator<Item = &'a str>,
+    repo_root: &std::path::Path,
+) -> Vec<String> {
+    let refs: Vec<String> = branches.map(|b| format!("refs/heads/{b}")).collect();
+    if refs.is_empty() {
+        return Vec::new();
+    }
+    let refs: Vec<&str> = refs.iter().map(String::as_str).collect();
+    let output = git
+        .for_each_ref(&refs, "%(upstream:remotename)", Some(repo_root))
+        .unwrap_or_default();
+    let mut remotes: Vec<String> = Vec::new();
+    for remote in output.lines().map(str::trim).filter(|r| !r.is_empty()) {
+        if !remotes.iter().any(|r| r == remote) {
+            remotes.push(remote.to_string());
+        }
+    }
+    remotes
+}
+
+/// What `rm.prune_remotes` cleans up after a batch, gathered before anything
+/// is removed (branches and PR metadata go away with the worktrees)
+struct PruneTargets {
+    /// Upstream remotes of the branches being removed
+    remotes: Vec<String>,
+    /// Worktrees of the batch created from a PR, with the PR number
+    pr_worktrees: Vec<(std::path::PathBuf, u32)>,
+}
+
+impl PruneTargets {
+    fn collect<'a>(
+        git: &impl GitClient,
+        removals: impl Iterator<Item = (&'a std::path::Path, Option<&'a str>)> + Clone,
+        repo_root: &std::path::Path,
+    ) -> Self {
+        let remotes = upstream_remotes(git, removals.clone().filter_map(|(_, b)| b), repo_root);
+        let pr_worktrees = removals
+            .filter_map(|(path, _)| {
+                integrations::gh::meta::load(path)
+                    .filter(|github_ref| {
+                        github_ref.kind == integrations::gh::meta::GithubRefKind::Pr
+                    })
+                    .map(|github_ref| (path.to_path_buf(), github_ref.number))
+            })
+            .collect();
+        Self {
+            remotes,
+            pr_worktrees,
+        }
+    }
+}
+
+/// `rm.prune_remotes`: prune stale remote-tracking branches and delete the
+/// temporary refs ofsht fetched the removed worktrees' fork PRs into
+///
+/// Runs once after the last removal of a batch. Refs of PR worktrees that were
+/// skipped or failed to be removed are kept. Failures only warn, since the
+/// worktrees themselves are already gone.
+fn prune_remote_state(
+    git: &impl GitClient,
+    targets: &PruneTargets,
+    repo_root: &std::path::Path,
+    color_mode: color::ColorMode,
+) {
+    for remote in &targets.remotes {
+        match git.prune_remote(remote, Some(repo_root)) {
+            Ok(()) => color::eprint_status(color::info(
+                color_mode,
+                format!("Pruned stale remote-tracking branches of {remote}"),
+            )),
+            Err(e) => eprintln!(
+                "{}",
+                color::warn(
+                    color_mode,
+                    format!("Failed to prune {remote}: {}", e.to_string().trim())
+                )
+            ),
+        }
+    }
+
+    // A removed worktree's directory is gone; skipped and failed ones remain
+    let removed_prs: Vec<u32> = targets
+        .pr_worktrees
+        .iter()
+        .filter(|(path, _)| !path.exists())
+        .map(|&(_, number)| number)
+        .collect();
+    if removed_prs.is_empty() {
+        return;
+    }
+
+    let output = match git.for_each_ref(
+        &[integrations::gh::PR_REF_PREFIX],
+        "%(refname)",
+        Some(repo_root),
+    ) {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!(
+                "{}",
+                color::warn(
+                    color_mode,
+                    format!("Failed to list PR refs: {}", e.to_string().trim())
+                )
             );
+            return;
+        }
+    };
+    let refs: Vec<&str> = integrations::gh::owned_pr_refs(&output)
+        .into_iter()
+        .filter(|refname| {
+            integrations::gh::parse_pr_ref(refname).is_some_and(|n| removed_prs.contains(&n))
+        })
+        .collect();
+    if refs.is_empty() {
+        return;
+    }
+    let prs: Vec<String> = refs
+        .iter()
+        .filter_map(|refname| integrations::gh::parse_pr_ref(refname))
+        .map(|number| format!("#{number}"))
+        .collect();
+    let detail = format!(" ({})", prs.join(", "));
+    match git.delete_refs(&refs, Some(repo_root)) {
+        Ok(()) => color::eprint_status(color::info(
+            color_mode,
+            format!("Deleted {} temporary PR ref(s){detail}", refs.len()),
+        )),
+        Err(e) => eprintln!(
+            "{}",
+            color::warn(
+                color_mode,
+                format!("Failed to delete PR refs: {}", e.to_string().trim())
+            )
+        ),
+    }
+}
+
+/// Pick removal targets with fzf, or with a numbered menu when fzf is disabled
+/// or not installed
+///
+/// Returns `Ok(None)` when the user cancels the selection.
+fn select_targets_interactively(
+    config: &config::Config,
+    list_stdout: &str,
+    color_mode: color::ColorMode,
+) -> Result<Option<Vec<String>>> {
+    // Build items for fzf (also used as menu labels)
+    let exclude = WorktreeExclude::new(&config.worktree.exclude)?;
+    let items = integrations::fzf::build_worktree_items(
+        list_stdout,
+        &exclude,
+        Some(&config.worktree.dir),
+        color_mode,
+    );
+    let fzf_enabled = config.integrations.fzf.enabled;
+
+    if !fzf_enabled || !integrations::fzf::is_fzf_available() {
+        // The main worktree can't be removed, so it isn't offered
+        let candidates = items.get(1..).unwrap_or_default();
+        let labels: Vec<String> = candidates.iter().map(|item| item.display.clone()).collect();
+        if !labels.is_empty() {
+            if let Some(selected) =
+                crate::ui::prompt_select("Select worktrees to remove", &labels, true)?
+            {
+                let values: Vec<String> = selected
+                    .into_iter()
+                    .map(|index| candidates[index].value.clone())
+                    .collect();
+                return Ok((!values.is_empty()).then_some(values));
+            }
+        }
+        if !fzf_enabled {
+            anyhow::bail!("Provide at least one target or enable fzf in config");
         }
+        anyhow::bail!("fzf is not installed. Install it or provide at least one target");
     }
 
-    Ok(())
+    if items.is_empty() {
+        anyhow::bail!("No worktrees found");
+    }
+
+    // Use fzf to select (multi-select enabled)
+    let picker = integrations::fzf::RealFzfPicker::new(config.integrations.fzf.options.clone());
+    let selected = picker.pick(&items, true)?;
+
+    if selected.is_empty() {
+        // User pressed Esc or no selection
+        return Ok(None);
+    }
+
+    // selected contains paths, convert them to branch names or paths as targets
+    Ok(Some(
+        selected
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect(),
+    ))
 }
 
 /// Remove one or multiple worktrees
@@ -104,9 +683,14 @@ fn remove_worktree_internal(
 /// - Not in a git repository
 /// - Git worktree list command fails
 /// - Target resolution fails
-/// - Worktree removal fails
+/// - Worktree removal fails (after the remaining targets with `keep_going`)
+/// - Any worktree was skipped because it has uncommitted changes (without `--force`)
 #[allow(clippy::too_many_lines)]
-pub fn cmd_rm_many(targets: &[String], color_mode: color::ColorMode) -> Result<()> {
+pub fn cmd_rm_many(
+    targets: &[String],
+    options: RmOptions,
+    color_mode: color::ColorMode,
+) -> Result<()> {
     // Get main repository root first to avoid issues when current directory is removed
     let repo_root = get_main_repo_root()?;
 
@@ -117,40 +701,16 @@ pub fn cmd_rm_many(targets: &[String], color_mode: color::ColorMode) -> Result<(
     let git = RealGitClient;
     let list_stdout = git.list_worktrees(Some(&repo_root))?;
 
-    // Resolve targets: CLI args > stdin (when piped) > fzf
+    // Resolve targets: CLI args > stdin (when piped) > fzf > numbered menu
     let targets: Vec<String> = if targets.is_empty() {
         let stdin_targets = crate::stdin::try_read_stdin_lines()?;
         if stdin_targets.is_empty() {
-            if !config.integrations.fzf.enabled {
-                anyhow::bail!("Provide at least one target or enable fzf in config");
-            }
-
-            if !integrations::fzf::is_fzf_available() {
-                anyhow::bail!("fzf is not installed. Install it or provide at least one target");
-            }
-
-            // Build items for fzf
-            let items = integrations::fzf::build_worktree_items(&list_stdout);
-
-            if items.is_empty() {
-                anyhow::bail!("No worktrees found");
-            }
-
-            // Use fzf to select (multi-select enabled)
-            let picker =
-                integrations::fzf::RealFzfPicker::new(config.integrations.fzf.options.clone());
-            let selected = picker.pick(&items, true)?;
-
-            if selected.is_empty() {
-                // User pressed Esc or no selection
+            let Some(selected) = select_targets_interactively(&config, &list_stdout, color_mode)?
+            else {
+                // User cancelled the selection
                 return Ok(());
-            }
-
-            // selected contains paths, convert them to branch names or paths as targets
+            };
             selected
-                .iter()
-                .map(std::string::ToString::to_string)
-                .collect()
         } else {
             stdin_targets
         }
@@ -158,7 +718,7 @@ pub fn cmd_rm_many(targets: &[String], color_mode: color::ColorMode) -> Result<(
         targets.to_vec()
     };
 
-    let mp = MultiProgress::new();
+    let mp = hooks::new_multi_progress();
 
     // First, resolve all targets to detect duplicates and validate them
     let mut non_current_removals = Vec::new();
@@ -166,9 +726,35 @@ pub fn cmd_rm_many(targets: &[String], color_mode: color::ColorMode) -> Result<(
         None;
     let mut seen_paths = HashSet::new();
 
+    let list = WorktreeList::parse(&list_stdout, None);
+
     for target in &targets {
         match resolve_worktree_target(target, &list_stdout, &repo_root) {
             Ok((canonical_path, worktree_path, branch_name, is_current)) => {
+                // Refuse locked worktrees up front instead of surfacing git's raw error
+                if let Some(entry) = list.find_by_path(&worktree_path).filter(|e| e.locked) {
+                    let reason = entry
+                        .lock_reason
+                        .as_deref()
+                        .map_or_else(String::new, |r| format!(" ({r})"));
+                    if !options.force {
+                        anyhow::bail!(
+                            "Worktree {} is locked{reason}\nRun `ofsht unlock {target}` first, or pass --force to remove it anyway",
+                            display_path(&worktree_path)
+                        );
+                    }
+                    eprintln!(
+                        "{}",
+                        color::warn(
+                            color_mode,
+                            format!(
+                                "Removing locked worktree {}{reason}",
+                                display_path(&worktree_path)
+                            )
+                        )
+                    );
+                }
+
                 // Special handling for current worktree (.)
                 if is_current {
                     // If we've already seen this path as a non-current target,
@@ -215,43 +801,206 @@ pub fn cmd_rm_many(targets: &[String], color_mode: color::ColorMode) -> Result<(
         }
     }
 
-    // Execute removals: non-current first, then current (if present)
-    for (_, worktree_path, branch_name) in &non_current_removals {
-        let path_label = display_path(worktree_path);
-        let label = branch_name.as_deref().unwrap_or(&path_label);
-        remove_worktree_internal(
-            worktree_path,
-            branch_name.as_deref(),
-            label,
+    // Removals run non-current first, then current (if present)
+    let queue: Vec<(&std::path::Path, Option<&str>, bool)> = non_current_removals
+        .iter()
+        .map(|(_, path, branch)| (path.as_path(), branch.as_deref(), false))
+        .chain(
+            current_removal
+                .iter()
+                .map(|(_, path, branch)| (path.as_path(), branch.as_deref(), true)),
+        )
+        .collect();
+
+    if options.dry_run {
+        print_dry_run(&queue, &list, options.stat, color_mode);
+        return Ok(());
+    }
+
+    let prune_remotes = config.rm.prune_remotes.then(|| {
+        let removals = queue.iter().map(|&(path, branch, _)| (path, branch));
+        PruneTargets::collect(&git, removals, &repo_root)
+    });
+
+    if options.assume_clean {
+        // Removing the worktree we are standing in needs the shell wrapper
+        // round-trip of the regular path; the fast path never does that.
+        let cwd = std::env::current_dir()
+            .ok()
+            .and_then(|dir| dir.canonicalize().ok());
+        let inside = cwd.as_deref().and_then(|cwd| {
+            non_current_removals
+                .iter()
+                .find(|(canonical, _, _)| cwd.starts_with(canonical))
+        });
+        if let Some((_, worktree_path, _)) = inside {
+            anyhow::bail!(
+                "--assume-clean cannot remove the current worktree ({})",
+                display_path(worktree_path)
+            );
+        }
+        if let Some((_, worktree_path, _)) = &current_removal {
+            anyhow::bail!(
+                "--assume-clean cannot remove the current worktree ({})",
+                display_path(worktree_path)
+            );
+        }
+        let result = remove_worktrees_assume_clean(
+            &git,
+            &non_current_removals,
             &config,
             &repo_root,
+            options,
             color_mode,
-            &mp,
-        )?;
+        );
+        if let Some(targets) = &prune_remotes {
+            prune_remote_state(&git, targets, &repo_root, color_mode);
+        }
+        return result;
     }
 
-    // Remove current worktree last (if requested)
-    if let Some((_, worktree_path, branch_name)) = &current_removal {
-        let path_label = display_path(worktree_path);
-        let label = branch_name.as_deref().unwrap_or(&path_label);
-        remove_worktree_internal(
+    let mut tally = RemovalTally::default();
+    for (i, &(worktree_path, branch_name, is_current)) in queue.iter().enumerate() {
+        match remove_worktree_internal(
             worktree_path,
-            branch_name.as_deref(),
-            label,
+            branch_name,
             &config,
             &repo_root,
+            options,
             color_mode,
             &mp,
-        )?;
+        ) {
+            Ok(RemovalOutcome::SkippedDirty) => tally.skipped += 1,
+            Ok(RemovalOutcome::Removed) => {
+                tally.removed += 1;
+                if is_current {
+                    // Print main worktree path for shell wrapper
+                    let main_path = list
+                        .main()
+                        .map(|m| m.path.as_str())
+                        .context("git worktree list returned no entries")?;
+                    println!("{main_path}");
+                }
+            }
+            Err(e) if options.keep_going => {
+                tally.failed += 1;
+                hooks::emit_warning(
+                    &mp,
+                    color_mode.should_colorize(),
+                    format!(
+                        "{}",
+                        color::error(
+                            color_mode,
+                            format!(
+                                "Failed to remove {}: {e:#}",
+                                removal_label(worktree_path, branch_name)
+                            )
+                        )
+                    ),
+                );
+            }
+            Err(e) => {
+                let unprocessed: Vec<String> = queue[i + 1..]
+                    .iter()
+                    .map(|&(path, branch, _)| removal_label(path, branch))
+                    .collect();
+                if !unprocessed.is_empty() {
+                    hooks::emit_warning(
+                        &mp,
+                        color_mode.should_colorize(),
+                        format!(
+                            "{}",
+                            color::warn(
+                                color_mode,
+                                format!(
+                                    "Not processed: {} (use --keep-going to continue past failures)",
+                                    unprocessed.join(", ")
+                                )
+                            )
+                        ),
+                    );
+                }
+                return Err(e);
+            }
+        }
+    }
+
+    if let Some(targets) = &prune_remotes {
+        prune_remote_state(&git, targets, &repo_root, color_mode);
+    }
 
-        // Print main worktree path for shell wrapper
-        let list = WorktreeList::parse(&list_stdout, None);
-        let main_path = list
-            .main()
-            .map(|m| m.path.as_str())
-            .context("git worktree list returned no entries")?;
-        println!("{main_path}");
+    if options.keep_going {
+        color::eprint_status(color::info(color_mode, tally.summary()));
+    }
+    if tally.failed > 0 {
+        anyhow::bail!("{} worktree(s) could not be removed", tally.failed);
+    }
+    if tally.skipped > 0 {
+        anyhow::bail!(
+            "{} worktree(s) with uncommitted changes were not removed (use --force to remove anyway)",
+            tally.skipped
+        );
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(label: &str, size: u64, missing: bool) -> ReclaimCandidate {
+        ReclaimCandidate {
+            label: label.to_string(),
+            size,
+            missing,
+        }
+    }
+
+    #[test]
+    fn test_reclaim_report_sorts_largest_first_with_total() {
+        let lines = reclaim_report(vec![
+            candidate("small", 512, false),
+            candidate("gone", 0, true),
+            candidate("big", 3 * 1024 * 1024 * 1024, false),
+            candidate("medium", 1536 * 1024, false),
+        ]);
+        assert_eq!(
+            lines,
+            [
+                "3.0 GB  big",
+                "1.5 MB  medium",
+                " 512 B  small",
+                "   0 B  gone (missing)",
+                "Would reclaim ~3.0 GB across 4 worktrees",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reclaim_report_single_and_empty() {
+        assert_eq!(
+            reclaim_report(vec![candidate("feature", 2048, false)]),
+            ["2.0 KB  feature", "Would reclaim ~2.0 KB across 1 worktree"]
+        );
+        assert_eq!(
+            reclaim_report(Vec::new()),
+            ["Would reclaim ~0 B across 0 worktrees"]
+        );
+    }
+
+    #[test]
+    fn test_removal_tally_summary() {
+        let tally = RemovalTally {
+            removed: 2,
+            skipped: 0,
+            failed: 1,
+        };
+        assert_eq!(tally.summary(), "Removed 2, failed 1");
+        let tally = RemovalTally {
+            skipped: 1,
+            ..tally
+        };
+        assert_eq!(tally.summary(), "Removed 2, skipped 1, failed 1");
+    }
+}
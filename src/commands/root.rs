@@ -0,0 +1,41 @@
+//! Root command - Print the main repository root or the worktrees root
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+use crate::commands::common::get_main_repo_root;
+use crate::config::Config;
+use crate::domain::worktree::{worktree_root_from_template, WorktreeLayout, WorktreeList};
+use crate::integrations::git::{GitClient, RealGitClient};
+use crate::path_utils::{canonicalize_allow_missing, normalize_absolute_path};
+
+/// Directory holding the worktrees: the root `ls` derives relative names from,
+/// or the location `worktree.dir` points at while no worktree exists yet
+fn worktrees_root(list: &WorktreeList, template: &str, repo_root: &Path) -> Result<PathBuf> {
+    if let Some(root) = WorktreeLayout::for_list(list, Some(template)).root() {
+        return Ok(root.to_path_buf());
+    }
+    Ok(canonicalize_allow_missing(&worktree_root_from_template(
+        template, repo_root,
+    )?))
+}
+
+/// Print the main repository root, or with `worktrees` the worktrees root
+///
+/// # Errors
+/// Returns an error if:
+/// - Not in a git repository
+/// - The configuration cannot be loaded or `worktree.dir` cannot be expanded
+pub fn cmd_root(worktrees: bool) -> Result<()> {
+    let repo_root = get_main_repo_root()?;
+    if !worktrees {
+        println!("{}", normalize_absolute_path(&repo_root));
+        return Ok(());
+    }
+
+    let config = Config::load_from_repo_root(&repo_root)?;
+    let list = WorktreeList::parse(&RealGitClient.list_worktrees(Some(&repo_root))?, None);
+    let root = worktrees_root(&list, &config.worktree.dir, &repo_root)?;
+    println!("{}", normalize_absolute_path(&root));
+    Ok(())
+}
@@ -0,0 +1,148 @@
+//! Shell command - Open an interactive shell inside a worktree
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+use crate::color;
+use crate::commands::common::{
+    get_main_repo_root, open_tmux, resolve_tmux, resolve_worktree_or_main,
+};
+use crate::config;
+use crate::integrations::git::{GitClient, RealGitClient};
+use crate::path_utils::{display_path, normalize_absolute_path};
+
+/// Environment variable carrying the worktree path into the spawned shell
+pub const WORKTREE_PATH_ENV: &str = "OFSHT_WORKTREE_PATH";
+/// Environment variable carrying the worktree branch into the spawned shell
+pub const BRANCH_ENV: &str = "OFSHT_BRANCH";
+
+/// Build the command that starts the shell
+///
+/// Priority: `--shell` > `shell.command` config > `$SHELL` > `sh`.
+/// Explicit commands run through `sh -c` so they may carry arguments.
+fn shell_command(override_cmd: Option<&str>, configured: Option<&str>) -> Command {
+    if let Some(cmd) = override_cmd.or(configured) {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(cmd);
+        return command;
+    }
+    let program = std::env::var("SHELL")
+        .ok()
+        .filter(|shell| !shell.is_empty())
+        .unwrap_or_else(|| "sh".to_string());
+    Command::new(program)
+}
+
+/// Open the worktree in a new tmux window, pane or session instead of a nested shell
+fn open_in_tmux(
+    path: &Path,
+    branch: Option<&str>,
+    config: &config::Config,
+    color_mode: color::ColorMode,
+) -> Result<()> {
+    let branch = branch.map_or_else(|| display_path(path), ToString::to_string);
+    let name = open_tmux(&config.integrations.tmux, path, &branch, None)?;
+    color::eprint_status(color::success(color_mode, format!("Opened {name} in tmux")));
+    Ok(())
+}
+
+/// Spawn an interactive shell in a worktree and wait for it to exit
+///
+/// Nothing is printed to stdout, so the shell wrapper never tries to `cd`.
+/// A non-zero exit of the shell becomes the exit code of ofsht.
+///
+/// # Errors
+/// Returns an error if:
+/// - Not in a git repository
+/// - Target resolution fails
+/// - The shell (or tmux) cannot be started
+pub fn cmd_shell(
+    target: &str,
+    shell: Option<&str>,
+    tmux: bool,
+    color_mode: color::ColorMode,
+) -> Result<()> {
+    let repo_root = get_main_repo_root()?;
+    let config = config::Config::load_from_repo_root(&repo_root)?;
+    let git = RealGitClient;
+    let list_stdout = git.list_worktrees(Some(&repo_root))?;
+    let (path, branch) = resolve_worktree_or_main(target, &list_stdout, &repo_root)?;
+
+    // `--tmux` and `integration.tmux.behavior` decide, unless disabled by the environment
+    if resolve_tmux(&config.integrations.tmux, tmux, false, color_mode)? {
+        return open_in_tmux(&path, branch.as_deref(), &config, color_mode);
+    }
+
+    let mut command = shell_command(shell, config.shell.command.as_deref());
+    command
+        .current_dir(&path)
+        .env(WORKTREE_PATH_ENV, normalize_absolute_path(&path));
+    if let Some(branch) = &branch {
+        command.env(BRANCH_ENV, branch);
+    } else {
+        command.env_remove(BRANCH_ENV);
+    }
+
+    color::eprint_status(color::info(
+        color_mode,
+        format!("Entering {} (exit to return)", display_path(&path)),
+    ));
+    let status = command
+        .status()
+        .with_context(|| format!("Failed to start shell in {}", path.display()))?;
+
+    match status.code() {
+        Some(0) => Ok(()),
+        Some(code) => std::process::exit(code),
+        None => anyhow::bail!("Shell terminated by a signal"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn program_and_args(command: &Command) -> (String, Vec<String>) {
+        (
+            command.get_program().to_string_lossy().into_owned(),
+            command
+                .get_args()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_shell_command_override_wins() {
+        let command = shell_command(Some("zsh -l"), Some("bash"));
+        assert_eq!(
+            program_and_args(&command),
+            (
+                "sh".to_string(),
+                vec!["-c".to_string(), "zsh -l".to_string()]
+            )
+        );
+    }
+
+    #[test]
+    fn test_shell_command_uses_config() {
+        let command = shell_command(None, Some("bash --norc"));
+        assert_eq!(
+            program_and_args(&command).1,
+            vec!["-c".to_string(), "bash --norc".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_shell_command_falls_back_to_shell_env() {
+        temp_env::with_var("SHELL", Some("/bin/fish"), || {
+            let command = shell_command(None, None);
+            assert_eq!(program_and_args(&command).0, "/bin/fish");
+        });
+        temp_env::with_var("SHELL", None::<&str>, || {
+            let command = shell_command(None, None);
+            assert_eq!(program_and_args(&command).0, "sh");
+        });
+    }
+}
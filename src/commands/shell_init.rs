@@ -2,18 +2,23 @@
 
 use anyhow::Result;
 
+use crate::commands::completion::CompletionShell;
+
 /// Generate shell integration script
 ///
 /// # Errors
-/// Returns an error if invalid shell is specified
-pub fn cmd_shell_init(shell: &str) -> Result<()> {
+/// Returns an error for shells without an integration script (PowerShell, elvish)
+pub fn cmd_shell_init(shell: CompletionShell) -> Result<()> {
     // Get shell integration script template
     let script = match shell {
-        "bash" => include_str!("../../templates/bash.sh"),
-        "zsh" => include_str!("../../templates/zsh.sh"),
-        "fish" => include_str!("../../templates/fish.fish"),
-        _ => {
-            anyhow::bail!("Invalid shell: {shell}. Supported shells: bash, zsh, fish");
+        CompletionShell::Bash => include_str!("../../templates/bash.sh"),
+        CompletionShell::Zsh => include_str!("../../templates/zsh.sh"),
+        CompletionShell::Fish => include_str!("../../templates/fish.fish"),
+        CompletionShell::Powershell | CompletionShell::Elvish => {
+            anyhow::bail!(
+                "Shell integration is not available for {}. Supported shells: bash, zsh, fish",
+                shell.name()
+            );
         }
     };
 
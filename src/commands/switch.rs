@@ -0,0 +1,49 @@
+//! Switch command - Go to a branch's worktree, adding it first when missing
+
+use anyhow::Result;
+
+use crate::color;
+use crate::commands::add::{cmd_new, AddOptions};
+use crate::commands::cd::cmd_goto;
+use crate::commands::common::get_main_repo_root;
+use crate::domain::worktree::WorktreeList;
+use crate::integrations::git::{GitClient, RealGitClient};
+
+/// Print the path of the worktree checked out on `branch`, creating it like
+/// `add` when no worktree has the branch (`git switch -c` semantics)
+///
+/// # Errors
+/// Returns an error if:
+/// - Not in a git repository
+/// - The branch name is invalid or the worktree cannot be created
+/// - The existing worktree's directory is missing
+pub fn cmd_switch(
+    branch: &str,
+    start_point: Option<&str>,
+    color_mode: color::ColorMode,
+) -> Result<()> {
+    let stdout = RealGitClient.list_worktrees(get_main_repo_root().ok().as_deref())?;
+    if WorktreeList::parse(&stdout, None)
+        .find_by_branch(branch)
+        .is_none()
+    {
+        return cmd_new(
+            Some(branch),
+            None,
+            start_point,
+            AddOptions::default(),
+            color_mode,
+        );
+    }
+
+    if let Some(start_point) = start_point {
+        eprintln!(
+            "{}",
+            color::warn(
+                color_mode,
+                format!("Ignoring start point '{start_point}': a worktree for '{branch}' already exists")
+            )
+        );
+    }
+    cmd_goto(Some(branch), false, color_mode)
+}
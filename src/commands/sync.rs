@@ -1,15 +1,15 @@
 //! Sync command - Re-apply hook file operations to existing worktrees
 
 use anyhow::Result;
-use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use indicatif::{ProgressBar, ProgressStyle};
 use std::path::Path;
 use std::time::Duration;
 
 use crate::color;
-use crate::commands::common::get_main_repo_root;
+use crate::commands::common::{emit_hook_timing, get_main_repo_root};
 use crate::config::{self, HookActions};
 use crate::domain::worktree::WorktreeList;
-use crate::hooks;
+use crate::hooks::{self, HookVars};
 use crate::integrations::git::{GitClient, RealGitClient};
 
 /// Sync hooks.create actions to all existing non-main worktrees
@@ -34,10 +34,13 @@ pub fn cmd_sync(run: bool, copy: bool, link: bool, color_mode: color::ColorMode)
         run: if do_run { create.run } else { vec![] },
         copy: if do_copy { create.copy } else { vec![] },
         link: if do_link { create.link } else { vec![] },
+        copy_on_conflict: create.copy_on_conflict,
+        copy_compare_content: create.copy_compare_content,
+        fail_on_error: create.fail_on_error,
     };
 
     if actions.run.is_empty() && actions.copy.is_empty() && actions.link.is_empty() {
-        eprintln!("No hook actions configured for hooks.create. Nothing to sync.");
+        color::eprint_status("No hook actions configured for hooks.create. Nothing to sync.");
         return Ok(());
     }
 
@@ -47,11 +50,11 @@ pub fn cmd_sync(run: bool, copy: bool, link: bool, color_mode: color::ColorMode)
     let worktrees = list.non_main();
 
     if worktrees.is_empty() {
-        eprintln!("No non-main worktrees found. Nothing to sync.");
+        color::eprint_status("No non-main worktrees found. Nothing to sync.");
         return Ok(());
     }
 
-    let mp = MultiProgress::new();
+    let mp = hooks::new_multi_progress();
     let is_tty = color_mode.should_colorize();
     let mut errors: Vec<String> = vec![];
 
@@ -71,7 +74,7 @@ pub fn cmd_sync(run: bool, copy: bool, link: bool, color_mode: color::ColorMode)
             pb.enable_steady_tick(Duration::from_millis(100));
             Some(pb)
         } else {
-            eprintln!("{}", color::success(color_mode, format!("Synced {label}")));
+            color::eprint_status(color::success(color_mode, format!("Synced {label}")));
             None
         };
 
@@ -99,10 +102,18 @@ pub fn cmd_sync(run: bool, copy: bool, link: bool, color_mode: color::ColorMode)
             continue;
         }
 
-        if let Err(e) =
-            hooks::execute_hooks_with_mp(&actions, worktree_path, &repo_root, color_mode, "  ", &mp)
-        {
-            errors.push(format!("{path}: {e}"));
+        let vars = HookVars::new(entry.branch.as_deref(), worktree_path, &repo_root);
+        match hooks::execute_hooks_with_mp(
+            &hooks::with_placeholders(&actions, &vars),
+            worktree_path,
+            &repo_root,
+            color_mode,
+            cfg.hooks.raw_output,
+            "  ",
+            &mp,
+        ) {
+            Ok(summary) => emit_hook_timing(&summary, color_mode, &mp),
+            Err(e) => errors.push(format!("{path}: {e}")),
         }
 
         // Finish header: Syncing → Synced
@@ -145,15 +156,19 @@ mod tests {
             run: if do_run { create.run.clone() } else { vec![] },
             copy: if do_copy { create.copy.clone() } else { vec![] },
             link: if do_link { create.link.clone() } else { vec![] },
+            copy_on_conflict: create.copy_on_conflict,
+            copy_compare_content: create.copy_compare_content,
+            fail_on_error: create.fail_on_error,
         }
     }
 
     #[test]
     fn test_no_flags_means_all_actions() {
         let create = HookActions {
-            run: vec!["echo run".to_string()],
+            run: vec!["echo run".into()],
             copy: vec!["file.txt".to_string()],
             link: vec![".env".to_string()],
+            ..Default::default()
         };
         let actions = build_actions(false, false, false, &create);
         assert_eq!(actions.run, create.run);
@@ -164,9 +179,10 @@ mod tests {
     #[test]
     fn test_link_only_flag() {
         let create = HookActions {
-            run: vec!["echo run".to_string()],
+            run: vec!["echo run".into()],
             copy: vec!["file.txt".to_string()],
             link: vec![".env".to_string()],
+            ..Default::default()
         };
         let actions = build_actions(false, false, true, &create);
         assert!(actions.run.is_empty());
@@ -177,9 +193,10 @@ mod tests {
     #[test]
     fn test_run_copy_flags() {
         let create = HookActions {
-            run: vec!["echo run".to_string()],
+            run: vec!["echo run".into()],
             copy: vec!["file.txt".to_string()],
             link: vec![".env".to_string()],
+            ..Default::default()
         };
         let actions = build_actions(true, true, false, &create);
         assert_eq!(actions.run, create.run);
@@ -190,9 +207,10 @@ mod tests {
     #[test]
     fn test_all_flags_same_as_no_flags() {
         let create = HookActions {
-            run: vec!["echo run".to_string()],
+            run: vec!["echo run".into()],
             copy: vec!["file.txt".to_string()],
             link: vec![".env".to_string()],
+            ..Default::default()
         };
         let all_flags = build_actions(true, true, true, &create);
         let no_flags = build_actions(false, false, false, &create);
@@ -213,9 +231,10 @@ mod tests {
     #[test]
     fn test_run_only_config_with_link_flag_yields_empty() {
         let create = HookActions {
-            run: vec!["echo run".to_string()],
+            run: vec!["echo run".into()],
             copy: vec![],
             link: vec![],
+            ..Default::default()
         };
         // --link flag but config has no link entries
         let actions = build_actions(false, false, true, &create);
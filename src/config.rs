@@ -3,6 +3,7 @@
 //! This module handles loading and managing ofsht configuration from TOML files.
 
 pub mod loader;
+pub mod reference;
 pub mod schema;
 pub mod template_generator;
 
@@ -10,8 +11,11 @@ pub mod template_generator;
 // Note: These are part of the public API and used in tests, even if not all are used in main.rs
 #[allow(unused_imports)]
 pub use schema::{
-    Config, FzfConfig, GhConfig, HookActions, Hooks, IntegrationsConfig, TmuxBehavior, TmuxConfig,
-    WorktreeConfig, ZoxideConfig,
+    integration_disabled_by_env, AutoFetch, ColorConfig, Config, CopyConflictPolicy,
+    CreateFailureAction, FzfConfig, GhConfig, HookActions, Hooks, IntegrationsConfig, ListColumn,
+    LsConfig, ResolverConfig, RmConfig, RunCommand, ShellConfig, TmuxBehavior, TmuxConfig,
+    WorktreeConfig, ZoxideConfig, DEFAULT_LIST_COLUMNS, NO_FZF_ENV, NO_GH_ENV, NO_INTEGRATIONS_ENV,
+    NO_TMUX_ENV, NO_ZOXIDE_ENV,
 };
 
 #[cfg(test)]
@@ -29,12 +33,113 @@ mod tests {
         assert_eq!(config.integrations.tmux.create, "window");
     }
 
+    #[test]
+    fn test_default_start_point_round_trip() {
+        let config: Config = toml::from_str(
+            r#"
+            [worktree]
+            default_start_point = "develop"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.worktree.default_start_point.as_deref(),
+            Some("develop")
+        );
+
+        let serialized = toml::to_string(&config).unwrap();
+        let reparsed: Config = toml::from_str(&serialized).unwrap();
+        assert_eq!(
+            reparsed.worktree.default_start_point.as_deref(),
+            Some("develop")
+        );
+
+        // Unset by default, and omitted when serialized
+        let config = Config::default();
+        assert!(config.worktree.default_start_point.is_none());
+        assert!(!toml::to_string(&config)
+            .unwrap()
+            .contains("default_start_point"));
+    }
+
     #[test]
     fn test_zoxide_config_default() {
         let config = ZoxideConfig::default();
         assert!(config.enabled);
     }
 
+    #[test]
+    fn test_run_entries_accept_strings_arrays_and_tables() {
+        let toml = r#"
+            [hooks.create]
+            run = [
+                "pnpm install",
+                ["cp", "-r", "my dir", "dest"],
+                { argv = ["pnpm", "install", "--frozen-lockfile"] },
+            ]
+        "#;
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(
+            config.hooks.create.run,
+            vec![
+                RunCommand::Shell("pnpm install".to_string()),
+                RunCommand::Argv(vec![
+                    "cp".to_string(),
+                    "-r".to_string(),
+                    "my dir".to_string(),
+                    "dest".to_string(),
+                ]),
+                RunCommand::Argv(vec![
+                    "pnpm".to_string(),
+                    "install".to_string(),
+                    "--frozen-lockfile".to_string(),
+                ]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_entries_reject_ambiguous_forms() {
+        let cases = [
+            ("run = [[]]", "argv must not be empty"),
+            ("run = [{ argv = [] }]", "argv must not be empty"),
+            ("run = [{ cmd = \"ls\" }]", "unknown key `cmd` in run entry"),
+            ("run = [{}]", "run entry table requires `argv`"),
+            (
+                "run = [1]",
+                "a command string, an array of strings, or a table",
+            ),
+            ("run = [[\"ls\", 1]]", "invalid type"),
+        ];
+        for (run, expected) in cases {
+            let toml = format!("[hooks.create]\n{run}\n");
+            let err = toml::from_str::<Config>(&toml).unwrap_err().to_string();
+            assert!(err.contains(expected), "{run}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_run_command_display_quotes_argv() {
+        assert_eq!(RunCommand::from("echo $HOME").to_string(), "echo $HOME");
+        let argv = RunCommand::Argv(vec![
+            "touch".to_string(),
+            "my file".to_string(),
+            "it's".to_string(),
+            String::new(),
+        ]);
+        assert_eq!(argv.to_string(), r"touch 'my file' 'it'\''s' ''");
+    }
+
+    #[test]
+    fn test_continue_on_error_is_an_alias_for_fail_on_error() {
+        let toml = r"
+            [hooks.create]
+            continue_on_error = true
+        ";
+        let config: Config = toml::from_str(toml).unwrap();
+        assert!(config.hooks.create.fail_on_error);
+    }
+
     #[test]
     fn test_zoxide_config_from_toml() {
         let toml = r"
@@ -93,6 +198,23 @@ mod tests {
         assert_eq!(config.integrations.tmux.create, "pane");
     }
 
+    #[test]
+    fn test_tmux_config_validate_create_modes() {
+        for mode in ["window", "pane", "session"] {
+            let config = TmuxConfig {
+                create: mode.to_string(),
+                ..Default::default()
+            };
+            assert!(config.validate().is_ok(), "{mode}");
+        }
+        let config = TmuxConfig {
+            create: "tab".to_string(),
+            ..Default::default()
+        };
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("'tab'") && err.contains("session"), "{err}");
+    }
+
     #[test]
     fn test_tmux_config_missing_defaults_to_window() {
         let toml = r#"
@@ -103,6 +225,22 @@ mod tests {
         assert_eq!(config.integrations.tmux.create, "window");
     }
 
+    #[test]
+    fn test_tmux_config_name_template() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.integrations.tmux.name, None);
+
+        let toml = r#"
+            [integration.tmux]
+            name = "{title_slug}"
+        "#;
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(
+            config.integrations.tmux.name.as_deref(),
+            Some("{title_slug}")
+        );
+    }
+
     #[test]
     fn test_tmux_config_behavior_auto() {
         let toml = r#"
@@ -179,6 +317,51 @@ mod tests {
         assert!(config.gh.enabled);
     }
 
+    #[test]
+    fn test_zoxide_score_from_toml() {
+        let toml = "
+            [integration.zoxide]
+            score = 10.0
+        ";
+        let config: Config = toml::from_str(toml).unwrap();
+        assert!(config.integrations.zoxide.enabled);
+        assert_eq!(config.integrations.zoxide.score, Some(10.0));
+
+        // Unset by default: zoxide's own increment is used
+        assert_eq!(IntegrationsConfig::default().zoxide.score, None);
+    }
+
+    #[test]
+    fn test_color_palette_from_toml() {
+        let toml = r#"
+            [color]
+            branch = "green"
+            relpath = "bright-blue"
+        "#;
+        let config: Config = toml::from_str(toml).unwrap();
+        let palette = config.color.palette();
+        assert_eq!(palette.branch, crate::color::TermColor::Green);
+        assert_eq!(palette.relpath, crate::color::TermColor::BrightBlue);
+        // Unset entries fall back to the defaults
+        let default = crate::color::Palette::default();
+        assert_eq!(palette.main, default.main);
+        assert_eq!(palette.timestamp, default.timestamp);
+        assert_eq!(ColorConfig::default().palette(), default);
+    }
+
+    #[test]
+    fn test_color_unknown_name_lists_allowed_values() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("config.toml");
+        std::fs::write(&path, "[color]\nmain = \"purple\"\n").unwrap();
+        let err = format!("{:#}", Config::from_file(&path).unwrap_err());
+        assert!(err.contains("purple"), "{err}");
+        assert!(
+            err.contains("bright-black") && err.contains("cyan"),
+            "{err}"
+        );
+    }
+
     #[test]
     fn test_integrations_config_from_toml() {
         let toml = r#"
@@ -205,6 +388,27 @@ mod tests {
         assert!(config.integrations.gh.enabled);
     }
 
+    #[test]
+    fn test_from_file_rejects_mid_path_branch_template() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".ofsht.toml");
+        std::fs::write(
+            &path,
+            "[worktree]\ndir = \"../{repo}-wt/{branch}/checkout\"\n",
+        )
+        .unwrap();
+
+        let err = format!("{:#}", Config::from_file(&path).unwrap_err());
+        assert!(
+            err.contains("Invalid config file"),
+            "unexpected error: {err}"
+        );
+        assert!(
+            err.contains("{branch} must be the last path component"),
+            "unexpected error: {err}"
+        );
+    }
+
     #[test]
     fn test_local_config_path() {
         let path = Config::local_config_path();
@@ -306,17 +510,27 @@ mod tests {
         let base = Config {
             worktree: WorktreeConfig {
                 dir: "/base/{branch}".to_string(),
+                ..Default::default()
             },
             hooks: Hooks::default(),
             integrations: IntegrationsConfig::default(),
+            shell: ShellConfig::default(),
+            rm: RmConfig::default(),
+            ls: LsConfig::default(),
+            color: ColorConfig::default(),
         };
 
         let override_config = Config {
             worktree: WorktreeConfig {
                 dir: "/override/{branch}".to_string(),
+                ..Default::default()
             },
             hooks: Hooks::default(),
             integrations: IntegrationsConfig::default(),
+            shell: ShellConfig::default(),
+            rm: RmConfig::default(),
+            ls: LsConfig::default(),
+            color: ColorConfig::default(),
         };
 
         let merged = base.merge(&override_config);
@@ -546,4 +760,142 @@ mod tests {
         let template = ctx.generate_global();
         assert!(template.contains("# Variables:"));
     }
+
+    const NO_ENV_VARS: [(&str, Option<&str>); 5] = [
+        (NO_TMUX_ENV, None),
+        (NO_ZOXIDE_ENV, None),
+        (NO_GH_ENV, None),
+        (NO_FZF_ENV, None),
+        (NO_INTEGRATIONS_ENV, None),
+    ];
+
+    fn always_tmux() -> IntegrationsConfig {
+        IntegrationsConfig {
+            tmux: TmuxConfig {
+                behavior: TmuxBehavior::Always,
+                ..TmuxConfig::default()
+            },
+            ..IntegrationsConfig::default()
+        }
+    }
+
+    /// Apply env overrides with only `name=value` set among the `OFSHT_NO_*` variables
+    fn overridden(name: &str, value: &str) -> IntegrationsConfig {
+        let mut vars = NO_ENV_VARS.to_vec();
+        vars.push((name, Some(value)));
+        temp_env::with_vars(vars, || {
+            let mut integrations = always_tmux();
+            integrations.apply_env_overrides();
+            integrations
+        })
+    }
+
+    #[test]
+    fn test_env_overrides_none_set() {
+        let integrations = temp_env::with_vars(NO_ENV_VARS, || {
+            let mut integrations = always_tmux();
+            integrations.apply_env_overrides();
+            integrations
+        });
+        assert_eq!(integrations.tmux.behavior, TmuxBehavior::Always);
+        assert!(integrations.zoxide.enabled);
+        assert!(integrations.gh.enabled);
+        assert!(integrations.fzf.enabled);
+    }
+
+    #[test]
+    fn test_env_override_no_tmux() {
+        let integrations = overridden(NO_TMUX_ENV, "1");
+        assert_eq!(integrations.tmux.behavior, TmuxBehavior::Never);
+        assert!(integrations.zoxide.enabled);
+    }
+
+    #[test]
+    fn test_env_override_no_zoxide() {
+        let integrations = overridden(NO_ZOXIDE_ENV, "1");
+        assert!(!integrations.zoxide.enabled);
+        assert!(integrations.fzf.enabled);
+    }
+
+    #[test]
+    fn test_env_override_no_gh() {
+        let integrations = overridden(NO_GH_ENV, "1");
+        assert!(!integrations.gh.enabled);
+        assert!(integrations.zoxide.enabled);
+    }
+
+    #[test]
+    fn test_env_override_no_fzf() {
+        let integrations = overridden(NO_FZF_ENV, "1");
+        assert!(!integrations.fzf.enabled);
+        assert!(integrations.gh.enabled);
+    }
+
+    #[test]
+    fn test_env_override_no_integrations_disables_all() {
+        let integrations = overridden(NO_INTEGRATIONS_ENV, "1");
+        assert_eq!(integrations.tmux.behavior, TmuxBehavior::Never);
+        assert!(!integrations.zoxide.enabled);
+        assert!(!integrations.gh.enabled);
+        assert!(!integrations.fzf.enabled);
+        assert!(!integrations.resolver.enabled);
+    }
+
+    #[test]
+    fn test_env_override_falsy_values_are_ignored() {
+        for value in ["", "0", "false", "FALSE"] {
+            let integrations = overridden(NO_ZOXIDE_ENV, value);
+            assert!(
+                integrations.zoxide.enabled,
+                "value {value:?} should not disable"
+            );
+        }
+    }
+
+    #[test]
+    fn test_integration_disabled_by_env() {
+        let mut vars = NO_ENV_VARS.to_vec();
+        vars.push((NO_INTEGRATIONS_ENV, Some("1")));
+        temp_env::with_vars(vars, || {
+            assert!(integration_disabled_by_env(NO_TMUX_ENV));
+            assert!(integration_disabled_by_env(NO_FZF_ENV));
+        });
+        temp_env::with_vars(NO_ENV_VARS, || {
+            assert!(!integration_disabled_by_env(NO_TMUX_ENV));
+        });
+    }
+
+    #[test]
+    fn test_list_columns_default() {
+        let config = Config::default();
+        assert_eq!(config.worktree.list_columns, DEFAULT_LIST_COLUMNS);
+    }
+
+    #[test]
+    fn test_list_columns_from_toml() {
+        let toml = r#"
+            [worktree]
+            list_columns = ["branch", "relpath", "dirty", "time"]
+        "#;
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(
+            config.worktree.list_columns,
+            [
+                ListColumn::Branch,
+                ListColumn::RelPath,
+                ListColumn::Dirty,
+                ListColumn::Time
+            ]
+        );
+    }
+
+    #[test]
+    fn test_list_columns_unknown_name_is_an_error() {
+        let toml = r#"
+            [worktree]
+            list_columns = ["branch", "colour"]
+        "#;
+        let err = toml::from_str::<Config>(toml).unwrap_err().to_string();
+        assert!(err.contains("colour"), "unexpected error: {err}");
+    }
 }
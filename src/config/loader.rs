@@ -4,6 +4,25 @@ use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
 
 use super::schema::{Config, IntegrationsConfig};
+use crate::color::{self, ColorMode};
+use crate::domain::worktree::validate_dir_template;
+use crate::path_utils::display_path;
+use crate::state::NoticeLatch;
+
+/// Config files behind a loaded [`Config`]
+///
+/// Settings come from a single file: a local `.ofsht.toml` replaces the global
+/// config entirely (apart from integration settings), nothing is merged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigSource {
+    /// File hooks and worktree settings were read from (`None` for the defaults)
+    pub path: Option<PathBuf>,
+    /// Global config that exists but was replaced by the local file
+    pub shadowed_global: Option<PathBuf>,
+}
+
+/// Latch for the "unreadable global config" warning
+static UNREADABLE_GLOBAL_NOTICE: NoticeLatch = NoticeLatch::new();
 
 impl Config {
     /// Load configuration from a TOML file
@@ -13,8 +32,20 @@ impl Config {
     pub fn from_file(path: &Path) -> Result<Self> {
         let content = std::fs::read_to_string(path)
             .with_context(|| format!("Failed to read config file: {}", path.display()))?;
-        let config: Self = toml::from_str(&content)
+        Self::parse_file_content(&content, path)
+    }
+
+    /// Parse and validate the contents of the config file at `path`
+    fn parse_file_content(content: &str, path: &Path) -> Result<Self> {
+        let config: Self = toml::from_str(content)
             .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+        validate_dir_template(&config.worktree.dir)
+            .with_context(|| format!("Invalid config file: {}", path.display()))?;
+        config
+            .integrations
+            .tmux
+            .validate()
+            .with_context(|| format!("Invalid config file: {}", path.display()))?;
         Ok(config)
     }
 
@@ -60,19 +91,59 @@ impl Config {
     /// Falls back to default if global config doesn't exist or can't be read
     fn load_integration_from_global() -> IntegrationsConfig {
         Self::global_config_path()
-            .and_then(|path| {
-                if path.exists() {
-                    Self::from_file(&path).ok()
-                } else {
-                    None
-                }
-            })
+            .and_then(|path| Self::read_global(&path).ok().flatten())
             .map(|config| config.integrations)
             .unwrap_or_default()
     }
 
+    /// Read the global config file, treating an unreadable one like a missing one
+    ///
+    /// A file that exists but cannot be read (e.g. a permission error under a
+    /// locked-down `HOME`) is skipped with a warning, printed once per process.
+    /// Parse and validation errors are still returned.
+    fn read_global(path: &Path) -> Result<Option<Self>> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Self::parse_file_content(&content, path).map(Some),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => {
+                if UNREADABLE_GLOBAL_NOTICE.first() {
+                    eprintln!(
+                        "{}",
+                        color::warn(
+                            ColorMode::resolve(None),
+                            format!("Ignoring global config {}: {e}", display_path(path))
+                        )
+                    );
+                }
+                Ok(None)
+            }
+        }
+    }
+
     /// Internal implementation for config loading
+    ///
+    /// Environment overrides (`OFSHT_NO_*`) are applied on top of whatever was loaded.
     fn load_impl(repo_root: Option<&Path>) -> Result<Self> {
+        Self::load_impl_with_source(repo_root).map(|(config, _)| config)
+    }
+
+    /// Like [`Config::load_from_repo_root`], also returning the file that hooks and
+    /// worktree settings were read from, and the global config it replaced
+    ///
+    /// # Errors
+    /// Returns an error if configuration files exist but cannot be read or parsed
+    pub fn load_from_repo_root_with_source(repo_root: &Path) -> Result<(Self, ConfigSource)> {
+        Self::load_impl_with_source(Some(repo_root))
+    }
+
+    fn load_impl_with_source(repo_root: Option<&Path>) -> Result<(Self, ConfigSource)> {
+        let (mut config, source) = Self::load_files(repo_root)?;
+        config.integrations.apply_env_overrides();
+        Ok((config, source))
+    }
+
+    /// Load config files without environment overrides
+    fn load_files(repo_root: Option<&Path>) -> Result<(Self, ConfigSource)> {
         // Try local config first
         let local_config = repo_root.map_or_else(Self::local_config_path, |root| {
             Self::local_config_path_from(root)
@@ -83,18 +154,26 @@ impl Config {
             // Integration configuration is only available in global config
             // Load integration settings from global config (or defaults if unavailable)
             config.integrations = Self::load_integration_from_global();
-            return Ok(config);
+            let source = ConfigSource {
+                path: Some(local_config),
+                shadowed_global: Self::global_config_path().filter(|path| path.exists()),
+            };
+            return Ok((config, source));
         }
 
         // Try global config
         if let Some(global_config) = Self::global_config_path() {
-            if global_config.exists() {
-                return Self::from_file(&global_config);
+            if let Some(config) = Self::read_global(&global_config)? {
+                let source = ConfigSource {
+                    path: Some(global_config),
+                    shadowed_global: None,
+                };
+                return Ok((config, source));
             }
         }
 
         // Return default config
-        Ok(Self::default())
+        Ok((Self::default(), ConfigSource::default()))
     }
 
     /// Get the local config path from a specific directory
@@ -132,6 +211,10 @@ impl Config {
             hooks: self.hooks.merge(&other.hooks),
             worktree: other.worktree.clone(),
             integrations: other.integrations.clone(),
+            shell: other.shell.clone(),
+            rm: other.rm.clone(),
+            ls: other.ls.clone(),
+            color: other.color,
         }
     }
 }
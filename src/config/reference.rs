@@ -0,0 +1,359 @@
+//! Reference documentation for every configuration key
+//!
+//! Rendered as the `ofsht-config(5)` man page by `ofsht man`. A unit test
+//! fails when a field of [`super::schema`] has no entry here, so document new
+//! keys in the same change that adds them.
+
+use std::fmt::Write;
+
+/// One documented configuration key
+#[derive(Debug, Clone, Copy)]
+pub struct ConfigKeyDoc {
+    /// Dotted key path; `hooks.<create|delete>` stands for both hook tables
+    pub key: &'static str,
+    /// Default value as written in TOML (empty when unset)
+    pub default: &'static str,
+    pub description: &'static str,
+}
+
+/// Every configuration key, grouped by table in file order
+pub const CONFIG_KEYS: &[ConfigKeyDoc] = &[
+    ConfigKeyDoc {
+        key: "worktree.dir",
+        default: "\"../{repo}-worktrees/{branch}\"",
+        description: "Where new worktrees go, relative to the main repository root. \
+            {repo} is the repository directory name and {branch} the branch name; \
+            a leading ~ and $VAR / ${VAR} are expanded.",
+    },
+    ConfigKeyDoc {
+        key: "worktree.strict_paths",
+        default: "false",
+        description: "Refuse to create or remove worktrees whose resolved path escapes \
+            the worktrees root derived from dir (symlinks are followed).",
+    },
+    ConfigKeyDoc {
+        key: "worktree.allow_inside_repo",
+        default: "false",
+        description: "Silence the warning printed when dir places worktrees inside the \
+            main repository.",
+    },
+    ConfigKeyDoc {
+        key: "worktree.default_start_point",
+        default: "",
+        description: "Start point for new branches when add or create get none. \
+            Existing branches are checked out as they are.",
+    },
+    ConfigKeyDoc {
+        key: "worktree.list_columns",
+        default: "[\"marker\", \"hash\", \"relpath\", \"branch\", \"time\"]",
+        description: "Columns of the ls table, in order. Available: marker, hash, \
+            relpath, path, branch, time, dirty, size (filled by ls --du), upstream \
+            (ahead/behind counts, or gone when the remote branch was deleted).",
+    },
+    ConfigKeyDoc {
+        key: "worktree.exclude",
+        default: "[]",
+        description: "Worktrees hidden from fzf and ls: branch names or relative paths, \
+            or glob: prefixed patterns. ls --all shows them.",
+    },
+    ConfigKeyDoc {
+        key: "hooks.on_create_failure",
+        default: "\"keep\"",
+        description: "What to do when a create hook fails: keep the worktree, remove it \
+            (with the branch it created), or prompt when interactive.",
+    },
+    ConfigKeyDoc {
+        key: "hooks.run_delete_hooks_in_repo_root_when_missing",
+        default: "false",
+        description: "When a worktree directory is already gone, run the delete hook's \
+            run commands from the repository root instead of skipping them.",
+    },
+    ConfigKeyDoc {
+        key: "hooks.raw_output",
+        default: "false",
+        description: "Show run output untouched instead of stripping ANSI escapes and \
+            collapsing progress redraws.",
+    },
+    ConfigKeyDoc {
+        key: "hooks.<create|delete>.run",
+        default: "[]",
+        description: "Commands run in the worktree, as shell strings (sh -c) or argv \
+            arrays. {branch}, {worktree}, {repo} and {repo_root} are substituted; \
+            {{ and }} give literal braces.",
+    },
+    ConfigKeyDoc {
+        key: "hooks.<create|delete>.copy",
+        default: "[]",
+        description: "Glob patterns of files copied from the main repository into the \
+            worktree.",
+    },
+    ConfigKeyDoc {
+        key: "hooks.<create|delete>.link",
+        default: "[]",
+        description: "Glob patterns of files symlinked from the main repository into the \
+            worktree at the same relative path.",
+    },
+    ConfigKeyDoc {
+        key: "hooks.<create|delete>.copy_on_conflict",
+        default: "\"overwrite\"",
+        description: "What copy does when the destination exists: skip, overwrite, or \
+            backup (rename it to <name>.bak, or <name>.bak.<N> when taken, first).",
+    },
+    ConfigKeyDoc {
+        key: "hooks.<create|delete>.copy_compare_content",
+        default: "false",
+        description: "Decide whether a copied file is up to date by its contents instead \
+            of size and modification time.",
+    },
+    ConfigKeyDoc {
+        key: "hooks.<create|delete>.fail_on_error",
+        default: "false",
+        description: "When a hook fails and the worktree is kept, still print its path \
+            but exit non-zero with a summary. Also accepted as continue_on_error.",
+    },
+    ConfigKeyDoc {
+        key: "shell.command",
+        default: "",
+        description: "Command started by ofsht shell (run through sh -c); $SHELL when \
+            unset.",
+    },
+    ConfigKeyDoc {
+        key: "rm.prune_remotes",
+        default: "false",
+        description: "After removing worktrees, prune the remotes their branches tracked \
+            and delete the refs/ofsht/pr/* refs ofsht fetched their fork PRs into.",
+    },
+    ConfigKeyDoc {
+        key: "ls.auto_fetch",
+        default: "\"never\"",
+        description: "never only fetches with ls --fetch; if-stale also fetches before \
+            showing the upstream column when FETCH_HEAD is missing or older than 15 minutes.",
+    },
+    ConfigKeyDoc {
+        key: "color.branch",
+        default: "\"cyan\"",
+        description: "Color of branch names in the ls table. Colors: black, red, green, \
+            yellow, blue, magenta, cyan, white and their bright- variants (e.g. bright-black).",
+    },
+    ConfigKeyDoc {
+        key: "color.main",
+        default: "\"green\"",
+        description: "Color of the main worktree's [@] in the ls table.",
+    },
+    ConfigKeyDoc {
+        key: "color.relpath",
+        default: "\"blue\"",
+        description: "Color of the relpath column.",
+    },
+    ConfigKeyDoc {
+        key: "color.timestamp",
+        default: "\"bright-black\"",
+        description: "Color of the time and upstream columns. --color=never disables \
+            every color regardless of these settings.",
+    },
+    ConfigKeyDoc {
+        key: "integration.zoxide.enabled",
+        default: "true",
+        description: "Register created worktrees and cd visits with zoxide.",
+    },
+    ConfigKeyDoc {
+        key: "integration.zoxide.score",
+        default: "",
+        description: "Initial score of new worktrees (zoxide add --score); zoxide's \
+            default increment when unset. cd visits always use the default.",
+    },
+    ConfigKeyDoc {
+        key: "integration.fzf.enabled",
+        default: "true",
+        description: "Pick worktrees with fzf when cd or rm get no name.",
+    },
+    ConfigKeyDoc {
+        key: "integration.fzf.options",
+        default: "[]",
+        description: "Extra command-line options passed to fzf.",
+    },
+    ConfigKeyDoc {
+        key: "integration.tmux.behavior",
+        default: "\"auto\"",
+        description: "auto uses tmux only with --tmux, always for every add and create \
+            (--no-tmux opts out), never disables it.",
+    },
+    ConfigKeyDoc {
+        key: "integration.tmux.create",
+        default: "\"window\"",
+        description: "What add and create open: window, pane, or session.",
+    },
+    ConfigKeyDoc {
+        key: "integration.tmux.open",
+        default: "\"window\"",
+        description: "Default mode of ofsht open: pane or window.",
+    },
+    ConfigKeyDoc {
+        key: "integration.tmux.name",
+        default: "",
+        description: "Window/session name template; variables {branch}, {title_slug} \
+            (GitHub title of #123 adds). The branch name when unset.",
+    },
+    ConfigKeyDoc {
+        key: "integration.gh.enabled",
+        default: "true",
+        description: "Resolve #123 and GitHub URLs through the gh CLI.",
+    },
+    ConfigKeyDoc {
+        key: "integration.gh.remote",
+        default: "",
+        description: "Remote to fetch pull requests from (default: the remote of the \
+            repository gh resolves, else origin).",
+    },
+    ConfigKeyDoc {
+        key: "integration.resolver.enabled",
+        default: "true",
+        description: "Turn ticket IDs into branch names (active only when pattern and \
+            command are set).",
+    },
+    ConfigKeyDoc {
+        key: "integration.resolver.pattern",
+        default: "\"\"",
+        description: "Regex matched against the whole branch argument, e.g. ^[A-Z]+-\\d+$.",
+    },
+    ConfigKeyDoc {
+        key: "integration.resolver.command",
+        default: "\"\"",
+        description: "Shell command printing ticket JSON; {id} is replaced with the input.",
+    },
+    ConfigKeyDoc {
+        key: "integration.resolver.title_field",
+        default: "\"title\"",
+        description: "JSON field holding the ticket title (dotted paths allowed).",
+    },
+    ConfigKeyDoc {
+        key: "integration.resolver.branch_template",
+        default: "\"{id_lower}-{title_slug}\"",
+        description: "Branch name template; variables {id}, {id_lower}, {title_slug}.",
+    },
+    ConfigKeyDoc {
+        key: "integration.resolver.timeout",
+        default: "10",
+        description: "Seconds to wait for command before using the literal input.",
+    },
+];
+
+/// Escape text for roff: backslashes and hyphens, and a leading `.` or `'`
+fn roff_escape(text: &str) -> String {
+    let escaped = text.replace('\\', "\\e").replace('-', "\\-");
+    if escaped.starts_with(['.', '\'']) {
+        format!("\\&{escaped}")
+    } else {
+        escaped
+    }
+}
+
+/// Table a key belongs to, e.g. `integration.tmux` for `integration.tmux.create`
+fn table_of(key: &str) -> &str {
+    key.rsplit_once('.').map_or("", |(table, _)| table)
+}
+
+/// Roff source of the `ofsht-config(5)` man page
+#[must_use]
+pub fn config_man_page(version: &str) -> String {
+    let mut page = format!(
+        ".TH OFSHT-CONFIG 5 \"\" \"ofsht {}\"\n\
+         .SH NAME\n\
+         ofsht\\-config \\- configuration files of ofsht\n\
+         .SH SYNOPSIS\n\
+         \\fI.ofsht.toml\\fR in the main repository root\n\
+         .br\n\
+         \\fI~/.config/ofsht/config.toml\\fR\n\
+         .SH DESCRIPTION\n\
+         Settings are TOML. The local file may set the [worktree], [hooks], [shell], \
+         [rm], [ls] and [color] tables; integration settings ([integration.*]) are only read from \
+         the global file. Keys missing from both files take the defaults below.\n\
+         .SH KEYS\n",
+        roff_escape(version)
+    );
+    let mut current_table = None;
+    for doc in CONFIG_KEYS {
+        let table = table_of(doc.key);
+        if current_table != Some(table) {
+            let _ = writeln!(page, ".SS [{}]", roff_escape(table));
+            current_table = Some(table);
+        }
+        let default = if doc.default.is_empty() {
+            String::new()
+        } else {
+            format!(" = {}", roff_escape(doc.default))
+        };
+        let _ = writeln!(
+            page,
+            ".TP\n\\fB{}\\fR{default}\n{}",
+            roff_escape(doc.key),
+            roff_escape(doc.description)
+        );
+    }
+    page.push_str(".SH SEE ALSO\n\\fBofsht\\fR(1), \\fBgit\\-worktree\\fR(1)\n");
+    page
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::schema::Config;
+
+    /// Dotted paths of every leaf key in a serialized config
+    fn leaf_keys(prefix: &str, value: &toml::Value, out: &mut Vec<String>) {
+        if let toml::Value::Table(table) = value {
+            for (key, value) in table {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                leaf_keys(&path, value, out);
+            }
+        } else {
+            out.push(prefix.to_string());
+        }
+    }
+
+    #[test]
+    fn test_every_config_key_is_documented() {
+        // Optional keys are only serialized when set
+        let mut config = Config::default();
+        config.worktree.default_start_point = Some("origin/main".to_string());
+        config.integrations.gh.remote = Some("upstream".to_string());
+        config.shell.command = Some("zsh".to_string());
+        config.integrations.zoxide.score = Some(10.0);
+        config.integrations.tmux.name = Some("{branch}".to_string());
+        config.color = crate::config::ColorConfig {
+            branch: Some(crate::color::TermColor::Cyan),
+            main: Some(crate::color::TermColor::Green),
+            relpath: Some(crate::color::TermColor::Blue),
+            timestamp: Some(crate::color::TermColor::BrightBlack),
+        };
+
+        let mut keys = Vec::new();
+        leaf_keys("", &toml::Value::try_from(&config).unwrap(), &mut keys);
+        assert!(keys.len() > 20, "unexpected key list: {keys:?}");
+
+        for key in keys {
+            let documented = key
+                .replacen("integrations.", "integration.", 1)
+                .replacen("hooks.create.", "hooks.<create|delete>.", 1)
+                .replacen("hooks.delete.", "hooks.<create|delete>.", 1);
+            assert!(
+                CONFIG_KEYS.iter().any(|doc| doc.key == documented),
+                "{documented} is missing from CONFIG_KEYS"
+            );
+        }
+    }
+
+    #[test]
+    fn test_config_man_page_lists_tables_and_keys() {
+        let page = config_man_page("1.2.3");
+        assert!(page.starts_with(".TH OFSHT-CONFIG 5"));
+        assert!(page.contains(".SS [integration.tmux]"));
+        assert!(page.contains("\\fBworktree.dir\\fR = \"../{repo}\\-worktrees/{branch}\""));
+        assert!(page.contains("^[A\\-Z]+\\-\\ed+$"));
+        assert_eq!(page.matches(".SS [worktree]").count(), 1);
+    }
+}
@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::color::{Palette, TermColor};
+
 /// Configuration for ofsht
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Config {
@@ -11,6 +13,80 @@ pub struct Config {
     pub worktree: WorktreeConfig,
     #[serde(default, alias = "integration")]
     pub integrations: IntegrationsConfig,
+    #[serde(default)]
+    pub shell: ShellConfig,
+    #[serde(default)]
+    pub rm: RmConfig,
+    #[serde(default)]
+    pub ls: LsConfig,
+    #[serde(default)]
+    pub color: ColorConfig,
+}
+
+/// Settings for `ofsht rm`
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RmConfig {
+    /// After removing worktrees, run `git remote prune` for the remotes their
+    /// branches tracked and delete the `refs/ofsht/pr/*` refs ofsht fetched their
+    /// fork PRs into
+    #[serde(default)]
+    pub prune_remotes: bool,
+}
+
+/// Settings for `ofsht ls`
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct LsConfig {
+    /// When the `upstream` column is shown, fetch first if the last fetch is old
+    #[serde(default)]
+    pub auto_fetch: AutoFetch,
+}
+
+/// When `ofsht ls` fetches before filling the `upstream` column on its own
+/// (`ls --fetch` always does)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum AutoFetch {
+    /// Only fetch with `ls --fetch` (default)
+    #[default]
+    Never,
+    /// Fetch when `FETCH_HEAD` is missing or older than a few minutes
+    IfStale,
+}
+
+/// Color overrides for the `ls` table (`--color=never` still disables all colors)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct ColorConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<TermColor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub main: Option<TermColor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relpath: Option<TermColor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<TermColor>,
+}
+
+impl ColorConfig {
+    /// The default palette with the configured colors applied
+    #[must_use]
+    pub fn palette(self) -> Palette {
+        let default = Palette::default();
+        Palette {
+            branch: self.branch.unwrap_or(default.branch),
+            main: self.main.unwrap_or(default.main),
+            relpath: self.relpath.unwrap_or(default.relpath),
+            timestamp: self.timestamp.unwrap_or(default.timestamp),
+        }
+    }
+}
+
+/// Settings for `ofsht shell`
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ShellConfig {
+    /// Command that starts the interactive shell (run through `sh -c`);
+    /// `$SHELL` is used when unset
+    #[serde(default)]
+    pub command: Option<String>,
 }
 
 /// Hook configuration
@@ -20,14 +96,38 @@ pub struct Hooks {
     pub create: HookActions,
     #[serde(default)]
     pub delete: HookActions,
+    /// What to do with a freshly added worktree when a create hook fails
+    #[serde(default)]
+    pub on_create_failure: CreateFailureAction,
+    /// When a worktree's directory is already gone, run the delete hook's `run`
+    /// commands from the repository root instead of skipping them
+    #[serde(default)]
+    pub run_delete_hooks_in_repo_root_when_missing: bool,
+    /// Re-emit `run` output untouched instead of stripping ANSI escapes and
+    /// collapsing `\r` progress redraws
+    #[serde(default)]
+    pub raw_output: bool,
+}
+
+/// Recovery behavior when create hooks fail
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CreateFailureAction {
+    /// Keep the worktree and report the failure as a warning (default)
+    #[default]
+    Keep,
+    /// Remove the new worktree (and the branch it created) and fail
+    Remove,
+    /// Ask on stderr when interactive; keep the worktree otherwise
+    Prompt,
 }
 
 /// Actions to perform in a hook
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct HookActions {
-    /// Commands to run
+    /// Commands to run (shell strings or argv arrays)
     #[serde(default)]
-    pub run: Vec<String>,
+    pub run: Vec<RunCommand>,
     /// Files to copy from source repository
     #[serde(default)]
     pub copy: Vec<String>,
@@ -35,23 +135,228 @@ pub struct HookActions {
     /// Patterns are expanded and linked to the same relative path in the worktree
     #[serde(default)]
     pub link: Vec<String>,
+    /// What `copy` does when the destination already exists
+    #[serde(default)]
+    pub copy_on_conflict: CopyConflictPolicy,
+    /// Decide whether a copied file is up to date by comparing contents
+    /// instead of size and modification time
+    #[serde(default)]
+    pub copy_compare_content: bool,
+    /// When a create hook fails and the worktree is kept, still print its path
+    /// but exit non-zero with a summary so scripts notice the partial failure
+    /// (by default hook failures are reported as warnings and the exit code is 0)
+    ///
+    /// Also accepted as `continue_on_error`: hooks always continue past a failure,
+    /// so that spelling turns on the same non-zero exit.
+    #[serde(default, alias = "continue_on_error")]
+    pub fail_on_error: bool,
+}
+
+/// A `run` hook entry
+///
+/// Accepted forms in TOML:
+/// - `"pnpm install"`: a shell command line, run through `sh -c`
+/// - `["pnpm", "install"]`: an argv array, executed directly without a shell
+/// - `{ argv = ["pnpm", "install"] }`: the same argv array as an inline table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunCommand {
+    /// Command line interpreted by `sh -c`
+    Shell(String),
+    /// Program and arguments executed without a shell (never empty)
+    Argv(Vec<String>),
+}
+
+impl From<&str> for RunCommand {
+    fn from(cmd: &str) -> Self {
+        Self::Shell(cmd.to_string())
+    }
+}
+
+impl From<String> for RunCommand {
+    fn from(cmd: String) -> Self {
+        Self::Shell(cmd)
+    }
+}
+
+impl std::fmt::Display for RunCommand {
+    /// Shell commands as written; argv entries joined by spaces, quoting
+    /// arguments that contain whitespace or quotes
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Shell(cmd) => f.write_str(cmd),
+            Self::Argv(argv) => {
+                let quoted: Vec<String> = argv
+                    .iter()
+                    .map(|arg| {
+                        if arg.is_empty()
+                            || arg.contains(|c: char| c.is_whitespace() || c == '\'' || c == '"')
+                        {
+                            format!("'{}'", arg.replace('\'', "'\\''"))
+                        } else {
+                            arg.clone()
+                        }
+                    })
+                    .collect();
+                f.write_str(&quoted.join(" "))
+            }
+        }
+    }
+}
+
+impl Serialize for RunCommand {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Shell(cmd) => serializer.serialize_str(cmd),
+            Self::Argv(argv) => argv.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RunCommand {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(RunCommandVisitor)
+    }
+}
+
+struct RunCommandVisitor;
+
+impl RunCommandVisitor {
+    fn argv<E: serde::de::Error>(argv: Vec<String>) -> Result<RunCommand, E> {
+        if argv.is_empty() {
+            return Err(E::custom("run entry argv must not be empty"));
+        }
+        Ok(RunCommand::Argv(argv))
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for RunCommandVisitor {
+    type Value = RunCommand;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("a command string, an array of strings, or a table with `argv`")
+    }
+
+    fn visit_str<E: serde::de::Error>(self, cmd: &str) -> Result<Self::Value, E> {
+        Ok(RunCommand::Shell(cmd.to_string()))
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut argv = Vec::new();
+        while let Some(arg) = seq.next_element::<String>()? {
+            argv.push(arg);
+        }
+        Self::argv(argv)
+    }
+
+    fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        use serde::de::Error;
+
+        let mut argv: Option<Vec<String>> = None;
+        while let Some(key) = map.next_key::<String>()? {
+            if key != "argv" {
+                return Err(A::Error::custom(format!(
+                    "unknown key `{key}` in run entry (expected `argv`)"
+                )));
+            }
+            argv = Some(map.next_value()?);
+        }
+        let argv = argv.ok_or_else(|| A::Error::custom("run entry table requires `argv`"))?;
+        Self::argv(argv)
+    }
+}
+
+/// Conflict handling for `copy` actions whose destination already exists
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CopyConflictPolicy {
+    /// Leave the existing file untouched
+    Skip,
+    /// Replace the existing file (default)
+    #[default]
+    Overwrite,
+    /// Rename the existing file to `<name>.bak` (`<name>.bak.<N>` when taken), then copy
+    Backup,
 }
 
 /// Worktree settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorktreeConfig {
     /// Directory template for worktree creation
-    /// Variables: {repo}, {branch}
+    /// Variables: {repo}, {branch}; a leading `~` and `$VAR` / `${VAR}` are expanded
     #[serde(default = "default_dir")]
     pub dir: String,
+    /// Refuse to create or remove worktrees whose resolved path (after
+    /// following symlinks) escapes the worktrees root derived from `dir`
+    #[serde(default)]
+    pub strict_paths: bool,
+    /// Silence the warning printed when `dir` places worktrees inside the
+    /// main repository
+    #[serde(default)]
+    pub allow_inside_repo: bool,
+    /// Start point for new branches when `add` / `create` get none
+    /// (existing branches are checked out as they are)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_start_point: Option<String>,
+    /// Columns shown by `ofsht ls`, in order
+    #[serde(default = "default_list_columns")]
+    pub list_columns: Vec<ListColumn>,
+    /// Worktrees hidden from fzf and `ofsht ls`: literal branch names or
+    /// relative paths, or `glob:`-prefixed patterns
+    #[serde(default)]
+    pub exclude: Vec<String>,
 }
 
 impl Default for WorktreeConfig {
     fn default() -> Self {
-        Self { dir: default_dir() }
+        Self {
+            dir: default_dir(),
+            strict_paths: false,
+            allow_inside_repo: false,
+            default_start_point: None,
+            list_columns: default_list_columns(),
+            exclude: Vec::new(),
+        }
     }
 }
 
+/// A column of the `ofsht ls` table
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ListColumn {
+    /// Active worktree marker (`*`)
+    Marker,
+    /// Abbreviated HEAD commit hash
+    Hash,
+    /// Path relative to the worktrees root (hidden when no worktree has one)
+    RelPath,
+    /// Absolute path (`~` abbreviated)
+    Path,
+    /// Branch name in brackets (`[@]` for the main worktree)
+    Branch,
+    /// Time since the last commit
+    Time,
+    /// Number of uncommitted changes (hidden when every worktree is clean)
+    Dirty,
+    /// Disk usage of the worktree directory (only filled by `ls --du`)
+    Size,
+    /// Ahead/behind counts against the upstream branch, or `gone` when it was
+    /// deleted on the remote (as fresh as the last fetch; see `ls --fetch`)
+    Upstream,
+}
+
+/// Default `ofsht ls` column layout
+pub const DEFAULT_LIST_COLUMNS: [ListColumn; 5] = [
+    ListColumn::Marker,
+    ListColumn::Hash,
+    ListColumn::RelPath,
+    ListColumn::Branch,
+    ListColumn::Time,
+];
+
+fn default_list_columns() -> Vec<ListColumn> {
+    DEFAULT_LIST_COLUMNS.to_vec()
+}
+
 fn default_dir() -> String {
     "../{repo}-worktrees/{branch}".to_string()
 }
@@ -67,6 +372,57 @@ pub struct IntegrationsConfig {
     pub tmux: TmuxConfig,
     #[serde(default)]
     pub gh: GhConfig,
+    #[serde(default)]
+    pub resolver: ResolverConfig,
+}
+
+/// Environment variable that disables the tmux integration
+pub const NO_TMUX_ENV: &str = "OFSHT_NO_TMUX";
+/// Environment variable that disables the zoxide integration
+pub const NO_ZOXIDE_ENV: &str = "OFSHT_NO_ZOXIDE";
+/// Environment variable that disables the GitHub CLI integration
+pub const NO_GH_ENV: &str = "OFSHT_NO_GH";
+/// Environment variable that disables the fzf integration
+pub const NO_FZF_ENV: &str = "OFSHT_NO_FZF";
+/// Environment variable that disables every integration at once
+pub const NO_INTEGRATIONS_ENV: &str = "OFSHT_NO_INTEGRATIONS";
+
+/// Whether a boolean-ish environment variable is set (anything but empty, `0` or `false`)
+fn env_flag(name: &str) -> bool {
+    std::env::var(name).is_ok_and(|value| {
+        !matches!(value.trim(), "" | "0") && !value.eq_ignore_ascii_case("false")
+    })
+}
+
+/// Whether the integration controlled by `name` (one of the `NO_*_ENV` constants)
+/// is disabled through the environment, either directly or via `OFSHT_NO_INTEGRATIONS`
+#[must_use]
+pub fn integration_disabled_by_env(name: &str) -> bool {
+    env_flag(name) || env_flag(NO_INTEGRATIONS_ENV)
+}
+
+impl IntegrationsConfig {
+    /// Force integrations off according to the `OFSHT_NO_*` environment variables
+    ///
+    /// Applied after every config load so the environment always beats the config
+    /// files (e.g. in CI images where editing the global config is awkward).
+    pub fn apply_env_overrides(&mut self) {
+        if integration_disabled_by_env(NO_TMUX_ENV) {
+            self.tmux.behavior = TmuxBehavior::Never;
+        }
+        if integration_disabled_by_env(NO_ZOXIDE_ENV) {
+            self.zoxide.enabled = false;
+        }
+        if integration_disabled_by_env(NO_GH_ENV) {
+            self.gh.enabled = false;
+        }
+        if integration_disabled_by_env(NO_FZF_ENV) {
+            self.fzf.enabled = false;
+        }
+        if env_flag(NO_INTEGRATIONS_ENV) {
+            self.resolver.enabled = false;
+        }
+    }
 }
 
 /// zoxide integration configuration
@@ -75,11 +431,18 @@ pub struct ZoxideConfig {
     /// Enable zoxide integration
     #[serde(default = "default_zoxide_enabled")]
     pub enabled: bool,
+    /// Initial score of new worktrees (`zoxide add --score`); `None` keeps
+    /// zoxide's default increment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
 }
 
 impl Default for ZoxideConfig {
     fn default() -> Self {
-        Self { enabled: true }
+        Self {
+            enabled: true,
+            score: None,
+        }
     }
 }
 
@@ -131,12 +494,16 @@ pub struct TmuxConfig {
     #[serde(default)]
     pub behavior: TmuxBehavior,
     /// What to create when adding a worktree with --tmux
-    /// Values: "window" or "pane"
+    /// Values: "window", "pane" or "session" (see [`TMUX_CREATE_MODES`])
     #[serde(default = "default_tmux_create")]
     pub create: String,
     /// Default mode for `ofsht open`: "pane" or "window"
     #[serde(default = "default_tmux_open")]
     pub open: String,
+    /// Window/session name template for `add` and `create`
+    /// Variables: `{branch}`, `{title_slug}` (slug of the GitHub PR/issue title)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
 }
 
 impl Default for TmuxConfig {
@@ -145,10 +512,31 @@ impl Default for TmuxConfig {
             behavior: TmuxBehavior::default(),
             create: default_tmux_create(),
             open: default_tmux_open(),
+            name: None,
         }
     }
 }
 
+/// Accepted values of `integration.tmux.create`
+pub const TMUX_CREATE_MODES: &[&str] = &["window", "pane", "session"];
+
+impl TmuxConfig {
+    /// Reject unknown `create` modes instead of silently opening a window
+    ///
+    /// # Errors
+    /// Returns an error naming the accepted values
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if !TMUX_CREATE_MODES.contains(&self.create.as_str()) {
+            anyhow::bail!(
+                "Invalid integration.tmux.create value '{}': expected one of {}",
+                self.create,
+                TMUX_CREATE_MODES.join(", ")
+            );
+        }
+        Ok(())
+    }
+}
+
 fn default_tmux_create() -> String {
     "window".to_string()
 }
@@ -163,11 +551,18 @@ pub struct GhConfig {
     /// Enable GitHub CLI integration
     #[serde(default = "default_gh_enabled")]
     pub enabled: bool,
+    /// Remote to fetch pull requests from (default: the remote pointing at
+    /// the repository gh resolves, falling back to `origin`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
 }
 
 impl Default for GhConfig {
     fn default() -> Self {
-        Self { enabled: true }
+        Self {
+            enabled: true,
+            remote: None,
+        }
     }
 }
 
@@ -175,12 +570,77 @@ const fn default_gh_enabled() -> bool {
     true
 }
 
+/// External ticket resolver configuration (e.g. Linear / Jira IDs for `ofsht add`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolverConfig {
+    /// Enable the resolver (it is only active when `pattern` and `command` are also set)
+    #[serde(default = "default_resolver_enabled")]
+    pub enabled: bool,
+    /// Regex matched against the whole branch argument, e.g. `^[A-Z]+-\d+$`
+    #[serde(default)]
+    pub pattern: String,
+    /// Shell command printing ticket JSON to stdout; `{id}` is replaced with the input
+    #[serde(default)]
+    pub command: String,
+    /// JSON field holding the ticket title (dotted paths like `fields.summary` allowed)
+    #[serde(default = "default_resolver_title_field")]
+    pub title_field: String,
+    /// Branch name template
+    /// Variables: `{id}`, `{id_lower}`, `{title_slug}`
+    #[serde(default = "default_resolver_branch_template")]
+    pub branch_template: String,
+    /// Seconds to wait for `command` before falling back to the literal input
+    #[serde(default = "default_resolver_timeout")]
+    pub timeout: u64,
+}
+
+impl Default for ResolverConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_resolver_enabled(),
+            pattern: String::new(),
+            command: String::new(),
+            title_field: default_resolver_title_field(),
+            branch_template: default_resolver_branch_template(),
+            timeout: default_resolver_timeout(),
+        }
+    }
+}
+
+impl ResolverConfig {
+    /// Whether the resolver is enabled and fully configured
+    #[must_use]
+    pub const fn is_active(&self) -> bool {
+        self.enabled && !self.pattern.is_empty() && !self.command.is_empty()
+    }
+}
+
+const fn default_resolver_enabled() -> bool {
+    true
+}
+
+fn default_resolver_title_field() -> String {
+    "title".to_string()
+}
+
+fn default_resolver_branch_template() -> String {
+    "{id_lower}-{title_slug}".to_string()
+}
+
+const fn default_resolver_timeout() -> u64 {
+    10
+}
+
 impl Hooks {
     #[allow(dead_code)]
     pub(super) fn merge(&self, other: &Self) -> Self {
         Self {
             create: self.create.merge(&other.create),
             delete: self.delete.merge(&other.delete),
+            on_create_failure: other.on_create_failure,
+            run_delete_hooks_in_repo_root_when_missing: other
+                .run_delete_hooks_in_repo_root_when_missing,
+            raw_output: other.raw_output,
         }
     }
 }
@@ -197,6 +657,13 @@ impl HookActions {
         let mut link = self.link.clone();
         link.extend(other.link.clone());
 
-        Self { run, copy, link }
+        Self {
+            run,
+            copy,
+            link,
+            copy_on_conflict: other.copy_on_conflict,
+            copy_compare_content: other.copy_compare_content,
+            fail_on_error: other.fail_on_error,
+        }
     }
 }
@@ -1,9 +1,13 @@
 #![allow(clippy::missing_errors_doc)]
 #![allow(clippy::must_use_candidate)]
 
+#[cfg(feature = "cli")]
 use crate::integrations::fzf::is_fzf_available;
+#[cfg(feature = "cli")]
 use crate::integrations::gh::{GhClient, RealGhClient};
+#[cfg(feature = "cli")]
 use crate::integrations::tmux::{RealTmuxLauncher, TmuxLauncher};
+#[cfg(feature = "cli")]
 use crate::integrations::zoxide::is_zoxide_available;
 
 /// Context for template generation based on detected tool availability
@@ -25,16 +29,52 @@ pub struct TemplateContext {
 }
 
 impl TemplateContext {
-    /// Detect all tool availability in the current environment
-    pub fn detect() -> Self {
+    /// Context with every integration available, for deterministic output
+    pub const fn all_enabled() -> Self {
         Self {
-            gh_available: RealGhClient.is_available(),
-            zoxide_available: is_zoxide_available(),
-            fzf_available: is_fzf_available(),
-            tmux_available: RealTmuxLauncher.detect().is_ok(),
+            gh_available: true,
+            zoxide_available: true,
+            fzf_available: true,
+            tmux_available: true,
         }
     }
 
+    /// Detect all tool availability in the current environment
+    ///
+    /// The probes run concurrently, and each integration caches its result for the
+    /// rest of the process.
+    #[cfg(feature = "cli")]
+    pub fn detect() -> Self {
+        Self::detect_with(
+            || RealGhClient.is_available(),
+            is_zoxide_available,
+            is_fzf_available,
+            || RealTmuxLauncher.detect().is_ok(),
+        )
+    }
+
+    /// Run the given availability probes concurrently (gh, zoxide, fzf, tmux)
+    #[cfg(feature = "cli")]
+    fn detect_with(
+        gh: impl FnOnce() -> bool + Send,
+        zoxide: impl FnOnce() -> bool + Send,
+        fzf: impl FnOnce() -> bool + Send,
+        tmux: impl FnOnce() -> bool + Send,
+    ) -> Self {
+        std::thread::scope(|scope| {
+            let gh = scope.spawn(gh);
+            let zoxide = scope.spawn(zoxide);
+            let fzf = scope.spawn(fzf);
+            let tmux = scope.spawn(tmux);
+            Self {
+                gh_available: gh.join().unwrap_or(false),
+                zoxide_available: zoxide.join().unwrap_or(false),
+                fzf_available: fzf.join().unwrap_or(false),
+                tmux_available: tmux.join().unwrap_or(false),
+            }
+        })
+    }
+
     /// Generate global config template based on tool availability
     pub fn generate_global(&self) -> String {
         let zoxide_section = if self.zoxide_available {
@@ -69,8 +109,8 @@ enabled = false"
 # Configure tmux integration behavior
 # behavior: \"auto\" (use --tmux flag), \"always\" (always enabled), \"never\" (disabled)
 behavior = \"auto\"
-# Determines what to create: a new window or split pane
-create = \"window\"  # \"window\" or \"pane\"
+# Determines what to create: a new window, split pane or session
+create = \"window\"  # \"window\", \"pane\" or \"session\"
 # Default mode for `ofsht open`: \"pane\" or \"window\"
 open = \"window\""
         } else {
@@ -106,10 +146,16 @@ enabled = false"
 # Relative paths are resolved from the main repository root
 dir = "../{{repo}}-worktrees/{{branch}}"
 
+[hooks]
+# What to do with a new worktree when a create hook fails:
+# "keep" (default), "remove" (roll back worktree and branch) or "prompt"
+on_create_failure = "keep"
+
 [hooks.create]
-# Commands to run after creating a worktree (executed in worktree directory)
+# Commands to run in the new worktree (strings via `sh -c`, arrays run directly)
 run = [
     # "pnpm install",
+    # ["pnpm", "install", "--frozen-lockfile"],
 ]
 
 # Files to copy from main repository to new worktree
@@ -118,6 +164,14 @@ copy = [
     # ".vscode/settings.json",
 ]
 
+# When a copied file already exists in the worktree: "overwrite" (default),
+# "skip" or "backup" (rename the existing file to <name>.bak, numbered when taken)
+copy_on_conflict = "overwrite"
+
+# Exit non-zero (after printing the worktree path) when a hook fails and the
+# worktree is kept (`continue_on_error` is accepted as another name for this)
+fail_on_error = false
+
 # Files to symlink from main repository to new worktree
 # Supports glob patterns: "*.env", "config/**/*.json"
 link = [
@@ -154,11 +208,23 @@ run = [
 # This file overrides global settings for this specific repository.
 # Add this file to .gitignore if settings are user-specific,
 # or commit it if settings should be shared with the team.
+#
+# worktree.dir can be overridden here too. Keep it outside this repository
+# (e.g. "../{repo}-worktrees/{branch}"); a dir such as "worktrees/{branch}"
+# nests worktrees inside the repo and prints a warning. After adding that
+# directory to .gitignore, set worktree.allow_inside_repo = true to silence it.
+
+[hooks]
+# What to do with a new worktree when a create hook fails:
+# "keep" (default), "remove" (roll back worktree and branch) or "prompt"
+on_create_failure = "keep"
 
 [hooks.create]
 # Commands to run after creating a worktree
+# Strings run through `sh -c`; arrays run the program directly (no quoting)
 run = [
     # "pnpm install",
+    # ["pnpm", "install", "--frozen-lockfile"],
 ]
 
 # Files to copy from main repository
@@ -166,6 +232,14 @@ copy = [
     # ".env.local",
 ]
 
+# When a copied file already exists in the worktree: "overwrite" (default),
+# "skip" or "backup" (rename the existing file to <name>.bak, numbered when taken)
+copy_on_conflict = "overwrite"
+
+# Exit non-zero (after printing the worktree path) when a hook fails and the
+# worktree is kept (`continue_on_error` is accepted as another name for this)
+fail_on_error = false
+
 # Files to symlink (supports glob patterns)
 link = [
     # ".claude/settings.local.json",
@@ -182,6 +256,56 @@ run = []
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Condvar, Mutex};
+    use std::time::Duration;
+
+    #[test]
+    #[cfg(feature = "cli")]
+    fn test_detect_with_runs_probes_concurrently() {
+        // Each probe waits until all four have started, which only happens when
+        // they run at the same time (the timeout just keeps a regression from hanging)
+        let started = (Mutex::new(0), Condvar::new());
+        let probe = |result: bool| {
+            let started = &started;
+            move || {
+                let (count, all_started) = started;
+                *count.lock().unwrap() += 1;
+                all_started.notify_all();
+                let timed_out = all_started
+                    .wait_timeout_while(count.lock().unwrap(), Duration::from_secs(10), |n| *n < 4)
+                    .unwrap()
+                    .1
+                    .timed_out();
+                assert!(!timed_out, "probes ran one after another");
+                result
+            }
+        };
+        let ctx =
+            TemplateContext::detect_with(probe(true), probe(false), probe(true), probe(false));
+        assert_eq!(
+            ctx,
+            TemplateContext {
+                gh_available: true,
+                zoxide_available: false,
+                fzf_available: true,
+                tmux_available: false,
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "cli")]
+    fn test_detect_with_calls_each_probe_once() {
+        let calls = AtomicUsize::new(0);
+        let probe = || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            true
+        };
+        let ctx = TemplateContext::detect_with(probe, probe, probe, probe);
+        assert_eq!(ctx, TemplateContext::all_enabled());
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
 
     #[test]
     fn test_detect_all_tools_available() {
@@ -218,12 +342,7 @@ mod tests {
 
     #[test]
     fn test_generate_global_all_enabled() {
-        let ctx = TemplateContext {
-            gh_available: true,
-            zoxide_available: true,
-            fzf_available: true,
-            tmux_available: true,
-        };
+        let ctx = TemplateContext::all_enabled();
 
         let template = ctx.generate_global();
 
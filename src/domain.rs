@@ -1,4 +1,9 @@
 // Domain module
 // This module contains domain entities and business logic
 
+pub mod branch;
+#[cfg(feature = "cli")]
+pub mod exclude;
+pub mod path;
+pub mod status;
 pub mod worktree;
@@ -0,0 +1,160 @@
+//! Branch name validation
+//!
+//! A pure-Rust version of the core `git check-ref-format --branch` rules, so
+//! invalid names are rejected before any path is expanded or git is invoked.
+
+use anyhow::Result;
+
+/// Characters git never allows in a ref name
+const FORBIDDEN_CHARS: [char; 7] = ['~', '^', ':', '?', '*', '[', '\\'];
+
+/// Validate a branch name, explaining the first rule it breaks
+///
+/// # Examples
+///
+/// ```
+/// # use ofsht::domain::branch::validate;
+/// assert!(validate("feature/login").is_ok());
+/// assert!(validate("feat something").is_err());
+/// ```
+///
+/// # Errors
+/// Returns an error of the form `Invalid branch name '<name>': <reason>`
+pub fn validate(name: &str) -> Result<()> {
+    match violation(name) {
+        Some(reason) => anyhow::bail!("Invalid branch name '{name}': {reason}"),
+        None => Ok(()),
+    }
+}
+
+/// Return the first rule `name` violates, if any
+fn violation(name: &str) -> Option<String> {
+    if name.is_empty() {
+        return Some("branch names cannot be empty".to_string());
+    }
+    if name == "@" {
+        return Some("'@' is not a valid branch name".to_string());
+    }
+    if name.starts_with('-') {
+        return Some("branch names cannot start with '-'".to_string());
+    }
+    if name.chars().any(char::is_whitespace) {
+        return Some("branch names cannot contain spaces".to_string());
+    }
+    if name.chars().any(char::is_control) {
+        return Some("branch names cannot contain control characters".to_string());
+    }
+    if let Some(c) = name.chars().find(|c| FORBIDDEN_CHARS.contains(c)) {
+        return Some(format!("branch names cannot contain '{c}'"));
+    }
+    if name.contains("..") {
+        return Some("branch names cannot contain '..'".to_string());
+    }
+    if name.contains("@{") {
+        return Some("branch names cannot contain '@{'".to_string());
+    }
+    if name.starts_with('/') || name.ends_with('/') || name.contains("//") {
+        return Some("branch names cannot start or end with '/' or contain '//'".to_string());
+    }
+    if name.ends_with('.') {
+        return Some("branch names cannot end with '.'".to_string());
+    }
+    for component in name.split('/') {
+        if component.starts_with('.') {
+            return Some(format!(
+                "path components cannot start with '.' ('{component}')"
+            ));
+        }
+        // Git compares ref names case-sensitively, `.LOCK` is allowed
+        #[allow(clippy::case_sensitive_file_extension_comparisons)]
+        if component.ends_with(".lock") {
+            return Some(format!(
+                "path components cannot end with '.lock' ('{component}')"
+            ));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reason(name: &str) -> String {
+        validate(name).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_validate_accepts_common_names() {
+        for name in [
+            "main",
+            "feature-x",
+            "feat/auth/login",
+            "fix_123",
+            "release-1.2.3",
+            "user@feature",
+            "日本語",
+            "a.b",
+        ] {
+            assert!(validate(name).is_ok(), "{name} should be valid");
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_spaces() {
+        assert_eq!(
+            reason("feat something"),
+            "Invalid branch name 'feat something': branch names cannot contain spaces"
+        );
+        assert!(reason("tab\there").contains("cannot contain spaces"));
+    }
+
+    #[test]
+    fn test_validate_rejects_double_dot() {
+        assert!(reason("feat..x").contains("cannot contain '..'"));
+        assert!(reason("../../etc").contains("'..'"));
+    }
+
+    #[test]
+    fn test_validate_rejects_leading_dash_and_empty() {
+        assert!(reason("-f").contains("cannot start with '-'"));
+        assert!(reason("").contains("cannot be empty"));
+        assert!(reason("@").contains("'@' is not a valid branch name"));
+    }
+
+    #[test]
+    fn test_validate_rejects_control_and_forbidden_chars() {
+        assert!(reason("feat\u{7f}x").contains("control characters"));
+        for (name, c) in [
+            ("a~1", '~'),
+            ("a^", '^'),
+            ("a:b", ':'),
+            ("a?", '?'),
+            ("a*", '*'),
+            ("a[b", '['),
+            ("a\\b", '\\'),
+        ] {
+            assert!(
+                reason(name).contains(&format!("cannot contain '{c}'")),
+                "{name}"
+            );
+        }
+        assert!(reason("a@{1}").contains("'@{'"));
+    }
+
+    #[test]
+    fn test_validate_rejects_slash_rules() {
+        for name in ["/feat", "feat/", "feat//x"] {
+            assert!(reason(name).contains("'/'"), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_dot_rules() {
+        assert!(reason("feat.").contains("cannot end with '.'"));
+        assert!(reason(".hidden").contains("cannot start with '.' ('.hidden')"));
+        assert!(reason("feat/.x").contains("('.x')"));
+        assert!(reason("feat.lock").contains("cannot end with '.lock'"));
+        assert!(reason("a.lock/b").contains("('a.lock')"));
+    }
+}
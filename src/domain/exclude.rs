@@ -0,0 +1,146 @@
+//! `worktree.exclude` patterns hiding worktrees from fzf and `ofsht ls`
+//!
+//! A pattern is either a literal name or, with a `glob:` prefix, a glob. Both
+//! are matched against the branch name and the path relative to the worktrees
+//! root. The main worktree is never excluded.
+
+use super::worktree::{WorktreeEntry, WorktreeLayout};
+use anyhow::{Context, Result};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+
+/// Prefix marking a pattern as a glob
+const GLOB_PREFIX: &str = "glob:";
+
+/// Compiled `worktree.exclude` patterns
+#[derive(Debug, Clone)]
+pub struct WorktreeExclude {
+    literals: Vec<String>,
+    globs: GlobSet,
+}
+
+impl WorktreeExclude {
+    /// Compile `patterns` (literal names, or globs prefixed with `glob:`)
+    ///
+    /// # Errors
+    /// Returns an error if a glob pattern is invalid
+    pub fn new(patterns: &[String]) -> Result<Self> {
+        let mut literals = Vec::new();
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            if let Some(glob) = pattern.strip_prefix(GLOB_PREFIX) {
+                builder.add(
+                    GlobBuilder::new(glob)
+                        .literal_separator(true)
+                        .build()
+                        .with_context(|| format!("Invalid worktree.exclude pattern: {pattern}"))?,
+                );
+            } else {
+                literals.push(pattern.clone());
+            }
+        }
+        let globs = builder
+            .build()
+            .context("Failed to compile worktree.exclude patterns")?;
+        Ok(Self { literals, globs })
+    }
+
+    /// Whether no pattern is configured
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.literals.is_empty() && self.globs.is_empty()
+    }
+
+    /// Whether a branch name or relative path matches any pattern
+    #[must_use]
+    pub fn matches(&self, branch: Option<&str>, relative_path: Option<&str>) -> bool {
+        [branch, relative_path].into_iter().flatten().any(|name| {
+            self.literals.iter().any(|literal| literal == name) || self.globs.is_match(name)
+        })
+    }
+
+    /// Whether a non-main worktree is excluded, given the layout its relative
+    /// path is computed from
+    #[must_use]
+    pub fn is_excluded(&self, entry: &WorktreeEntry, layout: &WorktreeLayout) -> bool {
+        self.matches(entry.branch.as_deref(), layout.rel_path(entry).as_deref())
+    }
+
+    /// `entries` without the excluded worktrees; the main worktree (the first
+    /// entry) is always kept
+    #[must_use]
+    pub fn filter_entries(
+        &self,
+        entries: &[WorktreeEntry],
+        layout: &WorktreeLayout,
+    ) -> Vec<WorktreeEntry> {
+        if self.is_empty() {
+            return entries.to_vec();
+        }
+        entries
+            .iter()
+            .enumerate()
+            .filter(|(index, entry)| *index == 0 || !self.is_excluded(entry, layout))
+            .map(|(_, entry)| entry.clone())
+            .collect()
+    }
+}
+
+impl Default for WorktreeExclude {
+    fn default() -> Self {
+        Self {
+            literals: Vec::new(),
+            globs: GlobSet::empty(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::worktree::WorktreeList;
+
+    fn exclude(patterns: &[&str]) -> WorktreeExclude {
+        let patterns: Vec<String> = patterns.iter().map(ToString::to_string).collect();
+        WorktreeExclude::new(&patterns).unwrap()
+    }
+
+    #[test]
+    fn test_matches_literal_and_glob() {
+        let exclude = exclude(&["release", "glob:archive/*"]);
+        assert!(exclude.matches(Some("release"), None));
+        assert!(exclude.matches(None, Some("release")));
+        assert!(exclude.matches(Some("archive/2023"), None));
+        assert!(!exclude.matches(Some("release-2"), Some("release-2")));
+        assert!(!exclude.matches(Some("archive/2023/q1"), None));
+        assert!(!exclude.matches(None, None));
+    }
+
+    #[test]
+    fn test_invalid_glob_is_an_error() {
+        let err = WorktreeExclude::new(&["glob:[".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("glob:["), "{err}");
+    }
+
+    #[test]
+    fn test_filter_entries_keeps_main_worktree() {
+        let porcelain = "worktree /repo\nHEAD a\nbranch refs/heads/release\n\n\
+                         worktree /wt/release\nHEAD b\nbranch refs/heads/rel\n\n\
+                         worktree /wt/feature\nHEAD c\nbranch refs/heads/feature\n\n\
+                         worktree /wt/old\nHEAD d\nbranch refs/heads/archive/old\n\n";
+        let list = WorktreeList::parse(porcelain, None);
+        let layout = WorktreeLayout::for_list(&list, None);
+        let kept: Vec<String> = exclude(&["release", "glob:archive/*"])
+            .filter_entries(list.entries(), &layout)
+            .into_iter()
+            .map(|entry| entry.path)
+            .collect();
+        // `/wt/release` is excluded by its relative path, `/repo` never is
+        assert_eq!(kept, vec!["/repo", "/wt/feature"]);
+        assert_eq!(
+            WorktreeExclude::default()
+                .filter_entries(list.entries(), &layout)
+                .len(),
+            4
+        );
+    }
+}
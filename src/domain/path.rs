@@ -0,0 +1,159 @@
+//! Path string expansion for user-facing configuration values
+
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+
+/// Expand a leading `~` and `$VAR` / `${VAR}` references in `input`
+///
+/// - `~` or `~/...` at the start expands to the home directory
+///   (`~user` forms are left untouched)
+/// - `$NAME` and `${NAME}` expand to the environment variable's value
+/// - A `$` not followed by a variable name is kept literally
+///
+/// # Errors
+/// Returns an error when a referenced variable is not set, a `${` is left
+/// unclosed, or the home directory cannot be determined.
+///
+/// # Examples
+///
+/// ```
+/// # use ofsht::domain::path::expand_user_and_env;
+/// assert_eq!(expand_user_and_env("../{repo}-worktrees").unwrap(), "../{repo}-worktrees");
+/// assert!(expand_user_and_env("$OFSHT_SURELY_UNDEFINED_VAR/x").is_err());
+/// ```
+pub fn expand_user_and_env(input: &str) -> Result<String> {
+    let with_home = expand_home(input, dirs::home_dir)?;
+    expand_env(&with_home)
+}
+
+/// Expand a leading `~` using the directory returned by `home_dir`
+fn expand_home(input: &str, home_dir: impl FnOnce() -> Option<PathBuf>) -> Result<String> {
+    let rest = match input.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => rest,
+        _ => return Ok(input.to_string()),
+    };
+    let home = home_dir().context("Cannot expand `~`: home directory is unknown")?;
+    Ok(format!("{}{rest}", home.display()))
+}
+
+fn expand_env(input: &str) -> Result<String> {
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.char_indices().peekable();
+
+    while let Some((_, c)) = chars.next() {
+        if c != '$' {
+            out.push(c);
+            continue;
+        }
+
+        let name = match chars.peek() {
+            Some(&(_, '{')) => {
+                chars.next();
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '}')) => break,
+                        Some((_, ch)) => name.push(ch),
+                        None => anyhow::bail!("Unclosed `${{` in path: {input}"),
+                    }
+                }
+                name
+            }
+            Some(&(_, ch)) if ch == '_' || ch.is_ascii_alphabetic() => {
+                let mut name = String::new();
+                while let Some(&(_, ch)) = chars.peek() {
+                    if ch == '_' || ch.is_ascii_alphanumeric() {
+                        name.push(ch);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                name
+            }
+            _ => {
+                out.push('$');
+                continue;
+            }
+        };
+
+        let value = std::env::var(&name).with_context(|| {
+            format!("Environment variable `{name}` is not set (in path: {input})")
+        })?;
+        out.push_str(&value);
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_tilde() {
+        let home = || Some(PathBuf::from("/home/tester"));
+        assert_eq!(expand_home("~/foo", home).unwrap(), "/home/tester/foo");
+        assert_eq!(expand_home("~", home).unwrap(), "/home/tester");
+    }
+
+    #[test]
+    fn test_expand_tilde_without_home_fails() {
+        assert!(expand_home("~/foo", || None).is_err());
+        // Nothing to expand, so the missing home directory does not matter
+        assert_eq!(expand_home("/srv/wt", || None).unwrap(), "/srv/wt");
+    }
+
+    #[test]
+    fn test_tilde_only_expanded_at_start() {
+        assert_eq!(expand_user_and_env("a/~/b").unwrap(), "a/~/b");
+        assert_eq!(expand_user_and_env("~other/b").unwrap(), "~other/b");
+    }
+
+    #[test]
+    fn test_expand_env_var() {
+        temp_env::with_var("OFSHT_TEST_WT_ROOT", Some("/data/wt"), || {
+            assert_eq!(
+                expand_user_and_env("$OFSHT_TEST_WT_ROOT/{branch}").unwrap(),
+                "/data/wt/{branch}"
+            );
+            assert_eq!(
+                expand_user_and_env("${OFSHT_TEST_WT_ROOT}-x/{branch}").unwrap(),
+                "/data/wt-x/{branch}"
+            );
+        });
+    }
+
+    #[test]
+    fn test_expand_home_var() {
+        temp_env::with_var("HOME", Some("/home/tester"), || {
+            assert_eq!(
+                expand_user_and_env("$HOME/foo").unwrap(),
+                "/home/tester/foo"
+            );
+        });
+    }
+
+    #[test]
+    fn test_missing_var_is_error() {
+        temp_env::with_var_unset("OFSHT_TEST_MISSING", || {
+            let err = expand_user_and_env("$OFSHT_TEST_MISSING/{branch}")
+                .unwrap_err()
+                .to_string();
+            assert!(
+                err.contains("OFSHT_TEST_MISSING"),
+                "unexpected error: {err}"
+            );
+        });
+    }
+
+    #[test]
+    fn test_unclosed_brace_is_error() {
+        assert!(expand_user_and_env("${HOME/foo").is_err());
+    }
+
+    #[test]
+    fn test_literal_dollar_kept() {
+        assert_eq!(expand_user_and_env("a$/b$1").unwrap(), "a$/b$1");
+    }
+}
@@ -0,0 +1,124 @@
+//! `git status --porcelain` and upstream tracking parsing
+
+use std::collections::HashMap;
+
+/// Two-letter status codes git uses for unmerged paths
+const UNMERGED_CODES: [&str; 7] = ["DD", "AU", "UD", "UA", "DU", "AA", "UU"];
+
+/// Paths left with unresolved conflicts, in the order git lists them
+///
+/// Quoted paths (names with spaces or special characters) lose their quotes.
+#[must_use]
+pub fn unmerged_paths(porcelain: &str) -> Vec<String> {
+    porcelain
+        .lines()
+        .filter_map(|line| {
+            let (code, path) = (line.get(..2)?, line.get(3..)?);
+            UNMERGED_CODES
+                .contains(&code)
+                .then(|| path.trim_matches('"').to_string())
+        })
+        .collect()
+}
+
+/// `git for-each-ref` format read by [`upstream_states`]
+pub const UPSTREAM_FORMAT: &str = "%(refname:short)%09%(upstream)%09%(upstream:track)";
+
+/// Upstream state of each local branch, from `git for-each-ref` output in
+/// [`UPSTREAM_FORMAT`]
+///
+/// The state reads `↑2 ↓1` (ahead/behind), `gone` when the upstream branch was
+/// deleted on the remote, or `=` when in sync. Branches without an upstream
+/// are left out.
+#[must_use]
+pub fn upstream_states(for_each_ref_output: &str) -> HashMap<String, String> {
+    for_each_ref_output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let (branch, upstream) = (fields.next()?, fields.next()?);
+            if branch.is_empty() || upstream.is_empty() {
+                return None;
+            }
+            let track = fields.next().unwrap_or_default();
+            Some((branch.to_string(), track_label(track)))
+        })
+        .collect()
+}
+
+/// `[ahead 2, behind 1]` → `↑2 ↓1`, `[gone]` → `gone`, empty → `=`
+fn track_label(track: &str) -> String {
+    let track = track.trim().trim_start_matches('[').trim_end_matches(']');
+    if track.is_empty() {
+        return "=".to_string();
+    }
+    track
+        .split(", ")
+        .map(|part| match part.split_once(' ') {
+            Some(("ahead", n)) => format!("↑{n}"),
+            Some(("behind", n)) => format!("↓{n}"),
+            _ => part.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unmerged_paths_picks_conflict_codes() {
+        let status = "UU src/main.rs\n\
+                      M  src/lib.rs\n\
+                      AA new.txt\n\
+                      ?? scratch.txt\n\
+                      DU removed.txt\n\
+                      A  staged.txt\n";
+        assert_eq!(
+            unmerged_paths(status),
+            ["src/main.rs", "new.txt", "removed.txt"]
+        );
+    }
+
+    #[test]
+    fn test_unmerged_paths_clean_or_dirty_without_conflicts() {
+        assert!(unmerged_paths("").is_empty());
+        assert!(unmerged_paths(" M a.rs\n?? b.rs\nR  old -> new\n").is_empty());
+    }
+
+    #[test]
+    fn test_unmerged_paths_strips_quotes() {
+        assert_eq!(
+            unmerged_paths("UU \"with space.txt\"\n"),
+            ["with space.txt"]
+        );
+    }
+
+    #[test]
+    fn test_unmerged_paths_ignores_malformed_lines() {
+        assert!(unmerged_paths("UU\nU\n").is_empty());
+    }
+
+    #[test]
+    fn test_upstream_states_labels() {
+        let output = "main\trefs/remotes/origin/main\t\n\
+                      feature\trefs/remotes/origin/feature\t[ahead 2, behind 1]\n\
+                      ahead\trefs/remotes/origin/ahead\t[ahead 3]\n\
+                      merged\trefs/remotes/origin/merged\t[gone]\n\
+                      local\t\t\n";
+        let states = upstream_states(output);
+        assert_eq!(states.len(), 4);
+        assert_eq!(states["main"], "=");
+        assert_eq!(states["feature"], "↑2 ↓1");
+        assert_eq!(states["ahead"], "↑3");
+        assert_eq!(states["merged"], "gone");
+        assert!(!states.contains_key("local"));
+    }
+
+    #[test]
+    fn test_upstream_states_ignores_malformed_lines() {
+        assert!(upstream_states("").is_empty());
+        assert!(upstream_states("main\n\trefs/remotes/origin/x\t\n").is_empty());
+    }
+}
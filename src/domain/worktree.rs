@@ -2,34 +2,129 @@
 //!
 //! This module contains data structures and parsing logic for git worktrees.
 
+use anyhow::{Context, Result};
+
+use super::path::expand_user_and_env;
 use crate::path_utils::canonicalize_allow_missing;
 
 /// Worktree entry for enhanced display
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct WorktreeEntry {
     pub path: String,
     pub branch: Option<String>,
     pub hash: Option<String>,
     pub is_active: bool,
+    /// Whether the worktree is locked (`git worktree lock`)
+    pub locked: bool,
+    /// Lock reason, when one was given to `git worktree lock --reason`
+    pub lock_reason: Option<String>,
+    /// Number of uncommitted changes, when computed by the caller
+    /// (not part of the porcelain output)
+    pub dirty: Option<usize>,
+    /// Whether git reports the worktree as prunable (its directory is gone)
+    pub prunable: bool,
+    /// PR or issue number the worktree was created from, when recorded by
+    /// `ofsht add '#123'` (not part of the porcelain output)
+    pub github_number: Option<u32>,
+    /// Size of the worktree directory in bytes, when computed by `ls --du`
+    /// (not part of the porcelain output)
+    pub disk_size: Option<u64>,
+    /// Upstream tracking state of the branch (`↑2 ↓1`, `gone`, `=`), when
+    /// computed by the caller (not part of the porcelain output)
+    pub upstream: Option<String>,
 }
 
-/// Check if a worktree path matches the active path
-fn is_path_active(worktree_path: &str, canonical_active: Option<&std::path::PathBuf>) -> bool {
-    if let Some(active) = canonical_active {
-        // Try canonical comparison first (works for real paths)
-        if let Ok(canonical_worktree) = std::path::Path::new(worktree_path).canonicalize() {
-            return &canonical_worktree == active;
+/// How a worktree changed between two listings (see [`diff_entries`])
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryChange {
+    /// Present now, absent before
+    Added,
+    /// Present before, gone now
+    Removed,
+    /// Present in both, but HEAD moved
+    Changed,
+    /// Present in both with the same HEAD
+    Unchanged,
+}
+
+impl EntryChange {
+    /// One-character marker used to prefix table rows (`+`, `-`, `~`, or a space)
+    #[must_use]
+    pub const fn marker(self) -> char {
+        match self {
+            Self::Added => '+',
+            Self::Removed => '-',
+            Self::Changed => '~',
+            Self::Unchanged => ' ',
         }
-        // Fallback to string comparison (useful for tests with non-existent paths)
-        return std::path::Path::new(worktree_path) == active.as_path();
     }
-    false
+}
+
+/// Compare two worktree listings, keyed by path
+///
+/// Returns every current entry in order, tagged as added, changed (different HEAD
+/// hash) or unchanged, followed by the previous entries that are gone.
+#[must_use]
+pub fn diff_entries(
+    previous: &[WorktreeEntry],
+    current: &[WorktreeEntry],
+) -> Vec<(EntryChange, WorktreeEntry)> {
+    let mut diff: Vec<(EntryChange, WorktreeEntry)> = current
+        .iter()
+        .map(|entry| {
+            let change = previous.iter().find(|old| old.path == entry.path).map_or(
+                EntryChange::Added,
+                |old| {
+                    if old.hash == entry.hash {
+                        EntryChange::Unchanged
+                    } else {
+                        EntryChange::Changed
+                    }
+                },
+            );
+            (change, entry.clone())
+        })
+        .collect();
+    diff.extend(
+        previous
+            .iter()
+            .filter(|old| !current.iter().any(|entry| entry.path == old.path))
+            .map(|old| (EntryChange::Removed, old.clone())),
+    );
+    diff
+}
+
+/// How deep `worktree_path` is when the active path is it or lies inside it
+///
+/// Returns the component count of the worktree path, so that with nested
+/// worktrees (one inside another's directory) the deepest one can win.
+fn active_match_depth(
+    worktree_path: &str,
+    canonical_active: Option<&std::path::PathBuf>,
+) -> Option<usize> {
+    let active = canonical_active?;
+    // Resolved like the active path, so symlinked ancestors compare equal even
+    // when part of the path does not exist (yet)
+    let worktree = canonicalize_allow_missing(std::path::Path::new(worktree_path));
+    active
+        .starts_with(&worktree)
+        .then(|| worktree.components().count())
+}
+
+/// Outcome of [`WorktreeList::match_name`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NameMatch<'a> {
+    /// Exactly one worktree matched
+    Unique(&'a WorktreeEntry),
+    /// Several worktrees contain the query; listed in `git worktree list` order
+    Ambiguous(Vec<&'a WorktreeEntry>),
+    NotFound,
 }
 
 /// Unified worktree list parsed from `git worktree list --porcelain` output.
 ///
-/// Single parser + six query methods (`entries`, `main`, `non_main`,
-/// `find_by_branch`, `find_by_path`, `current`) replacing the previous family
+/// Single parser + query methods (`entries`, `main`, `non_main`,
+/// `find_by_branch`, `find_by_path`, `find_by_name`, `match_name`, `current`) replacing the previous family
 /// of standalone porcelain scanners scattered across `commands/*` and `cli.rs`.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct WorktreeList {
@@ -39,59 +134,65 @@ pub struct WorktreeList {
 impl WorktreeList {
     /// Parse `git worktree list --porcelain` output.
     ///
-    /// `active_path`: when `Some`, the matching entry's `is_active` field is set to `true`
-    /// using canonicalize-then-string-fallback comparison (same semantics as the legacy
-    /// `is_path_active` helper).
+    /// `active_path`: when `Some`, the entry containing it (the worktree itself or any
+    /// subdirectory) gets `is_active = true`; with nested worktrees only the deepest one.
+    /// Both sides are resolved with [`canonicalize_allow_missing`], so a worktree reached
+    /// through a symlinked ancestor (e.g. macOS `/Users` vs `/System/Volumes/Data/Users`)
+    /// still matches.
     #[must_use]
     pub fn parse(porcelain: &str, active_path: Option<&std::path::Path>) -> Self {
         let mut entries = Vec::new();
-        let mut current_path: Option<String> = None;
-        let mut current_branch: Option<String> = None;
-        let mut current_hash: Option<String> = None;
+        let mut current: Option<WorktreeEntry> = None;
 
-        let canonical_active =
-            active_path.map(|p| p.canonicalize().unwrap_or_else(|_| p.to_path_buf()));
+        let canonical_active = active_path.map(canonicalize_allow_missing);
+
+        let mut finish = |entry: Option<WorktreeEntry>| entries.extend(entry);
 
         for line in porcelain.lines() {
             if let Some(path) = line.strip_prefix("worktree ") {
-                if let Some(prev_path) = current_path.take() {
-                    let is_active = is_path_active(&prev_path, canonical_active.as_ref());
-                    entries.push(WorktreeEntry {
-                        path: prev_path,
-                        branch: current_branch.take(),
-                        hash: current_hash.take(),
-                        is_active,
-                    });
-                }
-                current_path = Some(path.to_string());
-            } else if let Some(full_hash) = line.strip_prefix("HEAD ") {
-                current_hash = Some(full_hash.chars().take(8).collect());
+                finish(current.take());
+                current = Some(WorktreeEntry {
+                    path: path.to_string(),
+                    ..WorktreeEntry::default()
+                });
+                continue;
+            }
+
+            let Some(entry) = current.as_mut() else {
+                continue;
+            };
+
+            if let Some(full_hash) = line.strip_prefix("HEAD ") {
+                entry.hash = Some(full_hash.chars().take(8).collect());
             } else if let Some(branch_ref) = line.strip_prefix("branch ") {
                 let branch = branch_ref.strip_prefix("refs/heads/").unwrap_or(branch_ref);
-                current_branch = Some(branch.to_string());
+                entry.branch = Some(branch.to_string());
             } else if line == "detached" {
-                current_branch = None;
+                entry.branch = None;
+            } else if line == "locked" {
+                entry.locked = true;
+            } else if let Some(reason) = line.strip_prefix("locked ") {
+                entry.locked = true;
+                entry.lock_reason = Some(reason.to_string());
+            } else if line == "prunable" || line.starts_with("prunable ") {
+                entry.prunable = true;
             } else if line.is_empty() {
-                if let Some(prev_path) = current_path.take() {
-                    let is_active = is_path_active(&prev_path, canonical_active.as_ref());
-                    entries.push(WorktreeEntry {
-                        path: prev_path,
-                        branch: current_branch.take(),
-                        hash: current_hash.take(),
-                        is_active,
-                    });
-                }
+                finish(current.take());
             }
         }
 
-        if let Some(prev_path) = current_path {
-            let is_active = is_path_active(&prev_path, canonical_active.as_ref());
-            entries.push(WorktreeEntry {
-                path: prev_path,
-                branch: current_branch,
-                hash: current_hash,
-                is_active,
-            });
+        finish(current);
+
+        let active_index = entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| {
+                active_match_depth(&entry.path, canonical_active.as_ref()).map(|d| (d, index))
+            })
+            .max()
+            .map(|(_, index)| index);
+        if let Some(index) = active_index {
+            entries[index].is_active = true;
         }
 
         Self { entries }
@@ -144,6 +245,79 @@ impl WorktreeList {
         })
     }
 
+    /// Resolve a user-supplied worktree name to a non-main worktree.
+    ///
+    /// Precedence, first match wins:
+    /// 1. exact branch name (`feature` never matches `feature-2` or `feat/feature`)
+    /// 2. path relative to `worktree_root` (plain components only, so `..` cannot
+    ///    reach a worktree outside the root)
+    /// 3. absolute path, or a path relative to the current directory
+    ///
+    /// A branch therefore wins over another worktree's directory of the same name.
+    #[must_use]
+    pub fn find_by_name(
+        &self,
+        name: &str,
+        worktree_root: Option<&std::path::Path>,
+    ) -> Option<&WorktreeEntry> {
+        use std::path::Component;
+
+        if let Some(entry) = self.find_by_branch(name) {
+            return Some(entry);
+        }
+        let relative = std::path::Path::new(name);
+        let is_plain_relative = !name.is_empty()
+            && relative
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)));
+        if let Some(root) = worktree_root.filter(|_| is_plain_relative) {
+            if let Some(entry) = self.find_by_path(&root.join(relative)) {
+                return Some(entry);
+            }
+        }
+        self.find_by_path(relative)
+    }
+
+    /// Resolve a partial worktree name.
+    ///
+    /// Tries [`Self::find_by_name`] first, then a case-insensitive substring
+    /// match over branch names and paths relative to `worktree_root`, so `log`
+    /// finds `feature/login-form` when nothing else contains it.
+    #[must_use]
+    pub fn match_name(
+        &self,
+        query: &str,
+        worktree_root: Option<&std::path::Path>,
+    ) -> NameMatch<'_> {
+        if let Some(entry) = self.find_by_name(query, worktree_root) {
+            return NameMatch::Unique(entry);
+        }
+        if query.is_empty() {
+            return NameMatch::NotFound;
+        }
+        let needle = query.to_lowercase();
+        let contains = |text: &str| text.to_lowercase().contains(&needle);
+        let mut candidates: Vec<&WorktreeEntry> = self
+            .non_main()
+            .iter()
+            .filter(|entry| {
+                let relative = worktree_root.and_then(|root| {
+                    std::path::Path::new(&entry.path)
+                        .strip_prefix(root)
+                        .ok()
+                        .map(|rel| rel.to_string_lossy().into_owned())
+                });
+                entry.branch.as_deref().is_some_and(contains)
+                    || relative.as_deref().is_some_and(contains)
+            })
+            .collect();
+        match candidates.len() {
+            0 => NameMatch::NotFound,
+            1 => NameMatch::Unique(candidates.remove(0)),
+            _ => NameMatch::Ambiguous(candidates),
+        }
+    }
+
     /// The currently-active worktree (matched against the `active_path` passed to `parse`).
     /// Returns `None` when no `active_path` was provided, or when no entry matched.
     ///
@@ -157,6 +331,152 @@ impl WorktreeList {
     }
 }
 
+/// Expand a worktree directory template into a concrete path
+///
+/// Expands a leading `~` and `$VAR` / `${VAR}` references in the template
+/// (see [`expand_user_and_env`]), then substitutes `{repo}` with the repository
+/// directory name and `{branch}` with the branch name. Variables are expanded
+/// before substitution so a `$` in a branch name is never interpreted.
+/// Relative results are resolved from `repo_root`.
+///
+/// # Errors
+/// Returns an error when the repository name cannot be determined from
+/// `repo_root` or the template references an undefined variable.
+///
+/// # Examples
+///
+/// ```
+/// # use std::path::{Path, PathBuf};
+/// # use ofsht::domain::worktree::expand_path_template;
+/// let path = expand_path_template("../{repo}-worktrees/{branch}", Path::new("/src/app"), "feat");
+/// assert_eq!(path.unwrap(), PathBuf::from("/src/app/../app-worktrees/feat"));
+/// ```
+pub fn expand_path_template(
+    template: &str,
+    repo_root: &std::path::Path,
+    branch: &str,
+) -> Result<std::path::PathBuf> {
+    let repo_name = repo_root
+        .file_name()
+        .and_then(|n| n.to_str())
+        .context("Failed to get repository name")?;
+
+    let expanded = expand_user_and_env(template)?
+        .replace("{repo}", repo_name)
+        .replace("{branch}", branch);
+
+    Ok(if expanded.starts_with('/') {
+        std::path::PathBuf::from(&expanded)
+    } else {
+        repo_root.join(&expanded)
+    })
+}
+
+/// Check that `{branch}` is the last component of a directory template
+///
+/// Relative-path display, completion and `rm`/`cd` resolution all identify a
+/// worktree by the path below the worktrees root, which only equals the branch
+/// name when nothing follows `{branch}`.
+///
+/// # Examples
+///
+/// ```
+/// # use ofsht::domain::worktree::validate_dir_template;
+/// assert!(validate_dir_template("../{repo}-worktrees/{branch}").is_ok());
+/// assert!(validate_dir_template("../{repo}-wt/{branch}/checkout").is_err());
+/// ```
+///
+/// # Errors
+/// Returns an error when anything other than a trailing `/` follows `{branch}`
+pub fn validate_dir_template(template: &str) -> Result<()> {
+    let Some((_, rest)) = template.split_once("{branch}") else {
+        return Ok(());
+    };
+    if rest.trim_end_matches('/').is_empty() {
+        return Ok(());
+    }
+    anyhow::bail!(
+        "Invalid worktree.dir \"{template}\": {{branch}} must be the last path component \
+         (e.g. \"../{{repo}}-worktrees/{{branch}}\")"
+    )
+}
+
+/// Where a new worktree lives relative to the main repository
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorktreeLocation {
+    /// Outside the main repository's working tree (the normal case)
+    Outside,
+    /// Nested inside the main repository, where it shows up as untracked
+    /// files unless ignored
+    InsideRepo,
+}
+
+/// Classify `worktree_path` against `repo_root`, following symlinks of the
+/// existing part of both paths
+///
+/// # Examples
+///
+/// ```
+/// # use std::path::Path;
+/// # use ofsht::domain::worktree::{validate_worktree_location, WorktreeLocation};
+/// let repo = Path::new("/src/app");
+/// let outside = validate_worktree_location(Path::new("/src/app-worktrees/feat"), repo);
+/// assert_eq!(outside.unwrap(), WorktreeLocation::Outside);
+/// let inside = validate_worktree_location(Path::new("/src/app/worktrees/feat"), repo);
+/// assert_eq!(inside.unwrap(), WorktreeLocation::InsideRepo);
+/// ```
+///
+/// # Errors
+/// Returns an error when the worktree path is the repository root itself
+pub fn validate_worktree_location(
+    worktree_path: &std::path::Path,
+    repo_root: &std::path::Path,
+) -> Result<WorktreeLocation> {
+    let worktree = canonicalize_allow_missing(worktree_path);
+    let root = canonicalize_allow_missing(repo_root);
+    if worktree == root {
+        anyhow::bail!(
+            "Worktree path {} is the main repository itself; check worktree.dir",
+            worktree.display()
+        );
+    }
+    Ok(if worktree.starts_with(&root) {
+        WorktreeLocation::InsideRepo
+    } else {
+        WorktreeLocation::Outside
+    })
+}
+
+/// Derive the worktrees root directory from a directory template
+///
+/// The root is the template prefix before `{branch}`, expanded and resolved from
+/// `repo_root` like [`expand_path_template`]. Templates without `{branch}` use the
+/// parent of the expanded path.
+///
+/// # Examples
+///
+/// ```
+/// # use std::path::{Path, PathBuf};
+/// # use ofsht::domain::worktree::worktree_root_from_template;
+/// let root = worktree_root_from_template("../{repo}-worktrees/{branch}", Path::new("/src/app"));
+/// assert_eq!(root.unwrap(), PathBuf::from("/src/app/../app-worktrees/"));
+/// ```
+///
+/// # Errors
+/// Same conditions as [`expand_path_template`].
+pub fn worktree_root_from_template(
+    template: &str,
+    repo_root: &std::path::Path,
+) -> Result<std::path::PathBuf> {
+    if let Some((prefix, _)) = template.split_once("{branch}") {
+        return expand_path_template(prefix, repo_root, "");
+    }
+    let path = expand_path_template(template, repo_root, "")?;
+    Ok(path
+        .parent()
+        .map_or_else(|| path.clone(), std::path::Path::to_path_buf))
+}
+
 /// Calculate the depth from {branch} placeholder to the worktree root
 ///
 /// Returns the number of directory levels from the worktree root to where {branch} is located.
@@ -289,6 +609,96 @@ pub fn calculate_worktree_root_from_paths(
     Some(result)
 }
 
+/// Resolve the worktree root used to build relative worktree names
+///
+/// Prefers the root derived from the `worktree.dir` template (see
+/// [`worktree_root_from_template`]) when every worktree lives under it. This keeps
+/// names stable for absolute templates, nested branch names, and repositories with
+/// a single worktree, where the common-parent heuristic of
+/// [`calculate_worktree_root_from_paths`] collapses names to their last component.
+/// Falls back to that heuristic when the template cannot be expanded or some
+/// worktrees live elsewhere (e.g. created under a previous `worktree.dir`).
+#[must_use]
+pub fn resolve_worktree_root(
+    worktree_paths: &[std::path::PathBuf],
+    template: &str,
+    repo_root: &std::path::Path,
+) -> Option<std::path::PathBuf> {
+    let template_root = worktree_root_from_template(template, repo_root)
+        .ok()
+        .map(|root| canonicalize_allow_missing(&root))
+        .filter(|root| {
+            !worktree_paths.is_empty()
+                && worktree_paths
+                    .iter()
+                    .all(|path| path != root && path.starts_with(root))
+        });
+    template_root.or_else(|| calculate_worktree_root_from_paths(worktree_paths))
+}
+
+/// Worktrees root shared by every consumer of relative worktree names
+///
+/// Built once per command from the complete `git worktree list` (before any
+/// entries are hidden), so `ls`, fzf, completion and name resolution always
+/// derive the same relative path for a worktree.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorktreeLayout {
+    root: Option<std::path::PathBuf>,
+}
+
+impl WorktreeLayout {
+    /// Layout of `entries`, main worktree first as in `git worktree list`
+    ///
+    /// With a `worktree.dir` template the root derived from it is preferred (see
+    /// [`resolve_worktree_root`]); without one, or when some worktree lives
+    /// elsewhere, the common parent of the non-main worktrees is used.
+    #[must_use]
+    pub fn new(entries: &[WorktreeEntry], template: Option<&str>) -> Self {
+        let paths: Vec<std::path::PathBuf> = entries
+            .iter()
+            .skip(1)
+            .map(|entry| std::path::PathBuf::from(&entry.path))
+            .collect();
+        let root = match (template, entries.first()) {
+            (Some(template), Some(main)) => {
+                resolve_worktree_root(&paths, template, std::path::Path::new(&main.path))
+            }
+            _ => calculate_worktree_root_from_paths(&paths),
+        };
+        Self { root }
+    }
+
+    /// Layout of a parsed worktree list
+    #[must_use]
+    pub fn for_list(list: &WorktreeList, template: Option<&str>) -> Self {
+        Self::new(list.entries(), template)
+    }
+
+    /// The worktrees root, when the worktrees share one
+    #[must_use]
+    pub fn root(&self) -> Option<&std::path::Path> {
+        self.root.as_deref()
+    }
+
+    /// Path of `entry` relative to the root (`None` outside of it)
+    #[must_use]
+    pub fn rel_path(&self, entry: &WorktreeEntry) -> Option<String> {
+        self.root
+            .as_deref()
+            .and_then(|root| calculate_relative_path(std::path::Path::new(&entry.path), root))
+    }
+
+    /// Relative path of `entry`, or its directory name outside of the root
+    #[must_use]
+    pub fn display_name(&self, entry: &WorktreeEntry) -> String {
+        self.rel_path(entry).unwrap_or_else(|| {
+            std::path::Path::new(&entry.path)
+                .file_name()
+                .map_or_else(|| entry.path.clone(), |n| n.to_string_lossy().into_owned())
+        })
+    }
+}
+
 /// Calculate the relative path from worktree root to the worktree
 ///
 /// Returns None if the worktree path is not under the worktree root.
@@ -313,13 +723,132 @@ pub fn calculate_relative_path(
         .map(|p| p.display().to_string())
 }
 
+/// Total size in bytes of the regular files under `path`
+///
+/// Symlinks are neither followed nor counted, so files linked into a
+/// worktree by `link` hooks are not counted twice. Unreadable entries are
+/// skipped.
+#[cfg(feature = "cli")]
+#[must_use]
+pub fn directory_size(path: &std::path::Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// [`directory_size`] for many directories, one thread per path. Results
+/// follow the order of `paths`; `on_done` is called as each one finishes.
+#[cfg(feature = "cli")]
+pub fn directory_sizes(paths: &[std::path::PathBuf], on_done: impl Fn() + Sync) -> Vec<u64> {
+    let on_done = &on_done;
+    // Spawn every thread before joining any, otherwise they run one by one
+    #[allow(clippy::needless_collect)]
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = paths
+            .iter()
+            .map(|path| {
+                scope.spawn(move || {
+                    let size = directory_size(path);
+                    on_done();
+                    size
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap_or(0))
+            .collect()
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
 
     // --- Tests for relative path calculation utilities ---
 
+    #[test]
+    fn test_validate_dir_template_branch_last() {
+        assert!(validate_dir_template("../{repo}-worktrees/{branch}").is_ok());
+        assert!(validate_dir_template("~/wt/{repo}/{branch}/").is_ok());
+        assert!(validate_dir_template("../wt/pre-{branch}").is_ok());
+        assert!(validate_dir_template("../{repo}-worktrees").is_ok());
+    }
+
+    #[test]
+    fn test_validate_worktree_location_outside_and_inside() {
+        let repo = Path::new("/ofsht-location-test/app");
+        for (path, expected) in [
+            (
+                "/ofsht-location-test/app-worktrees/feat",
+                WorktreeLocation::Outside,
+            ),
+            ("/elsewhere/feat", WorktreeLocation::Outside),
+            (
+                "/ofsht-location-test/app/worktrees/feat",
+                WorktreeLocation::InsideRepo,
+            ),
+            (
+                "/ofsht-location-test/app/feat",
+                WorktreeLocation::InsideRepo,
+            ),
+            // `..` is resolved before comparing
+            (
+                "/ofsht-location-test/app/../app-wt/feat",
+                WorktreeLocation::Outside,
+            ),
+        ] {
+            assert_eq!(
+                validate_worktree_location(Path::new(path), repo).unwrap(),
+                expected,
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_worktree_location_boundaries() {
+        let repo = Path::new("/ofsht-location-test/app");
+        // A sibling sharing the name prefix is not inside
+        assert_eq!(
+            validate_worktree_location(Path::new("/ofsht-location-test/app2/feat"), repo).unwrap(),
+            WorktreeLocation::Outside
+        );
+        // The repository root itself is a hard error, however it is spelled
+        for path in [
+            "/ofsht-location-test/app",
+            "/ofsht-location-test/app/",
+            "/ofsht-location-test/app/feat/..",
+        ] {
+            let err = validate_worktree_location(Path::new(path), repo).unwrap_err();
+            assert!(
+                err.to_string().contains("is the main repository itself"),
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_dir_template_rejects_mid_path_branch() {
+        for template in [
+            "../{repo}-wt/{branch}/checkout",
+            "../wt/{branch}-{repo}",
+            "../wt/{branch}/{branch}",
+        ] {
+            let err = validate_dir_template(template).unwrap_err().to_string();
+            assert!(
+                err.contains("{branch} must be the last path component"),
+                "unexpected error for {template}: {err}"
+            );
+        }
+    }
+
     #[test]
     fn test_calculate_branch_depth_simple() {
         assert_eq!(calculate_branch_depth("../{repo}-worktrees/{branch}"), 1);
@@ -402,6 +931,127 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_resolve_worktree_root_single_nested_worktree() {
+        // The common-parent heuristic would return .../docs and name it "tweak"
+        let paths = vec![PathBuf::from("/ofsht-test/repo-worktrees/docs/tweak")];
+        let root = resolve_worktree_root(
+            &paths,
+            "../{repo}-worktrees/{branch}",
+            Path::new("/ofsht-test/repo"),
+        )
+        .unwrap();
+        assert_eq!(root, PathBuf::from("/ofsht-test/repo-worktrees"));
+        assert_eq!(
+            calculate_relative_path(&paths[0], &root),
+            Some("docs/tweak".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_worktree_root_absolute_template() {
+        let paths = vec![
+            PathBuf::from("/ofsht-data/worktrees/app/feat/login"),
+            PathBuf::from("/ofsht-data/worktrees/app/feat/signup"),
+        ];
+        let root = resolve_worktree_root(
+            &paths,
+            "/ofsht-data/worktrees/{repo}/{branch}",
+            Path::new("/src/app"),
+        )
+        .unwrap();
+        // The heuristic would stop at .../app/feat and drop the "feat/" prefix
+        assert_eq!(root, PathBuf::from("/ofsht-data/worktrees/app"));
+        assert_eq!(
+            calculate_relative_path(&paths[1], &root),
+            Some("feat/signup".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_worktree_root_falls_back_when_outside_template_root() {
+        let paths = vec![
+            PathBuf::from("/ofsht-old/app-worktrees/feature"),
+            PathBuf::from("/ofsht-old/app-worktrees/fix"),
+        ];
+        let root = resolve_worktree_root(
+            &paths,
+            "/ofsht-data/worktrees/{repo}/{branch}",
+            Path::new("/src/app"),
+        );
+        assert_eq!(root, Some(PathBuf::from("/ofsht-old/app-worktrees")));
+    }
+
+    #[test]
+    fn test_resolve_worktree_root_no_worktrees() {
+        let root =
+            resolve_worktree_root(&[], "../{repo}-worktrees/{branch}", Path::new("/src/app"));
+        assert_eq!(root, None);
+    }
+
+    fn entry(path: &str, hash: &str) -> WorktreeEntry {
+        WorktreeEntry {
+            path: path.to_string(),
+            branch: Some(path.trim_start_matches('/').to_string()),
+            hash: Some(hash.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_diff_entries_added_removed_changed_same() {
+        let previous = vec![
+            entry("/main", "aaaa"),
+            entry("/gone", "bbbb"),
+            entry("/moved", "cccc"),
+        ];
+        let current = vec![
+            entry("/main", "aaaa"),
+            entry("/moved", "dddd"),
+            entry("/new", "eeee"),
+        ];
+
+        let diff: Vec<(EntryChange, String)> = diff_entries(&previous, &current)
+            .into_iter()
+            .map(|(change, entry)| (change, entry.path))
+            .collect();
+
+        assert_eq!(
+            diff,
+            [
+                (EntryChange::Unchanged, "/main".to_string()),
+                (EntryChange::Changed, "/moved".to_string()),
+                (EntryChange::Added, "/new".to_string()),
+                (EntryChange::Removed, "/gone".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_entries_removed_keeps_snapshot_data() {
+        let previous = vec![entry("/main", "aaaa"), entry("/gone", "bbbb")];
+        let current = vec![entry("/main", "aaaa")];
+
+        let diff = diff_entries(&previous, &current);
+        assert_eq!(diff[1], (EntryChange::Removed, entry("/gone", "bbbb")));
+    }
+
+    #[test]
+    fn test_diff_entries_identical_lists_are_unchanged() {
+        let entries = vec![entry("/main", "aaaa"), entry("/feature", "bbbb")];
+        assert!(diff_entries(&entries, &entries)
+            .iter()
+            .all(|(change, _)| *change == EntryChange::Unchanged));
+    }
+
+    #[test]
+    fn test_entry_change_markers() {
+        assert_eq!(EntryChange::Added.marker(), '+');
+        assert_eq!(EntryChange::Removed.marker(), '-');
+        assert_eq!(EntryChange::Changed.marker(), '~');
+        assert_eq!(EntryChange::Unchanged.marker(), ' ');
+    }
+
     // -----------------------------------------------------------------
     // WorktreeList tests (Step 2: porcelain parser unification)
     // -----------------------------------------------------------------
@@ -607,6 +1257,123 @@ mod tests {
         assert!(list.find_by_path(std::path::Path::new("/repo")).is_none());
     }
 
+    /// Branches whose names overlap: `feature`, `feature-2`, `other` checked out in a
+    /// directory called `feature`, and nested `feat/feature`
+    fn ambiguous_list() -> WorktreeList {
+        let output = "worktree /nonexistent/repo\nHEAD a\nbranch refs/heads/main\n\n\
+                      worktree /nonexistent/wt/feature-main\nHEAD b\nbranch refs/heads/feature\n\n\
+                      worktree /nonexistent/wt/feature-2\nHEAD c\nbranch refs/heads/feature-2\n\n\
+                      worktree /nonexistent/wt/feature\nHEAD d\nbranch refs/heads/other\n\n\
+                      worktree /nonexistent/wt/feat/feature\nHEAD e\nbranch refs/heads/feat/feature\n\n";
+        WorktreeList::parse(output, None)
+    }
+
+    fn resolved_path<'a>(list: &'a WorktreeList, name: &str) -> Option<&'a str> {
+        list.find_by_name(name, Some(Path::new("/nonexistent/wt")))
+            .map(|entry| entry.path.as_str())
+    }
+
+    #[test]
+    fn test_worktree_list_find_by_name_exact_branch_wins() {
+        let list = ambiguous_list();
+        // The branch wins over the directory of the same name
+        assert_eq!(
+            resolved_path(&list, "feature"),
+            Some("/nonexistent/wt/feature-main")
+        );
+        assert_eq!(
+            resolved_path(&list, "feature-2"),
+            Some("/nonexistent/wt/feature-2")
+        );
+        assert_eq!(
+            resolved_path(&list, "feat/feature"),
+            Some("/nonexistent/wt/feat/feature")
+        );
+        assert_eq!(
+            resolved_path(&list, "other"),
+            Some("/nonexistent/wt/feature")
+        );
+    }
+
+    #[test]
+    fn test_worktree_list_find_by_name_relative_and_absolute_paths() {
+        let list = ambiguous_list();
+        assert_eq!(
+            resolved_path(&list, "feature-main"),
+            Some("/nonexistent/wt/feature-main")
+        );
+        assert_eq!(
+            resolved_path(&list, "/nonexistent/wt/feature"),
+            Some("/nonexistent/wt/feature")
+        );
+    }
+
+    #[test]
+    fn test_worktree_list_find_by_name_never_prefix_matches() {
+        let list = ambiguous_list();
+        for name in ["feat", "feature-", "featur", "2", "feat/", "wt/feature"] {
+            assert_eq!(resolved_path(&list, name), None, "{name}");
+        }
+        // `..` cannot walk out of the worktree root into a sibling match
+        assert_eq!(resolved_path(&list, "feat/../feature-2"), None);
+        assert_eq!(resolved_path(&list, ""), None);
+    }
+
+    fn matched_paths<'a>(list: &'a WorktreeList, query: &str) -> Vec<&'a str> {
+        match list.match_name(query, Some(Path::new("/nonexistent/wt"))) {
+            NameMatch::Unique(entry) => vec![entry.path.as_str()],
+            NameMatch::Ambiguous(entries) => entries.iter().map(|e| e.path.as_str()).collect(),
+            NameMatch::NotFound => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_worktree_list_match_name_exact_before_substring() {
+        let list = ambiguous_list();
+        assert!(matches!(
+            list.match_name("feature", Some(Path::new("/nonexistent/wt"))),
+            NameMatch::Unique(entry) if entry.path == "/nonexistent/wt/feature-main"
+        ));
+    }
+
+    #[test]
+    fn test_worktree_list_match_name_unique_substring() {
+        let list = ambiguous_list();
+        // Case-insensitive, over branch names ("other") and relative paths ("feature-main")
+        assert_eq!(matched_paths(&list, "OTH"), vec!["/nonexistent/wt/feature"]);
+        assert_eq!(
+            matched_paths(&list, "-main"),
+            vec!["/nonexistent/wt/feature-main"]
+        );
+    }
+
+    #[test]
+    fn test_worktree_list_match_name_ambiguous() {
+        let list = ambiguous_list();
+        assert!(matches!(
+            list.match_name("feat", Some(Path::new("/nonexistent/wt"))),
+            NameMatch::Ambiguous(entries) if entries.len() == 4
+        ));
+        assert_eq!(
+            matched_paths(&list, "ture-"),
+            vec!["/nonexistent/wt/feature-main", "/nonexistent/wt/feature-2"]
+        );
+    }
+
+    #[test]
+    fn test_worktree_list_match_name_not_found() {
+        let list = ambiguous_list();
+        assert_eq!(
+            list.match_name("nothing", Some(Path::new("/nonexistent/wt"))),
+            NameMatch::NotFound
+        );
+        assert_eq!(list.match_name("", None), NameMatch::NotFound);
+        // The main worktree is never a fuzzy candidate
+        assert_eq!(list.match_name("main", None), NameMatch::NotFound);
+        // Without a worktree root only branch names are searched
+        assert_eq!(list.match_name("-main", None), NameMatch::NotFound);
+    }
+
     #[test]
     fn test_worktree_list_current_with_active_path_canonicalize() {
         let tmp_canonical = std::fs::canonicalize("/tmp").unwrap();
@@ -630,6 +1397,72 @@ mod tests {
         assert_eq!(cur.unwrap().path, "/nonexistent/wt");
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_worktree_list_active_path_through_symlinked_ancestor() {
+        let tmp = tempfile::tempdir().unwrap();
+        let real = tmp.path().canonicalize().unwrap().join("real");
+        std::fs::create_dir_all(real.join("wt")).unwrap();
+        let link = tmp.path().join("link");
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+
+        // Listed through the symlink, active through the real path (and vice versa),
+        // including a tail that does not exist on disk
+        for (listed, active) in [
+            (link.join("wt"), real.join("wt/src")),
+            (real.join("wt"), link.join("wt/src")),
+            (link.join("gone"), real.join("gone/src")),
+        ] {
+            let output = format!(
+                "worktree /repo\nHEAD abc12345xxxxxx\nbranch refs/heads/main\n\n\
+                 worktree {}\nHEAD def67890xxxxxx\nbranch refs/heads/feat\n\n",
+                listed.display()
+            );
+            let list = WorktreeList::parse(&output, Some(&active));
+            assert_eq!(
+                list.current().map(|e| e.branch.as_deref()),
+                Some(Some("feat")),
+                "{} should contain {}",
+                listed.display(),
+                active.display()
+            );
+        }
+    }
+
+    #[test]
+    fn test_worktree_list_active_path_in_subdirectory() {
+        let output = "worktree /repo\nHEAD abc12345xxxxxx\nbranch refs/heads/main\n\nworktree /nonexistent/wt\nHEAD def67890xxxxxx\nbranch refs/heads/feat\n\n";
+        let list = WorktreeList::parse(
+            output,
+            Some(std::path::Path::new("/nonexistent/wt/src/deep/dir")),
+        );
+        assert_eq!(list.current().unwrap().path, "/nonexistent/wt");
+        assert_eq!(list.entries().iter().filter(|e| e.is_active).count(), 1);
+
+        // A sibling sharing the name prefix is not an ancestor
+        let list = WorktreeList::parse(output, Some(std::path::Path::new("/nonexistent/wt-2")));
+        assert!(list.current().is_none());
+    }
+
+    #[test]
+    fn test_worktree_list_active_path_nested_worktrees_pick_deepest() {
+        // Worktrees living inside the main worktree (e.g. `.worktrees/feat`)
+        let output = "worktree /nonexistent/repo\nHEAD abc12345xxxxxx\nbranch refs/heads/main\n\nworktree /nonexistent/repo/.worktrees/feat\nHEAD def67890xxxxxx\nbranch refs/heads/feat\n\n";
+
+        let list = WorktreeList::parse(
+            output,
+            Some(std::path::Path::new(
+                "/nonexistent/repo/.worktrees/feat/src",
+            )),
+        );
+        let active: Vec<_> = list.entries().iter().filter(|e| e.is_active).collect();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].branch.as_deref(), Some("feat"));
+
+        let list = WorktreeList::parse(output, Some(std::path::Path::new("/nonexistent/repo/src")));
+        assert_eq!(list.current().unwrap().branch.as_deref(), Some("main"));
+    }
+
     #[test]
     fn test_worktree_list_current_no_active_returns_none() {
         let output = "worktree /repo\nHEAD abc12345xxxxxx\nbranch refs/heads/main\n\nworktree /wt-a\nHEAD def67890xxxxxx\nbranch refs/heads/feature-a\n\n";
@@ -651,4 +1484,78 @@ mod tests {
         let list = WorktreeList::parse(output, None);
         assert_eq!(list.entries()[0].hash, None);
     }
+
+    #[test]
+    fn test_worktree_list_parse_prunable() {
+        let output = "worktree /repo\nHEAD abc12345xxxxxx\nbranch refs/heads/main\n\n\
+                      worktree /gone\nHEAD def67890xxxxxx\nbranch refs/heads/gone\n\
+                      prunable gitdir file points to non-existent location\n\n\
+                      worktree /bare\nHEAD 0123456789xxxx\ndetached\nprunable\n\n";
+        let list = WorktreeList::parse(output, None);
+        assert!(!list.entries()[0].prunable);
+        assert!(list.entries()[1].prunable);
+        assert_eq!(list.entries()[1].branch.as_deref(), Some("gone"));
+        assert!(list.entries()[2].prunable);
+    }
+
+    #[test]
+    fn test_worktree_list_parse_locked_without_reason() {
+        let output = "worktree /repo\nHEAD abc12345xxxxxx\nbranch refs/heads/main\n\nworktree /wt\nHEAD def67890xxxxxx\nbranch refs/heads/feat\nlocked\n\n";
+        let list = WorktreeList::parse(output, None);
+        assert!(!list.entries()[0].locked);
+        assert!(list.entries()[1].locked);
+        assert_eq!(list.entries()[1].lock_reason, None);
+    }
+
+    #[test]
+    fn test_worktree_list_parse_locked_with_reason() {
+        let output = "worktree /repo\nHEAD abc12345xxxxxx\nbranch refs/heads/main\n\nworktree /wt\nHEAD def67890xxxxxx\nbranch refs/heads/feat\nlocked on external drive\n\n";
+        let list = WorktreeList::parse(output, None);
+        assert!(list.entries()[1].locked);
+        assert_eq!(
+            list.entries()[1].lock_reason.as_deref(),
+            Some("on external drive")
+        );
+    }
+
+    #[cfg(all(unix, feature = "cli"))]
+    #[test]
+    fn test_directory_size_skips_symlinks() {
+        let tmp = tempfile::tempdir().unwrap();
+        let outside = tmp.path().join("outside");
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(outside.join("big.bin"), vec![0u8; 4096]).unwrap();
+
+        let worktree = tmp.path().join("worktree");
+        std::fs::create_dir_all(worktree.join("src/nested")).unwrap();
+        std::fs::write(worktree.join("a.txt"), "12345").unwrap();
+        std::fs::write(worktree.join("src/nested/b.txt"), "123").unwrap();
+        std::os::unix::fs::symlink(outside.join("big.bin"), worktree.join("linked.bin")).unwrap();
+        std::os::unix::fs::symlink(&outside, worktree.join("linked_dir")).unwrap();
+
+        assert_eq!(directory_size(&worktree), 8);
+        assert_eq!(directory_size(&tmp.path().join("missing")), 0);
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn test_directory_sizes_keeps_order() {
+        let tmp = tempfile::tempdir().unwrap();
+        let paths: Vec<_> = [3usize, 0, 7]
+            .iter()
+            .enumerate()
+            .map(|(i, len)| {
+                let dir = tmp.path().join(i.to_string());
+                std::fs::create_dir_all(&dir).unwrap();
+                std::fs::write(dir.join("f"), "x".repeat(*len)).unwrap();
+                dir
+            })
+            .collect();
+        let done = std::sync::atomic::AtomicUsize::new(0);
+        let sizes = directory_sizes(&paths, || {
+            done.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        });
+        assert_eq!(sizes, [3, 0, 7]);
+        assert_eq!(done.into_inner(), 3);
+    }
 }
@@ -2,12 +2,110 @@
 use anyhow::Result;
 use indicatif::MultiProgress;
 use std::path::Path;
+use std::time::{Duration, Instant};
 
-use super::output::emit_line;
+use super::output::emit_warning;
 use super::{files, runner, symlink};
 use crate::color;
 use crate::config::HookActions;
 
+/// Kind of a hook action, for [`HookSummary::timings`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookActionKind {
+    Run,
+    Copy,
+    Link,
+}
+
+/// What a hook run did: successful actions plus the errors of failed ones
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HookSummary {
+    /// Number of `run` commands that exited successfully
+    pub commands_run: usize,
+    /// Relative paths copied into the worktree
+    pub copied: Vec<String>,
+    /// Relative paths linked into the worktree
+    pub linked: Vec<String>,
+    /// Relative paths the worktree already had (up to date, or kept by
+    /// `copy_on_conflict = "skip"`), so re-running hooks leaves them alone
+    pub skipped: Vec<String>,
+    /// Error messages of failed actions, in execution order
+    pub errors: Vec<String>,
+    /// How long each executed action (failed ones included) took, in execution order
+    pub timings: Vec<(HookActionKind, Duration)>,
+}
+
+impl HookSummary {
+    /// Whether any hook action failed
+    #[must_use]
+    pub const fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    /// Short description of the performed actions, e.g.
+    /// `2 copied, 1 linked, 1 skipped, 3 commands run` (`None` when nothing was done)
+    #[must_use]
+    pub fn describe(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if !self.copied.is_empty() {
+            parts.push(format!("{} copied", self.copied.len()));
+        }
+        if !self.linked.is_empty() {
+            parts.push(format!("{} linked", self.linked.len()));
+        }
+        if !self.skipped.is_empty() {
+            parts.push(format!("{} skipped", self.skipped.len()));
+        }
+        match self.commands_run {
+            0 => {}
+            1 => parts.push("1 command run".to_string()),
+            n => parts.push(format!("{n} commands run")),
+        }
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+
+    /// Total time spent in hook actions
+    #[must_use]
+    pub fn total_duration(&self) -> Duration {
+        self.timings.iter().map(|(_, elapsed)| *elapsed).sum()
+    }
+
+    /// One-line timing report, e.g. `Hooks completed in 42.3s (3 run, 2 copy, 1 link)`
+    /// (`None` when no action was executed)
+    #[must_use]
+    pub fn timing_summary(&self) -> Option<String> {
+        if self.timings.is_empty() {
+            return None;
+        }
+        let count = |kind| self.timings.iter().filter(|(k, _)| *k == kind).count();
+        let parts: Vec<String> = [
+            (HookActionKind::Run, "run"),
+            (HookActionKind::Copy, "copy"),
+            (HookActionKind::Link, "link"),
+        ]
+        .into_iter()
+        .filter_map(|(kind, label)| {
+            let n = count(kind);
+            (n > 0).then(|| format!("{n} {label}"))
+        })
+        .collect();
+        Some(format!(
+            "Hooks completed in {:.1}s ({})",
+            self.total_duration().as_secs_f64(),
+            parts.join(", ")
+        ))
+    }
+
+    /// Turn collected errors into an `Err`, joined with "; "
+    fn into_result(self) -> Result<Self> {
+        if self.errors.is_empty() {
+            Ok(self)
+        } else {
+            anyhow::bail!("{}", self.errors.join("; "))
+        }
+    }
+}
+
 /// Execute hook actions in the specified directory
 ///
 /// Returns `Err` if any hook action fails, after executing all actions.
@@ -19,9 +117,17 @@ pub(super) fn execute_hooks(
     source_path: &Path,
     color_mode: color::ColorMode,
     indent: &str,
-) -> Result<()> {
+) -> Result<HookSummary> {
     let mp = MultiProgress::new();
-    execute_hooks_with_mp(actions, worktree_path, source_path, color_mode, indent, &mp)
+    execute_hooks_with_mp(
+        actions,
+        worktree_path,
+        source_path,
+        color_mode,
+        false,
+        indent,
+        &mp,
+    )
 }
 
 /// Execute hook actions with a shared `MultiProgress`.
@@ -33,15 +139,49 @@ pub fn execute_hooks_with_mp(
     worktree_path: &Path,
     source_path: &Path,
     color_mode: color::ColorMode,
+    raw_output: bool,
     indent: &str,
     mp: &MultiProgress,
-) -> Result<()> {
-    let errors = execute_hooks_impl(actions, worktree_path, source_path, color_mode, indent, mp);
-    if errors.is_empty() {
-        Ok(())
-    } else {
-        anyhow::bail!("{}", errors.join("; "))
-    }
+) -> Result<HookSummary> {
+    execute_hooks_impl(
+        actions,
+        worktree_path,
+        source_path,
+        &[],
+        color_mode,
+        raw_output,
+        indent,
+        mp,
+    )
+    .into_result()
+}
+
+/// Execute hook actions with a shared `MultiProgress`, returning failures in the
+/// summary instead of as `Err` so the caller can decide how to report them.
+///
+/// `env` is added to the environment of every `run` command.
+#[must_use]
+#[allow(clippy::too_many_arguments)]
+pub fn execute_hooks_collect_with_mp(
+    actions: &HookActions,
+    worktree_path: &Path,
+    source_path: &Path,
+    env: &[(&str, String)],
+    color_mode: color::ColorMode,
+    raw_output: bool,
+    indent: &str,
+    mp: &MultiProgress,
+) -> HookSummary {
+    execute_hooks_impl(
+        actions,
+        worktree_path,
+        source_path,
+        env,
+        color_mode,
+        raw_output,
+        indent,
+        mp,
+    )
 }
 
 /// Execute hook actions, printing warnings on failure but never returning Err.
@@ -55,27 +195,95 @@ pub(super) fn execute_hooks_lenient(
     source_path: &Path,
     color_mode: color::ColorMode,
     indent: &str,
-) {
+) -> HookSummary {
     let mp = MultiProgress::new();
-    execute_hooks_lenient_with_mp(actions, worktree_path, source_path, color_mode, indent, &mp);
+    execute_hooks_lenient_with_mp(
+        actions,
+        worktree_path,
+        source_path,
+        color_mode,
+        false,
+        indent,
+        &mp,
+    )
 }
 
 /// Execute hook actions leniently with a shared `MultiProgress`.
 ///
 /// Use this variant when the caller manages its own header spinner
 /// in the same `MultiProgress`, ensuring correct bar ordering.
+/// Failures are already reported, so callers may ignore the returned summary.
+#[allow(clippy::must_use_candidate)]
 pub fn execute_hooks_lenient_with_mp(
     actions: &HookActions,
     worktree_path: &Path,
     source_path: &Path,
     color_mode: color::ColorMode,
+    raw_output: bool,
+    indent: &str,
+    mp: &MultiProgress,
+) -> HookSummary {
+    let is_tty = color_mode.should_colorize();
+    let summary = execute_hooks_impl(
+        actions,
+        worktree_path,
+        source_path,
+        &[],
+        color_mode,
+        raw_output,
+        indent,
+        mp,
+    );
+    for err in &summary.errors {
+        emit_warning(
+            mp,
+            is_tty,
+            format!(
+                "{indent}{}",
+                color::warn(color_mode, format!("Hook error: {err}"))
+            ),
+        );
+    }
+    summary
+}
+
+/// Run only the `run` commands of `actions` in `working_dir` with extra
+/// environment variables, warning on failure like
+/// [`execute_hooks_lenient_with_mp`].
+///
+/// Used for delete hooks of worktrees whose directory is already gone, where
+/// copy and link actions have nothing to act on.
+#[allow(clippy::must_use_candidate)]
+pub fn execute_run_hooks_lenient_with_mp(
+    actions: &HookActions,
+    working_dir: &Path,
+    env: &[(&str, String)],
+    color_mode: color::ColorMode,
+    raw_output: bool,
     indent: &str,
     mp: &MultiProgress,
-) {
+) -> HookSummary {
     let is_tty = color_mode.should_colorize();
-    let errors = execute_hooks_impl(actions, worktree_path, source_path, color_mode, indent, mp);
-    for err in &errors {
-        emit_line(
+    let mut summary = HookSummary::default();
+    for cmd in &actions.run {
+        let start = Instant::now();
+        let result = runner::execute_command_with_env(
+            cmd,
+            working_dir,
+            env,
+            color_mode,
+            raw_output,
+            indent,
+            mp,
+        );
+        summary.timings.push((HookActionKind::Run, start.elapsed()));
+        match result {
+            Ok(()) => summary.commands_run += 1,
+            Err(e) => summary.errors.push(e.to_string()),
+        }
+    }
+    for err in &summary.errors {
+        emit_warning(
             mp,
             is_tty,
             format!(
@@ -84,48 +292,73 @@ pub fn execute_hooks_lenient_with_mp(
             ),
         );
     }
+    summary
 }
 
 /// Execute hook actions in the specified directory (internal implementation)
 ///
 /// Executes all hook actions regardless of individual failures, collecting
-/// error messages into a `Vec<String>`.
+/// what was done and the error messages into a [`HookSummary`].
+#[allow(clippy::too_many_arguments)]
 pub(super) fn execute_hooks_impl(
     actions: &HookActions,
     worktree_path: &Path,
     source_path: &Path,
+    env: &[(&str, String)],
     color_mode: color::ColorMode,
+    raw_output: bool,
     indent: &str,
     mp: &MultiProgress,
-) -> Vec<String> {
+) -> HookSummary {
     let total_actions = actions.run.len() + actions.copy.len() + actions.link.len();
     let mut action_index = 0;
-    let mut errors = Vec::new();
+    let mut summary = HookSummary::default();
 
     // Execute commands
     for cmd in &actions.run {
         action_index += 1;
-        let is_last = action_index == total_actions;
-        if let Err(e) = runner::execute_command(cmd, worktree_path, color_mode, is_last, indent, mp)
-        {
-            errors.push(e.to_string());
+        let start = Instant::now();
+        let result = runner::execute_command_with_env(
+            cmd,
+            worktree_path,
+            env,
+            color_mode,
+            raw_output,
+            indent,
+            mp,
+        );
+        summary.timings.push((HookActionKind::Run, start.elapsed()));
+        match result {
+            Ok(()) => summary.commands_run += 1,
+            Err(e) => summary.errors.push(e.to_string()),
         }
     }
 
     // Copy files from source to worktree
     for pattern in &actions.copy {
         action_index += 1;
-        let is_last = action_index == total_actions;
-        if let Err(e) = files::copy_files(
+        let start = Instant::now();
+        let result = files::copy_files(
             pattern,
             source_path,
             worktree_path,
             color_mode,
-            is_last,
+            files::CopyOptions {
+                on_conflict: actions.copy_on_conflict,
+                compare_content: actions.copy_compare_content,
+            },
             indent,
             mp,
-        ) {
-            errors.push(e.to_string());
+        );
+        summary
+            .timings
+            .push((HookActionKind::Copy, start.elapsed()));
+        match result {
+            Ok(outcome) => {
+                summary.copied.extend(outcome.applied);
+                summary.skipped.extend(outcome.skipped);
+            }
+            Err(e) => summary.errors.push(e.to_string()),
         }
     }
 
@@ -133,7 +366,8 @@ pub(super) fn execute_hooks_impl(
     for pattern in &actions.link {
         action_index += 1;
         let is_last = action_index == total_actions;
-        if let Err(e) = symlink::create_symlinks(
+        let start = Instant::now();
+        let result = symlink::create_symlinks(
             pattern,
             source_path,
             worktree_path,
@@ -141,18 +375,151 @@ pub(super) fn execute_hooks_impl(
             is_last,
             indent,
             mp,
-        ) {
-            errors.push(e.to_string());
+        );
+        summary
+            .timings
+            .push((HookActionKind::Link, start.elapsed()));
+        match result {
+            Ok(outcome) => {
+                summary.linked.extend(outcome.applied);
+                summary.skipped.extend(outcome.skipped);
+            }
+            Err(e) => summary.errors.push(e.to_string()),
         }
     }
 
-    errors
+    summary
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_execute_run_hooks_only_runs_commands_with_env() {
+        let tmp = tempfile::tempdir().unwrap();
+        let actions = HookActions {
+            run: vec![
+                "printf '%s' \"$OFSHT_TEST_PATH\" > marker".into(),
+                "false".into(),
+            ],
+            copy: vec!["missing".to_string()],
+            link: vec!["missing".to_string()],
+            ..Default::default()
+        };
+
+        let summary = execute_run_hooks_lenient_with_mp(
+            &actions,
+            tmp.path(),
+            &[("OFSHT_TEST_PATH", "/gone".to_string())],
+            color::ColorMode::Never,
+            false,
+            "  ",
+            &MultiProgress::new(),
+        );
+
+        assert_eq!(summary.commands_run, 1);
+        assert_eq!(summary.errors.len(), 1);
+        assert!(summary.copied.is_empty() && summary.linked.is_empty());
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join("marker")).unwrap(),
+            "/gone"
+        );
+    }
+
+    #[test]
+    fn test_execute_hooks_summary_counts_match_actions() {
+        let tmp = std::env::temp_dir().join("test_hooks_summary");
+        let src = tmp.join("src");
+        let dst = tmp.join("dst");
+        std::fs::create_dir_all(src.join("shared")).unwrap();
+        std::fs::create_dir_all(&dst).unwrap();
+        std::fs::write(src.join(".env"), "A=1").unwrap();
+        std::fs::write(src.join(".env.local"), "B=2").unwrap();
+
+        let actions = HookActions {
+            run: vec!["true".into(), "echo hi".into(), "true".into()],
+            copy: vec![".env*".to_string()],
+            link: vec!["shared".to_string()],
+            ..Default::default()
+        };
+
+        let summary = execute_hooks(&actions, &dst, &src, color::ColorMode::Never, "  ").unwrap();
+
+        assert_eq!(summary.commands_run, 3);
+        let mut copied = summary.copied.clone();
+        copied.sort();
+        assert_eq!(copied, [".env", ".env.local"]);
+        assert_eq!(summary.linked, ["shared"]);
+        assert!(!summary.has_errors());
+        assert_eq!(
+            summary.describe().as_deref(),
+            Some("2 copied, 1 linked, 3 commands run")
+        );
+
+        // Running the same hooks again over the same worktree changes nothing
+        let actions = HookActions {
+            run: Vec::new(),
+            ..actions
+        };
+        let summary = execute_hooks(&actions, &dst, &src, color::ColorMode::Never, "  ").unwrap();
+        assert!(summary.copied.is_empty() && summary.linked.is_empty());
+        assert_eq!(summary.skipped.len(), 3);
+        assert_eq!(summary.describe().as_deref(), Some("3 skipped"));
+
+        std::fs::remove_dir_all(&tmp).ok();
+    }
+
+    #[test]
+    fn test_hook_summary_describe() {
+        assert_eq!(HookSummary::default().describe(), None);
+        let summary = HookSummary {
+            commands_run: 1,
+            errors: vec!["boom".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(summary.describe().as_deref(), Some("1 command run"));
+    }
+
+    #[test]
+    fn test_hook_summary_timing_summary() {
+        assert_eq!(HookSummary::default().timing_summary(), None);
+        let summary = HookSummary {
+            timings: vec![
+                (HookActionKind::Run, Duration::from_secs(40)),
+                (HookActionKind::Run, Duration::from_secs(2)),
+                (HookActionKind::Copy, Duration::from_millis(250)),
+                (HookActionKind::Link, Duration::from_millis(50)),
+                (HookActionKind::Run, Duration::ZERO),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(summary.total_duration(), Duration::from_millis(42_300));
+        assert_eq!(
+            summary.timing_summary().as_deref(),
+            Some("Hooks completed in 42.3s (3 run, 1 copy, 1 link)")
+        );
+    }
+
+    #[test]
+    fn test_execute_hooks_records_timings() {
+        let tmp = tempfile::tempdir().unwrap();
+        let actions = HookActions {
+            run: vec!["true".into(), "false".into()],
+            ..Default::default()
+        };
+        let summary = execute_hooks_lenient(
+            &actions,
+            tmp.path(),
+            tmp.path(),
+            color::ColorMode::Never,
+            "  ",
+        );
+        let kinds: Vec<_> = summary.timings.iter().map(|(kind, _)| *kind).collect();
+        assert_eq!(kinds, vec![HookActionKind::Run, HookActionKind::Run]);
+        assert!(summary.timing_summary().unwrap().ends_with("(2 run)"));
+    }
+
     #[test]
     fn test_execute_hooks_empty() {
         let actions = HookActions::default();
@@ -175,19 +542,26 @@ mod tests {
         // Create a marker file to prove the second command ran
         let marker = tmp.join("second_ran");
         let actions = HookActions {
-            run: vec!["exit 1".to_string(), format!("touch {}", marker.display())],
+            run: vec![
+                "exit 1".into(),
+                format!("touch {}", marker.display()).into(),
+            ],
             copy: vec![],
             link: vec![],
+            ..Default::default()
         };
 
         let errors = execute_hooks_impl(
             &actions,
             &tmp,
             &tmp,
+            &[],
             color::ColorMode::Never,
+            false,
             "  ",
             &MultiProgress::new(),
-        );
+        )
+        .errors;
 
         // First command should have failed
         assert_eq!(errors.len(), 1);
@@ -205,19 +579,23 @@ mod tests {
         std::fs::create_dir_all(&tmp).unwrap();
 
         let actions = HookActions {
-            run: vec!["exit 1".to_string(), "exit 2".to_string()],
+            run: vec!["exit 1".into(), "exit 2".into()],
             copy: vec![],
             link: vec![],
+            ..Default::default()
         };
 
         let errors = execute_hooks_impl(
             &actions,
             &tmp,
             &tmp,
+            &[],
             color::ColorMode::Never,
+            false,
             "  ",
             &MultiProgress::new(),
-        );
+        )
+        .errors;
 
         // Both commands should have failed
         assert_eq!(errors.len(), 2);
@@ -231,9 +609,10 @@ mod tests {
         std::fs::create_dir_all(&tmp).unwrap();
 
         let actions = HookActions {
-            run: vec!["exit 1".to_string()],
+            run: vec!["exit 1".into()],
             copy: vec![],
             link: vec![],
+            ..Default::default()
         };
 
         let result = execute_hooks(&actions, &tmp, &tmp, color::ColorMode::Never, "  ");
@@ -248,13 +627,15 @@ mod tests {
         std::fs::create_dir_all(&tmp).unwrap();
 
         let actions = HookActions {
-            run: vec!["exit 1".to_string()],
+            run: vec!["exit 1".into()],
             copy: vec![],
             link: vec![],
+            ..Default::default()
         };
 
-        // execute_hooks_lenient returns () — it should not panic
-        execute_hooks_lenient(&actions, &tmp, &tmp, color::ColorMode::Never, "  ");
+        // execute_hooks_lenient never returns Err — failures are only reported
+        let summary = execute_hooks_lenient(&actions, &tmp, &tmp, color::ColorMode::Never, "  ");
+        assert!(summary.has_errors());
 
         std::fs::remove_dir_all(&tmp).ok();
     }
@@ -5,8 +5,10 @@ use indicatif::MultiProgress;
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
-use super::output::emit_line;
+use super::output::{emit_line, emit_warning};
 use crate::color;
+use crate::config::CopyConflictPolicy;
+use crate::path_utils::backup_path;
 
 /// Pattern type for file matching
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -58,6 +60,11 @@ pub(super) fn expand_pattern(pattern: &str, base: &Path) -> Result<(PatternKind,
     Ok((kind, paths))
 }
 
+/// Count the paths a copy/link pattern currently matches under `base` (read-only)
+pub fn count_pattern_matches(pattern: &str, base: &Path) -> Result<usize> {
+    expand_pattern(pattern, base).map(|(_, paths)| paths.len())
+}
+
 /// Expand glob pattern to matching paths using walkdir
 fn expand_glob(globset: &GlobSet, base: &Path) -> Vec<PathBuf> {
     let mut matches = Vec::new();
@@ -79,22 +86,42 @@ fn expand_glob(globset: &GlobSet, base: &Path) -> Vec<PathBuf> {
     matches
 }
 
+/// How `copy` treats destinations that already exist
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) struct CopyOptions {
+    pub on_conflict: CopyConflictPolicy,
+    /// Compare file contents instead of size and modification time
+    pub compare_content: bool,
+}
+
+/// Relative paths handled by one copy or link pattern
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(super) struct PatternOutcome {
+    /// Paths written to the worktree
+    pub applied: Vec<String>,
+    /// Paths left untouched because the worktree already had them
+    pub skipped: Vec<String>,
+}
+
 /// Copy files for a pattern (supports glob)
+///
+/// Destinations that are already up to date are skipped, so re-running a
+/// copy action over a partially copied worktree only writes what differs.
 pub(super) fn copy_files(
     pattern: &str,
     source_path: &Path,
     dest_path: &Path,
     color_mode: color::ColorMode,
-    _is_last: bool,
+    options: CopyOptions,
     indent: &str,
     mp: &MultiProgress,
-) -> Result<()> {
+) -> Result<PatternOutcome> {
     let is_tty = color_mode.should_colorize();
     let (kind, paths) = expand_pattern(pattern, source_path)?;
 
     // If literal and not found, warn user
     if kind == PatternKind::Literal && paths.is_empty() {
-        emit_line(
+        emit_warning(
             mp,
             is_tty,
             format!(
@@ -108,15 +135,17 @@ pub(super) fn copy_files(
                 )
             ),
         );
-        return Ok(());
+        return Ok(PatternOutcome::default());
     }
 
     // Copy each matched path
+    let mut outcome = PatternOutcome::default();
     for src_path in paths {
         // Get relative path from source
         let rel_path = src_path
             .strip_prefix(source_path)
             .with_context(|| format!("Failed to get relative path for {}", src_path.display()))?;
+        let rel = rel_path.display().to_string();
 
         // Create same relative path in destination
         let dst_path = dest_path.join(rel_path);
@@ -128,33 +157,146 @@ pub(super) fn copy_files(
             })?;
         }
 
+        let mut note = String::new();
+        if dst_path.symlink_metadata().is_ok() {
+            // On a case-insensitive filesystem `README` and `readme` share one
+            // destination; keep the first instead of overwriting it
+            if let Some(first) = outcome
+                .applied
+                .iter()
+                .find(|done| done.eq_ignore_ascii_case(&rel) && **done != rel)
+            {
+                emit_warning(
+                    mp,
+                    is_tty,
+                    format!(
+                        "{indent}{}",
+                        color::warn(
+                            color_mode,
+                            format!("Skipped (same path as {first} on this filesystem): {rel}")
+                        )
+                    ),
+                );
+                outcome.skipped.push(rel);
+                continue;
+            }
+
+            if is_up_to_date(&src_path, &dst_path, options.compare_content) {
+                emit_skipped(mp, color_mode, indent, "up to date", &rel);
+                outcome.skipped.push(rel);
+                continue;
+            }
+
+            match options.on_conflict {
+                CopyConflictPolicy::Skip => {
+                    emit_skipped(mp, color_mode, indent, "exists", &rel);
+                    outcome.skipped.push(rel);
+                    continue;
+                }
+                CopyConflictPolicy::Backup => {
+                    let backup = backup_path(&dst_path, "bak", Path::exists);
+                    std::fs::rename(&dst_path, &backup).with_context(|| {
+                        format!(
+                            "Failed to back up {} to {}",
+                            dst_path.display(),
+                            backup.display()
+                        )
+                    })?;
+                    note = format!(" (previous file kept as {})", display_file_name(&backup));
+                }
+                CopyConflictPolicy::Overwrite => {}
+            }
+        }
+
         emit_line(
             mp,
             is_tty,
             format!(
                 "{indent}{}",
-                color::success(color_mode, format!("Copied: {}", rel_path.display()))
+                color::success(color_mode, format!("Copied: {rel}{note}"))
             ),
         );
 
         if src_path.is_dir() {
-            copy_dir_all(&src_path, &dst_path)?;
+            copy_dir_all(&src_path, &dst_path, options.compare_content)?;
         } else {
-            std::fs::copy(&src_path, &dst_path).with_context(|| {
-                format!(
-                    "Failed to copy {} to {}",
-                    src_path.display(),
-                    dst_path.display()
-                )
-            })?;
+            copy_file(&src_path, &dst_path)?;
         }
+        outcome.applied.push(rel);
     }
 
+    Ok(outcome)
+}
+
+/// `Skipped (<reason>): <path>`, dimmed
+fn emit_skipped(
+    mp: &MultiProgress,
+    color_mode: color::ColorMode,
+    indent: &str,
+    reason: &str,
+    rel: &str,
+) {
+    emit_line(
+        mp,
+        color_mode.should_colorize(),
+        format!(
+            "{indent}{}",
+            color::dim(color_mode, format!("Skipped ({reason}): {rel}"))
+        ),
+    );
+}
+
+/// Whether `dst` already matches `src`: same size and modification time (or
+/// same bytes with `compare_content`), recursively for directories. Files that
+/// only exist in `dst` are ignored.
+fn is_up_to_date(src: &Path, dst: &Path, compare_content: bool) -> bool {
+    let (Ok(src_meta), Ok(dst_meta)) = (std::fs::metadata(src), std::fs::metadata(dst)) else {
+        return false;
+    };
+    if src_meta.is_dir() {
+        return dst_meta.is_dir()
+            && std::fs::read_dir(src).is_ok_and(|entries| {
+                entries.filter_map(std::result::Result::ok).all(|entry| {
+                    is_up_to_date(&entry.path(), &dst.join(entry.file_name()), compare_content)
+                })
+            });
+    }
+    if !dst_meta.is_file() || src_meta.len() != dst_meta.len() {
+        return false;
+    }
+    if compare_content {
+        return matches!(
+            (std::fs::read(src), std::fs::read(dst)),
+            (Ok(a), Ok(b)) if a == b
+        );
+    }
+    matches!(
+        (src_meta.modified(), dst_meta.modified()),
+        (Ok(a), Ok(b)) if a == b
+    )
+}
+
+/// Copy a single file, carrying over its modification time so a later run
+/// can tell the copy is up to date
+fn copy_file(src: &Path, dst: &Path) -> Result<()> {
+    std::fs::copy(src, dst)
+        .with_context(|| format!("Failed to copy {} to {}", src.display(), dst.display()))?;
+    // Best effort: without it the file is only copied again next time
+    if let Ok(modified) = std::fs::metadata(src).and_then(|meta| meta.modified()) {
+        if let Ok(file) = std::fs::File::options().write(true).open(dst) {
+            let _ = file.set_modified(modified);
+        }
+    }
     Ok(())
 }
 
-/// Recursively copy a directory
-fn copy_dir_all(src: &Path, dst: &Path) -> Result<()> {
+fn display_file_name(path: &Path) -> String {
+    path.file_name()
+        .map_or_else(String::new, |n| n.to_string_lossy().into_owned())
+}
+
+/// Recursively copy a directory, skipping files that are already up to date
+fn copy_dir_all(src: &Path, dst: &Path, compare_content: bool) -> Result<()> {
     std::fs::create_dir_all(dst)
         .with_context(|| format!("Failed to create directory: {}", dst.display()))?;
 
@@ -166,15 +308,9 @@ fn copy_dir_all(src: &Path, dst: &Path) -> Result<()> {
         let dst_path = dst.join(entry.file_name());
 
         if src_path.is_dir() {
-            copy_dir_all(&src_path, &dst_path)?;
-        } else {
-            std::fs::copy(&src_path, &dst_path).with_context(|| {
-                format!(
-                    "Failed to copy {} to {}",
-                    src_path.display(),
-                    dst_path.display()
-                )
-            })?;
+            copy_dir_all(&src_path, &dst_path, compare_content)?;
+        } else if !is_up_to_date(&src_path, &dst_path, compare_content) {
+            copy_file(&src_path, &dst_path)?;
         }
     }
 
@@ -357,7 +493,7 @@ mod tests {
             &temp_dir,
             &temp_dir,
             color::ColorMode::Never,
-            false,
+            CopyOptions::default(),
             "  ",
             &MultiProgress::new(),
         );
@@ -380,7 +516,7 @@ mod tests {
             &src_dir,
             &dst_dir,
             color::ColorMode::Never,
-            false,
+            CopyOptions::default(),
             "  ",
             &MultiProgress::new(),
         );
@@ -393,4 +529,202 @@ mod tests {
         std::fs::remove_dir_all(&src_dir).ok();
         std::fs::remove_dir_all(&dst_dir).ok();
     }
+
+    fn copy_env_with_policy(name: &str, policy: CopyConflictPolicy) -> PathBuf {
+        let src_dir = std::env::temp_dir().join(format!("test_copy_conflict_{name}_src"));
+        let dst_dir = std::env::temp_dir().join(format!("test_copy_conflict_{name}_dst"));
+        std::fs::remove_dir_all(&dst_dir).ok();
+        std::fs::create_dir_all(&src_dir).unwrap();
+        std::fs::create_dir_all(&dst_dir).unwrap();
+        std::fs::write(src_dir.join(".env"), "from-repo").unwrap();
+        std::fs::write(dst_dir.join(".env"), "local-edit").unwrap();
+
+        copy_files(
+            ".env",
+            &src_dir,
+            &dst_dir,
+            color::ColorMode::Never,
+            CopyOptions {
+                on_conflict: policy,
+                compare_content: false,
+            },
+            "  ",
+            &MultiProgress::new(),
+        )
+        .unwrap();
+
+        std::fs::remove_dir_all(&src_dir).ok();
+        dst_dir
+    }
+
+    #[test]
+    fn test_copy_files_conflict_skip_keeps_existing() {
+        let dst_dir = copy_env_with_policy("skip", CopyConflictPolicy::Skip);
+        assert_eq!(
+            std::fs::read_to_string(dst_dir.join(".env")).unwrap(),
+            "local-edit"
+        );
+        assert!(!dst_dir.join(".env.bak").exists());
+        std::fs::remove_dir_all(&dst_dir).ok();
+    }
+
+    #[test]
+    fn test_copy_files_conflict_overwrite_replaces_existing() {
+        let dst_dir = copy_env_with_policy("overwrite", CopyConflictPolicy::Overwrite);
+        assert_eq!(
+            std::fs::read_to_string(dst_dir.join(".env")).unwrap(),
+            "from-repo"
+        );
+        assert!(!dst_dir.join(".env.bak").exists());
+        std::fs::remove_dir_all(&dst_dir).ok();
+    }
+
+    #[test]
+    fn test_copy_files_conflict_backup_renames_existing() {
+        let dst_dir = copy_env_with_policy("backup", CopyConflictPolicy::Backup);
+        assert_eq!(
+            std::fs::read_to_string(dst_dir.join(".env")).unwrap(),
+            "from-repo"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dst_dir.join(".env.bak")).unwrap(),
+            "local-edit"
+        );
+        std::fs::remove_dir_all(&dst_dir).ok();
+    }
+
+    #[test]
+    fn test_copy_files_conflict_backup_keeps_earlier_backups() {
+        let dst_dir = copy_env_with_policy("backup_twice", CopyConflictPolicy::Backup);
+        std::fs::write(dst_dir.join(".env"), "second-edit").unwrap();
+        std::fs::write(dst_dir.join(".env.bak.1"), "unrelated").unwrap();
+        let src_dir = std::env::temp_dir().join("test_copy_conflict_backup_twice_src2");
+        std::fs::create_dir_all(&src_dir).unwrap();
+        std::fs::write(src_dir.join(".env"), "from-repo").unwrap();
+
+        copy_files(
+            ".env",
+            &src_dir,
+            &dst_dir,
+            color::ColorMode::Never,
+            CopyOptions {
+                on_conflict: CopyConflictPolicy::Backup,
+                compare_content: false,
+            },
+            "  ",
+            &MultiProgress::new(),
+        )
+        .unwrap();
+
+        let read = |name: &str| std::fs::read_to_string(dst_dir.join(name)).unwrap();
+        assert_eq!(read(".env"), "from-repo");
+        assert_eq!(read(".env.bak"), "local-edit");
+        assert_eq!(read(".env.bak.1"), "unrelated");
+        assert_eq!(read(".env.bak.2"), "second-edit");
+        std::fs::remove_dir_all(&src_dir).ok();
+        std::fs::remove_dir_all(&dst_dir).ok();
+    }
+
+    #[test]
+    fn test_copy_files_rerun_is_noop() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        let dst = tmp.path().join("dst");
+        std::fs::create_dir_all(src.join("config/nested")).unwrap();
+        std::fs::create_dir_all(&dst).unwrap();
+        std::fs::write(src.join(".env"), "A=1").unwrap();
+        std::fs::write(src.join("config/a.toml"), "a").unwrap();
+        std::fs::write(src.join("config/nested/b.toml"), "b").unwrap();
+
+        for compare_content in [false, true] {
+            let options = CopyOptions {
+                on_conflict: CopyConflictPolicy::Backup,
+                compare_content,
+            };
+            let run = |pattern: &str| {
+                copy_files(
+                    pattern,
+                    &src,
+                    &dst,
+                    color::ColorMode::Never,
+                    options,
+                    "  ",
+                    &MultiProgress::new(),
+                )
+                .unwrap()
+            };
+
+            std::fs::remove_dir_all(&dst).ok();
+            assert_eq!(run(".env").applied, [".env"]);
+            assert_eq!(run("config").applied, ["config"]);
+
+            let second = run(".env");
+            assert!(second.applied.is_empty(), "{second:?}");
+            assert_eq!(second.skipped, [".env"]);
+            let second = run("config");
+            assert!(second.applied.is_empty(), "{second:?}");
+            assert_eq!(second.skipped, ["config"]);
+            // Nothing was backed up, since nothing was replaced
+            assert!(!dst.join(".env.bak").exists());
+            assert!(!dst.join("config.bak").exists());
+        }
+    }
+
+    #[test]
+    fn test_copy_files_partial_directory_copy_is_completed() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        let dst = tmp.path().join("dst");
+        std::fs::create_dir_all(src.join("config")).unwrap();
+        std::fs::create_dir_all(dst.join("config")).unwrap();
+        std::fs::write(src.join("config/a.toml"), "a").unwrap();
+        std::fs::write(src.join("config/b.toml"), "b").unwrap();
+        std::fs::write(dst.join("config/a.toml"), "stale").unwrap();
+
+        let outcome = copy_files(
+            "config",
+            &src,
+            &dst,
+            color::ColorMode::Never,
+            CopyOptions::default(),
+            "  ",
+            &MultiProgress::new(),
+        )
+        .unwrap();
+        assert_eq!(outcome.applied, ["config"]);
+        assert_eq!(
+            std::fs::read_to_string(dst.join("config/a.toml")).unwrap(),
+            "a"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dst.join("config/b.toml")).unwrap(),
+            "b"
+        );
+    }
+
+    #[test]
+    fn test_is_up_to_date_detects_changes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        let dst = tmp.path().join("dst");
+        std::fs::write(&src, "same").unwrap();
+        assert!(!is_up_to_date(&src, &dst, false));
+
+        copy_file(&src, &dst).unwrap();
+        assert!(is_up_to_date(&src, &dst, false));
+        assert!(is_up_to_date(&src, &dst, true));
+
+        // Same size, different bytes: only a content comparison notices
+        // without a changed modification time
+        std::fs::write(&dst, "diff").unwrap();
+        let modified = std::fs::metadata(&src).unwrap().modified().unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&dst)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        assert!(is_up_to_date(&src, &dst, false));
+        assert!(!is_up_to_date(&src, &dst, true));
+    }
 }
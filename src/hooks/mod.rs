@@ -3,7 +3,15 @@ mod executor;
 mod files;
 mod output;
 mod runner;
+mod sanitize;
 mod symlink;
+mod template;
 
-pub use executor::{execute_hooks_lenient_with_mp, execute_hooks_with_mp};
-pub use output::emit_line;
+pub use executor::{
+    execute_hooks_collect_with_mp, execute_hooks_lenient_with_mp, execute_hooks_with_mp,
+    execute_run_hooks_lenient_with_mp, HookSummary,
+};
+pub use files::count_pattern_matches;
+pub use output::{emit_line, emit_warning, new_multi_progress};
+pub use runner::without_log_note;
+pub use template::{shell_quote, with_placeholders, HookVars};
@@ -1,14 +1,47 @@
 #![allow(clippy::missing_errors_doc)]
-use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
 use std::time::Duration;
 
+use crate::color;
+
+/// Create the `MultiProgress` used for spinners and tree output.
+///
+/// Under `--quiet` the draw target is hidden, so spinners and their
+/// completion messages never reach the terminal.
+#[must_use]
+pub fn new_multi_progress() -> MultiProgress {
+    if color::is_quiet() {
+        MultiProgress::with_draw_target(ProgressDrawTarget::hidden())
+    } else {
+        MultiProgress::new()
+    }
+}
+
 /// Emit a static line into a `MultiProgress`, preserving bar ordering in TTY mode.
 ///
 /// In TTY mode, creates a bar that immediately finishes with the message,
 /// keeping it positioned correctly relative to active spinners.
 /// In non-TTY mode, simply prints to stderr.
-#[allow(clippy::missing_panics_doc)]
+/// Suppressed entirely under `--quiet`.
 pub fn emit_line(mp: &MultiProgress, is_tty: bool, msg: String) {
+    if color::is_quiet() {
+        return;
+    }
+    write_line(mp, is_tty, msg);
+}
+
+/// Emit a warning line. Unlike [`emit_line`], this is shown under `--quiet`
+/// (printed straight to stderr, since the `MultiProgress` is hidden then).
+pub fn emit_warning(mp: &MultiProgress, is_tty: bool, msg: String) {
+    if color::is_quiet() {
+        eprintln!("{msg}");
+    } else {
+        write_line(mp, is_tty, msg);
+    }
+}
+
+#[allow(clippy::missing_panics_doc)]
+fn write_line(mp: &MultiProgress, is_tty: bool, msg: String) {
     if is_tty {
         let bar = mp.add(ProgressBar::new(0));
         // set_style MUST be called before finish_with_message —
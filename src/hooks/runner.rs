@@ -2,44 +2,173 @@
 use anyhow::{Context, Result};
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 use std::collections::VecDeque;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
-use std::process::{Command, Stdio};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc;
 use std::time::{Duration, Instant};
 
 use super::output::{emit_line, format_duration};
+use super::sanitize::OutputSanitizer;
 use crate::color;
+use crate::config::RunCommand;
+use crate::path_utils::display_path;
+use crate::state;
 
 /// Number of trailing output lines to keep for failure diagnostics
 const FAILURE_TAIL_LINES: usize = 10;
 
-pub(super) fn execute_command(
+/// Log of the last failed hook command, relative to its working directory
+pub const HOOK_ERROR_LOG: &str = ".ofsht/last-hook-error.log";
+
+/// Marks the [`HOOK_ERROR_LOG`] hint at the end of a hook failure message
+const LOG_NOTE: &str = "\nFull output: ";
+
+/// A hook failure message without its trailing "Full output: ..." hint
+///
+/// For failures whose worktree is about to be rolled back: the log lives in
+/// that worktree and is deleted with it.
+#[must_use]
+pub fn without_log_note(message: &str) -> &str {
+    message.find(LOG_NOTE).map_or(message, |at| &message[..at])
+}
+
+/// Write the full output of a failed hook to [`HOOK_ERROR_LOG`] under `working_dir`
+///
+/// Best-effort (see [`state::write_best_effort`]): returns the log path, or
+/// `None` when it could not be written. The `.ofsht` directory gets a `*`
+/// `.gitignore` so the log never shows up in `git status`.
+fn write_error_log(
+    working_dir: &Path,
     cmd: &str,
+    exit: &str,
+    output: &[String],
+    color_mode: color::ColorMode,
+) -> Option<PathBuf> {
+    let path = working_dir.join(HOOK_ERROR_LOG);
+    let gitignore = path.parent()?.join(".gitignore");
+    if !gitignore.exists() {
+        state::write_best_effort(&gitignore, "*\n", color_mode);
+    }
+    let mut log = format!(
+        "time: {}\ncommand: {cmd}\ndirectory: {}\nstatus: {exit}\n\n",
+        chrono::Local::now().to_rfc3339(),
+        working_dir.display()
+    );
+    for line in output {
+        log.push_str(line);
+        log.push('\n');
+    }
+    state::write_best_effort(&path, log, color_mode).then_some(path)
+}
+
+/// Spawn a hook command with its output piped
+///
+/// Shell commands merge stderr into stdout at shell level so there is only one
+/// pipe to drain; argv commands run without a shell and pipe both streams.
+fn spawn_command(cmd: &RunCommand, working_dir: &Path, env: &[(&str, String)]) -> Result<Child> {
+    let mut command = match cmd {
+        RunCommand::Shell(line) => {
+            let mut command = Command::new("sh");
+            command
+                .arg("-c")
+                // Redirect for the whole script: a trailing `2>&1` only covers
+                // the last command, and stderr of earlier ones would be lost
+                // (stderr is discarded) from both the failure tail and the log
+                .arg(format!("exec 2>&1\n{line}"))
+                .stderr(Stdio::null());
+            command
+        }
+        RunCommand::Argv(argv) => {
+            let (program, rest) = argv.split_first().context("Empty argv in run entry")?;
+            let mut command = Command::new(program);
+            command.args(rest).stderr(Stdio::piped());
+            command
+        }
+    };
+    command
+        .current_dir(working_dir)
+        .envs(env.iter().map(|(key, value)| (key, value)))
+        .stdout(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to execute command: {cmd}"))
+}
+
+/// Forward the lines of every pipe into one channel (closed once all pipes hit EOF)
+///
+/// Each pipe is read in chunks through its own [`OutputSanitizer`], so progress
+/// redraws are collapsed and escape sequences stripped unless `raw_output`.
+fn merge_lines(pipes: Vec<Box<dyn Read + Send>>, raw_output: bool) -> mpsc::Receiver<String> {
+    let (tx, rx) = mpsc::channel();
+    for mut pipe in pipes {
+        let tx = tx.clone();
+        std::thread::spawn(move || {
+            let mut sanitizer = OutputSanitizer::new(raw_output);
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = match pipe.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => n,
+                };
+                for line in sanitizer.push(&buf[..n]) {
+                    if tx.send(line).is_err() {
+                        return;
+                    }
+                }
+            }
+            if let Some(line) = sanitizer.finish() {
+                tx.send(line).ok();
+            }
+        });
+    }
+    rx
+}
+
+/// Run a hook command, streaming its (merged stdout/stderr) output to stderr line by line.
+///
+/// TTY: a spinner is shown until the first output line arrives; it is then replaced by a
+/// static header and subsequent lines are emitted beneath it.
+/// Nothing from the hook is ever written to stdout (reserved for the shell wrapper path).
+/// Output is sanitized (see [`OutputSanitizer`]) unless `raw_output` is set.
+#[cfg(test)]
+pub(super) fn execute_command(
+    run: &RunCommand,
     working_dir: &Path,
     color_mode: color::ColorMode,
-    _is_last: bool,
+    raw_output: bool,
+    indent: &str,
+    mp: &MultiProgress,
+) -> Result<()> {
+    execute_command_with_env(run, working_dir, &[], color_mode, raw_output, indent, mp)
+}
+
+/// [`execute_command`] with extra environment variables for the hook process
+#[allow(clippy::too_many_lines)]
+pub(super) fn execute_command_with_env(
+    run: &RunCommand,
+    working_dir: &Path,
+    env: &[(&str, String)],
+    color_mode: color::ColorMode,
+    raw_output: bool,
     indent: &str,
     mp: &MultiProgress,
 ) -> Result<()> {
     let start = Instant::now();
+    let cmd_label = run.to_string();
+    let cmd = cmd_label.as_str();
 
-    // Merge stderr into stdout at shell level, pipe the single stream.
-    // This avoids deadlock (only one pipe to drain) and keeps output ordering natural.
-    let merged_cmd = format!("{cmd} 2>&1");
-    let mut child = Command::new("sh")
-        .arg("-c")
-        .arg(&merged_cmd)
-        .current_dir(working_dir)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::null())
-        .spawn()
-        .with_context(|| format!("Failed to execute command: {cmd}"))?;
+    let mut child = spawn_command(run, working_dir, env)?;
 
-    let child_stdout = child.stdout.take().expect("stdout was piped");
+    let mut pipes: Vec<Box<dyn Read + Send>> =
+        vec![Box::new(child.stdout.take().expect("stdout was piped"))];
+    if let Some(stderr) = child.stderr.take() {
+        pipes.push(Box::new(stderr));
+    }
+    let lines = merge_lines(pipes, raw_output);
 
-    // Setup spinner + preview bar in the shared MultiProgress (TTY only)
+    // Spinner in the shared MultiProgress (TTY only), shown until output arrives
     let is_tty = color_mode.should_colorize();
-    let (spinner, preview_bar) = if is_tty {
+    let spinner = if is_tty {
         let spinner = mp.add(ProgressBar::new_spinner());
         spinner.set_style(
             ProgressStyle::default_spinner()
@@ -49,39 +178,45 @@ pub(super) fn execute_command(
         spinner.set_prefix(indent.to_string());
         spinner.set_message(cmd.to_string());
         spinner.enable_steady_tick(Duration::from_millis(100));
-
-        let preview = mp.add(ProgressBar::new(0));
-        preview.set_style(ProgressStyle::with_template("{prefix}  {msg:.dim}").unwrap());
-        preview.set_prefix(indent.to_string());
-
-        (Some(spinner), Some(preview))
+        Some(spinner)
     } else {
-        (None, None)
+        None
     };
 
     // Consume output in a background thread.
-    // Updates preview bar in real-time and keeps last N lines for failure diagnostics.
-    let preview_clone = preview_bar.clone();
+    // Streams each line as it arrives and keeps the last N lines for failure diagnostics.
+    let spinner_clone = spinner.clone();
+    let mp_clone = mp.clone();
+    let header = format!("{indent}{}", color::info(color_mode, cmd));
+    let line_indent = format!("{indent}  ");
     let reader_handle = std::thread::spawn(move || {
-        let reader = BufReader::new(child_stdout);
+        let mut full = Vec::new();
         let mut tail = VecDeque::<String>::with_capacity(FAILURE_TAIL_LINES);
-        for line in reader.lines().map_while(Result::ok) {
-            // Update preview bar with truncated last line
-            if let Some(ref pb) = preview_clone {
-                let display = if line.len() > 60 {
-                    format!("{}…", &line[..59])
-                } else {
-                    line.clone()
-                };
-                pb.set_message(display);
+        let mut has_output = false;
+        for line in lines {
+            if !has_output {
+                has_output = true;
+                // First output: freeze the spinner into a static header above the stream
+                if let Some(ref pb) = spinner_clone {
+                    pb.set_style(ProgressStyle::with_template("{msg}").unwrap());
+                    pb.finish_with_message(header.clone());
+                } else if !color::is_quiet() {
+                    eprintln!("{header}");
+                }
             }
+            emit_line(
+                &mp_clone,
+                is_tty,
+                format!("{line_indent}{}", color::dim(color_mode, &line)),
+            );
             // Ring buffer for failure diagnostics
             if tail.len() >= FAILURE_TAIL_LINES {
                 tail.pop_front();
             }
-            tail.push_back(line);
+            tail.push_back(line.clone());
+            full.push(line);
         }
-        tail
+        (tail, full, has_output)
     });
 
     let status = child
@@ -89,13 +224,8 @@ pub(super) fn execute_command(
         .with_context(|| format!("Failed to wait for command: {cmd}"))?;
     let elapsed = start.elapsed();
 
-    // Join reader thread to get tail buffer
-    let tail = reader_handle.join().unwrap_or_default();
-
-    // Clear preview bar
-    if let Some(pb) = preview_bar {
-        pb.finish_and_clear();
-    }
+    // Join reader thread to get the output buffers
+    let (tail, full, has_output) = reader_handle.join().unwrap_or_default();
 
     if status.success() {
         let timing_info = format_duration(elapsed);
@@ -104,42 +234,124 @@ pub(super) fn execute_command(
             color::success(color_mode, cmd),
             color::dim(color_mode, timing_info)
         );
-        if let Some(pb) = spinner {
-            // TTY: transform spinner into completion message (stays in place)
-            pb.set_style(ProgressStyle::with_template("{msg}").unwrap());
-            pb.finish_with_message(msg);
-        } else {
-            // non-TTY: print directly
-            eprintln!("{msg}");
+        match spinner {
+            // TTY without output: transform spinner into completion message (stays in place)
+            Some(pb) if !has_output => {
+                pb.set_style(ProgressStyle::with_template("{msg}").unwrap());
+                pb.finish_with_message(msg);
+            }
+            _ => emit_line(mp, is_tty, msg),
         }
+        Ok(())
     } else {
-        // Clear spinner on failure
+        // Clear spinner on failure (it is already finished if output was streamed)
         if let Some(pb) = spinner {
-            pb.finish_and_clear();
+            if !has_output {
+                pb.finish_and_clear();
+            }
         }
-        // Show last N lines of output for diagnostics
-        for line in &tail {
-            emit_line(
-                mp,
-                is_tty,
-                format!("{indent}  {}", color::dim(color_mode, line)),
-            );
+        let exit = status
+            .code()
+            .map_or_else(|| "signal".to_string(), |code| format!("exit {code}"));
+        let log_note = write_error_log(working_dir, cmd, &exit, &full, color_mode)
+            .map(|path| format!("{LOG_NOTE}{}", display_path(&path)))
+            .unwrap_or_default();
+        if tail.is_empty() {
+            anyhow::bail!("Hook command failed: {cmd} ({exit}){log_note}");
         }
-        anyhow::bail!("Hook command failed: {cmd}");
+        let output = Vec::from(tail).join("\n");
+        anyhow::bail!("Hook command failed: {cmd} ({exit})\n{output}{log_note}");
     }
-
-    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_execute_command_failure_includes_output() {
+        let temp_dir = std::env::temp_dir();
+        let result = execute_command(
+            &"echo first; echo boom >&2; exit 3".into(),
+            &temp_dir,
+            color::ColorMode::Never,
+            false,
+            "  ",
+            &MultiProgress::new(),
+        );
+        let err = result.unwrap_err().to_string();
+        assert!(
+            err.contains("Hook command failed"),
+            "unexpected error: {err}"
+        );
+        assert!(err.contains("exit 3"), "unexpected error: {err}");
+        assert!(
+            err.contains("first") && err.contains("boom"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn test_execute_command_failure_writes_error_log() {
+        let dir = std::env::temp_dir().join("test_hook_error_log");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let err = execute_command(
+            &"echo out-line; echo err-line >&2; exit 4".into(),
+            &dir,
+            color::ColorMode::Never,
+            false,
+            "  ",
+            &MultiProgress::new(),
+        )
+        .unwrap_err()
+        .to_string();
+
+        let log = std::fs::read_to_string(dir.join(HOOK_ERROR_LOG)).unwrap();
+        assert!(
+            log.contains("command: echo out-line; echo err-line >&2; exit 4"),
+            "{log}"
+        );
+        assert!(log.contains("status: exit 4"), "{log}");
+        assert!(log.contains("time: "), "{log}");
+        assert!(log.contains("out-line\nerr-line\n"), "{log}");
+        assert_eq!(
+            std::fs::read_to_string(dir.join(".ofsht/.gitignore")).unwrap(),
+            "*\n"
+        );
+        assert!(err.contains("last-hook-error.log"), "{err}");
+        assert!(!without_log_note(&err).contains("last-hook-error.log"));
+        assert!(without_log_note(&err).ends_with("err-line"), "{err}");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_execute_command_success_writes_no_log() {
+        let dir = std::env::temp_dir().join("test_hook_error_log_success");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        execute_command(
+            &"echo fine".into(),
+            &dir,
+            color::ColorMode::Never,
+            false,
+            "  ",
+            &MultiProgress::new(),
+        )
+        .unwrap();
+
+        assert!(!dir.join(".ofsht").exists());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_execute_command_success() {
         let temp_dir = std::env::temp_dir();
         let result = execute_command(
-            "echo test",
+            &"echo test".into(),
             &temp_dir,
             color::ColorMode::Never,
             false,
@@ -153,7 +365,7 @@ mod tests {
     fn test_execute_command_failure() {
         let temp_dir = std::env::temp_dir();
         let result = execute_command(
-            "exit 1",
+            &"exit 1".into(),
             &temp_dir,
             color::ColorMode::Never,
             false,
@@ -172,7 +384,7 @@ mod tests {
         // This test verifies the command executes successfully
         // The actual stream verification is done via integration testing
         let result = execute_command(
-            "echo 'hook output'",
+            &"echo 'hook output'".into(),
             &temp_dir,
             color::ColorMode::Never,
             false,
@@ -181,4 +393,65 @@ mod tests {
         );
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_execute_command_argv_keeps_spaces_in_arguments() {
+        let temp = tempfile::tempdir().unwrap();
+        let argv = RunCommand::Argv(vec!["touch".to_string(), "file with spaces".to_string()]);
+        let result = execute_command(
+            &argv,
+            temp.path(),
+            color::ColorMode::Never,
+            false,
+            "  ",
+            &MultiProgress::new(),
+        );
+        assert!(result.is_ok());
+        assert!(temp.path().join("file with spaces").exists());
+        // Naive shell splitting would have created three files instead
+        assert!(!temp.path().join("file").exists());
+    }
+
+    #[test]
+    fn test_execute_command_argv_failure_includes_stderr() {
+        let argv = RunCommand::Argv(vec![
+            "sh".to_string(),
+            "-c".to_string(),
+            "echo first; echo boom >&2; exit 4".to_string(),
+        ]);
+        let err = execute_command(
+            &argv,
+            &std::env::temp_dir(),
+            color::ColorMode::Never,
+            false,
+            "  ",
+            &MultiProgress::new(),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("exit 4"), "unexpected error: {err}");
+        assert!(
+            err.contains("first") && err.contains("boom"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn test_execute_command_argv_missing_program() {
+        let argv = RunCommand::Argv(vec!["ofsht-no-such-program".to_string()]);
+        let err = execute_command(
+            &argv,
+            &std::env::temp_dir(),
+            color::ColorMode::Never,
+            false,
+            "  ",
+            &MultiProgress::new(),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(
+            err.contains("Failed to execute command: ofsht-no-such-program"),
+            "unexpected error: {err}"
+        );
+    }
 }
@@ -0,0 +1,250 @@
+//! Hook output sanitizer
+//!
+//! Tools like `npm install` redraw progress bars with bare carriage returns and
+//! color them with ANSI escapes, which fights the spinners and leaves garbage
+//! lines in the tree output. [`OutputSanitizer`] turns a raw byte stream into
+//! plain lines: escape sequences are dropped and every `\r`-separated redraw
+//! collapses to its final state. Input arrives in arbitrary chunks, so escape
+//! sequences, `\r\n` pairs and UTF-8 characters may be split across calls.
+
+/// Where the parser is inside an escape sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Text,
+    /// After `ESC`
+    Escape,
+    /// After `ESC` and intermediate bytes (`ESC ( B`)
+    EscapeIntermediate,
+    /// Inside `ESC [` parameters
+    Csi,
+    /// Inside `ESC ]` (also DCS/SOS/PM/APC), terminated by `BEL` or `ESC \`
+    Osc,
+    /// `ESC` seen inside an OSC, possibly starting the `ESC \` terminator
+    OscEscape,
+}
+
+/// Incremental converter from raw hook output to displayable lines
+#[derive(Debug)]
+pub(super) struct OutputSanitizer {
+    raw: bool,
+    state: State,
+    line: Vec<u8>,
+    /// A `\r` was seen and not yet known to be part of `\r\n`
+    pending_cr: bool,
+}
+
+impl OutputSanitizer {
+    /// Create a sanitizer; with `raw`, bytes pass through untouched and only
+    /// `\n` splits lines
+    pub(super) const fn new(raw: bool) -> Self {
+        Self {
+            raw,
+            state: State::Text,
+            line: Vec::new(),
+            pending_cr: false,
+        }
+    }
+
+    /// Feed a chunk and return the lines it completed
+    pub(super) fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        let mut lines = Vec::new();
+        for &byte in chunk {
+            if self.raw {
+                if byte == b'\n' {
+                    lines.push(self.take_line());
+                } else {
+                    self.line.push(byte);
+                }
+                continue;
+            }
+            self.push_byte(byte, &mut lines);
+        }
+        lines
+    }
+
+    /// Flush the unterminated last line, if it has any content
+    pub(super) fn finish(mut self) -> Option<String> {
+        self.pending_cr = false;
+        (!self.line.is_empty()).then(|| self.take_line())
+    }
+
+    fn push_byte(&mut self, byte: u8, lines: &mut Vec<String>) {
+        match self.state {
+            State::Text => self.push_text(byte, lines),
+            State::Escape => {
+                self.state = match byte {
+                    b'[' => State::Csi,
+                    b']' | b'P' | b'X' | b'^' | b'_' => State::Osc,
+                    0x20..=0x2f => State::EscapeIntermediate,
+                    _ => State::Text,
+                };
+            }
+            State::EscapeIntermediate => {
+                if !(0x20..=0x2f).contains(&byte) {
+                    self.state = State::Text;
+                }
+            }
+            State::Csi => {
+                // Parameter and intermediate bytes continue, a final byte ends it
+                if (0x40..=0x7e).contains(&byte) {
+                    self.state = State::Text;
+                }
+            }
+            State::Osc => match byte {
+                0x07 => self.state = State::Text,
+                0x1b => self.state = State::OscEscape,
+                _ => {}
+            },
+            State::OscEscape => {
+                self.state = match byte {
+                    b'\\' => State::Text,
+                    0x1b => State::OscEscape,
+                    _ => State::Osc,
+                };
+            }
+        }
+    }
+
+    fn push_text(&mut self, byte: u8, lines: &mut Vec<String>) {
+        if self.pending_cr {
+            self.pending_cr = false;
+            if byte == b'\n' {
+                lines.push(self.take_line());
+                return;
+            }
+            // Bare `\r`: the next redraw replaces the line, unless it is
+            // another `\r` (the line content stays until real text arrives)
+            if byte == b'\r' {
+                self.pending_cr = true;
+                return;
+            }
+            self.line.clear();
+        }
+        match byte {
+            b'\n' => lines.push(self.take_line()),
+            b'\r' => self.pending_cr = true,
+            0x1b => self.state = State::Escape,
+            b'\t' => self.line.push(byte),
+            0x00..=0x1f | 0x7f => {}
+            _ => self.line.push(byte),
+        }
+    }
+
+    fn take_line(&mut self) -> String {
+        let line = String::from_utf8_lossy(&self.line).into_owned();
+        self.line.clear();
+        line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feed `chunks` one by one and collect every line, including the last one
+    fn sanitize(chunks: &[&[u8]]) -> Vec<String> {
+        let mut sanitizer = OutputSanitizer::new(false);
+        let mut lines = Vec::new();
+        for chunk in chunks {
+            lines.extend(sanitizer.push(chunk));
+        }
+        lines.extend(sanitizer.finish());
+        lines
+    }
+
+    /// Feed `input` one byte at a time
+    fn sanitize_bytewise(input: &[u8]) -> Vec<String> {
+        let chunks: Vec<&[u8]> = input.chunks(1).collect();
+        sanitize(&chunks)
+    }
+
+    #[test]
+    fn test_plain_lines_pass_through() {
+        assert_eq!(sanitize(&[b"one\ntwo\n"]), vec!["one", "two"]);
+        assert_eq!(sanitize(&[b"no newline"]), vec!["no newline"]);
+        assert_eq!(sanitize(&[b"a\n\nb\n"]), vec!["a", "", "b"]);
+    }
+
+    #[test]
+    fn test_crlf_is_a_single_line_break() {
+        assert_eq!(sanitize(&[b"one\r\ntwo\r\n"]), vec!["one", "two"]);
+        assert_eq!(sanitize(&[b"one\r", b"\ntwo"]), vec!["one", "two"]);
+    }
+
+    #[test]
+    fn test_bare_cr_keeps_final_redraw() {
+        assert_eq!(sanitize(&[b"10%\r50%\r100%\ndone\n"]), vec!["100%", "done"]);
+        // Trailing `\r` before the newline or EOF keeps the last state
+        assert_eq!(sanitize(&[b"10%\r100%\r\n"]), vec!["100%"]);
+        assert_eq!(sanitize(&[b"10%\r100%\r"]), vec!["100%"]);
+    }
+
+    #[test]
+    fn test_lone_cr_runs() {
+        assert_eq!(sanitize(&[b"abc\r\r\rxyz\n"]), vec!["xyz"]);
+        assert_eq!(sanitize(&[b"abc\r\r\r\n"]), vec!["abc"]);
+        assert!(sanitize(&[b"\r\r\r"]).is_empty());
+        assert_eq!(sanitize(&[b"a\r", b"\r", b"b"]), vec!["b"]);
+    }
+
+    #[test]
+    fn test_csi_sequences_are_stripped() {
+        assert_eq!(sanitize(&[b"\x1b[32mok\x1b[0m\n"]), vec!["ok"]);
+        assert_eq!(sanitize(&[b"\x1b[1;31;40mred\x1b[m\n"]), vec!["red"]);
+        // Cursor movement and erase-line used by progress bars
+        assert_eq!(sanitize(&[b"\x1b[2K\x1b[1Gstep 2\n"]), vec!["step 2"]);
+        assert_eq!(sanitize(&[b"\x1b[?25lhidden\x1b[?25h\n"]), vec!["hidden"]);
+    }
+
+    #[test]
+    fn test_osc_sequences_with_bel_and_st() {
+        assert_eq!(sanitize(&[b"\x1b]0;window title\x07text\n"]), vec!["text"]);
+        assert_eq!(
+            sanitize(&[b"\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\\n"]),
+            vec!["link"]
+        );
+        // `ESC` inside an OSC that is not a terminator keeps the OSC open
+        assert_eq!(sanitize(&[b"\x1b]0;a\x1bb\x07c\n"]), vec!["c"]);
+    }
+
+    #[test]
+    fn test_other_escapes_are_stripped() {
+        // Charset designation (ESC ( B) and a two-byte escape (ESC =)
+        assert_eq!(sanitize(&[b"\x1b(Babc\x1b=def\n"]), vec!["abcdef"]);
+        // DCS with ST terminator
+        assert_eq!(sanitize(&[b"\x1bPq#0\x1b\\x\n"]), vec!["x"]);
+    }
+
+    #[test]
+    fn test_sequences_split_across_chunks() {
+        assert_eq!(
+            sanitize(&[b"\x1b", b"[3", b"2m", b"green\x1b[", b"0m\n"]),
+            vec!["green"]
+        );
+        assert_eq!(
+            sanitize(&[b"\x1b]0;ti", b"tle\x1b", b"\\", b"after\n"]),
+            vec!["after"]
+        );
+        let input = b"\x1b[1mbold\x1b[0m\r\x1b]0;t\x07line\r\nnext\x1b[K\n";
+        assert_eq!(sanitize_bytewise(input), vec!["line", "next"]);
+    }
+
+    #[test]
+    fn test_control_characters_dropped_tabs_kept() {
+        assert_eq!(sanitize(&[b"a\x08b\x00c\td\x7f\n"]), vec!["abc\td"]);
+    }
+
+    #[test]
+    fn test_utf8_split_across_chunks() {
+        let text = "日本語\n".as_bytes();
+        assert_eq!(sanitize_bytewise(text), vec!["日本語"]);
+    }
+
+    #[test]
+    fn test_raw_mode_only_splits_on_newline() {
+        let mut sanitizer = OutputSanitizer::new(true);
+        let lines = sanitizer.push(b"\x1b[32m10%\r100%\x1b[0m\nrest");
+        assert_eq!(lines, vec!["\x1b[32m10%\r100%\x1b[0m"]);
+        assert_eq!(sanitizer.finish().as_deref(), Some("rest"));
+    }
+}
@@ -3,8 +3,8 @@ use anyhow::{Context, Result};
 use indicatif::MultiProgress;
 use std::path::Path;
 
-use super::files::{expand_pattern, PatternKind};
-use super::output::emit_line;
+use super::files::{expand_pattern, PatternKind, PatternOutcome};
+use super::output::{emit_line, emit_warning};
 use crate::color;
 
 /// Result of ensuring a symlink exists at the destination path
@@ -78,6 +78,9 @@ pub(super) fn ensure_symlink(src: &Path, dst: &Path) -> Result<SymlinkResult> {
 }
 
 /// Create symlinks for a pattern (supports glob)
+///
+/// Links that already point at the source are reported as skipped, so
+/// re-running a link action is a no-op.
 pub(super) fn create_symlinks(
     pattern: &str,
     source_path: &Path,
@@ -86,13 +89,13 @@ pub(super) fn create_symlinks(
     _is_last: bool,
     indent: &str,
     mp: &MultiProgress,
-) -> Result<()> {
+) -> Result<PatternOutcome> {
     let is_tty = color_mode.should_colorize();
     let (kind, paths) = expand_pattern(pattern, source_path)?;
 
     // If literal and not found, warn user
     if kind == PatternKind::Literal && paths.is_empty() {
-        emit_line(
+        emit_warning(
             mp,
             is_tty,
             format!(
@@ -106,10 +109,11 @@ pub(super) fn create_symlinks(
                 )
             ),
         );
-        return Ok(());
+        return Ok(PatternOutcome::default());
     }
 
     // Create symlink for each matched path
+    let mut outcome = PatternOutcome::default();
     for src_path in paths {
         // Get relative path from source
         let rel_path = src_path
@@ -126,23 +130,34 @@ pub(super) fn create_symlinks(
             })?;
         }
 
-        let result = ensure_symlink(&src_path, &dst_path)?;
-        let msg = match result {
+        let rel = rel_path.display().to_string();
+        match ensure_symlink(&src_path, &dst_path)? {
             SymlinkResult::Created | SymlinkResult::Replaced => {
-                format!("Linked: {}", rel_path.display())
+                emit_line(
+                    mp,
+                    is_tty,
+                    format!(
+                        "{indent}{}",
+                        color::success(color_mode, format!("Linked: {rel}"))
+                    ),
+                );
+                outcome.applied.push(rel);
             }
             SymlinkResult::AlreadyCorrect => {
-                format!("Linked (unchanged): {}", rel_path.display())
+                emit_line(
+                    mp,
+                    is_tty,
+                    format!(
+                        "{indent}{}",
+                        color::dim(color_mode, format!("Linked (unchanged): {rel}"))
+                    ),
+                );
+                outcome.skipped.push(rel);
             }
-        };
-        emit_line(
-            mp,
-            is_tty,
-            format!("{indent}{}", color::success(color_mode, msg)),
-        );
+        }
     }
 
-    Ok(())
+    Ok(outcome)
 }
 
 #[cfg(test)]
@@ -191,6 +206,43 @@ mod tests {
         std::fs::remove_dir_all(&dst_dir).ok();
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn test_create_symlinks_rerun_is_noop() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("src");
+        let dst = tmp.path().join("dst");
+        std::fs::create_dir_all(src.join("shared")).unwrap();
+        std::fs::create_dir_all(&dst).unwrap();
+        std::fs::write(src.join(".envrc"), "use flake").unwrap();
+
+        let run = |pattern: &str| {
+            create_symlinks(
+                pattern,
+                &src,
+                &dst,
+                color::ColorMode::Never,
+                false,
+                "  ",
+                &MultiProgress::new(),
+            )
+            .unwrap()
+        };
+
+        assert_eq!(run("shared").applied, ["shared"]);
+        assert_eq!(run(".envrc").applied, [".envrc"]);
+
+        for pattern in ["shared", ".envrc"] {
+            let second = run(pattern);
+            assert!(second.applied.is_empty(), "{second:?}");
+            assert_eq!(second.skipped, [pattern]);
+        }
+        assert_eq!(
+            std::fs::read_link(dst.join("shared")).unwrap(),
+            src.join("shared")
+        );
+    }
+
     // ensure_symlink tests (unix only)
     #[test]
     #[cfg(unix)]
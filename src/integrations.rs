@@ -1,10 +1,51 @@
 // External integrations module
 // This module contains integrations with external tools
 
+use std::process::Command;
+use std::sync::OnceLock;
+
 pub mod fzf;
 pub mod git;
+pub mod resolver;
 pub mod tmux;
 pub mod zoxide;
 
 // GitHub integration
 pub mod gh;
+
+/// Answer of `probe`, run only the first time `cache` is consulted
+///
+/// Tool availability checks go through this so each binary is spawned at most
+/// once per process, however many callers ask.
+pub fn probe_once(cache: &OnceLock<bool>, probe: impl FnOnce() -> bool) -> bool {
+    *cache.get_or_init(probe)
+}
+
+/// Whether `program arg` (e.g. `fzf --version`) runs and exits successfully
+#[must_use]
+pub fn command_succeeds(program: &str, arg: &str) -> bool {
+    Command::new(program)
+        .arg(arg)
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_probe_once_runs_probe_a_single_time() {
+        let cache = OnceLock::new();
+        let calls = AtomicUsize::new(0);
+        let probe = || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            true
+        };
+        for _ in 0..3 {
+            assert!(probe_once(&cache, probe));
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
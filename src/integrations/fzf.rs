@@ -2,12 +2,15 @@
 #![allow(clippy::must_use_candidate)]
 use anyhow::{Context, Result};
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::sync::OnceLock;
 
-use crate::domain::worktree::{
-    calculate_relative_path, calculate_worktree_root_from_paths, WorktreeList,
-};
+use crate::color::ColorMode;
+use crate::domain::exclude::WorktreeExclude;
+use crate::domain::worktree::{WorktreeEntry, WorktreeLayout, WorktreeList};
+use crate::integrations::gh::meta;
+use crate::integrations::{command_succeeds, probe_once};
 use crate::path_utils::display_path;
 
 /// Item to display in fzf
@@ -51,29 +54,11 @@ impl FzfPicker for RealFzfPicker {
 
         // Build fzf command
         let mut cmd = Command::new("fzf");
-
-        // Add multi-select if requested
-        if multi {
-            cmd.arg("--multi");
-        }
-
-        // Add extra options from config
-        for opt in &self.extra_options {
-            cmd.arg(opt);
-        }
-
-        // Add preview command to show git log for each worktree
-        // Extract the last field (path) and expand ~ to $HOME
-        // Use % as placeholder to avoid conflicts with fzf's {}
-        let preview_cmd =
-            "echo {} | awk '{print $NF}' | sed \"s|^~|$HOME|\" | xargs -I % git -C % log --oneline -n 10 2>/dev/null";
-        cmd.arg("--preview").arg(preview_cmd);
-
-        // Add some default options for better UX
-        cmd.arg("--height=50%")
-            .arg("--reverse")
-            .arg("--border")
-            .arg("--prompt=Select worktree: ");
+        cmd.args(build_fzf_args(
+            &self.extra_options,
+            multi,
+            items_have_ansi(items),
+        ));
 
         // Execute fzf with stdin
         cmd.stdin(Stdio::piped())
@@ -100,10 +85,15 @@ impl FzfPicker for RealFzfPicker {
                 let stdout = String::from_utf8_lossy(&output.stdout);
                 let selected_displays: Vec<&str> = stdout.lines().collect();
 
-                // Map selected display strings back to values
+                // Map selected display strings back to values (fzf prints the
+                // lines without escape codes under --ansi)
                 let mut results = Vec::new();
                 for display in selected_displays {
-                    if let Some(item) = items.iter().find(|item| item.display == display) {
+                    let display = console::strip_ansi_codes(display);
+                    if let Some(item) = items
+                        .iter()
+                        .find(|item| console::strip_ansi_codes(&item.display) == display)
+                    {
                         results.push(item.value.clone());
                     }
                 }
@@ -125,12 +115,86 @@ impl FzfPicker for RealFzfPicker {
     }
 }
 
-/// Check if fzf is available in the system
+/// Whether any item display carries escape codes (only when colors are enabled)
+fn items_have_ansi(items: &[FzfItem]) -> bool {
+    items.iter().any(|item| item.display.contains('\x1b'))
+}
+
+/// Default fzf options, each paired with the flags that count as a user override
+const DEFAULT_OPTIONS: &[(&[&str], &str)] = &[
+    (&["--height"], "--height=50%"),
+    (&["--reverse", "--no-reverse", "--layout"], "--reverse"),
+    (&["--border", "--no-border"], "--border"),
+    (&["--prompt"], "--prompt=Select worktree: "),
+];
+
+/// Whether `opt` is `flag` itself or `flag=value`
+fn is_flag(opt: &str, flag: &str) -> bool {
+    opt.strip_prefix(flag)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('='))
+}
+
+/// Assemble the fzf argument list
+///
+/// Defaults for layout and prompt are only added when `extra_options` has no
+/// equivalent flag, and the user options come last so they win either way.
+/// `--ansi` is passed only when the items contain escape codes (`ansi`).
+pub fn build_fzf_args(extra_options: &[String], multi: bool, ansi: bool) -> Vec<String> {
+    let overridden = |flags: &[&str]| {
+        extra_options
+            .iter()
+            .any(|opt| flags.iter().any(|flag| is_flag(opt, flag)))
+    };
+
+    let mut args: Vec<String> = DEFAULT_OPTIONS
+        .iter()
+        .filter(|(flags, _)| !overridden(flags))
+        .map(|(_, default)| (*default).to_string())
+        .collect();
+
+    if multi {
+        args.push("--multi".to_string());
+    }
+    if ansi {
+        args.push("--ansi".to_string());
+    }
+
+    // Preview dirty status, ahead/behind and recent commits for each worktree
+    if !overridden(&["--preview", "--no-preview"]) {
+        args.push("--preview".to_string());
+        args.push(build_preview_command());
+    }
+
+    args.extend(extra_options.iter().cloned());
+    args
+}
+
+/// Separator between the columns of an fzf item display string
+const COLUMN_SEPARATOR: &str = " · ";
+
+/// Build the fzf `--preview` shell snippet
+///
+/// fzf substitutes `{}` with the single-quoted selected line. The path is everything
+/// after the last column separator (so paths with spaces survive), a leading `~` is
+/// expanded to `$HOME`, and the path is always double-quoted. POSIX `sh` only.
+pub fn build_preview_command() -> String {
+    [
+        format!("p=$(printf '%s\\n' {{}} | sed 's/.*{COLUMN_SEPARATOR}//')"),
+        r#"case "$p" in "~"*) p="$HOME${p#\~}" ;; esac"#.to_string(),
+        r#"[ -d "$p" ] || exit 0"#.to_string(),
+        "echo '── Status ──'".to_string(),
+        r#"git -C "$p" status --short --branch 2>/dev/null"#.to_string(),
+        "echo".to_string(),
+        "echo '── Recent commits ──'".to_string(),
+        r#"git -C "$p" log --oneline -n 10 2>/dev/null"#.to_string(),
+    ]
+    .join("; ")
+}
+
+/// Check if fzf is available in the system (probed once per process)
 pub fn is_fzf_available() -> bool {
-    Command::new("fzf")
-        .arg("--version")
-        .output()
-        .is_ok_and(|output| output.status.success())
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    probe_once(&AVAILABLE, || command_succeeds("fzf", "--version"))
 }
 
 /// Build worktree items from git worktree list --porcelain output
@@ -138,82 +202,109 @@ pub fn is_fzf_available() -> bool {
 /// Display format: `{name} · {branch} · {path}`
 /// - Index 0 is the main worktree (displayed as `@`)
 /// - Non-main worktrees show their relative path from the worktree root
+///   ([`WorktreeLayout`], from the `worktree.dir` template when given)
+/// - Branches of worktrees created from `#123` are followed by the number
+/// - Worktrees matching `exclude` are left out (the main worktree never is)
 /// - Columns are padded for alignment (except the last column)
-pub fn build_worktree_items(porcelain_output: &str) -> Vec<FzfItem> {
+/// - Name and branch columns are colored according to `color_mode`; the value
+///   (the worktree path) never contains escape codes
+pub fn build_worktree_items(
+    porcelain_output: &str,
+    exclude: &WorktreeExclude,
+    template: Option<&str>,
+    color_mode: ColorMode,
+) -> Vec<FzfItem> {
     // Parse via the unified WorktreeList type (replaces the previous Pass 1
     // independent scanner). Real `git worktree list --porcelain` output never
     // has leading/trailing whitespace, so the legacy `.trim()` defense is
     // dropped — covered by `test_build_worktree_items_no_trim_behavior_equivalent`.
     let list = WorktreeList::parse(porcelain_output, None);
-    let entries = list.entries();
-
-    if entries.is_empty() {
+    if list.entries().is_empty() {
         return Vec::new();
     }
 
-    // Calculate worktree root from non-main paths
-    let non_main_paths: Vec<PathBuf> = list
-        .non_main()
+    let layout = WorktreeLayout::for_list(&list, template);
+
+    let entries: Vec<&WorktreeEntry> = list
+        .entries()
         .iter()
-        .map(|e| PathBuf::from(&e.path))
+        .enumerate()
+        .filter(|(index, entry)| *index == 0 || !exclude.is_excluded(entry, &layout))
+        .map(|(_, entry)| entry)
         .collect();
-    let worktree_root = calculate_worktree_root_from_paths(&non_main_paths);
 
-    // Build name, branch, display_path for each entry
-    let display_entries: Vec<(String, String, String)> = entries
+    // Build name, branch label, PR/issue suffix and display_path for each entry
+    let display_entries: Vec<(String, String, String, String)> = entries
         .iter()
         .enumerate()
         .map(|(index, entry)| {
             let name = if index == 0 {
                 "@".to_string()
             } else {
-                worktree_root
-                    .as_ref()
-                    .and_then(|root| calculate_relative_path(&PathBuf::from(&entry.path), root))
-                    .unwrap_or_else(|| {
-                        // Fallback: use directory name
-                        PathBuf::from(&entry.path)
-                            .file_name()
-                            .map_or_else(|| entry.path.clone(), |n| n.to_string_lossy().to_string())
-                    })
+                layout.display_name(entry)
             };
 
-            let branch = if index == 0 {
-                "[@]".to_string()
+            let (branch, suffix) = if index == 0 {
+                ("[@]".to_string(), String::new())
             } else {
-                entry
+                let branch = entry
                     .branch
                     .as_deref()
-                    .map_or_else(|| "[detached]".to_string(), |b| format!("[{b}]"))
+                    .map_or_else(|| "[detached]".to_string(), |b| format!("[{b}]"));
+                let suffix = meta::load(Path::new(&entry.path))
+                    .map(|github_ref| format!(" #{}", github_ref.number))
+                    .unwrap_or_default();
+                (branch, suffix)
             };
 
             let path = display_path(&PathBuf::from(&entry.path));
 
-            (name, branch, path)
+            (name, branch, suffix, path)
         })
         .collect();
 
     // Pass 2: Calculate column widths and format display strings
+    // (widths are measured on the plain text, before colors are applied)
     let max_name_width = display_entries
         .iter()
-        .map(|(n, _, _)| n.len())
+        .map(|(n, _, _, _)| n.len())
         .max()
         .unwrap_or(0);
     let max_branch_width = display_entries
         .iter()
-        .map(|(_, b, _)| b.len())
+        .map(|(_, b, s, _)| b.len() + s.len())
         .max()
         .unwrap_or(0);
 
     display_entries
         .into_iter()
         .zip(entries.iter())
-        .map(|((name, branch, path), entry)| {
+        .enumerate()
+        .map(|(index, ((name, branch, suffix, path), entry))| {
             let name_padding = " ".repeat(max_name_width.saturating_sub(name.len()));
-            let branch_padding = " ".repeat(max_branch_width.saturating_sub(branch.len()));
+            let branch_padding =
+                " ".repeat(max_branch_width.saturating_sub(branch.len() + suffix.len()));
+            let (name, branch) = if index == 0 {
+                (
+                    color_mode.colorize_main_worktree(&name),
+                    color_mode.colorize_main_worktree(&branch),
+                )
+            } else if entry.branch.is_none() {
+                (name, color_mode.colorize_detached(&branch))
+            } else {
+                (name, color_mode.colorize_branch(&branch))
+            };
+            let suffix = if suffix.is_empty() {
+                suffix
+            } else {
+                color_mode.colorize_secondary(&suffix)
+            };
 
-            // Last column (path) has no padding to avoid trailing whitespace
-            let display = format!("{name}{name_padding} · {branch}{branch_padding} · {path}");
+            // Last column (path) has no padding to avoid trailing whitespace, and
+            // stays plain so the preview command can extract it
+            let display = format!(
+                "{name}{name_padding}{COLUMN_SEPARATOR}{branch}{suffix}{branch_padding}{COLUMN_SEPARATOR}{path}"
+            );
 
             FzfItem {
                 display,
@@ -303,6 +394,92 @@ mod tests {
         let _ = is_fzf_available();
     }
 
+    #[test]
+    fn test_build_fzf_args_defaults() {
+        let args = build_fzf_args(&[], true, false);
+        assert!(args.contains(&"--height=50%".to_string()));
+        assert!(args.contains(&"--reverse".to_string()));
+        assert!(args.contains(&"--border".to_string()));
+        assert!(args.contains(&"--prompt=Select worktree: ".to_string()));
+        assert!(args.contains(&"--multi".to_string()));
+        assert!(args.contains(&"--preview".to_string()));
+    }
+
+    #[test]
+    fn test_build_fzf_args_ansi_follows_item_colors() {
+        let porcelain = "worktree /wt/main\nHEAD a\nbranch refs/heads/main\n\n\
+                         worktree /wt/feature\nHEAD b\nbranch refs/heads/feature\n\n\
+                         worktree /wt/detached\nHEAD c\ndetached\n\n";
+        let exclude = WorktreeExclude::default();
+
+        let colored = build_worktree_items(porcelain, &exclude, None, ColorMode::Always);
+        assert!(colored[1].display.contains("\x1b[36m[feature]\x1b[0m"));
+        assert!(
+            build_fzf_args(&[], false, items_have_ansi(&colored)).contains(&"--ansi".to_string())
+        );
+
+        let plain = build_worktree_items(porcelain, &exclude, None, ColorMode::Never);
+        assert!(
+            !build_fzf_args(&[], false, items_have_ansi(&plain)).contains(&"--ansi".to_string())
+        );
+
+        // Colors never change the values, and the aligned plain text is identical
+        for (colored, plain) in colored.iter().zip(&plain) {
+            assert!(!colored.value.contains('\x1b'));
+            assert_eq!(colored.value, plain.value);
+            assert_eq!(console::strip_ansi_codes(&colored.display), plain.display);
+        }
+    }
+
+    #[test]
+    fn test_build_fzf_args_user_options_win() {
+        let extra = vec![
+            "--height=100%".to_string(),
+            "--layout=default".to_string(),
+            "--no-border".to_string(),
+            "--no-preview".to_string(),
+        ];
+        let args = build_fzf_args(&extra, false, false);
+        assert!(!args.iter().any(|a| a == "--height=50%"));
+        assert!(!args.iter().any(|a| a == "--reverse"));
+        assert!(!args.iter().any(|a| a == "--border"));
+        assert!(!args.iter().any(|a| a == "--preview" || a == "--multi"));
+        assert!(args.contains(&"--prompt=Select worktree: ".to_string()));
+        assert!(args.ends_with(&extra));
+    }
+
+    #[test]
+    fn test_build_fzf_args_flag_prefix_is_not_an_override() {
+        // --border-label is its own option and must not suppress --border
+        let args = build_fzf_args(&["--border-label=wt".to_string()], false, false);
+        assert!(args.contains(&"--border".to_string()));
+    }
+
+    #[test]
+    fn test_build_preview_command_contents() {
+        let cmd = build_preview_command();
+        assert!(cmd.contains("printf '%s\\n' {}"), "fzf placeholder: {cmd}");
+        assert!(cmd.contains(r#"git -C "$p" status --short --branch"#));
+        assert!(cmd.contains(r#"git -C "$p" log --oneline -n 10"#));
+        assert!(cmd.contains("Status") && cmd.contains("Recent commits"));
+        // The legacy word-splitting pipeline must be gone
+        assert!(!cmd.contains("awk") && !cmd.contains("xargs"));
+    }
+
+    #[test]
+    fn test_build_preview_command_extracts_path_with_spaces() {
+        let home = std::env::var("HOME").unwrap_or_default();
+        let line = "feature · [feature] · ~/my projects/wt";
+        // Simulate fzf's substitution of `{}` with a single-quoted line,
+        // and replace the git calls with a plain echo of the extracted path
+        let script = build_preview_command()
+            .replace("{}", &format!("'{line}'"))
+            .replace(r#"[ -d "$p" ] || exit 0"#, r#"echo "path=$p"; exit 0"#);
+        let output = Command::new("sh").arg("-c").arg(&script).output().unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert_eq!(stdout.trim(), format!("path={home}/my projects/wt"));
+    }
+
     #[test]
     fn test_build_worktree_items_basic() {
         let porcelain = r"worktree /path/to/main
@@ -314,7 +491,12 @@ HEAD def456
 branch refs/heads/feature-branch
 
 ";
-        let items = build_worktree_items(porcelain);
+        let items = build_worktree_items(
+            porcelain,
+            &WorktreeExclude::default(),
+            None,
+            ColorMode::Never,
+        );
         assert_eq!(items.len(), 2);
 
         // First item: main worktree displayed as "@" with [@] branch
@@ -330,6 +512,30 @@ branch refs/heads/feature-branch
         assert!(items[1].display.contains(" · /path/to/feature"));
     }
 
+    #[test]
+    fn test_build_worktree_items_excluded() {
+        let porcelain = "worktree /wt/release\nHEAD a\nbranch refs/heads/release\n\n\
+                         worktree /wt/main/release\nHEAD b\nbranch refs/heads/release-2\n\n\
+                         worktree /wt/main/feature\nHEAD c\nbranch refs/heads/feature\n\n\
+                         worktree /wt/main/archive/old\nHEAD d\nbranch refs/heads/old\n\n";
+        let exclude =
+            WorktreeExclude::new(&["release".to_string(), "glob:archive/*".to_string()]).unwrap();
+        let values: Vec<String> = build_worktree_items(porcelain, &exclude, None, ColorMode::Never)
+            .into_iter()
+            .map(|item| item.value)
+            .collect();
+        // The main worktree stays even though its branch matches
+        assert_eq!(values, vec!["/wt/release", "/wt/main/feature"]);
+
+        let all = build_worktree_items(
+            porcelain,
+            &WorktreeExclude::default(),
+            None,
+            ColorMode::Never,
+        );
+        assert_eq!(all.len(), 4);
+    }
+
     #[test]
     fn test_build_worktree_items_detached() {
         let porcelain = r"worktree /path/to/main
@@ -341,7 +547,12 @@ HEAD def456
 detached
 
 ";
-        let items = build_worktree_items(porcelain);
+        let items = build_worktree_items(
+            porcelain,
+            &WorktreeExclude::default(),
+            None,
+            ColorMode::Never,
+        );
         assert_eq!(items.len(), 2);
         assert_eq!(items[1].value, "/path/to/detached");
         assert!(items[1].display.contains("detached"));
@@ -355,7 +566,12 @@ HEAD abc123
 branch refs/heads/main
 
 ";
-        let items = build_worktree_items(porcelain);
+        let items = build_worktree_items(
+            porcelain,
+            &WorktreeExclude::default(),
+            None,
+            ColorMode::Never,
+        );
         assert_eq!(items.len(), 1);
         assert_eq!(items[0].value, "/path/to/main");
         assert!(items[0].display.starts_with('@'));
@@ -377,7 +593,12 @@ HEAD ghi789
 branch refs/heads/fix/bar
 
 ";
-        let items = build_worktree_items(porcelain);
+        let items = build_worktree_items(
+            porcelain,
+            &WorktreeExclude::default(),
+            None,
+            ColorMode::Never,
+        );
         assert_eq!(items.len(), 3);
 
         // Nested worktree names should use relative path from root
@@ -404,7 +625,12 @@ HEAD ghi789
 branch refs/heads/very-long-branch-name
 
 ";
-        let items = build_worktree_items(porcelain);
+        let items = build_worktree_items(
+            porcelain,
+            &WorktreeExclude::default(),
+            None,
+            ColorMode::Never,
+        );
         assert_eq!(items.len(), 3);
 
         // All "·" separators should be at the same column positions
@@ -442,7 +668,12 @@ HEAD def456
 branch refs/heads/feature-branch
 
 ";
-        let items = build_worktree_items(porcelain);
+        let items = build_worktree_items(
+            porcelain,
+            &WorktreeExclude::default(),
+            None,
+            ColorMode::Never,
+        );
         for item in &items {
             assert_eq!(
                 item.display,
@@ -460,7 +691,12 @@ branch refs/heads/feature-branch
         // This test pins display equivalence on canonical input so any future regression
         // in the unified parser surfaces here.
         let porcelain = "worktree /path/to/main\nHEAD abc123\nbranch refs/heads/main\n\nworktree /worktrees/feature\nHEAD def456\nbranch refs/heads/feature\n\n";
-        let items = build_worktree_items(porcelain);
+        let items = build_worktree_items(
+            porcelain,
+            &WorktreeExclude::default(),
+            None,
+            ColorMode::Never,
+        );
         assert_eq!(items.len(), 2);
         // Main entry — name `@`, branch `[@]`
         assert!(items[0].display.starts_with('@'), "main name marker");
@@ -3,6 +3,9 @@
 use anyhow::{Context, Result};
 use serde::Deserialize;
 use std::process::Command;
+use std::sync::OnceLock;
+
+use crate::integrations::{command_succeeds, probe_once};
 
 /// Information about a GitHub issue
 #[derive(Debug, Clone, Deserialize)]
@@ -10,7 +13,6 @@ use std::process::Command;
 pub struct IssueInfo {
     pub number: u32,
     pub title: String,
-    #[allow(dead_code)]
     pub url: String,
 }
 
@@ -20,13 +22,55 @@ pub struct IssueInfo {
 pub struct PrInfo {
     pub number: u32,
     pub title: String,
-    #[allow(dead_code)]
     pub url: String,
     pub head_ref_name: String,
     /// Whether this PR is from a fork (cross-repository)
     pub is_cross_repository: bool,
 }
 
+/// Failure of a `gh issue view` / `gh pr view` lookup
+///
+/// gh exits with status 1 for every error, so the kind is taken from stderr.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GhError {
+    /// No issue or pull request with that number
+    NotFound(String),
+    /// gh has no usable credentials
+    Unauthenticated(String),
+    /// Anything else: network errors, rate limits, missing permissions
+    Failed(String),
+}
+
+impl GhError {
+    /// Classify the stderr of a failed gh command
+    pub fn from_stderr(stderr: &str) -> Self {
+        let message = stderr.trim().to_string();
+        let lower = message.to_ascii_lowercase();
+        if lower.contains("gh auth login") || lower.contains("not logged in") {
+            Self::Unauthenticated(message)
+        } else if lower.contains("could not resolve to")
+            || lower.contains("no pull requests found")
+            || lower.contains("not found")
+        {
+            Self::NotFound(message)
+        } else {
+            Self::Failed(message)
+        }
+    }
+}
+
+impl std::fmt::Display for GhError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound(msg) | Self::Unauthenticated(msg) | Self::Failed(msg) => {
+                f.write_str(msg)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GhError {}
+
 /// Trait for interacting with GitHub CLI
 pub trait GhClient {
     /// Get information about an issue
@@ -35,8 +79,24 @@ pub trait GhClient {
     /// Get information about a pull request
     fn pr_info(&self, number: u32) -> Result<PrInfo>;
 
+    /// Get the `owner/name` of the repository gh resolves for the current directory
+    fn repo_name_with_owner(&self) -> Result<String>;
+
     /// Check if gh CLI is available
     fn is_available(&self) -> bool;
+
+    /// Whether gh is logged in (`gh auth status`)
+    ///
+    /// `Ok(false)` means gh reported missing credentials; `Err` covers any
+    /// other failure, which the lookups themselves will report in more detail.
+    fn auth_status(&self) -> Result<bool>;
+}
+
+/// `gh repo view --json nameWithOwner` response
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RepoView {
+    name_with_owner: String,
 }
 
 /// Real implementation of `GhClient` using `gh` CLI
@@ -57,7 +117,7 @@ impl GhClient for RealGhClient {
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("gh issue view failed: {stderr}");
+            return Err(GhError::from_stderr(&stderr).into());
         }
 
         let json = String::from_utf8_lossy(&output.stdout);
@@ -79,18 +139,50 @@ impl GhClient for RealGhClient {
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("gh pr view failed: {stderr}");
+            return Err(GhError::from_stderr(&stderr).into());
         }
 
         let json = String::from_utf8_lossy(&output.stdout);
         serde_json::from_str(&json).with_context(|| format!("Failed to parse PR info JSON: {json}"))
     }
 
+    fn repo_name_with_owner(&self) -> Result<String> {
+        let output = Command::new("gh")
+            .args(["repo", "view", "--json", "nameWithOwner"])
+            .output()
+            .context("Failed to execute gh command")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("gh repo view failed: {stderr}");
+        }
+
+        let json = String::from_utf8_lossy(&output.stdout);
+        let repo: RepoView = serde_json::from_str(&json)
+            .with_context(|| format!("Failed to parse repo info JSON: {json}"))?;
+        Ok(repo.name_with_owner)
+    }
+
+    /// Probed once per process: `gh --version` can be slow (update check)
     fn is_available(&self) -> bool {
-        Command::new("gh")
-            .arg("--version")
+        static AVAILABLE: OnceLock<bool> = OnceLock::new();
+        probe_once(&AVAILABLE, || command_succeeds("gh", "--version"))
+    }
+
+    fn auth_status(&self) -> Result<bool> {
+        let output = Command::new("gh")
+            .args(["auth", "status"])
             .output()
-            .is_ok_and(|output| output.status.success())
+            .context("Failed to execute gh command")?;
+
+        if output.status.success() {
+            return Ok(true);
+        }
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        match GhError::from_stderr(&stderr) {
+            GhError::Unauthenticated(_) => Ok(false),
+            err => Err(err.into()),
+        }
     }
 }
 
@@ -108,7 +200,9 @@ pub mod tests {
     pub struct MockGhClient {
         issue_result: Option<MockResult<IssueInfo>>,
         pr_result: Option<MockResult<PrInfo>>,
+        repo_result: Option<String>,
         available: bool,
+        authenticated: bool,
     }
 
     impl Default for MockGhClient {
@@ -123,7 +217,9 @@ pub mod tests {
             Self {
                 issue_result: None,
                 pr_result: None,
+                repo_result: None,
                 available: true,
+                authenticated: true,
             }
         }
 
@@ -151,33 +247,55 @@ pub mod tests {
             self
         }
 
+        #[must_use]
+        pub fn with_repo(mut self, name_with_owner: &str) -> Self {
+            self.repo_result = Some(name_with_owner.to_string());
+            self
+        }
+
         #[must_use]
         pub fn unavailable(mut self) -> Self {
             self.available = false;
             self
         }
+
+        #[must_use]
+        pub fn unauthenticated(mut self) -> Self {
+            self.authenticated = false;
+            self
+        }
     }
 
     impl GhClient for MockGhClient {
         fn issue_info(&self, _number: u32) -> Result<IssueInfo> {
             match &self.issue_result {
                 Some(MockResult::Ok(info)) => Ok(info.clone()),
-                Some(MockResult::Err(msg)) => Err(anyhow::anyhow!("{msg}")),
-                None => Err(anyhow::anyhow!("No issue result configured")),
+                Some(MockResult::Err(msg)) => Err(GhError::from_stderr(msg).into()),
+                None => Err(GhError::NotFound("No issue result configured".to_string()).into()),
             }
         }
 
         fn pr_info(&self, _number: u32) -> Result<PrInfo> {
             match &self.pr_result {
                 Some(MockResult::Ok(info)) => Ok(info.clone()),
-                Some(MockResult::Err(msg)) => Err(anyhow::anyhow!("{msg}")),
-                None => Err(anyhow::anyhow!("No PR result configured")),
+                Some(MockResult::Err(msg)) => Err(GhError::from_stderr(msg).into()),
+                None => Err(GhError::NotFound("No PR result configured".to_string()).into()),
             }
         }
 
+        fn repo_name_with_owner(&self) -> Result<String> {
+            self.repo_result
+                .clone()
+                .ok_or_else(|| anyhow::anyhow!("No repo result configured"))
+        }
+
         fn is_available(&self) -> bool {
             self.available
         }
+
+        fn auth_status(&self) -> Result<bool> {
+            Ok(self.authenticated)
+        }
     }
 
     #[test]
@@ -218,6 +336,32 @@ pub mod tests {
         assert_eq!(result.unwrap_err().to_string(), "Not found");
     }
 
+    #[test]
+    fn test_gh_error_from_stderr() {
+        assert!(matches!(
+            GhError::from_stderr(
+                "GraphQL: Could not resolve to an issue or pull request with the number of 999."
+            ),
+            GhError::NotFound(_)
+        ));
+        assert!(matches!(
+            GhError::from_stderr("no pull requests found for branch \"x\""),
+            GhError::NotFound(_)
+        ));
+        assert!(matches!(
+            GhError::from_stderr("To get started with GitHub CLI, please run:  gh auth login\n"),
+            GhError::Unauthenticated(_)
+        ));
+        assert!(matches!(
+            GhError::from_stderr("You are not logged into any GitHub hosts."),
+            GhError::Unauthenticated(_)
+        ));
+        assert_eq!(
+            GhError::from_stderr("error connecting to api.github.com\n"),
+            GhError::Failed("error connecting to api.github.com".to_string())
+        );
+    }
+
     #[test]
     fn test_mock_client_unavailable() {
         let client = MockGhClient::new().unavailable();
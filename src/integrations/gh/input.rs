@@ -3,7 +3,7 @@
 /// Represents the type of branch input provided by the user
 #[derive(Debug, PartialEq, Eq)]
 pub enum BranchInput {
-    /// GitHub issue or PR number (e.g., "#123")
+    /// GitHub issue or PR number (e.g., "#123" or a PR/issue URL)
     Github(u32),
     /// Plain branch name
     Plain(String),
@@ -12,7 +12,7 @@ pub enum BranchInput {
 impl BranchInput {
     /// Parse a branch name string into a `BranchInput`
     ///
-    /// Recognizes `#123` pattern as GitHub issue/PR number.
+    /// Recognizes `#123` and GitHub PR/issue URLs as GitHub issue/PR numbers.
     /// Everything else is treated as a plain branch name.
     pub fn parse(input: &str) -> Self {
         if let Some(stripped) = input.strip_prefix('#') {
@@ -20,10 +20,46 @@ impl BranchInput {
                 return Self::Github(number);
             }
         }
+        if let Some((_repo, number)) = parse_github_url(input) {
+            return Self::Github(number);
+        }
         Self::Plain(input.to_string())
     }
 }
 
+/// Parse a `--from-pr` value: `#123`, a bare `123`, or a PR/issue URL
+///
+/// Returns the input in a form [`BranchInput::parse`] accepts (`#123` or the URL).
+pub fn normalize_pr_reference(input: &str) -> Option<String> {
+    let input = input.trim();
+    let digits = input.strip_prefix('#').unwrap_or(input);
+    if digits.parse::<u32>().is_ok() {
+        return Some(format!("#{digits}"));
+    }
+    parse_github_url(input).map(|_| input.to_string())
+}
+
+/// Extract `owner/name` and the number from a GitHub PR or issue URL
+///
+/// Accepts `https://<host>/<owner>/<name>/pull/<n>` (and `/issues/<n>`), with
+/// optional trailing segments, query or fragment (e.g. `/files`, `#issuecomment-1`).
+/// The host is not checked so GitHub Enterprise URLs work too.
+pub fn parse_github_url(input: &str) -> Option<(String, u32)> {
+    let rest = input
+        .strip_prefix("https://")
+        .or_else(|| input.strip_prefix("http://"))?;
+    let rest = rest.split(['?', '#']).next()?;
+    let mut segments = rest.split('/');
+    let _host = segments.next()?;
+    let owner = segments.next().filter(|owner| !owner.is_empty())?;
+    let name = segments.next().filter(|name| !name.is_empty())?;
+    if !matches!(segments.next()?, "pull" | "issues") {
+        return None;
+    }
+    let number = segments.next()?.parse::<u32>().ok()?;
+    Some((format!("{owner}/{name}"), number))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -52,6 +88,69 @@ mod tests {
         assert_eq!(result, BranchInput::Github(1));
     }
 
+    #[test]
+    fn test_parse_pr_url() {
+        assert_eq!(
+            BranchInput::parse("https://github.com/org/repo/pull/123"),
+            BranchInput::Github(123)
+        );
+        assert_eq!(
+            BranchInput::parse("https://github.com/org/repo/issues/7"),
+            BranchInput::Github(7)
+        );
+        assert_eq!(
+            BranchInput::parse("https://github.example.com/org/repo/pull/5/files?w=1#diff"),
+            BranchInput::Github(5)
+        );
+    }
+
+    #[test]
+    fn test_parse_non_matching_urls_stay_plain() {
+        for input in [
+            "https://github.com/org/repo",
+            "https://github.com/org/repo/tree/main",
+            "https://github.com/org/repo/pull/abc",
+            "https://github.com//repo/pull/1",
+            "ftp://github.com/org/repo/pull/1",
+        ] {
+            assert_eq!(
+                BranchInput::parse(input),
+                BranchInput::Plain(input.to_string()),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_bare_number_is_a_branch_name() {
+        // Only `--from-pr` treats a bare number as a reference
+        assert_eq!(
+            BranchInput::parse("123"),
+            BranchInput::Plain("123".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_github_url_extracts_repo() {
+        assert_eq!(
+            parse_github_url("https://github.com/Org/Repo/pull/42"),
+            Some(("Org/Repo".to_string(), 42))
+        );
+        assert_eq!(parse_github_url("#42"), None);
+    }
+
+    #[test]
+    fn test_normalize_pr_reference() {
+        assert_eq!(normalize_pr_reference("#123").as_deref(), Some("#123"));
+        assert_eq!(normalize_pr_reference("123").as_deref(), Some("#123"));
+        assert_eq!(
+            normalize_pr_reference("https://github.com/org/repo/pull/123").as_deref(),
+            Some("https://github.com/org/repo/pull/123")
+        );
+        assert_eq!(normalize_pr_reference("feature"), None);
+        assert_eq!(normalize_pr_reference("#abc"), None);
+    }
+
     #[test]
     fn test_parse_large_number() {
         let result = BranchInput::parse("#99999");
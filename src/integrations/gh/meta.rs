@@ -0,0 +1,213 @@
+//! GitHub reference recorded for worktrees created from `#123`
+//!
+//! The metadata lives in the worktree's private git directory
+//! (`.git/worktrees/<name>/ofsht.toml`), so it never shows up in `git status`
+//! and is deleted together with the worktree by `git worktree remove`.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// File name of the metadata inside the worktree's git directory
+const META_FILE: &str = "ofsht.toml";
+
+/// Whether the reference was a pull request or an issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GithubRefKind {
+    Pr,
+    Issue,
+}
+
+/// The PR or issue a worktree was created from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GithubRef {
+    pub kind: GithubRefKind,
+    pub number: u32,
+    pub title: String,
+    pub url: String,
+    /// Head branch of a pull request (`None` for issues and older metadata)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub head_ref: Option<String>,
+}
+
+impl GithubRefKind {
+    /// Value of `OFSHT_GH_KIND`: `pr` or `issue`
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Pr => "pr",
+            Self::Issue => "issue",
+        }
+    }
+
+    /// Label used in status lines: `PR` or `issue`
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Pr => "PR",
+            Self::Issue => "issue",
+        }
+    }
+}
+
+impl GithubRef {
+    /// Environment variables describing the reference to create hooks
+    ///
+    /// `OFSHT_GH_HEAD_REF` is only set for pull requests.
+    #[must_use]
+    pub fn hook_env(&self) -> Vec<(&'static str, String)> {
+        let mut env = vec![
+            ("OFSHT_GH_KIND", self.kind.as_str().to_string()),
+            ("OFSHT_GH_NUMBER", self.number.to_string()),
+            ("OFSHT_GH_TITLE", self.title.clone()),
+            ("OFSHT_GH_URL", self.url.clone()),
+        ];
+        if let Some(head_ref) = &self.head_ref {
+            env.push(("OFSHT_GH_HEAD_REF", head_ref.clone()));
+        }
+        env
+    }
+}
+
+/// Git directory of a worktree: `<path>/.git` for the main worktree, or the
+/// directory named by the `gitdir:` line of a linked worktree's `.git` file
+fn worktree_git_dir(worktree: &Path) -> Option<PathBuf> {
+    let dot_git = worktree.join(".git");
+    if dot_git.is_dir() {
+        return Some(dot_git);
+    }
+    let content = std::fs::read_to_string(&dot_git).ok()?;
+    let git_dir = content
+        .lines()
+        .find_map(|line| line.strip_prefix("gitdir:"))?;
+    Some(worktree.join(git_dir.trim()))
+}
+
+/// Metadata recorded for `worktree` (best-effort; `None` when missing or unreadable)
+pub fn load(worktree: &Path) -> Option<GithubRef> {
+    let path = worktree_git_dir(worktree)?.join(META_FILE);
+    let content = std::fs::read_to_string(path).ok()?;
+    toml::from_str(&content).ok()
+}
+
+/// Record the reference `worktree` was created from
+///
+/// # Errors
+/// Returns an error if the worktree's git directory cannot be found or the
+/// file cannot be written
+pub fn save(worktree: &Path, github_ref: &GithubRef) -> Result<()> {
+    let git_dir = worktree_git_dir(worktree)
+        .with_context(|| format!("No git directory found for {}", worktree.display()))?;
+    let content = toml::to_string(github_ref).context("Failed to serialize worktree metadata")?;
+    let path = git_dir.join(META_FILE);
+    std::fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> GithubRef {
+        GithubRef {
+            kind: GithubRefKind::Pr,
+            number: 456,
+            title: "Add feature".to_string(),
+            url: "https://github.com/owner/repo/pull/456".to_string(),
+            head_ref: Some("feature/add".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_linked_worktree() {
+        let temp = tempfile::tempdir().unwrap();
+        let git_dir = temp.path().join("repo/.git/worktrees/feature");
+        let worktree = temp.path().join("feature");
+        std::fs::create_dir_all(&git_dir).unwrap();
+        std::fs::create_dir_all(&worktree).unwrap();
+        std::fs::write(
+            worktree.join(".git"),
+            format!("gitdir: {}\n", git_dir.display()),
+        )
+        .unwrap();
+
+        save(&worktree, &sample()).unwrap();
+        assert!(git_dir.join(META_FILE).exists());
+        assert_eq!(load(&worktree), Some(sample()));
+    }
+
+    #[test]
+    fn test_relative_gitdir_is_resolved_against_worktree() {
+        let temp = tempfile::tempdir().unwrap();
+        let worktree = temp.path().join("feature");
+        std::fs::create_dir_all(temp.path().join("repo/.git/worktrees/feature")).unwrap();
+        std::fs::create_dir_all(&worktree).unwrap();
+        std::fs::write(
+            worktree.join(".git"),
+            "gitdir: ../repo/.git/worktrees/feature\n",
+        )
+        .unwrap();
+
+        save(&worktree, &sample()).unwrap();
+        assert!(temp
+            .path()
+            .join("repo/.git/worktrees/feature")
+            .join(META_FILE)
+            .exists());
+    }
+
+    #[test]
+    fn test_load_missing_or_invalid_is_none() {
+        let temp = tempfile::tempdir().unwrap();
+        assert_eq!(load(temp.path()), None);
+
+        std::fs::create_dir_all(temp.path().join(".git")).unwrap();
+        std::fs::write(temp.path().join(".git").join(META_FILE), "number = \"x\"").unwrap();
+        assert_eq!(load(temp.path()), None);
+    }
+
+    #[test]
+    fn test_save_without_git_dir_fails() {
+        let temp = tempfile::tempdir().unwrap();
+        assert!(save(temp.path(), &sample()).is_err());
+    }
+
+    #[test]
+    fn test_metadata_without_head_ref_still_loads() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(temp.path().join(".git")).unwrap();
+        std::fs::write(
+            temp.path().join(".git").join(META_FILE),
+            "kind = \"issue\"\nnumber = 7\ntitle = \"Bug\"\nurl = \"https://x/7\"\n",
+        )
+        .unwrap();
+        assert_eq!(load(temp.path()).unwrap().head_ref, None);
+    }
+
+    #[test]
+    fn test_hook_env() {
+        let env = sample().hook_env();
+        assert_eq!(
+            env,
+            vec![
+                ("OFSHT_GH_KIND", "pr".to_string()),
+                ("OFSHT_GH_NUMBER", "456".to_string()),
+                ("OFSHT_GH_TITLE", "Add feature".to_string()),
+                (
+                    "OFSHT_GH_URL",
+                    "https://github.com/owner/repo/pull/456".to_string()
+                ),
+                ("OFSHT_GH_HEAD_REF", "feature/add".to_string()),
+            ]
+        );
+
+        let issue = GithubRef {
+            kind: GithubRefKind::Issue,
+            head_ref: None,
+            ..sample()
+        };
+        let env = issue.hook_env();
+        assert_eq!(env[0], ("OFSHT_GH_KIND", "issue".to_string()));
+        assert!(env.iter().all(|(key, _)| *key != "OFSHT_GH_HEAD_REF"));
+    }
+}
@@ -2,12 +2,15 @@
 #![allow(clippy::must_use_candidate)]
 mod client;
 mod input;
+pub mod meta;
+mod remote;
 
-pub use client::{GhClient, PrInfo, RealGhClient};
+pub use client::{GhClient, GhError, PrInfo, RealGhClient};
 
 #[cfg(test)]
 pub use client::{tests::MockGhClient, IssueInfo};
-pub use input::BranchInput;
+pub use input::{normalize_pr_reference, parse_github_url, BranchInput};
+pub use remote::{parse_remotes, select_pr_remote};
 
 /// Build a branch name from an issue number
 ///
@@ -16,6 +19,30 @@ pub fn build_issue_branch(number: u32) -> String {
     format!("issue-{number}")
 }
 
+/// Prefix of the refs fork PR heads are fetched into
+pub const PR_REF_PREFIX: &str = "refs/ofsht/pr/";
+
+/// Ref a fork PR's head is fetched into: `refs/ofsht/pr/{number}`
+pub fn pr_ref(number: u32) -> String {
+    format!("{PR_REF_PREFIX}{number}")
+}
+
+/// PR number of a ref built by [`pr_ref`]
+pub fn parse_pr_ref(refname: &str) -> Option<u32> {
+    refname.strip_prefix(PR_REF_PREFIX)?.parse().ok()
+}
+
+/// Refs in `for-each-ref` output that [`pr_ref`] could have created
+///
+/// Anything else under `refs/ofsht/` is left alone.
+pub fn owned_pr_refs(for_each_ref_output: &str) -> Vec<&str> {
+    for_each_ref_output
+        .lines()
+        .map(str::trim)
+        .filter(|refname| parse_pr_ref(refname).is_some())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -30,6 +57,25 @@ mod tests {
         assert_eq!(build_issue_branch(1), "issue-1");
     }
 
+    #[test]
+    fn test_pr_ref_round_trip() {
+        assert_eq!(pr_ref(456), "refs/ofsht/pr/456");
+        assert_eq!(parse_pr_ref(&pr_ref(456)), Some(456));
+        assert_eq!(parse_pr_ref("refs/pull/456/head"), None);
+        assert_eq!(parse_pr_ref("refs/ofsht/pr/x"), None);
+        assert_eq!(parse_pr_ref("refs/heads/ofsht/pr/1"), None);
+    }
+
+    #[test]
+    fn test_owned_pr_refs_keeps_only_pr_refs() {
+        let output = "refs/ofsht/pr/1\nrefs/heads/ofsht/pr/2\nrefs/tags/v1\n\nrefs/ofsht/pr/3\n\
+                      refs/ofshtx/pr/4\nrefs/ofsht/other\nrefs/ofsht/pr/x\nrefs/ofsht/pr/5/y\n";
+        assert_eq!(
+            owned_pr_refs(output),
+            vec!["refs/ofsht/pr/1", "refs/ofsht/pr/3"]
+        );
+    }
+
     #[test]
     fn test_build_issue_branch_large_number() {
         assert_eq!(build_issue_branch(99999), "issue-99999");
@@ -2,7 +2,7 @@
 #![allow(clippy::must_use_candidate)]
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 /// Git client interface for git operations.
@@ -15,7 +15,8 @@ pub trait GitClient {
     ///
     /// When `start_point` is `Some`, runs `git worktree add -b <branch> <path> <start>`.
     /// When `start_point` is `None`, the implementation checks whether `branch`
-    /// already exists; if so, runs `git worktree add <path> <branch>`,
+    /// already exists locally or in exactly one remote; if so, runs
+    /// `git worktree add <path> <branch>` (which tracks the remote branch),
     /// otherwise `git worktree add -b <branch> <path>`.
     fn create_worktree(
         &self,
@@ -25,18 +26,46 @@ pub trait GitClient {
         dir: Option<&Path>,
     ) -> Result<()>;
 
+    /// Run `git worktree add --detach <path> <commitish>`.
+    fn create_detached_worktree(
+        &self,
+        path: &Path,
+        commitish: &str,
+        dir: Option<&Path>,
+    ) -> Result<()>;
+
     /// Run `git worktree list --porcelain`.
     fn list_worktrees(&self, dir: Option<&Path>) -> Result<String>;
 
-    /// Run `git worktree remove <path>`.
-    fn remove_worktree(&self, path: &Path, dir: Option<&Path>) -> Result<()>;
+    /// Run `git worktree remove [--force --force] <path>`.
+    ///
+    /// `force` is passed twice so locked worktrees are removed as well.
+    fn remove_worktree(&self, path: &Path, force: bool, dir: Option<&Path>) -> Result<()>;
+
+    /// Run `git -C <worktree_path> status --porcelain` and return stdout
+    /// (empty when the worktree has no uncommitted or untracked changes).
+    fn status_porcelain(&self, worktree_path: &Path) -> Result<String>;
+
+    /// Run `git worktree lock [--reason <reason>] <path>`.
+    fn lock_worktree(&self, path: &Path, reason: Option<&str>, dir: Option<&Path>) -> Result<()>;
+
+    /// Run `git worktree unlock <path>`.
+    fn unlock_worktree(&self, path: &Path, dir: Option<&Path>) -> Result<()>;
 
     /// Run `git branch -D <branch>`.
     ///
-    /// Returns `Ok(true)` on success, `Ok(false)` when git exits non-zero
-    /// (lenient case used by callers that treat deletion failure as a warning),
-    /// and `Err` only when the git process cannot be spawned.
-    fn remove_branch(&self, branch: &str, dir: Option<&Path>) -> Result<bool>;
+    /// A non-zero exit is not an error: it is classified from git's stderr
+    /// (lenient case used by callers that treat deletion failure as a warning).
+    /// `Err` only when the git process cannot be spawned.
+    fn remove_branch(&self, branch: &str, dir: Option<&Path>) -> Result<BranchDeletion>;
+
+    /// Run a single `git branch -D <branch>...` for all `branches`.
+    ///
+    /// Returns the names git reports as deleted and, for the rest, git's
+    /// reason (branches that no longer exist are left out). `Err` only when
+    /// the git process cannot be spawned.
+    fn remove_branches(&self, branches: &[&str], dir: Option<&Path>)
+        -> Result<BranchBatchDeletion>;
 
     /// Run `git rev-parse --verify <ref>` and return whether it succeeded.
     ///
@@ -53,14 +82,75 @@ pub trait GitClient {
     /// `fetch`).
     fn fetch(&self, args: &[&str], dir: Option<&Path>) -> Result<()>;
 
+    /// Run `git remote -v` and return stdout (`<name>\t<url> (fetch|push)` lines).
+    fn remote_list(&self, dir: Option<&Path>) -> Result<String>;
+
+    /// Run `git remote prune <remote>` to drop remote-tracking branches that
+    /// no longer exist on the remote.
+    fn prune_remote(&self, remote: &str, dir: Option<&Path>) -> Result<()>;
+
+    /// Run `git update-ref -d <ref>` for each of `refs`, stopping at the first failure.
+    fn delete_refs(&self, refs: &[&str], dir: Option<&Path>) -> Result<()>;
+
     /// Run `git for-each-ref --format=<format> <refs...>` and return stdout.
     fn for_each_ref(&self, refs: &[&str], format: &str, dir: Option<&Path>) -> Result<String>;
 
+    /// Run `git for-each-ref --format=<format> --sort=<sort> --count=<count> <patterns...>`
+    /// and return stdout. Used where only the top `count` refs are needed.
+    fn for_each_ref_sorted(
+        &self,
+        patterns: &[&str],
+        format: &str,
+        sort: &str,
+        count: usize,
+        dir: Option<&Path>,
+    ) -> Result<String>;
+
+    /// Whether exactly one remote has a remote-tracking branch named `branch`
+    /// (the case `git worktree add <path> <branch>` turns into a tracking branch)
+    fn unique_remote_branch(&self, branch: &str, dir: Option<&Path>) -> bool {
+        self.for_each_ref(&[&format!("refs/remotes/*/{branch}")], "%(refname)", dir)
+            .is_ok_and(|stdout| stdout.lines().count() == 1)
+    }
+
+    /// Run `git stash push --include-untracked -m <message>`.
+    fn stash_push(&self, message: &str, dir: Option<&Path>) -> Result<()>;
+
+    /// Run `git stash list --format=%H` and return stdout (newest entry first).
+    fn stash_list(&self, dir: Option<&Path>) -> Result<String>;
+
+    /// Run `git stash pop <stash_ref>`. Git keeps the entry when the pop conflicts.
+    fn stash_pop(&self, stash_ref: &str, dir: Option<&Path>) -> Result<()>;
+
+    /// Run `git merge --no-edit <rev>`. A conflicting merge fails and leaves
+    /// the conflicts in the working tree.
+    fn merge(&self, rev: &str, dir: Option<&Path>) -> Result<()>;
+
     /// Run `git -C <worktree_path> log -1 --format=%ct` and return the
     /// resulting timestamp. Returns `None` for any failure (spawn / non-zero
     /// exit / parse) to preserve the prior `domain::worktree::get_last_commit_time`
     /// silent-failure semantics.
     fn last_commit_time(&self, worktree_path: &Path) -> Option<DateTime<Utc>>;
+
+    /// [`Self::last_commit_time`] for many worktrees, one thread per path so the
+    /// `git log` processes run concurrently. Results follow the order of `paths`.
+    fn last_commit_times_parallel(&self, paths: &[PathBuf]) -> Vec<Option<DateTime<Utc>>>
+    where
+        Self: Sync,
+    {
+        // Spawn every thread before joining any, otherwise they run one by one
+        #[allow(clippy::needless_collect)]
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = paths
+                .iter()
+                .map(|path| scope.spawn(move || self.last_commit_time(path)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap_or(None))
+                .collect()
+        })
+    }
 }
 
 /// Real git implementation. Zero-sized type.
@@ -75,6 +165,14 @@ fn build_command(dir: Option<&Path>) -> Command {
     cmd
 }
 
+/// [`build_command`] with git's messages left untranslated, for commands
+/// whose human-readable output is parsed (e.g. `Deleted branch ...`)
+fn build_c_locale_command(dir: Option<&Path>) -> Command {
+    let mut cmd = build_command(dir);
+    cmd.env("LC_ALL", "C");
+    cmd
+}
+
 /// Spawn the configured command, fail with `git {op} failed: ...` on non-zero
 /// exit, and return stdout on success. Centralizes the spawn-context, status
 /// check, and bail pattern shared by every `RealGitClient` method that
@@ -90,6 +188,94 @@ fn run_capturing(mut cmd: Command, op: &str) -> Result<String> {
     Ok(String::from_utf8_lossy(&output.stdout).to_string())
 }
 
+/// Extract branch names from `git branch -D` output
+/// (`Deleted branch <name> (was <sha>).` per line)
+fn parse_deleted_branches(stdout: &str) -> Vec<String> {
+    stdout
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("Deleted branch "))
+        .filter_map(|rest| rest.split(" (was ").next())
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Result of deleting one branch with `git branch -D/-d`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BranchDeletion {
+    Deleted,
+    /// The branch no longer exists; nothing worth reporting
+    NotFound,
+    /// Checked out in another worktree (git's reason attached)
+    CheckedOut(String),
+    /// `git branch -d` refused an unmerged branch (git's reason attached)
+    NotFullyMerged(String),
+    /// Any other failure (git's reason attached)
+    Failed(String),
+}
+
+impl BranchDeletion {
+    /// Classify a failed `git branch -D/-d` from its stderr
+    pub fn from_stderr(stderr: &str) -> Self {
+        let reason = stderr
+            .lines()
+            .map(str::trim)
+            .find(|line| line.starts_with("error:") || line.starts_with("fatal:"))
+            .or_else(|| stderr.lines().map(str::trim).find(|line| !line.is_empty()))
+            .unwrap_or("git branch exited with an error");
+        let reason = reason
+            .strip_prefix("error:")
+            .or_else(|| reason.strip_prefix("fatal:"))
+            .unwrap_or(reason)
+            .trim()
+            .to_string();
+        let lower = reason.to_lowercase();
+        if lower.contains("not found") {
+            Self::NotFound
+        } else if lower.contains("checked out at") || lower.contains("used by worktree at") {
+            Self::CheckedOut(reason)
+        } else if lower.contains("not fully merged") {
+            Self::NotFullyMerged(reason)
+        } else {
+            Self::Failed(reason)
+        }
+    }
+
+    /// Git's reason for a failure worth reporting (`None` when the branch was
+    /// deleted or did not exist)
+    pub fn failure_reason(&self) -> Option<&str> {
+        match self {
+            Self::Deleted | Self::NotFound => None,
+            Self::CheckedOut(reason) | Self::NotFullyMerged(reason) | Self::Failed(reason) => {
+                Some(reason)
+            }
+        }
+    }
+}
+
+/// Result of deleting several branches with one `git branch -D`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BranchBatchDeletion {
+    pub deleted: Vec<String>,
+    /// `(branch, reason)` for branches git refused to delete
+    pub failed: Vec<(String, String)>,
+}
+
+/// Collect reportable failures from a batch `git branch -D` stderr
+/// (one `error: ... '<branch>' ...` line per branch)
+fn parse_branch_delete_failures(stderr: &str) -> Vec<(String, String)> {
+    stderr
+        .lines()
+        .filter(|line| line.trim_start().starts_with("error:"))
+        .filter_map(|line| {
+            let branch = line.split('\'').nth(1)?.to_string();
+            let reason = BranchDeletion::from_stderr(line)
+                .failure_reason()?
+                .to_string();
+            Some((branch, reason))
+        })
+        .collect()
+}
+
 impl GitClient for RealGitClient {
     fn create_worktree(
         &self,
@@ -103,7 +289,8 @@ impl GitClient for RealGitClient {
 
         if let Some(start) = start_point {
             cmd.arg("-b").arg(branch).arg(path).arg(start);
-        } else if self.branch_exists(branch, dir)? {
+        } else if self.branch_exists(branch, dir)? || self.unique_remote_branch(branch, dir) {
+            // For a branch only one remote has, git creates a local branch tracking it
             cmd.arg(path).arg(branch);
         } else {
             cmd.arg("-b").arg(branch).arg(path);
@@ -113,27 +300,98 @@ impl GitClient for RealGitClient {
         Ok(())
     }
 
+    fn create_detached_worktree(
+        &self,
+        path: &Path,
+        commitish: &str,
+        dir: Option<&Path>,
+    ) -> Result<()> {
+        let mut cmd = build_command(dir);
+        cmd.args(["worktree", "add", "--detach"])
+            .arg(path)
+            .arg(commitish);
+        run_capturing(cmd, "worktree add")?;
+        Ok(())
+    }
+
     fn list_worktrees(&self, dir: Option<&Path>) -> Result<String> {
         let mut cmd = build_command(dir);
         cmd.args(["worktree", "list", "--porcelain"]);
         run_capturing(cmd, "worktree list")
     }
 
-    fn remove_worktree(&self, path: &Path, dir: Option<&Path>) -> Result<()> {
+    fn remove_worktree(&self, path: &Path, force: bool, dir: Option<&Path>) -> Result<()> {
         let mut cmd = build_command(dir);
-        cmd.arg("worktree").arg("remove").arg(path);
+        cmd.arg("worktree").arg("remove");
+        if force {
+            // A second --force is needed for locked worktrees
+            cmd.arg("--force").arg("--force");
+        }
+        cmd.arg(path);
         run_capturing(cmd, "worktree remove")?;
         Ok(())
     }
 
-    fn remove_branch(&self, branch: &str, dir: Option<&Path>) -> Result<bool> {
+    fn status_porcelain(&self, worktree_path: &Path) -> Result<String> {
+        let mut cmd = Command::new("git");
+        cmd.arg("-C")
+            .arg(worktree_path)
+            .args(["status", "--porcelain"]);
+        run_capturing(cmd, "status")
+    }
+
+    fn lock_worktree(&self, path: &Path, reason: Option<&str>, dir: Option<&Path>) -> Result<()> {
+        let mut cmd = build_command(dir);
+        cmd.arg("worktree").arg("lock");
+        if let Some(reason) = reason {
+            cmd.arg("--reason").arg(reason);
+        }
+        cmd.arg(path);
+        run_capturing(cmd, "worktree lock")?;
+        Ok(())
+    }
+
+    fn unlock_worktree(&self, path: &Path, dir: Option<&Path>) -> Result<()> {
         let mut cmd = build_command(dir);
+        cmd.arg("worktree").arg("unlock").arg(path);
+        run_capturing(cmd, "worktree unlock")?;
+        Ok(())
+    }
+
+    fn remove_branch(&self, branch: &str, dir: Option<&Path>) -> Result<BranchDeletion> {
+        let mut cmd = build_c_locale_command(dir);
         let output = cmd
             .args(["branch", "-D", branch])
             .output()
             .context("Failed to execute git branch -D")?;
 
-        Ok(output.status.success())
+        if output.status.success() {
+            return Ok(BranchDeletion::Deleted);
+        }
+        Ok(BranchDeletion::from_stderr(&String::from_utf8_lossy(
+            &output.stderr,
+        )))
+    }
+
+    fn remove_branches(
+        &self,
+        branches: &[&str],
+        dir: Option<&Path>,
+    ) -> Result<BranchBatchDeletion> {
+        if branches.is_empty() {
+            return Ok(BranchBatchDeletion::default());
+        }
+        let mut cmd = build_c_locale_command(dir);
+        let output = cmd
+            .args(["branch", "-D"])
+            .args(branches)
+            .output()
+            .context("Failed to execute git branch -D")?;
+
+        Ok(BranchBatchDeletion {
+            deleted: parse_deleted_branches(&String::from_utf8_lossy(&output.stdout)),
+            failed: parse_branch_delete_failures(&String::from_utf8_lossy(&output.stderr)),
+        })
     }
 
     fn branch_exists(&self, ref_: &str, dir: Option<&Path>) -> Result<bool> {
@@ -158,6 +416,28 @@ impl GitClient for RealGitClient {
         Ok(())
     }
 
+    fn remote_list(&self, dir: Option<&Path>) -> Result<String> {
+        let mut cmd = build_command(dir);
+        cmd.args(["remote", "-v"]);
+        run_capturing(cmd, "remote")
+    }
+
+    fn prune_remote(&self, remote: &str, dir: Option<&Path>) -> Result<()> {
+        let mut cmd = build_command(dir);
+        cmd.args(["remote", "prune", remote]);
+        run_capturing(cmd, "remote prune")?;
+        Ok(())
+    }
+
+    fn delete_refs(&self, refs: &[&str], dir: Option<&Path>) -> Result<()> {
+        for ref_ in refs {
+            let mut cmd = build_command(dir);
+            cmd.args(["update-ref", "-d", ref_]);
+            run_capturing(cmd, "update-ref")?;
+        }
+        Ok(())
+    }
+
     fn for_each_ref(&self, refs: &[&str], format: &str, dir: Option<&Path>) -> Result<String> {
         let mut cmd = build_command(dir);
         cmd.arg("for-each-ref")
@@ -166,6 +446,50 @@ impl GitClient for RealGitClient {
         run_capturing(cmd, "for-each-ref")
     }
 
+    fn for_each_ref_sorted(
+        &self,
+        patterns: &[&str],
+        format: &str,
+        sort: &str,
+        count: usize,
+        dir: Option<&Path>,
+    ) -> Result<String> {
+        let mut cmd = build_command(dir);
+        cmd.arg("for-each-ref")
+            .arg(format!("--format={format}"))
+            .arg(format!("--sort={sort}"))
+            .arg(format!("--count={count}"))
+            .args(patterns);
+        run_capturing(cmd, "for-each-ref")
+    }
+
+    fn stash_push(&self, message: &str, dir: Option<&Path>) -> Result<()> {
+        let mut cmd = build_command(dir);
+        cmd.args(["stash", "push", "--include-untracked", "-m", message]);
+        run_capturing(cmd, "stash push")?;
+        Ok(())
+    }
+
+    fn stash_list(&self, dir: Option<&Path>) -> Result<String> {
+        let mut cmd = build_command(dir);
+        cmd.args(["stash", "list", "--format=%H"]);
+        run_capturing(cmd, "stash list")
+    }
+
+    fn stash_pop(&self, stash_ref: &str, dir: Option<&Path>) -> Result<()> {
+        let mut cmd = build_command(dir);
+        cmd.args(["stash", "pop", stash_ref]);
+        run_capturing(cmd, "stash pop")?;
+        Ok(())
+    }
+
+    fn merge(&self, rev: &str, dir: Option<&Path>) -> Result<()> {
+        let mut cmd = build_command(dir);
+        cmd.args(["merge", "--no-edit", rev]);
+        run_capturing(cmd, "merge")?;
+        Ok(())
+    }
+
     fn last_commit_time(&self, worktree_path: &Path) -> Option<DateTime<Utc>> {
         let output = Command::new("git")
             .args([
@@ -208,12 +532,21 @@ pub mod tests {
         pub create_should_fail: bool,
         pub list_output: String,
         pub remove_worktree_should_fail: bool,
+        pub status_output: String,
+        pub lock_should_fail: bool,
         pub remove_branch_returns: bool,
+        /// Stderr reported when `remove_branch_returns` is false
+        pub remove_branch_stderr: String,
         pub branch_exists_value: bool,
         pub rev_parse_output: String,
         pub rev_parse_should_fail: bool,
         pub fetch_should_fail: bool,
+        pub remote_output: String,
         pub for_each_ref_output: String,
+        pub stash_list_output: String,
+        pub stash_pop_should_fail: bool,
+        /// Revisions whose `merge` fails
+        pub merge_fails_for: Vec<String>,
         pub last_commit_time_value: Option<DateTime<Utc>>,
     }
 
@@ -231,19 +564,83 @@ pub mod tests {
             Ok(())
         }
 
+        fn create_detached_worktree(
+            &self,
+            _path: &Path,
+            _commitish: &str,
+            _dir: Option<&Path>,
+        ) -> Result<()> {
+            if self.create_should_fail {
+                anyhow::bail!("Mock git create detached worktree failure");
+            }
+            Ok(())
+        }
+
         fn list_worktrees(&self, _dir: Option<&Path>) -> Result<String> {
             Ok(self.list_output.clone())
         }
 
-        fn remove_worktree(&self, _path: &Path, _dir: Option<&Path>) -> Result<()> {
+        fn remove_worktree(&self, _path: &Path, _force: bool, _dir: Option<&Path>) -> Result<()> {
             if self.remove_worktree_should_fail {
                 anyhow::bail!("Mock git remove worktree failure");
             }
             Ok(())
         }
 
-        fn remove_branch(&self, _branch: &str, _dir: Option<&Path>) -> Result<bool> {
-            Ok(self.remove_branch_returns)
+        fn status_porcelain(&self, _worktree_path: &Path) -> Result<String> {
+            Ok(self.status_output.clone())
+        }
+
+        fn lock_worktree(
+            &self,
+            _path: &Path,
+            _reason: Option<&str>,
+            _dir: Option<&Path>,
+        ) -> Result<()> {
+            if self.lock_should_fail {
+                anyhow::bail!("Mock git worktree lock failure");
+            }
+            Ok(())
+        }
+
+        fn unlock_worktree(&self, _path: &Path, _dir: Option<&Path>) -> Result<()> {
+            if self.lock_should_fail {
+                anyhow::bail!("Mock git worktree unlock failure");
+            }
+            Ok(())
+        }
+
+        fn remove_branch(&self, _branch: &str, _dir: Option<&Path>) -> Result<BranchDeletion> {
+            if self.remove_branch_returns {
+                return Ok(BranchDeletion::Deleted);
+            }
+            Ok(BranchDeletion::from_stderr(&self.remove_branch_stderr))
+        }
+
+        fn remove_branches(
+            &self,
+            branches: &[&str],
+            _dir: Option<&Path>,
+        ) -> Result<BranchBatchDeletion> {
+            if !self.remove_branch_returns {
+                let failed = branches
+                    .iter()
+                    .filter_map(|b| {
+                        let deletion = BranchDeletion::from_stderr(&self.remove_branch_stderr);
+                        deletion
+                            .failure_reason()
+                            .map(|reason| ((*b).to_string(), reason.to_string()))
+                    })
+                    .collect();
+                return Ok(BranchBatchDeletion {
+                    deleted: Vec::new(),
+                    failed,
+                });
+            }
+            Ok(BranchBatchDeletion {
+                deleted: branches.iter().map(ToString::to_string).collect(),
+                failed: Vec::new(),
+            })
         }
 
         fn branch_exists(&self, _ref_: &str, _dir: Option<&Path>) -> Result<bool> {
@@ -264,6 +661,21 @@ pub mod tests {
             Ok(())
         }
 
+        fn remote_list(&self, _dir: Option<&Path>) -> Result<String> {
+            Ok(self.remote_output.clone())
+        }
+
+        fn prune_remote(&self, _remote: &str, _dir: Option<&Path>) -> Result<()> {
+            if self.fetch_should_fail {
+                anyhow::bail!("Mock git remote prune failure");
+            }
+            Ok(())
+        }
+
+        fn delete_refs(&self, _refs: &[&str], _dir: Option<&Path>) -> Result<()> {
+            Ok(())
+        }
+
         fn for_each_ref(
             &self,
             _refs: &[&str],
@@ -273,6 +685,44 @@ pub mod tests {
             Ok(self.for_each_ref_output.clone())
         }
 
+        fn for_each_ref_sorted(
+            &self,
+            _patterns: &[&str],
+            _format: &str,
+            _sort: &str,
+            count: usize,
+            _dir: Option<&Path>,
+        ) -> Result<String> {
+            Ok(self
+                .for_each_ref_output
+                .lines()
+                .take(count)
+                .flat_map(|line| [line, "\n"])
+                .collect())
+        }
+
+        fn stash_push(&self, _message: &str, _dir: Option<&Path>) -> Result<()> {
+            Ok(())
+        }
+
+        fn stash_list(&self, _dir: Option<&Path>) -> Result<String> {
+            Ok(self.stash_list_output.clone())
+        }
+
+        fn stash_pop(&self, _stash_ref: &str, _dir: Option<&Path>) -> Result<()> {
+            if self.stash_pop_should_fail {
+                anyhow::bail!("Mock git stash pop failure");
+            }
+            Ok(())
+        }
+
+        fn merge(&self, rev: &str, _dir: Option<&Path>) -> Result<()> {
+            if self.merge_fails_for.iter().any(|failing| failing == rev) {
+                anyhow::bail!("Mock git merge failure");
+            }
+            Ok(())
+        }
+
         fn last_commit_time(&self, _worktree_path: &Path) -> Option<DateTime<Utc>> {
             self.last_commit_time_value
         }
@@ -310,7 +760,7 @@ pub mod tests {
     fn test_mock_git_client_remove_worktree_success() {
         let client = MockGitClient::default();
         let path = PathBuf::from("/test/worktree");
-        let result = client.remove_worktree(&path, None);
+        let result = client.remove_worktree(&path, false, None);
         assert!(result.is_ok());
     }
 
@@ -321,7 +771,79 @@ pub mod tests {
             ..Default::default()
         };
         let result = client.remove_branch("feature", None);
-        assert!(result.unwrap());
+        assert_eq!(result.unwrap(), BranchDeletion::Deleted);
+    }
+
+    #[test]
+    fn test_branch_deletion_checked_out() {
+        for stderr in [
+            "error: Cannot delete branch 'feature' checked out at '/tmp/wt'\n",
+            "error: cannot delete branch 'feature' used by worktree at '/tmp/wt'\n",
+        ] {
+            let deletion = BranchDeletion::from_stderr(stderr);
+            assert!(
+                matches!(deletion, BranchDeletion::CheckedOut(_)),
+                "{stderr}"
+            );
+            assert!(deletion.failure_reason().unwrap().contains("/tmp/wt"));
+        }
+    }
+
+    #[test]
+    fn test_branch_deletion_not_found_is_silent() {
+        let deletion = BranchDeletion::from_stderr("error: branch 'feature' not found.\n");
+        assert_eq!(deletion, BranchDeletion::NotFound);
+        assert_eq!(deletion.failure_reason(), None);
+        assert_eq!(BranchDeletion::Deleted.failure_reason(), None);
+    }
+
+    #[test]
+    fn test_branch_deletion_not_fully_merged() {
+        let stderr = "error: the branch 'feature' is not fully merged\n\
+            hint: If you are sure you want to delete it, run 'git branch -D feature'\n";
+        let deletion = BranchDeletion::from_stderr(stderr);
+        assert_eq!(
+            deletion,
+            BranchDeletion::NotFullyMerged("the branch 'feature' is not fully merged".to_string())
+        );
+    }
+
+    #[test]
+    fn test_branch_deletion_other_failure_keeps_reason() {
+        let deletion = BranchDeletion::from_stderr("fatal: cannot lock ref 'refs/heads/x'\n");
+        assert_eq!(
+            deletion.failure_reason(),
+            Some("cannot lock ref 'refs/heads/x'")
+        );
+        assert!(BranchDeletion::from_stderr("").failure_reason().is_some());
+    }
+
+    #[test]
+    fn test_parse_branch_delete_failures_skips_not_found() {
+        let stderr = "error: branch 'gone' not found.\n\
+            error: cannot delete branch 'busy' used by worktree at '/tmp/busy'\n";
+        assert_eq!(
+            parse_branch_delete_failures(stderr),
+            vec![(
+                "busy".to_string(),
+                "cannot delete branch 'busy' used by worktree at '/tmp/busy'".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_c_locale_command_forces_untranslated_messages() {
+        let cmd = build_c_locale_command(None);
+        assert!(cmd
+            .get_envs()
+            .any(|(key, value)| key == "LC_ALL" && value == Some("C".as_ref())));
+    }
+
+    #[test]
+    fn test_parse_deleted_branches() {
+        let stdout = "Deleted branch feature/a (was 1a2b3c4).\nDeleted branch b (was 5d6e7f8).\n";
+        assert_eq!(parse_deleted_branches(stdout), vec!["feature/a", "b"]);
+        assert!(parse_deleted_branches("").is_empty());
     }
 
     #[test]
@@ -346,6 +868,24 @@ pub mod tests {
         assert!(result.is_some(), "Current repository should have commits");
     }
 
+    #[test]
+    fn test_last_commit_times_parallel_matches_sequential() {
+        let client = RealGitClient;
+        let current_dir = std::env::current_dir().unwrap();
+        let paths = vec![
+            current_dir.clone(),
+            std::path::PathBuf::from("/nonexistent/path/to/worktree"),
+            current_dir.join("src"),
+        ];
+        let sequential: Vec<_> = paths
+            .iter()
+            .map(|path| client.last_commit_time(path))
+            .collect();
+        assert_eq!(client.last_commit_times_parallel(&paths), sequential);
+        assert!(sequential[0].is_some());
+        assert!(sequential[1].is_none());
+    }
+
     #[test]
     fn test_real_git_client_last_commit_time_nonexistent_path() {
         let client = RealGitClient;
@@ -0,0 +1,336 @@
+#![allow(clippy::missing_errors_doc)]
+#![allow(clippy::must_use_candidate)]
+//! External ticket resolver (Linear, Jira, ...) for `ofsht add <TICKET-ID>`
+//!
+//! When the branch argument matches `[integration.resolver] pattern`, the
+//! configured command is run and the ticket title is read from its JSON output
+//! to build a descriptive branch name.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::config::ResolverConfig;
+use crate::hooks::shell_quote;
+
+/// Maximum length of the slugified title segment
+const MAX_SLUG_LEN: usize = 50;
+
+/// Ticket resolver interface: fetch ticket JSON for an ID
+pub trait TicketResolver {
+    fn fetch(&self, id: &str) -> Result<serde_json::Value>;
+}
+
+/// Real resolver running a shell command (`{id}` replaced with the quoted ID)
+#[derive(Debug)]
+pub struct RealTicketResolver {
+    command: String,
+    timeout: Duration,
+}
+
+impl RealTicketResolver {
+    pub fn new(command: impl Into<String>, timeout: Duration) -> Self {
+        Self {
+            command: command.into(),
+            timeout,
+        }
+    }
+}
+
+impl TicketResolver for RealTicketResolver {
+    fn fetch(&self, id: &str) -> Result<serde_json::Value> {
+        let command = self.command.replace("{id}", &shell_quote(id));
+        let stdout = run_with_timeout(&command, self.timeout)?;
+        serde_json::from_str(&stdout)
+            .with_context(|| format!("Resolver output is not valid JSON: {command}"))
+    }
+}
+
+/// Run `sh -c <command>` and return stdout, killing the process after `timeout`
+fn run_with_timeout(command: &str, timeout: Duration) -> Result<String> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to execute resolver command: {command}"))?;
+
+    // Drain pipes in background threads so a chatty command cannot block on a full pipe
+    let mut stdout_pipe = child.stdout.take().expect("stdout was piped");
+    let mut stderr_pipe = child.stderr.take().expect("stderr was piped");
+    let stdout_handle = std::thread::spawn(move || {
+        let mut buf = String::new();
+        stdout_pipe.read_to_string(&mut buf).ok();
+        buf
+    });
+    let stderr_handle = std::thread::spawn(move || {
+        let mut buf = String::new();
+        stderr_pipe.read_to_string(&mut buf).ok();
+        buf
+    });
+
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if start.elapsed() >= timeout {
+            child.kill().ok();
+            child.wait().ok();
+            anyhow::bail!(
+                "Resolver command timed out after {}s: {command}",
+                timeout.as_secs_f64()
+            );
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    };
+
+    let stdout = stdout_handle.join().unwrap_or_default();
+    let stderr = stderr_handle.join().unwrap_or_default();
+    if !status.success() {
+        anyhow::bail!("Resolver command failed: {command}\n{}", stderr.trim());
+    }
+    Ok(stdout)
+}
+
+/// Read a string field from JSON, following dotted paths (`fields.summary`)
+pub fn extract_field(json: &serde_json::Value, field: &str) -> Option<String> {
+    field
+        .split('.')
+        .try_fold(json, |value, key| value.get(key))
+        .and_then(serde_json::Value::as_str)
+        .map(String::from)
+}
+
+/// Convert a title into a branch-safe slug: lowercase ASCII alphanumerics
+/// separated by single `-`, truncated to a reasonable length
+pub fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    if slug.len() > MAX_SLUG_LEN {
+        slug.truncate(MAX_SLUG_LEN);
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Expand the branch template (`{id}`, `{id_lower}`, `{title_slug}`)
+pub fn build_branch(template: &str, id: &str, title: &str) -> String {
+    template
+        .replace("{id_lower}", &id.to_lowercase())
+        .replace("{id}", id)
+        .replace("{title_slug}", &slugify(title))
+        .trim_matches('-')
+        .to_string()
+}
+
+/// A ticket resolved into a branch name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedTicket {
+    pub branch: String,
+    pub title: String,
+}
+
+/// Resolve `input` into a branch name when it matches the configured pattern
+///
+/// Returns `Ok(None)` when the resolver is inactive or `input` does not match
+/// (the caller uses it literally). Returns `Err` when the pattern is invalid or
+/// the resolver fails; callers fall back to the literal input with a warning.
+pub fn resolve_ticket_branch(
+    resolver: &impl TicketResolver,
+    config: &ResolverConfig,
+    input: &str,
+) -> Result<Option<ResolvedTicket>> {
+    if !config.is_active() {
+        return Ok(None);
+    }
+
+    let pattern = Regex::new(&config.pattern)
+        .with_context(|| format!("Invalid resolver pattern: {}", config.pattern))?;
+    if !pattern.is_match(input) {
+        return Ok(None);
+    }
+
+    let json = resolver.fetch(input)?;
+    let title = extract_field(&json, &config.title_field).with_context(|| {
+        format!(
+            "Resolver output has no string field `{}`",
+            config.title_field
+        )
+    })?;
+
+    let branch = build_branch(&config.branch_template, input, &title);
+    if branch.is_empty() {
+        anyhow::bail!("Resolver produced an empty branch name for {input}");
+    }
+
+    Ok(Some(ResolvedTicket { branch, title }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    /// Mock resolver returning fixed JSON and counting calls
+    struct MockTicketResolver {
+        json: serde_json::Value,
+        calls: Cell<usize>,
+    }
+
+    impl MockTicketResolver {
+        fn new(json: serde_json::Value) -> Self {
+            Self {
+                json,
+                calls: Cell::new(0),
+            }
+        }
+    }
+
+    impl TicketResolver for MockTicketResolver {
+        fn fetch(&self, _id: &str) -> Result<serde_json::Value> {
+            self.calls.set(self.calls.get() + 1);
+            Ok(self.json.clone())
+        }
+    }
+
+    fn linear_config() -> ResolverConfig {
+        ResolverConfig {
+            pattern: r"^[A-Z]+-\d+$".to_string(),
+            command: "linear-cli issue view {id} --json".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(
+            slugify("Fix login: redirect loop!"),
+            "fix-login-redirect-loop"
+        );
+        assert_eq!(slugify("  --Already--dashed--  "), "already-dashed");
+        assert_eq!(slugify("日本語 title"), "title");
+        assert!(slugify(&"a".repeat(80)).len() <= MAX_SLUG_LEN);
+    }
+
+    #[test]
+    fn test_build_branch_default_template() {
+        assert_eq!(
+            build_branch("{id_lower}-{title_slug}", "ENG-1423", "Short title"),
+            "eng-1423-short-title"
+        );
+        assert_eq!(
+            build_branch("feature/{id}", "ENG-1423", "ignored"),
+            "feature/ENG-1423"
+        );
+    }
+
+    #[test]
+    fn test_extract_field_dotted_path() {
+        let json = serde_json::json!({"fields": {"summary": "Jira title"}, "title": 3});
+        assert_eq!(
+            extract_field(&json, "fields.summary"),
+            Some("Jira title".to_string())
+        );
+        assert_eq!(extract_field(&json, "title"), None);
+        assert_eq!(extract_field(&json, "missing.path"), None);
+    }
+
+    #[test]
+    fn test_resolve_matching_input() {
+        let resolver = MockTicketResolver::new(serde_json::json!({"title": "Short title"}));
+        let ticket = resolve_ticket_branch(&resolver, &linear_config(), "ENG-1423")
+            .unwrap()
+            .unwrap();
+        assert_eq!(ticket.branch, "eng-1423-short-title");
+        assert_eq!(ticket.title, "Short title");
+    }
+
+    #[test]
+    fn test_resolve_non_matching_input_untouched() {
+        let resolver = MockTicketResolver::new(serde_json::json!({"title": "x"}));
+        for input in [
+            "feature-x",
+            "eng-1423",
+            "ENG-1423-extra",
+            "XENG-12a",
+            "#123",
+        ] {
+            assert_eq!(
+                resolve_ticket_branch(&resolver, &linear_config(), input).unwrap(),
+                None,
+                "{input} must not be resolved"
+            );
+        }
+        assert_eq!(resolver.calls.get(), 0);
+    }
+
+    #[test]
+    fn test_resolve_inactive_without_command() {
+        let resolver = MockTicketResolver::new(serde_json::json!({"title": "x"}));
+        let config = ResolverConfig {
+            command: String::new(),
+            ..linear_config()
+        };
+        assert_eq!(
+            resolve_ticket_branch(&resolver, &config, "ENG-1").unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_resolve_missing_title_field_is_error() {
+        let resolver = MockTicketResolver::new(serde_json::json!({"name": "x"}));
+        assert!(resolve_ticket_branch(&resolver, &linear_config(), "ENG-1").is_err());
+    }
+
+    #[test]
+    fn test_resolve_invalid_pattern_is_error() {
+        let resolver = MockTicketResolver::new(serde_json::json!({"title": "x"}));
+        let config = ResolverConfig {
+            pattern: "([".to_string(),
+            ..linear_config()
+        };
+        assert!(resolve_ticket_branch(&resolver, &config, "ENG-1").is_err());
+    }
+
+    #[test]
+    fn test_real_resolver_with_stub_command() {
+        let resolver = RealTicketResolver::new(
+            r#"printf '{"id":"%s","title":"Stub title"}' {id}"#,
+            Duration::from_secs(5),
+        );
+        let json = resolver.fetch("ENG-7").unwrap();
+        assert_eq!(extract_field(&json, "id"), Some("ENG-7".to_string()));
+        assert_eq!(
+            extract_field(&json, "title"),
+            Some("Stub title".to_string())
+        );
+    }
+
+    #[test]
+    fn test_real_resolver_times_out() {
+        let resolver = RealTicketResolver::new("sleep 5", Duration::from_millis(200));
+        let start = Instant::now();
+        let err = resolver.fetch("ENG-7").unwrap_err().to_string();
+        assert!(err.contains("timed out"), "unexpected error: {err}");
+        assert!(start.elapsed() < Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_real_resolver_failure_includes_stderr() {
+        let resolver =
+            RealTicketResolver::new("echo 'not authorized' >&2; exit 1", Duration::from_secs(5));
+        let err = resolver.fetch("ENG-7").unwrap_err().to_string();
+        assert!(err.contains("not authorized"), "unexpected error: {err}");
+    }
+}
@@ -3,15 +3,23 @@
 use anyhow::{bail, Context, Result};
 use std::path::Path;
 use std::process::Command;
+use std::sync::OnceLock;
+
+use crate::integrations::{command_succeeds, probe_once};
 
 /// tmux integration trait
 pub trait TmuxLauncher {
     /// Detect if tmux is available and we're inside a tmux session
     fn detect(&self) -> Result<()>;
+    /// Detect if the tmux binary is available (sessions can be created from outside tmux)
+    fn detect_binary(&self) -> Result<()>;
     /// Create a new tmux window at the specified path
     fn create_window(&self, path: &Path, branch: &str) -> Result<()>;
     /// Create a new tmux pane at the specified path
     fn create_pane(&self, path: &Path) -> Result<()>;
+    /// Create (or reuse) a detached tmux session at the specified path, then
+    /// switch to it when inside tmux or attach to it otherwise
+    fn create_session(&self, path: &Path, name: &str) -> Result<()>;
 }
 
 /// Real tmux launcher that executes actual tmux commands
@@ -28,13 +36,15 @@ impl TmuxLauncher for RealTmuxLauncher {
             );
         }
 
-        // Check if tmux binary exists
-        let status = Command::new("tmux")
-            .arg("-V")
-            .output()
-            .context("Failed to execute tmux command")?;
+        self.detect_binary()
+    }
+
+    /// Detect if the tmux binary is available
+    fn detect_binary(&self) -> Result<()> {
+        static AVAILABLE: OnceLock<bool> = OnceLock::new();
 
-        if !status.status.success() {
+        // Check if tmux binary exists (probed once per process)
+        if !probe_once(&AVAILABLE, || command_succeeds("tmux", "-V")) {
             bail!("tmux binary not found or not executable");
         }
 
@@ -85,6 +95,84 @@ impl TmuxLauncher for RealTmuxLauncher {
 
         Ok(())
     }
+
+    /// Create (or reuse) a tmux session at the specified path and move to it
+    fn create_session(&self, path: &Path, name: &str) -> Result<()> {
+        self.detect_binary()?;
+
+        let name = sanitize_session_name(name);
+        // `=` makes tmux match the session name exactly instead of by prefix
+        let target = format!("={name}");
+
+        let exists = Command::new("tmux")
+            .args(["has-session", "-t", &target])
+            .output()
+            .is_ok_and(|output| output.status.success());
+        if !exists {
+            let output = Command::new("tmux")
+                .args(["new-session", "-d", "-s", &name, "-c"])
+                .arg(path)
+                .output()
+                .context("Failed to execute tmux new-session command")?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                bail!("tmux new-session command failed: {}", stderr.trim());
+            }
+        }
+
+        if std::env::var_os("TMUX").is_some() {
+            let output = Command::new("tmux")
+                .args(["switch-client", "-t", &target])
+                .output()
+                .context("Failed to execute tmux switch-client command")?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                bail!("tmux switch-client command failed: {}", stderr.trim());
+            }
+        } else {
+            // Attaching takes over the terminal until the user detaches
+            let status = Command::new("tmux")
+                .args(["attach-session", "-t", &target])
+                .status()
+                .context("Failed to execute tmux attach-session command")?;
+            if !status.success() {
+                bail!("tmux attach-session command failed");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Sanitize branch name for use as tmux session name
+/// - Replaces `.` and `:` (rejected by tmux in session names) with `_`
+/// - Returns "worktree" if empty
+pub fn sanitize_session_name(branch: &str) -> String {
+    if branch.is_empty() {
+        return "worktree".to_string();
+    }
+    branch.replace(['.', ':'], "_")
+}
+
+/// Expand an `integration.tmux.name` template into a window/session name
+///
+/// `{branch}` is the worktree's branch and `{title_slug}` the slug of the
+/// GitHub PR/issue title. Without a template, or when the expansion is empty
+/// (e.g. `{title_slug}` for a plain branch), the branch name is used.
+pub fn expand_name_template(template: Option<&str>, branch: &str, title: Option<&str>) -> String {
+    let Some(template) = template else {
+        return branch.to_string();
+    };
+    let name = template.replace("{branch}", branch).replace(
+        "{title_slug}",
+        &title.map(super::resolver::slugify).unwrap_or_default(),
+    );
+    let name = name.trim_matches(['-', '_', ' ', '/']);
+    if name.is_empty() {
+        branch.to_string()
+    } else {
+        name.to_string()
+    }
 }
 
 /// Sanitize branch name for use as tmux window name
@@ -110,6 +198,14 @@ pub fn sanitize_window_name(branch: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_sanitize_session_name() {
+        assert_eq!(sanitize_session_name("feature/login"), "feature/login");
+        assert_eq!(sanitize_session_name("release-1.2.3"), "release-1_2_3");
+        assert_eq!(sanitize_session_name("fix:colon"), "fix_colon");
+        assert_eq!(sanitize_session_name(""), "worktree");
+    }
+
     #[test]
     fn test_sanitize_window_name_simple() {
         assert_eq!(sanitize_window_name("feature"), "feature");
@@ -146,4 +242,29 @@ mod tests {
     fn test_sanitize_window_name_only_special_chars() {
         assert_eq!(sanitize_window_name("///"), "···");
     }
+
+    #[test]
+    fn test_expand_name_template() {
+        assert_eq!(
+            expand_name_template(None, "fix/login", Some("Fix login")),
+            "fix/login"
+        );
+        assert_eq!(
+            expand_name_template(Some("{title_slug}"), "fix/login", Some("Fix: the login!")),
+            "fix-the-login"
+        );
+        assert_eq!(
+            expand_name_template(Some("{branch}-{title_slug}"), "issue-7", Some("Crash")),
+            "issue-7-crash"
+        );
+        // No GitHub title: the leftover separator is trimmed
+        assert_eq!(
+            expand_name_template(Some("{branch}-{title_slug}"), "feature", None),
+            "feature"
+        );
+        assert_eq!(
+            expand_name_template(Some("{title_slug}"), "feature", None),
+            "feature"
+        );
+    }
 }
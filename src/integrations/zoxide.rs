@@ -3,10 +3,15 @@
 use anyhow::{Context, Result};
 use std::path::Path;
 use std::process::Command;
+use std::sync::OnceLock;
+
+use crate::integrations::{command_succeeds, probe_once};
 
 /// Zoxide client interface for adding directories
 pub trait ZoxideClient {
-    fn add(&self, path: &Path) -> Result<()>;
+    /// Add `path` to the database; `score` overrides zoxide's default
+    /// increment (`zoxide add --score <n>`)
+    fn add(&self, path: &Path, score: Option<f64>) -> Result<()>;
 }
 
 /// Real zoxide implementation
@@ -14,9 +19,13 @@ pub trait ZoxideClient {
 pub struct RealZoxideClient;
 
 impl ZoxideClient for RealZoxideClient {
-    fn add(&self, path: &Path) -> Result<()> {
-        let output = Command::new("zoxide")
-            .arg("add")
+    fn add(&self, path: &Path, score: Option<f64>) -> Result<()> {
+        let mut cmd = Command::new("zoxide");
+        cmd.arg("add");
+        if let Some(score) = score {
+            cmd.arg("--score").arg(score.to_string());
+        }
+        let output = cmd
             .arg(path)
             .output()
             .context("Failed to execute zoxide add")?;
@@ -30,12 +39,10 @@ impl ZoxideClient for RealZoxideClient {
     }
 }
 
-/// Check if zoxide is available in the system
+/// Check if zoxide is available in the system (probed once per process)
 pub fn is_zoxide_available() -> bool {
-    Command::new("zoxide")
-        .arg("--version")
-        .output()
-        .is_ok_and(|output| output.status.success())
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    probe_once(&AVAILABLE, || command_succeeds("zoxide", "--version"))
 }
 
 #[cfg(test)]
@@ -48,7 +55,7 @@ mod tests {
     }
 
     impl ZoxideClient for MockZoxideClient {
-        fn add(&self, _path: &Path) -> Result<()> {
+        fn add(&self, _path: &Path, _score: Option<f64>) -> Result<()> {
             if self.should_fail {
                 anyhow::bail!("Mock zoxide failure");
             }
@@ -60,7 +67,7 @@ mod tests {
     fn test_mock_zoxide_client_success() {
         let client = MockZoxideClient { should_fail: false };
         let path = PathBuf::from("/test/path");
-        let result = client.add(&path);
+        let result = client.add(&path, None);
         assert!(result.is_ok());
     }
 
@@ -68,7 +75,7 @@ mod tests {
     fn test_mock_zoxide_client_failure() {
         let client = MockZoxideClient { should_fail: true };
         let path = PathBuf::from("/test/path");
-        let result = client.add(&path);
+        let result = client.add(&path, None);
         assert!(result.is_err());
     }
 
@@ -1,17 +1,46 @@
+//! Core library behind the `ofsht` CLI.
+//!
+//! The pure core (porcelain parsing, path utilities, config schema, and table
+//! formatting) is always available. Everything that spawns `git`, `gh`, `tmux`,
+//! `fzf`, or `zoxide` lives behind the default `cli` feature, so the core can be
+//! built with `--no-default-features` for sandboxed or WASM targets.
+//!
+//! ```
+//! use ofsht::color::ColorMode;
+//! use ofsht::commands::list_display::format_worktree_table;
+//! use ofsht::domain::worktree::WorktreeList;
+//!
+//! let porcelain = "worktree /repo\nHEAD 0123456789abcdef\nbranch refs/heads/main\n\n\
+//!                  worktree /repo-worktrees/feature\nHEAD fedcba9876543210\nbranch refs/heads/feature\n\n";
+//! let list = WorktreeList::parse(porcelain, None);
+//! assert_eq!(list.non_main()[0].branch.as_deref(), Some("feature"));
+//!
+//! let lines = format_worktree_table(list.entries(), &[None, None], false, ColorMode::Never, None, None);
+//! assert!(lines[0].contains("[@]"));
+//! assert!(lines[1].contains("fedcba98") && lines[1].contains("[feature]"));
+//! ```
+
 // Internal-only library surface. Modules are exposed solely so that the binary's
 // own integration tests and doctests can reach them; this crate is published
 // primarily as a CLI binary, not as a stable library API. Signatures here may
 // change in any release without a major version bump.
 #![allow(clippy::literal_string_with_formatting_args)]
+#[cfg(feature = "cli")]
 pub mod cli;
 pub mod color;
 pub mod config;
+#[cfg(feature = "cli")]
 pub mod hooks;
 pub mod path_utils;
+pub mod report;
+#[cfg(feature = "cli")]
 pub mod service;
+pub mod state;
 pub mod stdin;
+pub mod ui;
 
-// Integration modules
+// Integration modules (process-spawning)
+#[cfg(feature = "cli")]
 pub mod integrations;
 
 // Command modules — internal handlers, not a stable API surface.
@@ -7,20 +7,31 @@ mod domain;
 mod hooks;
 mod integrations;
 mod path_utils;
+mod report;
 mod service;
 mod shell_completion;
+mod state;
 mod stdin;
+mod ui;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::{CommandFactory, Parser};
 use clap_complete::env::{CompleteEnv, Shells};
 
 // Use shared CLI definitions from cli module
-use cli::{Cli, Commands};
+use cli::{Cli, Commands, HookCommand};
 use shell_completion::{FilteredBash, FilteredFish, FilteredZsh};
 
 fn main() -> Result<()> {
     // Handle dynamic completion via COMPLETE environment variable.
+    // Candidates come from the `-C` directory when the completed command line has one
+    // (arguments after the first `--` are the command line, starting with the program).
+    if std::env::var_os("COMPLETE").is_some() {
+        let words = std::env::args_os().skip_while(|arg| arg != "--").skip(2);
+        if let Some(dir) = cli::directory_override(words) {
+            let _ = std::env::set_current_dir(dir);
+        }
+    }
     // Custom shell adapters hide flag candidates unless the current word starts with `-`.
     CompleteEnv::with_factory(Cli::command)
         .shells(Shells(&[&FilteredBash, &FilteredZsh, &FilteredFish]))
@@ -30,36 +41,225 @@ fn main() -> Result<()> {
 
     // Resolve color mode from CLI flag and environment variables
     let color_mode = color::ColorMode::resolve(cli.color);
+    color::set_quiet(cli.quiet);
+    // Like `git -C`: everything below, including a relative `--repo`, starts from there
+    if let Some(dir) = &cli.directory {
+        std::env::set_current_dir(dir)
+            .with_context(|| format!("Cannot change to directory {}", dir.display()))?;
+    }
+    if let Some(repo) = cli.repo {
+        commands::common::set_repo_override(repo);
+    }
 
-    match cli.command {
+    run_command(cli.command, color_mode).map_err(|e| {
+        // Carried changes or merges that conflict leave a usable worktree behind;
+        // give wrappers a status they can tell apart from a failed command
+        if e.downcast_ref::<commands::carry::CarryConflict>().is_some() {
+            eprintln!("Error: {e:?}");
+            std::process::exit(commands::carry::CARRY_CONFLICT_EXIT_CODE);
+        }
+        if e.downcast_ref::<commands::merge::MergeConflict>().is_some() {
+            eprintln!("Error: {e:?}");
+            std::process::exit(commands::merge::MERGE_CONFLICT_EXIT_CODE);
+        }
+        e
+    })
+}
+
+/// Dispatch a parsed subcommand to its handler
+#[allow(clippy::too_many_lines)]
+fn run_command(command: Commands, color_mode: color::ColorMode) -> Result<()> {
+    match command {
         Commands::Add {
             branch,
+            from_pr,
             start_point,
+            detach,
             tmux,
             no_tmux,
+            porcelain,
+            no_hooks,
+            copy,
+            link,
+            skip_pattern,
+            carry_changes,
+            merge,
+            hooks_on_conflict,
         } => commands::add::cmd_new(
             branch.as_deref(),
+            from_pr.as_deref(),
             start_point.as_deref(),
+            commands::add::AddOptions {
+                detach,
+                tmux,
+                no_tmux,
+                porcelain,
+                no_hooks,
+                hook_overrides: commands::common::HookOverrides {
+                    copy,
+                    link,
+                    skip: skip_pattern,
+                },
+                carry_changes,
+                merge,
+                hooks_on_conflict,
+            },
+            color_mode,
+        ),
+        Commands::Create {
+            branch,
+            start_point,
+            detach,
             tmux,
             no_tmux,
+            porcelain,
+            no_hooks,
+            copy,
+            link,
+            skip_pattern,
+        } => commands::create::cmd_create(
+            branch.as_deref(),
+            start_point.as_deref(),
+            &commands::add::AddOptions {
+                detach,
+                tmux,
+                no_tmux,
+                porcelain,
+                no_hooks,
+                hook_overrides: commands::common::HookOverrides {
+                    copy,
+                    link,
+                    skip: skip_pattern,
+                },
+                carry_changes: false,
+                merge: Vec::new(),
+                hooks_on_conflict: false,
+            },
             color_mode,
         ),
-        Commands::Create {
+        Commands::Switch {
             branch,
             start_point,
-        } => commands::create::cmd_create(branch.as_deref(), start_point.as_deref(), color_mode),
-        Commands::Ls { show_path } => commands::list::cmd_list(show_path, color_mode),
-        Commands::Rm { targets } => commands::rm::cmd_rm_many(&targets, color_mode),
-        Commands::Cd { name } => commands::cd::cmd_goto(name.as_deref(), color_mode),
+        } => commands::switch::cmd_switch(&branch, start_point.as_deref(), color_mode),
+        Commands::Ls {
+            show_path,
+            sort,
+            no_truncate,
+            print0,
+            diff,
+            prunable,
+            stale,
+            all,
+            stdin_porcelain,
+            du,
+            fetch,
+        } => commands::list::cmd_list(
+            commands::list::ListOptions {
+                show_path,
+                sort,
+                no_truncate,
+                print0,
+                diff,
+                filter: if prunable {
+                    Some(commands::list::ListFilter::Prunable)
+                } else {
+                    stale.map(commands::list::ListFilter::Stale)
+                },
+                all,
+                stdin_porcelain,
+                du,
+                fetch,
+            },
+            color_mode,
+        ),
+        Commands::Rm {
+            targets,
+            keep_branch,
+            force,
+            assume_clean,
+            no_hooks,
+            keep_going,
+            dry_run,
+            stat,
+        } => commands::rm::cmd_rm_many(
+            &targets,
+            commands::rm::RmOptions {
+                keep_branch,
+                force,
+                assume_clean,
+                no_hooks,
+                keep_going,
+                dry_run,
+                stat,
+            },
+            color_mode,
+        ),
+        Commands::Lock { target, reason } => {
+            commands::lock::cmd_lock(&target, reason.as_deref(), color_mode)
+        }
+        Commands::Unlock { target } => commands::lock::cmd_unlock(&target, color_mode),
+        Commands::Exec {
+            targets,
+            all,
+            keep_going,
+            command,
+        } => commands::exec::cmd_exec(&targets, all, keep_going, &command, color_mode),
+        Commands::Shell {
+            target,
+            shell,
+            tmux,
+        } => commands::shell::cmd_shell(&target, shell.as_deref(), tmux, color_mode),
+        Commands::Cd { name, restore } => {
+            commands::cd::cmd_goto(name.as_deref(), restore, color_mode)
+        }
+        Commands::Doctor => {
+            commands::doctor::cmd_doctor(color_mode);
+            Ok(())
+        }
         Commands::Init {
             global,
             local,
             force,
-        } => commands::init::cmd_init(global, local, force, color_mode),
-        Commands::Completion { shell } => commands::completion::cmd_completion(&shell),
-        Commands::ShellInit { shell } => commands::shell_init::cmd_shell_init(&shell),
+            no_backup,
+            stdout,
+            no_detect,
+            dry_run,
+        } => commands::init::cmd_init(
+            commands::init::InitOptions {
+                global,
+                local,
+                force,
+                no_backup,
+                stdout,
+                no_detect,
+                dry_run,
+            },
+            color_mode,
+        ),
+        Commands::Completion {
+            shell,
+            output,
+            check_shell_integration,
+        } => commands::completion::cmd_completion(
+            shell,
+            output.as_deref(),
+            check_shell_integration,
+            color_mode,
+        ),
+        Commands::ShellInit { shell } => commands::shell_init::cmd_shell_init(shell),
         Commands::Open { pane, window } => commands::open::cmd_open(pane, window, color_mode),
+        Commands::Hook {
+            command:
+                HookCommand::List {
+                    event,
+                    branch,
+                    expand,
+                    json,
+                },
+        } => commands::hook::cmd_hook_list(event, branch.as_deref(), expand, json, color_mode),
         Commands::Sync { run, copy, link } => commands::sync::cmd_sync(run, copy, link, color_mode),
+        Commands::Root { worktrees } => commands::root::cmd_root(worktrees),
+        Commands::Man { out_dir } => commands::man::cmd_man(out_dir.as_deref()),
     }
 }
 
@@ -59,6 +59,52 @@ pub fn canonicalize_allow_missing(path: &Path) -> PathBuf {
     }
 }
 
+/// Ensure `target` resolves to a location inside `root`
+///
+/// Both paths are resolved with [`canonicalize_allow_missing`], so symlinks in the
+/// deepest existing ancestor are followed and neither path needs to exist yet
+/// (e.g. the first worktree, before the root directory is created).
+///
+/// # Errors
+/// Returns an error when the resolved target is outside the resolved root.
+pub fn ensure_within_root(target: &Path, root: &Path) -> anyhow::Result<()> {
+    let canonical_root = canonicalize_allow_missing(root);
+    let canonical_target = canonicalize_allow_missing(target);
+
+    if canonical_target == canonical_root || !canonical_target.starts_with(&canonical_root) {
+        anyhow::bail!(
+            "Refusing to touch {}: it resolves to {}, outside the worktrees root {} (worktree.strict_paths is enabled)",
+            target.display(),
+            canonical_target.display(),
+            canonical_root.display()
+        );
+    }
+    Ok(())
+}
+
+/// Free backup location next to `path`: `<name>.<suffix>`, or `<name>.<suffix>.<N>`
+/// when that is already taken, so an earlier backup is never overwritten
+///
+/// `exists` decides whether a candidate is taken (normally [`Path::exists`]).
+pub fn backup_path(path: &Path, suffix: &str, exists: impl Fn(&Path) -> bool) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{suffix}"));
+    let candidate = path.with_file_name(&name);
+    if !exists(&candidate) {
+        return candidate;
+    }
+    let mut n = 1;
+    loop {
+        let mut numbered = name.clone();
+        numbered.push(format!(".{n}"));
+        let candidate = path.with_file_name(numbered);
+        if !exists(&candidate) {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
 /// Lexically normalize a path by resolving `.` and `..` components
 ///
 /// Does NOT resolve symlinks or touch the filesystem
@@ -422,4 +468,83 @@ mod tests {
         assert!(result.contains("worktrees"));
         assert!(result.contains("feature"));
     }
+
+    // --- ensure_within_root tests ---
+
+    #[test]
+    #[cfg(unix)]
+    fn test_ensure_within_root_blocks_symlinked_intermediate_escape() {
+        let tmp = std::env::temp_dir().join("test_strict_paths_escape");
+        std::fs::remove_dir_all(&tmp).ok();
+        let root = tmp.join("repo-worktrees");
+        let outside = tmp.join("outside");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        // A previous hook (or attacker) planted root/evil -> outside
+        std::os::unix::fs::symlink(&outside, root.join("evil")).unwrap();
+
+        let result = ensure_within_root(&root.join("evil").join("branch"), &root);
+        assert!(result.is_err());
+        let err = result.unwrap_err().to_string();
+        assert!(
+            err.contains("outside the worktrees root"),
+            "unexpected error: {err}"
+        );
+
+        std::fs::remove_dir_all(&tmp).ok();
+    }
+
+    #[test]
+    fn test_ensure_within_root_allows_nested_branch() {
+        let tmp = std::env::temp_dir().join("test_strict_paths_nested");
+        std::fs::remove_dir_all(&tmp).ok();
+        let root = tmp.join("repo-worktrees");
+        std::fs::create_dir_all(root.join("docs")).unwrap();
+
+        assert!(ensure_within_root(&root.join("docs").join("tweak"), &root).is_ok());
+
+        std::fs::remove_dir_all(&tmp).ok();
+    }
+
+    #[test]
+    fn test_ensure_within_root_allows_missing_root() {
+        // First worktree: neither the root nor the target exist yet
+        let tmp = std::env::temp_dir().join("test_strict_paths_missing_root");
+        std::fs::remove_dir_all(&tmp).ok();
+        let root = tmp.join("repo").join("..").join("repo-worktrees");
+
+        assert!(ensure_within_root(&root.join("feature"), &root).is_ok());
+    }
+
+    #[test]
+    fn test_ensure_within_root_blocks_parent_dir_traversal() {
+        let root = PathBuf::from("/nonexistent/repo-worktrees");
+        let target = root.join("..").join("..").join("etc");
+        assert!(ensure_within_root(&target, &root).is_err());
+        // The root itself is not a valid worktree location
+        assert!(ensure_within_root(&root, &root).is_err());
+    }
+
+    #[test]
+    fn test_backup_path_appends_suffix() {
+        assert_eq!(
+            backup_path(Path::new("/wt/config/.env.local"), "bak", |_| false),
+            PathBuf::from("/wt/config/.env.local.bak")
+        );
+    }
+
+    #[test]
+    fn test_backup_path_numbers_collisions() {
+        let path = Path::new("/repo/.ofsht.toml");
+        let taken = [
+            PathBuf::from("/repo/.ofsht.toml.bak.20261015-120000"),
+            PathBuf::from("/repo/.ofsht.toml.bak.20261015-120000.1"),
+        ];
+        assert_eq!(
+            backup_path(path, "bak.20261015-120000", |candidate| taken
+                .iter()
+                .any(|t| t == candidate)),
+            PathBuf::from("/repo/.ofsht.toml.bak.20261015-120000.2")
+        );
+    }
 }
@@ -0,0 +1,109 @@
+//! Stable stderr event lines
+//!
+//! Scripts match these phrases in ofsht's stderr, so every command builds its
+//! event lines through the functions below instead of ad-hoc `format!` calls.
+//! The phrases are listed in `ofsht --help` via [`vocabulary_help`]; changing
+//! one is a breaking change.
+
+use std::path::Path;
+
+use crate::path_utils::display_path;
+
+/// `Created worktree <branch> at <path>` (`add`, `create`)
+pub const CREATED_WORKTREE: &str = "Created worktree";
+/// `Removed worktree <name>` (`rm`)
+pub const REMOVED_WORKTREE: &str = "Removed worktree";
+/// `Deleted branch <branch>` (`rm`)
+pub const DELETED_BRANCH: &str = "Deleted branch";
+/// `Failed to delete branch <branch>: <reason>` (`rm`)
+pub const FAILED_TO_DELETE_BRANCH: &str = "Failed to delete branch";
+/// `Fetched <PR|issue> #<number>: <title>` (`add` with `#123` or a GitHub URL)
+pub const FETCHED: &str = "Fetched";
+
+/// A worktree was added, for a branch or a detached name
+#[must_use]
+pub fn created_worktree(branch: &str, path: &Path) -> String {
+    format!("{CREATED_WORKTREE} {branch} at {}", display_path(path))
+}
+
+/// A worktree was removed; `label` is its branch, or its path when detached
+#[must_use]
+pub fn removed_worktree(label: &str) -> String {
+    format!("{REMOVED_WORKTREE} {label}")
+}
+
+/// The branch of a removed worktree was deleted
+#[must_use]
+pub fn deleted_branch(branch: &str) -> String {
+    format!("{DELETED_BRANCH} {branch}")
+}
+
+/// Git refused to delete the branch of a removed worktree
+#[must_use]
+pub fn failed_to_delete_branch(branch: &str, reason: &str) -> String {
+    format!("{FAILED_TO_DELETE_BRANCH} {branch}: {reason}")
+}
+
+/// A GitHub pull request or issue was looked up; `kind` is `PR` or `issue`,
+/// and `fork` marks a pull request from another repository
+#[must_use]
+pub fn fetched_github_ref(kind: &str, number: u32, title: &str, fork: bool) -> String {
+    let suffix = if fork { " (fork)" } else { "" };
+    format!("{FETCHED} {kind} #{number}: {title}{suffix}")
+}
+
+/// The `--help` section listing the phrases above
+#[must_use]
+pub fn vocabulary_help() -> String {
+    format!(
+        "Status lines on stderr (stable, safe to match in scripts):\n  \
+         {CREATED_WORKTREE} <branch> at <path>\n  \
+         {REMOVED_WORKTREE} <name>\n  \
+         {DELETED_BRANCH} <branch>\n  \
+         {FAILED_TO_DELETE_BRANCH} <branch>: <reason>\n  \
+         {FETCHED} <PR|issue> #<number>: <title>"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_lines_start_with_their_phrase() {
+        let created = created_worktree("feature", Path::new("/tmp/wt/feature"));
+        assert_eq!(created, "Created worktree feature at /tmp/wt/feature");
+        assert_eq!(removed_worktree("feature"), "Removed worktree feature");
+        assert_eq!(deleted_branch("feature"), "Deleted branch feature");
+        assert_eq!(
+            failed_to_delete_branch("feature", "not fully merged"),
+            "Failed to delete branch feature: not fully merged"
+        );
+        assert_eq!(
+            fetched_github_ref("PR", 12, "Fix login", false),
+            "Fetched PR #12: Fix login"
+        );
+        assert_eq!(
+            fetched_github_ref("PR", 12, "Fix login", true),
+            "Fetched PR #12: Fix login (fork)"
+        );
+        assert_eq!(
+            fetched_github_ref("issue", 3, "Crash", false),
+            "Fetched issue #3: Crash"
+        );
+    }
+
+    #[test]
+    fn test_vocabulary_help_lists_every_phrase() {
+        let help = vocabulary_help();
+        for phrase in [
+            CREATED_WORKTREE,
+            REMOVED_WORKTREE,
+            DELETED_BRANCH,
+            FAILED_TO_DELETE_BRANCH,
+            FETCHED,
+        ] {
+            assert!(help.contains(phrase), "{phrase} missing from help");
+        }
+    }
+}
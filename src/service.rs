@@ -2,8 +2,13 @@
 use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
 
-use crate::integrations::git::GitClient;
+use crate::config::WorktreeConfig;
+use crate::domain::worktree::{
+    expand_path_template, worktree_root_from_template, WorktreeEntry, WorktreeLayout, WorktreeList,
+};
+use crate::integrations::git::{BranchDeletion, GitClient};
 use crate::integrations::zoxide::ZoxideClient;
+use crate::path_utils::ensure_within_root;
 
 /// Request describing where and how to create a worktree.
 ///
@@ -15,6 +20,13 @@ pub struct CreateWorktreeRequest<'a> {
     pub repo_root: &'a Path,
     pub path_template: &'a str,
     pub zoxide_enabled: bool,
+    /// Initial zoxide score of the new worktree (`None`: zoxide's default increment)
+    pub zoxide_score: Option<f64>,
+    /// Verify the expanded path stays inside the worktrees root before creating it
+    pub strict_paths: bool,
+    /// Create a detached worktree at `start_point` (or `HEAD`) instead of a branch.
+    /// `branch` is then only used to expand the `{branch}` template variable.
+    pub detach: bool,
 }
 
 /// Worktree service that coordinates git creation and zoxide registration.
@@ -54,38 +66,243 @@ where
     where
         F: FnOnce(&Path) -> Result<()>,
     {
-        let repo_name = req
-            .repo_root
-            .file_name()
-            .and_then(|n| n.to_str())
-            .context("Failed to get repository name")?;
-
-        #[allow(clippy::literal_string_with_formatting_args)]
-        let expanded = req
-            .path_template
-            .replace("{repo}", repo_name)
-            .replace("{branch}", req.branch);
-
-        let worktree_path = if expanded.starts_with('/') {
-            PathBuf::from(&expanded)
-        } else {
-            req.repo_root.join(&expanded)
-        };
+        let worktree_path = add_worktree(&self.git_client, req)?;
+
+        on_after_git(&worktree_path)?;
+
+        if req.zoxide_enabled {
+            self.zoxide_client.add(&worktree_path, req.zoxide_score)?;
+        }
+
+        Ok(worktree_path)
+    }
+}
+
+/// Expand the path template of `req`, check containment when `strict_paths`
+/// is set and run `git worktree add`. Shared by [`WorktreeService`] and
+/// [`WorktreeManager`].
+fn add_worktree<G: GitClient>(git: &G, req: &CreateWorktreeRequest<'_>) -> Result<PathBuf> {
+    let worktree_path = expand_path_template(req.path_template, req.repo_root, req.branch)?;
+
+    if req.strict_paths {
+        let root = worktree_root_from_template(req.path_template, req.repo_root)?;
+        ensure_within_root(&worktree_path, &root)?;
+    }
 
-        self.git_client.create_worktree(
+    if req.detach {
+        git.create_detached_worktree(
+            &worktree_path,
+            req.start_point.unwrap_or("HEAD"),
+            Some(req.repo_root),
+        )?;
+    } else {
+        git.create_worktree(
             req.branch,
             &worktree_path,
             req.start_point,
             Some(req.repo_root),
         )?;
+    }
 
-        on_after_git(&worktree_path)?;
+    Ok(worktree_path)
+}
 
-        if req.zoxide_enabled {
-            self.zoxide_client.add(&worktree_path)?;
+/// Options for [`WorktreeManager::create`]
+#[derive(Debug, Clone, Default)]
+pub struct CreateOptions {
+    /// Commit, branch or tag the new branch starts from (default: `HEAD`)
+    pub start_point: Option<String>,
+    /// Create a detached worktree at `start_point` instead of a branch
+    pub detach: bool,
+}
+
+/// Options for [`WorktreeManager::remove`] and [`WorktreeManager::remove_path`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RemoveOptions {
+    /// Remove even with uncommitted changes or a lock
+    pub force: bool,
+    /// Keep the branch after removing its worktree
+    pub keep_branch: bool,
+}
+
+/// What [`WorktreeManager::remove`] / [`WorktreeManager::remove_path`] did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoveOutcome {
+    /// The worktree at `path` was removed
+    Removed {
+        path: PathBuf,
+        /// Branch deleted together with the worktree, if any
+        deleted_branch: Option<String>,
+        /// Git's reason when the branch could not be deleted (a branch that
+        /// no longer exists is not a failure)
+        branch_error: Option<String>,
+    },
+    /// The worktree had `changes` uncommitted files and `force` was not set;
+    /// nothing was touched
+    SkippedDirty { path: PathBuf, changes: usize },
+}
+
+/// Library entry point for managing the worktrees of one repository.
+///
+/// Unlike the CLI commands, the manager never reads the current directory,
+/// loads configuration, prints or exits: the repository root and worktree
+/// settings are passed in, and every method returns data. Hooks, spinners and
+/// integrations stay with the caller; `ls`, `add`, `create` and `rm` are
+/// printing wrappers around it.
+pub struct WorktreeManager<G: GitClient> {
+    git: G,
+    repo_root: PathBuf,
+    worktree: WorktreeConfig,
+}
+
+impl<G: GitClient> WorktreeManager<G> {
+    pub fn new(git: G, repo_root: impl Into<PathBuf>, worktree: WorktreeConfig) -> Self {
+        Self {
+            git,
+            repo_root: repo_root.into(),
+            worktree,
         }
+    }
 
-        Ok(worktree_path)
+    #[allow(dead_code)] // Library API, unused by the binary
+    #[must_use]
+    pub fn repo_root(&self) -> &Path {
+        &self.repo_root
+    }
+
+    #[allow(dead_code)] // Library API, unused by the binary
+    /// All worktrees, main first. `is_active` is never set.
+    pub fn list(&self) -> Result<Vec<WorktreeEntry>> {
+        Ok(self.worktree_list(None)?.entries().to_vec())
+    }
+
+    /// All worktrees, main first, with the one containing `active_path` (if
+    /// any) marked `is_active`
+    pub fn worktree_list(&self, active_path: Option<&Path>) -> Result<WorktreeList> {
+        let stdout = self.git.list_worktrees(Some(&self.repo_root))?;
+        Ok(WorktreeList::parse(&stdout, active_path))
+    }
+
+    #[allow(dead_code)] // Library API, unused by the binary
+    /// Resolve `target` to a worktree path: `@` for the main worktree, else a
+    /// branch name, a path relative to the worktrees root or a path
+    pub fn resolve(&self, target: &str) -> Result<PathBuf> {
+        let list = self.worktree_list(None)?;
+        let entry = if target == "@" {
+            list.main()
+        } else {
+            self.find_in(&list, target)
+        };
+        entry
+            .map(|e| PathBuf::from(&e.path))
+            .with_context(|| format!("Worktree not found: {target}"))
+    }
+
+    /// Find a non-main worktree in an already parsed `list` by name, using the
+    /// worktrees root derived from `worktree.dir`
+    #[must_use]
+    pub fn find_in<'l>(&self, list: &'l WorktreeList, name: &str) -> Option<&'l WorktreeEntry> {
+        let layout = WorktreeLayout::for_list(list, Some(&self.worktree.dir));
+        list.find_by_name(name, layout.root())
+    }
+
+    /// Request that creates `branch` at the path given by `worktree.dir`
+    ///
+    /// Zoxide registration is off; callers going through [`WorktreeService`]
+    /// turn it on with struct update syntax.
+    #[must_use]
+    pub fn create_request<'a>(
+        &'a self,
+        branch: &'a str,
+        opts: &'a CreateOptions,
+    ) -> CreateWorktreeRequest<'a> {
+        CreateWorktreeRequest {
+            branch,
+            start_point: opts.start_point.as_deref(),
+            repo_root: &self.repo_root,
+            path_template: &self.worktree.dir,
+            zoxide_enabled: false,
+            zoxide_score: None,
+            strict_paths: self.worktree.strict_paths,
+            detach: opts.detach,
+        }
+    }
+
+    #[allow(dead_code)] // Library API, unused by the binary
+    /// Create a worktree for `branch` at the path given by `worktree.dir`
+    pub fn create(&self, branch: &str, opts: &CreateOptions) -> Result<PathBuf> {
+        add_worktree(&self.git, &self.create_request(branch, opts))
+    }
+
+    #[allow(dead_code)] // Library API, unused by the binary
+    /// Remove the non-main worktree named by `target` (see [`Self::resolve`])
+    pub fn remove(&self, target: &str, opts: RemoveOptions) -> Result<RemoveOutcome> {
+        if target == "@" {
+            anyhow::bail!("Cannot remove main worktree");
+        }
+        let list = self.worktree_list(None)?;
+        let entry = self
+            .find_in(&list, target)
+            .with_context(|| format!("Worktree not found: {target}"))?;
+        self.remove_path(
+            Path::new(&entry.path),
+            entry.branch.as_deref(),
+            opts,
+            |_| {},
+        )
+    }
+
+    /// Remove the worktree at `path` and, unless `keep_branch`, its `branch`.
+    ///
+    /// The containment and dirty checks run first; `before_remove` is called
+    /// only once the removal is going ahead (e.g. to run delete hooks).
+    /// A failing `git status` counts as clean and is left to `git worktree remove`,
+    /// and a branch that cannot be deleted is reported in the outcome, not as an error.
+    pub fn remove_path<F>(
+        &self,
+        path: &Path,
+        branch: Option<&str>,
+        opts: RemoveOptions,
+        before_remove: F,
+    ) -> Result<RemoveOutcome>
+    where
+        F: FnOnce(&Path),
+    {
+        if self.worktree.strict_paths {
+            let root = worktree_root_from_template(&self.worktree.dir, &self.repo_root)?;
+            ensure_within_root(path, &root)?;
+        }
+
+        if !opts.force && path.exists() {
+            let status = self.git.status_porcelain(path).unwrap_or_default();
+            let changes = status.lines().filter(|l| !l.trim().is_empty()).count();
+            if changes > 0 {
+                return Ok(RemoveOutcome::SkippedDirty {
+                    path: path.to_path_buf(),
+                    changes,
+                });
+            }
+        }
+
+        before_remove(path);
+        self.git
+            .remove_worktree(path, opts.force, Some(&self.repo_root))?;
+
+        let mut deleted_branch = None;
+        let mut branch_error = None;
+        if let Some(branch) = branch.filter(|_| !opts.keep_branch) {
+            match self.git.remove_branch(branch, Some(&self.repo_root)) {
+                Ok(BranchDeletion::Deleted) => deleted_branch = Some(branch.to_string()),
+                Ok(deletion) => branch_error = deletion.failure_reason().map(ToString::to_string),
+                Err(e) => branch_error = Some(e.to_string()),
+            }
+        }
+
+        Ok(RemoveOutcome::Removed {
+            path: path.to_path_buf(),
+            deleted_branch,
+            branch_error,
+        })
     }
 }
 
@@ -93,28 +310,40 @@ where
 mod tests {
     use super::*;
     use crate::integrations::git::tests::MockGitClient;
-    use std::cell::Cell;
+    use std::cell::{Cell, RefCell};
     use std::path::PathBuf;
 
     struct MockZoxideClient {
         should_fail: bool,
+        added: RefCell<Vec<PathBuf>>,
+        scores: RefCell<Vec<Option<f64>>>,
     }
 
     impl MockZoxideClient {
         fn new() -> Self {
-            Self { should_fail: false }
+            Self {
+                should_fail: false,
+                added: RefCell::default(),
+                scores: RefCell::default(),
+            }
         }
 
         fn with_failure() -> Self {
-            Self { should_fail: true }
+            Self {
+                should_fail: true,
+                added: RefCell::default(),
+                scores: RefCell::default(),
+            }
         }
     }
 
     impl ZoxideClient for MockZoxideClient {
-        fn add(&self, _path: &Path) -> Result<()> {
+        fn add(&self, path: &Path, score: Option<f64>) -> Result<()> {
             if self.should_fail {
                 anyhow::bail!("Mock zoxide failure");
             }
+            self.added.borrow_mut().push(path.to_path_buf());
+            self.scores.borrow_mut().push(score);
             Ok(())
         }
     }
@@ -131,6 +360,9 @@ mod tests {
             repo_root,
             path_template,
             zoxide_enabled,
+            zoxide_score: None,
+            strict_paths: false,
+            detach: false,
         }
     }
 
@@ -147,6 +379,10 @@ mod tests {
             result.unwrap(),
             PathBuf::from("/test/repo/../repo-worktrees/feature")
         );
+        assert_eq!(
+            *service.zoxide_client.added.borrow(),
+            [PathBuf::from("/test/repo/../repo-worktrees/feature")]
+        );
     }
 
     #[test]
@@ -159,6 +395,9 @@ mod tests {
             repo_root: &repo_root,
             path_template: "../{repo}-worktrees/{branch}",
             zoxide_enabled: false,
+            zoxide_score: None,
+            strict_paths: false,
+            detach: false,
         };
 
         let result = service.create(&req, |_| Ok(()));
@@ -226,6 +465,38 @@ mod tests {
             .contains("Mock zoxide failure"));
     }
 
+    #[test]
+    fn test_create_forwards_zoxide_score() {
+        let service = WorktreeService::new(MockGitClient::default(), MockZoxideClient::new());
+        let repo_root = PathBuf::from("/test/repo");
+        let req = CreateWorktreeRequest {
+            zoxide_score: Some(10.0),
+            ..make_req("feature", &repo_root, "../{repo}-worktrees/{branch}", true)
+        };
+        service.create(&req, |_| Ok(())).unwrap();
+        assert_eq!(*service.zoxide_client.scores.borrow(), [Some(10.0)]);
+
+        // Without a score zoxide keeps its default increment
+        let req = make_req("other", &repo_root, "../{repo}-worktrees/{branch}", true);
+        service.create(&req, |_| Ok(())).unwrap();
+        assert_eq!(*service.zoxide_client.scores.borrow(), [Some(10.0), None]);
+    }
+
+    #[test]
+    fn test_create_detached_registers_with_zoxide() {
+        let service = WorktreeService::new(MockGitClient::default(), MockZoxideClient::new());
+        let repo_root = PathBuf::from("/test/repo");
+        let req = CreateWorktreeRequest {
+            start_point: Some("v1.0"),
+            detach: true,
+            ..make_req("v1.0", &repo_root, "../{repo}-worktrees/{branch}", true)
+        };
+
+        let path = service.create(&req, |_| Ok(())).unwrap();
+
+        assert_eq!(*service.zoxide_client.added.borrow(), [path]);
+    }
+
     #[test]
     fn test_create_zoxide_disabled_skips_zoxide() {
         let service = WorktreeService::new(
@@ -236,6 +507,7 @@ mod tests {
         let req = make_req("feature", &repo_root, "../{repo}-worktrees/{branch}", true);
         let req = CreateWorktreeRequest {
             zoxide_enabled: false,
+            zoxide_score: None,
             ..req
         };
 
@@ -273,4 +545,329 @@ mod tests {
 
         assert_eq!(result, PathBuf::from("/tmp/wt/myrepo/feature"));
     }
+
+    #[test]
+    fn test_create_expands_env_in_template() {
+        let service = WorktreeService::new(MockGitClient::default(), MockZoxideClient::new());
+        let repo_root = PathBuf::from("/Users/me/projects/myrepo");
+        temp_env::with_var("OFSHT_TEST_WORKTREES", Some("/data/wt"), || {
+            let req = make_req(
+                "feature",
+                &repo_root,
+                "$OFSHT_TEST_WORKTREES/{repo}/{branch}",
+                false,
+            );
+            let result = service.create(&req, |_| Ok(())).unwrap();
+            assert_eq!(result, PathBuf::from("/data/wt/myrepo/feature"));
+        });
+    }
+
+    #[test]
+    fn test_create_strict_paths_rejects_escaping_branch() {
+        let service = WorktreeService::new(
+            MockGitClient {
+                create_should_fail: true, // would fail if reached
+                ..Default::default()
+            },
+            MockZoxideClient::new(),
+        );
+        let repo_root = PathBuf::from("/test/repo");
+        let req = CreateWorktreeRequest {
+            strict_paths: true,
+            ..make_req(
+                "../../../etc",
+                &repo_root,
+                "../{repo}-worktrees/{branch}",
+                false,
+            )
+        };
+
+        let result = service.create(&req, |_| Ok(()));
+
+        let err = result.unwrap_err().to_string();
+        assert!(
+            err.contains("outside the worktrees root"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[test]
+    fn test_create_strict_paths_allows_nested_branch() {
+        let service = WorktreeService::new(MockGitClient::default(), MockZoxideClient::new());
+        let repo_root = PathBuf::from("/test/repo");
+        let req = CreateWorktreeRequest {
+            strict_paths: true,
+            ..make_req(
+                "docs/tweak",
+                &repo_root,
+                "../{repo}-worktrees/{branch}",
+                false,
+            )
+        };
+
+        assert!(service.create(&req, |_| Ok(())).is_ok());
+    }
+
+    const PORCELAIN: &str = "worktree /test/repo\nHEAD abc\nbranch refs/heads/main\n\n\
+worktree /test/repo/../repo-worktrees/feature\nHEAD def\nbranch refs/heads/feature\n\n";
+
+    fn manager(git: MockGitClient) -> WorktreeManager<MockGitClient> {
+        WorktreeManager::new(git, "/test/repo", WorktreeConfig::default())
+    }
+
+    #[test]
+    fn test_manager_list_returns_entries_main_first() {
+        let manager = manager(MockGitClient {
+            list_output: PORCELAIN.to_string(),
+            ..Default::default()
+        });
+
+        let entries = manager.list().unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].path, "/test/repo");
+        assert_eq!(entries[1].branch.as_deref(), Some("feature"));
+        assert!(entries.iter().all(|e| !e.is_active));
+    }
+
+    #[test]
+    fn test_manager_create_list_resolve_remove_round_trip() {
+        let manager = manager(MockGitClient {
+            list_output: PORCELAIN.to_string(),
+            remove_branch_returns: true,
+            ..Default::default()
+        });
+
+        let created = manager
+            .create("feature", &CreateOptions::default())
+            .unwrap();
+        assert_eq!(
+            created,
+            PathBuf::from("/test/repo/../repo-worktrees/feature")
+        );
+
+        // The path `create` returned is what `list` and `resolve` report back
+        let entries = manager.list().unwrap();
+        assert_eq!(Path::new(&entries[1].path), created);
+        assert_eq!(manager.resolve("feature").unwrap(), created);
+        assert_eq!(manager.resolve("@").unwrap(), PathBuf::from("/test/repo"));
+
+        let outcome = manager.remove("feature", RemoveOptions::default()).unwrap();
+        assert_eq!(
+            outcome,
+            RemoveOutcome::Removed {
+                path: created,
+                deleted_branch: Some("feature".to_string()),
+                branch_error: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_manager_create_request_uses_worktree_config() {
+        let manager = WorktreeManager::new(
+            MockGitClient::default(),
+            "/test/repo",
+            WorktreeConfig {
+                dir: "/srv/wt/{branch}".to_string(),
+                strict_paths: true,
+                ..Default::default()
+            },
+        );
+        let opts = CreateOptions {
+            start_point: Some("v1.0".to_string()),
+            detach: true,
+        };
+
+        let req = manager.create_request("hotfix", &opts);
+
+        assert_eq!(req.path_template, "/srv/wt/{branch}");
+        assert_eq!(req.start_point, Some("v1.0"));
+        assert!(req.strict_paths && req.detach && !req.zoxide_enabled);
+    }
+
+    #[test]
+    fn test_manager_create_propagates_git_failure() {
+        let manager = manager(MockGitClient {
+            create_should_fail: true,
+            ..Default::default()
+        });
+
+        assert!(manager
+            .create("feature", &CreateOptions::default())
+            .is_err());
+    }
+
+    #[test]
+    fn test_manager_resolve_and_remove_reject_unknown_targets() {
+        let manager = manager(MockGitClient {
+            list_output: PORCELAIN.to_string(),
+            ..Default::default()
+        });
+
+        let err = manager.resolve("missing").unwrap_err().to_string();
+        assert!(err.contains("Worktree not found: missing"), "{err}");
+        let err = manager.remove("@", RemoveOptions::default()).unwrap_err();
+        assert!(err.to_string().contains("Cannot remove main worktree"));
+        let err = manager
+            .remove("nope", RemoveOptions::default())
+            .unwrap_err();
+        assert!(err.to_string().contains("Worktree not found: nope"));
+    }
+
+    /// Remove the (nonexistent, hence clean) `feature` worktree
+    fn remove_feature(
+        manager: &WorktreeManager<MockGitClient>,
+        opts: RemoveOptions,
+    ) -> RemoveOutcome {
+        manager
+            .remove_path(
+                Path::new("/test/repo-worktrees/feature"),
+                Some("feature"),
+                opts,
+                |_| {},
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_manager_remove_deletes_branch() {
+        let manager = manager(MockGitClient {
+            remove_branch_returns: true,
+            ..Default::default()
+        });
+
+        let outcome = remove_feature(&manager, RemoveOptions::default());
+
+        assert_eq!(
+            outcome,
+            RemoveOutcome::Removed {
+                path: PathBuf::from("/test/repo-worktrees/feature"),
+                deleted_branch: Some("feature".to_string()),
+                branch_error: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_manager_remove_reports_branch_failure() {
+        let manager = manager(MockGitClient {
+            remove_branch_returns: false,
+            remove_branch_stderr:
+                "error: cannot delete branch 'feature' used by worktree at '/elsewhere'\n"
+                    .to_string(),
+            ..Default::default()
+        });
+
+        let outcome = remove_feature(&manager, RemoveOptions::default());
+
+        assert_eq!(
+            outcome,
+            RemoveOutcome::Removed {
+                path: PathBuf::from("/test/repo-worktrees/feature"),
+                deleted_branch: None,
+                branch_error: Some(
+                    "cannot delete branch 'feature' used by worktree at '/elsewhere'".to_string()
+                ),
+            }
+        );
+    }
+
+    #[test]
+    fn test_manager_remove_missing_branch_is_silent() {
+        let manager = manager(MockGitClient {
+            remove_branch_returns: false,
+            remove_branch_stderr: "error: branch 'feature' not found.\n".to_string(),
+            ..Default::default()
+        });
+
+        let outcome = remove_feature(&manager, RemoveOptions::default());
+
+        assert!(matches!(
+            outcome,
+            RemoveOutcome::Removed {
+                deleted_branch: None,
+                branch_error: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_manager_remove_keep_branch() {
+        let manager = manager(MockGitClient {
+            remove_branch_returns: true,
+            ..Default::default()
+        });
+
+        let outcome = remove_feature(
+            &manager,
+            RemoveOptions {
+                keep_branch: true,
+                ..Default::default()
+            },
+        );
+
+        assert!(matches!(
+            outcome,
+            RemoveOutcome::Removed {
+                deleted_branch: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_manager_remove_path_skips_dirty_without_callback() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = manager(MockGitClient {
+            status_output: " M a.txt\n?? b.txt\n".to_string(),
+            remove_worktree_should_fail: true, // would fail if reached
+            ..Default::default()
+        });
+        let called = Cell::new(false);
+
+        let outcome = manager
+            .remove_path(
+                temp.path(),
+                Some("feature"),
+                RemoveOptions::default(),
+                |_| called.set(true),
+            )
+            .unwrap();
+
+        assert_eq!(
+            outcome,
+            RemoveOutcome::SkippedDirty {
+                path: temp.path().to_path_buf(),
+                changes: 2,
+            }
+        );
+        assert!(!called.get());
+    }
+
+    #[test]
+    fn test_manager_remove_path_force_ignores_dirty() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = manager(MockGitClient {
+            status_output: " M a.txt\n".to_string(),
+            ..Default::default()
+        });
+        let called = Cell::new(false);
+
+        let outcome = manager
+            .remove_path(
+                temp.path(),
+                None,
+                RemoveOptions {
+                    force: true,
+                    ..Default::default()
+                },
+                |_| called.set(true),
+            )
+            .unwrap();
+
+        assert!(matches!(outcome, RemoveOutcome::Removed { .. }));
+        assert!(called.get());
+    }
 }
@@ -0,0 +1,112 @@
+//! Best-effort writes of ofsht's own state files
+//!
+//! State such as the `ls --diff` snapshot lives under the home directory,
+//! which is read-only in some CI containers, and the hook error log lives in
+//! a worktree that may be read-only too. Such writes must never fail the
+//! command that triggered them, so [`write_best_effort`] swallows errors and
+//! prints one dimmed notice per process when the location is not writable.
+
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::color::{self, ColorMode};
+use crate::path_utils::display_path;
+
+/// Flag that lets a message through only the first time it is asked
+#[derive(Debug, Default)]
+pub struct NoticeLatch(AtomicBool);
+
+impl NoticeLatch {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(AtomicBool::new(false))
+    }
+
+    /// `true` on the first call, `false` on every later one
+    pub fn first(&self) -> bool {
+        !self.0.swap(true, Ordering::Relaxed)
+    }
+}
+
+/// Latch for the "state not saved" notice
+static READ_ONLY_NOTICE: NoticeLatch = NoticeLatch::new();
+
+/// Whether `err` means the location is read-only or not permitted (`EROFS`/`EACCES`)
+#[must_use]
+pub fn is_read_only_error(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::PermissionDenied | io::ErrorKind::ReadOnlyFilesystem
+    )
+}
+
+/// Write `contents` to `path`, creating its parent directories
+///
+/// Returns whether the file was written. A read-only or non-permitted location
+/// prints a dimmed notice (once per process); other errors are ignored silently.
+pub fn write_best_effort(path: &Path, contents: impl AsRef<[u8]>, color_mode: ColorMode) -> bool {
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| std::fs::write(path, contents));
+    match result {
+        Ok(()) => true,
+        Err(e) => {
+            if is_read_only_error(&e) && READ_ONLY_NOTICE.first() {
+                let dir = path.parent().unwrap_or(path);
+                color::eprint_status(color::dim(
+                    color_mode,
+                    format!("Not saving ofsht state to {}: {e}", display_path(dir)),
+                ));
+            }
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notice_latch_fires_once() {
+        let latch = NoticeLatch::new();
+        assert!(latch.first());
+        assert!(!latch.first());
+        assert!(!latch.first());
+    }
+
+    #[test]
+    fn test_is_read_only_error() {
+        assert!(is_read_only_error(&io::Error::from(
+            io::ErrorKind::PermissionDenied
+        )));
+        assert!(is_read_only_error(&io::Error::from(
+            io::ErrorKind::ReadOnlyFilesystem
+        )));
+        assert!(!is_read_only_error(&io::Error::from(
+            io::ErrorKind::NotFound
+        )));
+    }
+
+    #[test]
+    fn test_write_best_effort_creates_parents() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("a/b/state.json");
+        assert!(write_best_effort(&path, "{}", ColorMode::Never));
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "{}");
+    }
+
+    #[test]
+    fn test_write_best_effort_reports_failure() {
+        let temp = tempfile::tempdir().unwrap();
+        let blocker = temp.path().join("file");
+        std::fs::write(&blocker, "").unwrap();
+        assert!(!write_best_effort(
+            &blocker.join("state.json"),
+            "{}",
+            ColorMode::Never
+        ));
+    }
+}
@@ -5,7 +5,7 @@
 //! sessions keep their existing behavior (errors / fzf fallbacks).
 
 use anyhow::{Context, Result};
-use std::io::{self, BufRead, BufReader, IsTerminal, Read};
+use std::io::{self, BufRead, BufReader, IsTerminal, Read, Write};
 
 /// Read the first non-empty trimmed line from stdin when it is piped/redirected.
 ///
@@ -35,6 +35,34 @@ pub fn try_read_stdin_lines() -> Result<Vec<String>> {
     read_lines_from(stdin.lock())
 }
 
+/// Ask a yes/no question on stderr and read the answer from stdin.
+///
+/// Returns `Ok(None)` without prompting when stdin is not a TTY, so callers
+/// can fall back to a non-interactive default.
+///
+/// # Errors
+/// Returns an error if reading the answer fails.
+pub fn confirm(question: &str) -> Result<Option<bool>> {
+    let stdin = io::stdin();
+    if !stdin.is_terminal() {
+        return Ok(None);
+    }
+    eprint!("{question} [y/N] ");
+    io::stderr().flush().ok();
+    read_yes_from(stdin.lock()).map(Some)
+}
+
+fn read_yes_from<R: Read>(reader: R) -> Result<bool> {
+    let mut line = String::new();
+    BufReader::new(reader)
+        .read_line(&mut line)
+        .context("Failed to read from stdin")?;
+    Ok(matches!(
+        line.trim().to_ascii_lowercase().as_str(),
+        "y" | "yes"
+    ))
+}
+
 fn read_first_from<R: Read>(reader: R) -> Result<Option<String>> {
     // Read line by line and short-circuit on the first non-empty trimmed line.
     // This avoids blocking on producers that keep the pipe open after writing
@@ -70,6 +98,15 @@ mod tests {
     use super::*;
     use std::io::Cursor;
 
+    #[test]
+    fn read_yes_from_answers() {
+        assert!(read_yes_from(Cursor::new(b"y\n")).unwrap());
+        assert!(read_yes_from(Cursor::new(b" YES \n")).unwrap());
+        assert!(!read_yes_from(Cursor::new(b"n\n")).unwrap());
+        assert!(!read_yes_from(Cursor::new(b"\n")).unwrap());
+        assert!(!read_yes_from(Cursor::new(b"")).unwrap());
+    }
+
     #[test]
     fn read_first_from_single_line() {
         let result = read_first_from(Cursor::new(b"feat\n")).unwrap();
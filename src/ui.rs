@@ -0,0 +1,162 @@
+//! Plain terminal prompts
+//!
+//! Fallbacks for interactive selection when fzf is unavailable or disabled.
+//! Prompts are drawn on stderr and answered on stdin, and only appear when both
+//! are TTYs, so scripts keep failing fast instead of blocking on input.
+
+use anyhow::{Context, Result};
+use std::io::{self, BufRead, BufReader, IsTerminal, Read, Write};
+
+/// Show a numbered menu on stderr and read the selection from stdin.
+///
+/// Numbers are 1-based; with `multi`, several numbers and ranges may be given,
+/// separated by commas or spaces (e.g. `1,3 5-7`). Returns the selected indices
+/// (0-based, in input order, without duplicates), an empty list when the user
+/// enters nothing, or `Ok(None)` without prompting when stdin or stderr is not
+/// a TTY.
+///
+/// # Errors
+/// Returns an error if reading the answer fails or the input is not a valid
+/// selection.
+pub fn prompt_select(prompt: &str, items: &[String], multi: bool) -> Result<Option<Vec<usize>>> {
+    let stdin = io::stdin();
+    if !stdin.is_terminal() || !io::stderr().is_terminal() {
+        return Ok(None);
+    }
+
+    let width = items.len().to_string().len();
+    for (index, item) in items.iter().enumerate() {
+        eprintln!("  {:>width$}) {item}", index + 1);
+    }
+    let hint = if multi {
+        "numbers or ranges, e.g. 1,3 or 2-4"
+    } else {
+        "a number"
+    };
+    eprint!("{prompt} ({hint}; empty to cancel): ");
+    io::stderr().flush().ok();
+
+    let answer = read_answer_from(stdin.lock())?;
+    parse_selection(&answer, items.len(), multi).map(Some)
+}
+
+fn read_answer_from<R: Read>(reader: R) -> Result<String> {
+    let mut line = String::new();
+    BufReader::new(reader)
+        .read_line(&mut line)
+        .context("Failed to read from stdin")?;
+    Ok(line)
+}
+
+/// Parse a menu answer into 0-based indices for a menu of `count` items.
+///
+/// Empty input means "cancel" and yields an empty list.
+fn parse_selection(input: &str, count: usize, multi: bool) -> Result<Vec<usize>> {
+    let mut selected: Vec<usize> = Vec::new();
+    let tokens = input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty());
+
+    for token in tokens {
+        let (start, end) = if let Some((start, end)) = token.split_once('-') {
+            (
+                parse_number(start, token, count)?,
+                parse_number(end, token, count)?,
+            )
+        } else {
+            let number = parse_number(token, token, count)?;
+            (number, number)
+        };
+        if start > end {
+            anyhow::bail!("Invalid range: {token}");
+        }
+        for number in start..=end {
+            if !selected.contains(&(number - 1)) {
+                selected.push(number - 1);
+            }
+        }
+    }
+
+    if !multi && selected.len() > 1 {
+        anyhow::bail!("Select a single entry");
+    }
+    Ok(selected)
+}
+
+fn parse_number(text: &str, token: &str, count: usize) -> Result<usize> {
+    let number: usize = text
+        .trim()
+        .parse()
+        .with_context(|| format!("Invalid selection: {token}"))?;
+    if number == 0 || number > count {
+        anyhow::bail!("Selection out of range: {number} (choose 1-{count})");
+    }
+    Ok(number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_parse_selection_single_number() {
+        assert_eq!(parse_selection("2\n", 3, false).unwrap(), vec![1]);
+    }
+
+    #[test]
+    fn test_parse_selection_comma_and_space_separated() {
+        assert_eq!(parse_selection("1, 3 2", 3, true).unwrap(), vec![0, 2, 1]);
+    }
+
+    #[test]
+    fn test_parse_selection_ranges_and_duplicates() {
+        assert_eq!(
+            parse_selection("2-4,3 1", 5, true).unwrap(),
+            vec![1, 2, 3, 0]
+        );
+    }
+
+    #[test]
+    fn test_parse_selection_empty_cancels() {
+        assert!(parse_selection("", 3, true).unwrap().is_empty());
+        assert!(parse_selection("  \n", 3, false).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_selection_rejects_invalid_numbers() {
+        let err = parse_selection("abc", 3, true).unwrap_err().to_string();
+        assert!(err.contains("Invalid selection: abc"), "{err}");
+        let err = parse_selection("0", 3, true).unwrap_err().to_string();
+        assert!(err.contains("out of range"), "{err}");
+        let err = parse_selection("4", 3, true).unwrap_err().to_string();
+        assert!(err.contains("choose 1-3"), "{err}");
+        assert!(parse_selection("3-1", 3, true).is_err());
+        assert!(parse_selection("1-", 3, true).is_err());
+    }
+
+    #[test]
+    fn test_parse_selection_single_mode_rejects_multiple() {
+        assert!(parse_selection("1,2", 3, false).is_err());
+        assert!(parse_selection("1-2", 3, false).is_err());
+        // Repeating the same entry is still a single selection
+        assert_eq!(parse_selection("2 2", 3, false).unwrap(), vec![1]);
+    }
+
+    #[test]
+    fn test_eof_answer_cancels() {
+        let answer = read_answer_from(Cursor::new(b"")).unwrap();
+        assert!(parse_selection(&answer, 3, false).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_selection_single_number_with_padding() {
+        assert_eq!(parse_selection(" 3 \r\n", 3, false).unwrap(), vec![2]);
+    }
+
+    #[test]
+    fn test_read_answer_from_reads_one_line() {
+        let answer = read_answer_from(Cursor::new(b"1,2\n3\n")).unwrap();
+        assert_eq!(answer, "1,2\n");
+    }
+}
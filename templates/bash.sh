@@ -1,22 +1,52 @@
 # ofsht shell integration for Bash
 #
 # This function wraps the ofsht command to provide automatic directory
-# changing for 'cd', 'add', and 'rm' subcommands.
+# changing for 'cd', 'add', 'switch', and 'rm' subcommands.
 #
 # Usage:
 #   Add this to your ~/.bashrc:
 #   eval "$(ofsht shell-init bash)"
+#
+# OFSHT_SHELL_INTEGRATION tells ofsht the wrapper is active
+# (checked by 'ofsht completion bash --check-shell-integration').
 
 ofsht() {
-    # Handle cd, add, and rm subcommands with automatic directory changing
-    if [[ "$1" == "cd" ]] || [[ "$1" == "add" ]] || [[ "$1" == "rm" ]]; then
-        local result
-        result=$(command ofsht "$@") || return $?
+    # Find the subcommand, skipping global options such as `-C <path>`
+    local arg subcommand="" skip_value=""
+    for arg in "$@"; do
+        if [[ -n "$skip_value" ]]; then
+            skip_value=""
+        elif [[ "$arg" == "-C" ]] || [[ "$arg" == "--repo" ]] || [[ "$arg" == "--color" ]]; then
+            skip_value=1
+        elif [[ "$arg" != -* ]]; then
+            subcommand="$arg"
+            break
+        fi
+    done
+
+    # Handle cd, add, switch, and rm subcommands with automatic directory changing
+    if [[ "$subcommand" == "cd" ]] || [[ "$subcommand" == "add" ]] || [[ "$subcommand" == "switch" ]] || [[ "$subcommand" == "rm" ]]; then
+        for arg in "$@"; do
+            # --porcelain output is data for the caller, not a directory
+            if [[ "$arg" == "--porcelain" ]]; then
+                OFSHT_SHELL_INTEGRATION=bash command ofsht "$@"
+                return
+            fi
+        done
+        local result exit_code
+        result=$(OFSHT_SHELL_INTEGRATION=bash command ofsht "$@")
+        exit_code=$?
+        # Exit codes 3 (carried changes conflict) and 4 (merge conflict) still
+        # created the worktree: land in it so the conflicts can be resolved there
+        if [[ $exit_code -ne 0 ]] && [[ $exit_code -ne 3 ]] && [[ $exit_code -ne 4 ]]; then
+            return $exit_code
+        fi
         if [[ -n "$result" ]]; then
             cd -- "$result" || return $?
         fi
+        return $exit_code
     else
         # Pass through all other subcommands
-        command ofsht "$@"
+        OFSHT_SHELL_INTEGRATION=bash command ofsht "$@"
     fi
 }
@@ -1,21 +1,50 @@
 # ofsht shell integration for Fish
 #
 # This function wraps the ofsht command to provide automatic directory
-# changing for 'cd', 'add', and 'rm' subcommands.
+# changing for 'cd', 'add', 'switch', and 'rm' subcommands.
 #
 # Usage:
 #   Add this to your ~/.config/fish/config.fish:
 #   ofsht shell-init fish | source
+#
+# OFSHT_SHELL_INTEGRATION tells ofsht the wrapper is active
+# (checked by 'ofsht completion fish --check-shell-integration').
 
 function ofsht
-    # Handle cd, add, and rm subcommands with automatic directory changing
-    if test "$argv[1]" = "cd"; or test "$argv[1]" = "add"; or test "$argv[1]" = "rm"
+    set -lx OFSHT_SHELL_INTEGRATION fish
+    # Find the subcommand, skipping global options such as `-C <path>`
+    set -l subcommand ""
+    set -l skip_value 0
+    for arg in $argv
+        if test $skip_value = 1
+            set skip_value 0
+        else if contains -- $arg -C --repo --color
+            set skip_value 1
+        else if not string match -q -- '-*' $arg
+            set subcommand $arg
+            break
+        end
+    end
+
+    # Handle cd, add, switch, and rm subcommands with automatic directory changing
+    if contains -- "$subcommand" "cd" "add" "switch" "rm"
+        # --porcelain output is data for the caller, not a directory
+        if contains -- --porcelain $argv
+            command ofsht $argv
+            return $status
+        end
         set -l result (command ofsht $argv)
-        or return $status
+        set -l exit_code $status
+        # Exit codes 3 (carried changes conflict) and 4 (merge conflict) still
+        # created the worktree: land in it so the conflicts can be resolved there
+        if not contains -- $exit_code 0 3 4
+            return $exit_code
+        end
         if test -n "$result"
             cd -- $result
             or return $status
         end
+        return $exit_code
     else
         # Pass through all other subcommands
         command ofsht $argv
@@ -0,0 +1,157 @@
+#![allow(deprecated)]
+#![cfg(unix)]
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+use std::os::unix::fs::PermissionsExt;
+use std::process::Command;
+
+fn git(args: &[&str], dir: &std::path::Path) {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {args:?} failed: {output:?}");
+}
+
+/// Initialize an upstream repository with a `feature/login` branch and clone it
+/// to `repo_dir`, so `origin` has the head branch of the stubbed PR
+fn init_repo(temp: &assert_fs::TempDir, repo_dir: &assert_fs::fixture::ChildPath) {
+    let upstream = temp.child("upstream");
+    upstream.create_dir_all().unwrap();
+    for args in [
+        &["init", "-b", "main"][..],
+        &["config", "user.name", "Test User"],
+        &["config", "user.email", "test@example.com"],
+        &["commit", "--allow-empty", "-m", "Initial commit"],
+        &["branch", "feature/login"],
+    ] {
+        git(args, upstream.path());
+    }
+    git(
+        &["clone", "-q", "upstream", repo_dir.path().to_str().unwrap()],
+        temp.path(),
+    );
+}
+
+/// Install a stub `gh` that knows PR #12 (head `feature/login`) and issue #7
+fn install_gh_stub(bin_dir: &assert_fs::fixture::ChildPath) {
+    bin_dir.create_dir_all().unwrap();
+    let stub = bin_dir.child("gh");
+    stub.write_str(
+        r#"#!/bin/sh
+case "$1 $2 $3" in
+  "pr view 12")
+    echo '{"number":12,"title":"Fix login","url":"https://github.com/owner/repo/pull/12","headRefName":"feature/login","isCrossRepository":false}' ;;
+  "issue view 7")
+    echo '{"number":7,"title":"Crash on save","url":"https://github.com/owner/repo/issues/7"}' ;;
+  "pr view "*|"issue view "*)
+    echo 'GraphQL: Could not resolve to an issue or pull request' >&2; exit 1 ;;
+  "repo view "*)
+    echo '{"nameWithOwner":"owner/repo"}' ;;
+esac
+exit 0
+"#,
+    )
+    .unwrap();
+    std::fs::set_permissions(stub.path(), std::fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+fn path_with(bin_dir: &std::path::Path) -> String {
+    format!(
+        "{}:{}",
+        bin_dir.display(),
+        std::env::var("PATH").unwrap_or_default()
+    )
+}
+
+/// Local config with a create hook that dumps the `OFSHT_GH_*` variables
+fn write_env_hook(repo_dir: &assert_fs::fixture::ChildPath) {
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str(
+            r#"[hooks.create]
+run = ["printf '%s|%s|%s|%s|%s' \"$OFSHT_GH_KIND\" \"$OFSHT_GH_NUMBER\" \"$OFSHT_GH_TITLE\" \"$OFSHT_GH_URL\" \"$OFSHT_GH_HEAD_REF\" > \"$HOME/gh-env\""]
+"#,
+        )
+        .unwrap();
+}
+
+fn ofsht(temp: &assert_fs::TempDir, repo_dir: &assert_fs::fixture::ChildPath) -> Command {
+    let mut cmd = Command::cargo_bin("ofsht").unwrap();
+    cmd.current_dir(repo_dir.path())
+        .env("PATH", path_with(&temp.path().join("bin")))
+        .env("HOME", temp.path())
+        .env("XDG_CONFIG_HOME", temp.path().join("xdg"))
+        .env_remove("OFSHT_NO_GH")
+        .env_remove("OFSHT_NO_INTEGRATIONS");
+    cmd
+}
+
+#[test]
+fn test_add_issue_reports_kind_and_exports_metadata_to_hooks() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&temp, &repo_dir);
+    install_gh_stub(&temp.child("bin"));
+    write_env_hook(&repo_dir);
+
+    ofsht(&temp, &repo_dir)
+        .args(["add", "#7"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Fetched issue #7: Crash on save"));
+
+    assert_eq!(
+        std::fs::read_to_string(temp.path().join("gh-env")).unwrap(),
+        "issue|7|Crash on save|https://github.com/owner/repo/issues/7|"
+    );
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_add_pr_reports_kind_and_exports_head_ref_to_hooks() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&temp, &repo_dir);
+    install_gh_stub(&temp.child("bin"));
+    write_env_hook(&repo_dir);
+
+    ofsht(&temp, &repo_dir)
+        .args(["add", "#12"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Fetched PR #12: Fix login"));
+
+    assert_eq!(
+        std::fs::read_to_string(temp.path().join("gh-env")).unwrap(),
+        "pr|12|Fix login|https://github.com/owner/repo/pull/12|feature/login"
+    );
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_add_plain_branch_exports_no_github_metadata() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&temp, &repo_dir);
+    install_gh_stub(&temp.child("bin"));
+    write_env_hook(&repo_dir);
+
+    ofsht(&temp, &repo_dir)
+        .args(["add", "plain"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Fetched").not());
+
+    assert_eq!(
+        std::fs::read_to_string(temp.path().join("gh-env")).unwrap(),
+        "||||"
+    );
+
+    temp.close().unwrap();
+}
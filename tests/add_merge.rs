@@ -0,0 +1,189 @@
+#![allow(deprecated)]
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+fn git(args: &[&str], dir: &std::path::Path) {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {args:?} failed: {output:?}");
+}
+
+/// Initialize a repository where `feature-x` and `feature-y` each add a file,
+/// and `conflicting` changes `shared.txt` like `main` does
+fn init_repo(repo_dir: &assert_fs::fixture::ChildPath) {
+    repo_dir.create_dir_all().unwrap();
+    let dir = repo_dir.path();
+    git(&["init", "-b", "main"], dir);
+    git(&["config", "user.name", "Test User"], dir);
+    git(&["config", "user.email", "test@example.com"], dir);
+    repo_dir.child("shared.txt").write_str("base\n").unwrap();
+    git(&["add", "."], dir);
+    git(&["commit", "-m", "Initial commit"], dir);
+
+    for (branch, file) in [("feature-x", "x.txt"), ("feature-y", "y.txt")] {
+        git(&["checkout", "-q", "-b", branch, "main"], dir);
+        repo_dir.child(file).write_str(branch).unwrap();
+        git(&["add", "."], dir);
+        git(&["commit", "-m", branch], dir);
+    }
+
+    git(&["checkout", "-q", "-b", "conflicting", "main"], dir);
+    repo_dir.child("shared.txt").write_str("theirs\n").unwrap();
+    git(&["commit", "-am", "conflicting"], dir);
+
+    git(&["checkout", "-q", "main"], dir);
+    repo_dir.child("shared.txt").write_str("ours\n").unwrap();
+    git(&["commit", "-am", "main change"], dir);
+}
+
+/// Local config with a create hook that leaves a marker file in the worktree
+fn write_marker_hook(repo_dir: &assert_fs::fixture::ChildPath) {
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str("[hooks.create]\nrun = [\"touch hook-ran\"]\n")
+        .unwrap();
+}
+
+#[test]
+fn test_add_merge_two_refs_cleanly() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    write_marker_hook(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args([
+            "add",
+            "integration-test",
+            "--merge",
+            "main",
+            "feature-x",
+            "feature-y",
+        ])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Merged feature-x"))
+        .stderr(predicate::str::contains("Merged feature-y"));
+
+    let worktree = temp.path().join("test-repo-worktrees/integration-test");
+    assert!(worktree.join("x.txt").exists());
+    assert!(worktree.join("y.txt").exists());
+    assert_eq!(
+        std::fs::read_to_string(worktree.join("shared.txt")).unwrap(),
+        "ours\n"
+    );
+    assert!(worktree.join("hook-ran").exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_add_merge_conflict_leaves_conflicted_worktree() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    write_marker_hook(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args([
+            "add",
+            "integration-test",
+            "--merge",
+            "main",
+            "conflicting",
+            "feature-x",
+        ])
+        .current_dir(repo_dir.path())
+        .assert()
+        .code(4)
+        .stderr(predicate::str::contains("Merging conflicting conflicts in"))
+        .stderr(predicate::str::contains("shared.txt"))
+        .stderr(predicate::str::contains("Not merged yet: feature-x"))
+        .stderr(predicate::str::contains("Skipped create hooks"));
+
+    let worktree = temp.path().join("test-repo-worktrees/integration-test");
+    let content = std::fs::read_to_string(worktree.join("shared.txt")).unwrap();
+    assert!(content.contains("<<<<<<<"), "{content}");
+    assert!(!worktree.join("x.txt").exists());
+    assert!(!worktree.join("hook-ran").exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_add_merge_hooks_on_conflict_runs_hooks() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    write_marker_hook(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args([
+            "add",
+            "integration-test",
+            "--merge",
+            "main",
+            "conflicting",
+            "--hooks-on-conflict",
+        ])
+        .current_dir(repo_dir.path())
+        .assert()
+        .code(4);
+
+    assert!(temp
+        .path()
+        .join("test-repo-worktrees/integration-test/hook-ran")
+        .exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_add_merge_requires_two_refs() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "integration-test", "--merge", "main"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("2 values required"));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_add_merge_rejects_unknown_ref_before_creating() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "integration-test", "--merge", "main", "no-such-ref"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "'no-such-ref' does not resolve to a commit",
+        ));
+    assert!(!temp
+        .path()
+        .join("test-repo-worktrees/integration-test")
+        .exists());
+
+    temp.close().unwrap();
+}
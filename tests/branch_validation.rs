@@ -0,0 +1,69 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use predicates::prelude::*;
+use std::process::Command;
+
+#[test]
+fn test_invalid_branch_names_are_rejected_before_creation() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    for (command, name, reason) in [
+        (
+            "add",
+            "feat something",
+            "branch names cannot contain spaces",
+        ),
+        (
+            "create",
+            "feat something",
+            "branch names cannot contain spaces",
+        ),
+        ("add", "feat..x", "branch names cannot contain '..'"),
+        (
+            "create",
+            "wip.lock",
+            "path components cannot end with '.lock'",
+        ),
+        ("add", "bad~name", "branch names cannot contain '~'"),
+    ] {
+        Command::cargo_bin("ofsht")
+            .unwrap()
+            .args([command, name])
+            .current_dir(repo_dir.path())
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(format!(
+                "Invalid branch name '{name}': {reason}"
+            )));
+    }
+
+    // Nothing was created, not even the worktrees root
+    assert!(!temp.path().join("test-repo-worktrees").exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_detached_add_skips_branch_validation() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    // `HEAD~0` is a commit-ish, not a branch name, so `~` is fine here
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "--detach", "HEAD~0"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+    assert!(temp.path().join("test-repo-worktrees/HEAD~0").exists());
+
+    temp.close().unwrap();
+}
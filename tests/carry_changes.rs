@@ -0,0 +1,99 @@
+#![allow(deprecated)]
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+use std::path::Path;
+use std::process::Command;
+
+fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// Initialize a git repository with a committed `a.txt`
+fn init_repo(repo_dir: &assert_fs::fixture::ChildPath) {
+    repo_dir.create_dir_all().unwrap();
+    git(repo_dir.path(), &["init"]);
+    git(repo_dir.path(), &["config", "user.name", "Test User"]);
+    git(
+        repo_dir.path(),
+        &["config", "user.email", "test@example.com"],
+    );
+    repo_dir.child("a.txt").write_str("base\n").unwrap();
+    git(repo_dir.path(), &["add", "a.txt"]);
+    git(repo_dir.path(), &["commit", "-m", "Initial commit"]);
+}
+
+#[test]
+fn test_carry_changes_moves_edits_into_new_worktree() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    repo_dir.child("a.txt").write_str("mine\n").unwrap();
+    repo_dir.child("new.txt").write_str("untracked\n").unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "feature", "--carry-changes"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    let worktree = temp.child("test-repo-worktrees/feature");
+    worktree.child("a.txt").assert("mine\n");
+    worktree.child("new.txt").assert("untracked\n");
+    repo_dir.child("a.txt").assert("base\n");
+    repo_dir.child("new.txt").assert(predicate::path::missing());
+    assert!(git(repo_dir.path(), &["stash", "list"]).is_empty());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_carry_changes_conflict_keeps_stash_and_exits_3() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    git(repo_dir.path(), &["checkout", "-q", "-b", "other"]);
+    repo_dir.child("a.txt").write_str("other\n").unwrap();
+    git(repo_dir.path(), &["commit", "-qam", "Other"]);
+    git(repo_dir.path(), &["checkout", "-q", "-"]);
+    repo_dir.child("a.txt").write_str("mine\n").unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "feature", "other", "--carry-changes"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .code(3)
+        .stderr(predicate::str::contains("└─ a.txt"))
+        .stderr(predicate::str::contains("git stash drop stash@{0}"));
+
+    assert!(temp.path().join("test-repo-worktrees/feature").exists());
+    assert!(git(repo_dir.path(), &["stash", "list"]).contains("ofsht: carry to feature"));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_carry_changes_clean_worktree_is_a_no_op() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "feature", "--carry-changes"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    assert!(git(repo_dir.path(), &["stash", "list"]).is_empty());
+
+    temp.close().unwrap();
+}
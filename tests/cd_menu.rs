@@ -0,0 +1,136 @@
+#![allow(deprecated)]
+#![cfg(unix)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo_on_main;
+use predicates::prelude::*;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Initialize a git repository on `main` with a `feature` worktree next to it
+fn init_repo_with_feature_worktree(repo_dir: &assert_fs::fixture::ChildPath) {
+    init_repo_on_main(repo_dir);
+    Command::new("git")
+        .args([
+            "worktree",
+            "add",
+            "-b",
+            "feature",
+            "../test-repo-worktrees/feature",
+        ])
+        .current_dir(repo_dir.path())
+        .output()
+        .unwrap();
+}
+
+/// Whether util-linux `script` is available to run ofsht on a pseudo-terminal
+///
+/// Without it the menu tests are skipped locally (e.g. BSD `script` on macOS),
+/// but on CI (`CI` set) a missing `script` fails them instead of hiding them.
+fn has_script() -> bool {
+    let available = Command::new("script")
+        .args(["-qec", "true", "/dev/null"])
+        .stdin(Stdio::null())
+        .output()
+        .is_ok_and(|output| output.status.success());
+    assert!(
+        available || std::env::var_os("CI").is_none(),
+        "util-linux `script` is required on CI to run the menu tests"
+    );
+    if !available {
+        eprintln!("skipping: `script` is not available");
+    }
+    available
+}
+
+/// Run `ofsht cd` on a pseudo-terminal (so the menu is shown), answering with `input`
+fn cd_on_tty(repo_dir: &std::path::Path, input: &str) -> std::process::Output {
+    let ofsht = assert_cmd::cargo::cargo_bin("ofsht");
+    let mut child = Command::new("script")
+        .args([
+            "-qec",
+            &format!("{} --color=never cd", ofsht.display()),
+            "/dev/null",
+        ])
+        .current_dir(repo_dir)
+        .env("OFSHT_NO_FZF", "1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn test_cd_menu_prints_selected_worktree() {
+    if !has_script() {
+        return;
+    }
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_feature_worktree(&repo_dir);
+
+    let output = cd_on_tty(repo_dir.path(), "2\n");
+    assert!(output.status.success(), "{output:?}");
+    // stdout and stderr share the terminal: menu rows, prompt, then the path
+    let screen = String::from_utf8_lossy(&output.stdout);
+    assert!(screen.contains("1) "), "{screen}");
+    assert!(screen.contains("2) "), "{screen}");
+    assert!(screen.contains("[feature]"), "{screen}");
+    assert!(
+        screen.contains("test-repo-worktrees/feature\r\n"),
+        "{screen}"
+    );
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_cd_menu_eof_cancels_cleanly() {
+    if !has_script() {
+        return;
+    }
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_feature_worktree(&repo_dir);
+
+    let output = cd_on_tty(repo_dir.path(), "");
+    assert!(output.status.success(), "{output:?}");
+    let screen = String::from_utf8_lossy(&output.stdout);
+    assert!(screen.contains("Select a worktree"), "{screen}");
+    assert!(!screen.contains("test-repo-worktrees/feature"), "{screen}");
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_cd_without_fzf_or_tty_keeps_failing() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_feature_worktree(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .arg("cd")
+        .current_dir(repo_dir.path())
+        .env("OFSHT_NO_FZF", "1")
+        .stdin(Stdio::null())
+        .assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains(
+            "Provide a worktree name or enable fzf in config",
+        ));
+
+    temp.close().unwrap();
+}
@@ -0,0 +1,70 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use predicates::prelude::*;
+use std::process::Command;
+
+/// Create a `feature` worktree and delete its directory behind git's back
+fn create_prunable_worktree(
+    temp: &assert_fs::TempDir,
+    repo_dir: &assert_fs::fixture::ChildPath,
+) -> std::path::PathBuf {
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+    let worktree = temp.child("test-repo-worktrees/feature");
+    std::fs::remove_dir_all(worktree.path()).unwrap();
+    worktree.path().to_path_buf()
+}
+
+#[test]
+fn test_cd_prunable_worktree_errors_with_hint() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    create_prunable_worktree(&temp, &repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["cd", "feature"])
+        .env("OFSHT_NO_FZF", "1")
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("Worktree 'feature' is prunable"))
+        .stderr(predicate::str::contains("ofsht rm feature"));
+}
+
+#[test]
+fn test_cd_restore_recreates_worktree_on_same_branch() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    let worktree = create_prunable_worktree(&temp, &repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["cd", "--restore", "feature"])
+        .env("OFSHT_NO_FZF", "1")
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("test-repo-worktrees/feature"))
+        .stderr(predicate::str::contains("Restored feature"));
+
+    assert!(worktree.is_dir());
+    let head = Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .current_dir(&worktree)
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&head.stdout).trim(), "feature");
+}
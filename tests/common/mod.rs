@@ -0,0 +1,41 @@
+//! Fixtures shared by the integration tests (`mod common;` in each test file)
+
+// Every test binary compiles its own copy and uses only some of the helpers
+#![allow(dead_code)]
+
+use assert_fs::fixture::ChildPath;
+use assert_fs::prelude::*;
+use std::process::Command;
+
+fn init_repo_with(repo_dir: &ChildPath, init: &[&str]) {
+    repo_dir.create_dir_all().unwrap();
+    for args in [
+        init,
+        &["config", "user.name", "Test User"],
+        &["config", "user.email", "test@example.com"],
+        &["commit", "--allow-empty", "-m", "Initial commit"],
+    ] {
+        Command::new("git")
+            .args(args)
+            .current_dir(repo_dir.path())
+            .output()
+            .unwrap();
+    }
+}
+
+/// Initialize a git repository with an initial commit
+pub fn init_repo(repo_dir: &ChildPath) {
+    init_repo_with(repo_dir, &["init"]);
+}
+
+/// [`init_repo`] with the initial branch named `main`, whatever
+/// `init.defaultBranch` says
+pub fn init_repo_on_main(repo_dir: &ChildPath) {
+    init_repo_with(repo_dir, &["init", "-b", "main"]);
+}
+
+/// [`init_repo`] with `config` written to `.ofsht.toml`
+pub fn init_repo_with_config(repo_dir: &ChildPath, config: &str) {
+    init_repo(repo_dir);
+    repo_dir.child(".ofsht.toml").write_str(config).unwrap();
+}
@@ -80,6 +80,72 @@ fn test_completions_invalid_shell() {
     );
 }
 
+#[test]
+fn test_completions_output_writes_script_file() {
+    let temp = tempfile::tempdir().unwrap();
+    let output_path = temp.path().join("share/zsh/site-functions/_ofsht");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_ofsht"))
+        .args(["completion", "zsh", "--output"])
+        .arg(&output_path)
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success(), "Command should succeed");
+    assert!(
+        output.stdout.is_empty(),
+        "Nothing should be printed to stdout"
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Wrote zsh completion script"));
+    let script = std::fs::read_to_string(&output_path).unwrap();
+    assert!(!script.is_empty());
+    assert!(script.contains("COMPLETE="), "{script}");
+}
+
+#[test]
+fn test_completions_rejects_unknown_shell_with_allowed_values() {
+    let output = Command::new(env!("CARGO_BIN_EXE_ofsht"))
+        .args(["completion", "nope"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("invalid value 'nope'"), "{stderr}");
+    assert!(
+        stderr.contains("[possible values: bash, zsh, fish, powershell, elvish]"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn test_completions_powershell_and_elvish_print_static_scripts() {
+    for shell in ["powershell", "elvish"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_ofsht"))
+            .args(["completion", shell])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(output.status.success(), "{shell} should succeed");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(!stdout.is_empty(), "{shell} output should not be empty");
+        assert!(stdout.contains("ofsht"), "{shell}: {stdout}");
+    }
+}
+
+#[test]
+fn test_shell_init_rejects_shells_without_integration() {
+    let output = Command::new(env!("CARGO_BIN_EXE_ofsht"))
+        .args(["shell-init", "powershell"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains(
+        "Shell integration is not available for powershell. Supported shells: bash, zsh, fish"
+    ));
+}
+
 // ----- Flag-filter integration tests -----
 // These tests exercise the FilteredBash/Zsh/Fish adapters end-to-end by invoking
 // the binary directly via CARGO_BIN_EXE_ofsht (no cargo run recompilation).
@@ -189,3 +255,50 @@ fn test_fish_empty_word_excludes_flags() {
         );
     }
 }
+
+// ----- completion --check-shell-integration -----
+
+fn check_shell_integration(home: &std::path::Path, wrapper: Option<&str>) -> std::process::Output {
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_ofsht"));
+    cmd.args([
+        "--color=never",
+        "completion",
+        "bash",
+        "--check-shell-integration",
+    ])
+    .env("HOME", home)
+    .env_remove("XDG_CONFIG_HOME")
+    .env_remove("COMPLETE");
+    match wrapper {
+        Some(shell) => cmd.env("OFSHT_SHELL_INTEGRATION", shell),
+        None => cmd.env_remove("OFSHT_SHELL_INTEGRATION"),
+    };
+    cmd.output().unwrap()
+}
+
+#[test]
+fn test_check_shell_integration_all_pass() {
+    let home = tempfile::tempdir().unwrap();
+    let output = check_shell_integration(home.path(), Some("bash"));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "stderr: {stderr}");
+    assert!(stderr.contains("✓ Shell wrapper function is loaded"));
+    assert!(stderr.contains("✓ Completion engine returns candidates"));
+    assert!(!stderr.contains('✗'), "stderr: {stderr}");
+}
+
+#[test]
+fn test_check_shell_integration_reports_failures() {
+    let home = tempfile::tempdir().unwrap();
+    let static_dir = home.path().join(".local/share/bash-completion/completions");
+    std::fs::create_dir_all(&static_dir).unwrap();
+    std::fs::write(static_dir.join("ofsht"), "complete -F _ofsht ofsht\n").unwrap();
+
+    let output = check_shell_integration(home.path(), None);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("✗ Shell wrapper function is loaded"));
+    assert!(stderr.contains("ofsht shell-init bash"));
+    assert!(stderr.contains("✗ Static completion script found"));
+    assert!(stderr.contains("2 shell integration check(s) failed"));
+}
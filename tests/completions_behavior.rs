@@ -130,9 +130,41 @@ fn get_completions(args: &[&str], git_repo_dir: &std::path::Path) -> Vec<String>
         .collect()
 }
 
-// Note: The branch argument for `add` command does NOT have completion
-// because it's meant for a new branch name, not an existing ref.
-// Completion is only available for the start_point argument.
+#[test]
+#[serial]
+fn test_add_branch_completion_skips_checked_out_branches() {
+    let repo = GitTestRepo::new();
+
+    repo.create_worktree("feature-1");
+    repo.create_branch("feature-2");
+    repo.create_remote("origin");
+    repo.create_remote_branch("origin", "colleague-fix");
+
+    for command in ["add", "create"] {
+        let candidates = get_completions(&[command, ""], repo.path());
+        assert!(
+            !candidates.contains(&"feature-1".to_string()),
+            "feature-1 has a worktree: {candidates:?}"
+        );
+        assert!(
+            !candidates.contains(&"main".to_string()),
+            "main is checked out: {candidates:?}"
+        );
+        assert!(
+            candidates.contains(&"feature-2".to_string()),
+            "Expected feature-2 in candidates: {candidates:?}"
+        );
+        // Remote-only branches are offered without the remote prefix
+        assert!(
+            candidates.contains(&"colleague-fix".to_string()),
+            "Expected colleague-fix in candidates: {candidates:?}"
+        );
+        assert!(
+            !candidates.iter().any(|c| c.starts_with("origin/")),
+            "Remote prefixes should be stripped: {candidates:?}"
+        );
+    }
+}
 
 #[test]
 #[serial]
@@ -240,3 +272,144 @@ fn test_rm_completion_shows_flags_with_dash() {
         "Should contain flags in candidates: {candidates:?}"
     );
 }
+
+#[test]
+#[serial]
+fn test_cd_completion_includes_relative_and_absolute_paths_for_nested_worktree() {
+    let repo = GitTestRepo::new();
+
+    let worktrees = repo.dir.child("wt");
+    let flat = worktrees.child("feature");
+    let nested = worktrees.child("docs").child("tweak");
+    for (branch, path) in [("feature", &flat), ("docs/tweak", &nested)] {
+        GitTestRepo::run_git(
+            &repo.dir,
+            &[
+                "worktree",
+                "add",
+                "-b",
+                branch,
+                path.path().to_str().unwrap(),
+            ],
+        );
+    }
+
+    // Test: ofsht cd <TAB>
+    let candidates = get_completions(&["cd", ""], repo.path());
+
+    assert!(
+        candidates.contains(&"docs/tweak".to_string()),
+        "Expected relative path docs/tweak in candidates: {candidates:?}"
+    );
+    let absolute = nested
+        .path()
+        .canonicalize()
+        .unwrap()
+        .to_string_lossy()
+        .to_string();
+    assert!(
+        candidates.contains(&absolute),
+        "Expected absolute path {absolute} in candidates: {candidates:?}"
+    );
+    // Branch name and relative path coincide for docs/tweak: no duplicates
+    assert_eq!(
+        candidates.iter().filter(|c| *c == "docs/tweak").count(),
+        1,
+        "docs/tweak must be deduplicated: {candidates:?}"
+    );
+}
+
+#[test]
+#[serial]
+fn test_cd_completion_keeps_branch_and_same_named_directory_apart() {
+    let repo = GitTestRepo::new();
+
+    let worktrees = repo.dir.child("wt");
+    for (branch, dir) in [
+        ("feature", "feature-main"),
+        ("feature-2", "feature-2"),
+        ("other", "feature"),
+        ("feat/feature", "feat/feature"),
+    ] {
+        GitTestRepo::run_git(
+            &repo.dir,
+            &[
+                "worktree",
+                "add",
+                "-b",
+                branch,
+                worktrees.child(dir).path().to_str().unwrap(),
+            ],
+        );
+    }
+
+    let mut candidates = get_completions(&["cd", "feat"], repo.path());
+    // `feature` (branch and directory) appears once; branches come before relative paths
+    assert_eq!(candidates.last().map(String::as_str), Some("feature-main"));
+    candidates.sort();
+    assert_eq!(
+        candidates,
+        ["feat/feature", "feature", "feature-2", "feature-main"],
+        "{candidates:?}"
+    );
+
+    let candidates = get_completions(&["rm", "oth"], repo.path());
+    assert_eq!(candidates, ["other"], "{candidates:?}");
+}
+
+#[test]
+#[serial]
+fn test_start_point_completion_limit_prefers_local_branches() {
+    let repo = GitTestRepo::new();
+
+    repo.create_branch("develop");
+    repo.create_branch("feature");
+    repo.create_remote("origin");
+    repo.create_remote_branch("origin", "release");
+    GitTestRepo::run_git(&repo.dir, &["tag", "v1.0.0"]);
+
+    std::env::set_var("OFSHT_COMPLETION_LIMIT", "3");
+    let candidates: Vec<String> = get_completions(&["add", "new-feature", ""], repo.path())
+        .into_iter()
+        .filter(|c| !c.starts_with('-'))
+        .collect();
+    std::env::remove_var("OFSHT_COMPLETION_LIMIT");
+
+    // Local branches (develop, feature, main) fill the cap; tags and remotes are cut
+    assert_eq!(candidates.len(), 3, "Expected 3 candidates: {candidates:?}");
+    for branch in ["develop", "feature", "main"] {
+        assert!(
+            candidates.contains(&branch.to_string()),
+            "Expected {branch} in candidates: {candidates:?}"
+        );
+    }
+    assert!(
+        !candidates.contains(&"origin/release".to_string()),
+        "Remote branch should be truncated: {candidates:?}"
+    );
+}
+
+#[test]
+#[serial]
+fn test_start_point_completion_prefix_matches_nested_branches() {
+    let repo = GitTestRepo::new();
+
+    repo.create_branch("feat/login");
+    repo.create_branch("feat-logout");
+    repo.create_branch("other");
+
+    let candidates = get_completions(&["add", "new-feature", "feat"], repo.path());
+
+    assert!(
+        candidates.contains(&"feat/login".to_string()),
+        "Expected feat/login in candidates: {candidates:?}"
+    );
+    assert!(
+        candidates.contains(&"feat-logout".to_string()),
+        "Expected feat-logout in candidates: {candidates:?}"
+    );
+    assert!(
+        !candidates.contains(&"other".to_string()),
+        "Unexpected other in candidates: {candidates:?}"
+    );
+}
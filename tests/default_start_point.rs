@@ -0,0 +1,131 @@
+#![allow(deprecated)]
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use std::process::Command;
+
+fn git(repo_dir: &assert_fs::fixture::ChildPath, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(repo_dir.path())
+        .output()
+        .unwrap();
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// Initialize a repository whose `develop` branch is one commit ahead of HEAD,
+/// with `worktree.default_start_point = "develop"`
+fn init_repo(repo_dir: &assert_fs::fixture::ChildPath) {
+    repo_dir.create_dir_all().unwrap();
+    for args in [
+        &["init"][..],
+        &["config", "user.name", "Test User"],
+        &["config", "user.email", "test@example.com"],
+        &["commit", "--allow-empty", "-m", "Initial commit"],
+        &["branch", "existing"],
+        &["checkout", "-b", "develop"],
+        &["commit", "--allow-empty", "-m", "Develop commit"],
+        &["checkout", "-"],
+    ] {
+        git(repo_dir, args);
+    }
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str("[worktree]\ndefault_start_point = \"develop\"\n")
+        .unwrap();
+}
+
+#[test]
+fn test_add_new_branch_starts_from_default_start_point() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "feature", "--no-tmux"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    assert_eq!(
+        git(&repo_dir, &["rev-parse", "feature"]),
+        git(&repo_dir, &["rev-parse", "develop"])
+    );
+}
+
+#[test]
+fn test_explicit_start_point_overrides_default() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature", "existing"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    assert_eq!(
+        git(&repo_dir, &["rev-parse", "feature"]),
+        git(&repo_dir, &["rev-parse", "existing"])
+    );
+}
+
+#[test]
+fn test_existing_branch_is_checked_out_unchanged() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    let before = git(&repo_dir, &["rev-parse", "existing"]);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "existing", "--no-tmux"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    assert_eq!(git(&repo_dir, &["rev-parse", "existing"]), before);
+    assert_ne!(before, git(&repo_dir, &["rev-parse", "develop"]));
+}
+
+#[test]
+fn test_remote_only_branch_tracks_remote_instead_of_default_start_point() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    let remote_dir = temp.child("origin.git");
+    remote_dir.create_dir_all().unwrap();
+    git(&remote_dir, &["init", "--bare"]);
+    let remote_url = remote_dir.path().to_str().unwrap();
+    git(&repo_dir, &["remote", "add", "origin", remote_url]);
+    git(
+        &repo_dir,
+        &["push", "--quiet", "origin", "existing:colleague-fix"],
+    );
+    git(&repo_dir, &["fetch", "--quiet", "origin"]);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "colleague-fix"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    assert_eq!(
+        git(&repo_dir, &["rev-parse", "colleague-fix"]),
+        git(&repo_dir, &["rev-parse", "existing"])
+    );
+    assert_eq!(
+        git(
+            &repo_dir,
+            &["rev-parse", "--abbrev-ref", "colleague-fix@{upstream}"]
+        ),
+        "origin/colleague-fix"
+    );
+
+    temp.close().unwrap();
+}
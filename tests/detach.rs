@@ -0,0 +1,239 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use ofsht::report;
+use predicates::prelude::*;
+use std::process::Command;
+
+/// Initialize a git repository with a `v1.0.0` tag one commit behind `HEAD`
+fn init_repo_with_tag(repo_dir: &assert_fs::fixture::ChildPath) {
+    init_repo(repo_dir);
+    for args in [
+        &["tag", "v1.0.0"][..],
+        &["commit", "--allow-empty", "-m", "Second commit"],
+    ] {
+        Command::new("git")
+            .args(args)
+            .current_dir(repo_dir.path())
+            .output()
+            .unwrap();
+    }
+}
+
+fn rev_parse(dir: &std::path::Path, rev: &str) -> String {
+    let output = Command::new("git")
+        .args(["rev-parse", rev])
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+#[test]
+fn test_add_detach_at_tag_lifecycle() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_tag(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "--detach", "v1.0.0"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    let worktree_path = temp.path().join("test-repo-worktrees/v1.0.0");
+    assert!(worktree_path.exists());
+    assert_eq!(
+        rev_parse(&worktree_path, "HEAD"),
+        rev_parse(repo_dir.path(), "v1.0.0^{commit}")
+    );
+
+    // No branch was created for the tag
+    Command::new("git")
+        .args(["show-ref", "--verify", "refs/heads/v1.0.0"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure();
+
+    // ls shows the detached entry
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["--color=never", "ls", "--show-path"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[detached]"));
+
+    // cd resolves it by its relative path
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["cd", "v1.0.0"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("test-repo-worktrees/v1.0.0"));
+
+    // rm removes it without attempting branch deletion
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "v1.0.0"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Removed"))
+        .stderr(predicate::str::contains(report::DELETED_BRANCH).not());
+    assert!(!worktree_path.exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_create_detach_with_explicit_name() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_tag(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "--detach", "repro", "v1.0.0"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    let worktree_path = temp.path().join("test-repo-worktrees/repro");
+    assert!(worktree_path.exists());
+    assert_eq!(
+        rev_parse(&worktree_path, "HEAD"),
+        rev_parse(repo_dir.path(), "v1.0.0^{commit}")
+    );
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_add_detach_with_start_point_has_no_symbolic_head() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_tag(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "--detach", "scratch", "v1.0.0"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    let worktree_path = temp.path().join("test-repo-worktrees/scratch");
+    // HEAD is not a symbolic ref in a detached worktree
+    Command::new("git")
+        .args(["-C"])
+        .arg(&worktree_path)
+        .args(["symbolic-ref", "-q", "HEAD"])
+        .assert()
+        .failure();
+    assert_eq!(
+        rev_parse(&worktree_path, "HEAD"),
+        rev_parse(repo_dir.path(), "v1.0.0^{commit}")
+    );
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_add_detach_rejects_github_reference() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_tag(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "--detach", "#123"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--detach cannot be combined with a GitHub reference",
+        ));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_add_no_branch_at_commit_sha() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_tag(&repo_dir);
+    let sha = rev_parse(repo_dir.path(), "HEAD~1");
+    let short = &sha[..10];
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "--no-branch", short])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    let worktree_path = temp.path().join(format!("test-repo-worktrees/{short}"));
+    assert_eq!(rev_parse(&worktree_path, "HEAD"), sha);
+    // No branch was created for the commit
+    Command::new("git")
+        .args(["show-ref", "--verify", &format!("refs/heads/{short}")])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure();
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_add_no_branch_at_stash_uses_sanitized_directory() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_tag(&repo_dir);
+    repo_dir.child("wip.txt").write_str("wip").unwrap();
+    Command::new("git")
+        .args(["stash", "push", "-u", "-m", "wip"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "--no-branch", "stash@{0}"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    let worktree_path = temp.path().join("test-repo-worktrees/stash-0");
+    assert_eq!(
+        rev_parse(&worktree_path, "HEAD"),
+        rev_parse(repo_dir.path(), "stash@{0}")
+    );
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_add_no_branch_rejects_unresolvable_commitish() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_tag(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "--no-branch", "no-such-ref"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "'no-such-ref' does not resolve to a commit",
+        ));
+    assert!(!temp.path().join("test-repo-worktrees/no-such-ref").exists());
+
+    temp.close().unwrap();
+}
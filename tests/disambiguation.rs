@@ -0,0 +1,174 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use predicates::prelude::*;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Worktrees with overlapping names under `test-repo-worktrees/`:
+///
+/// | branch         | directory      |
+/// |----------------|----------------|
+/// | `feature`      | `feature-main` |
+/// | `feature-2`    | `feature-2`    |
+/// | `other`        | `feature`      |
+/// | `feat/feature` | `feat/feature` |
+fn setup(temp: &assert_fs::TempDir) -> (PathBuf, PathBuf) {
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    let worktrees = temp.path().join("test-repo-worktrees");
+    for (branch, dir) in [
+        ("feature", "feature-main"),
+        ("feature-2", "feature-2"),
+        ("other", "feature"),
+        ("feat/feature", "feat/feature"),
+    ] {
+        Command::new("git")
+            .args(["worktree", "add", "-b", branch])
+            .arg(worktrees.join(dir))
+            .current_dir(repo_dir.path())
+            .assert()
+            .success();
+    }
+    (
+        repo_dir.path().to_path_buf(),
+        worktrees.canonicalize().unwrap(),
+    )
+}
+
+fn cd(repo_dir: &Path, name: &str) -> Option<PathBuf> {
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["cd", name])
+        .env("OFSHT_NO_FZF", "1")
+        .current_dir(repo_dir)
+        .output()
+        .unwrap();
+    output
+        .status
+        .success()
+        .then(|| PathBuf::from(String::from_utf8(output.stdout).unwrap().trim()))
+}
+
+#[test]
+fn test_cd_prefers_exact_branch_then_relative_path() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let (repo_dir, worktrees) = setup(&temp);
+
+    assert_eq!(
+        cd(&repo_dir, "feature"),
+        Some(worktrees.join("feature-main"))
+    );
+    assert_eq!(
+        cd(&repo_dir, "feature-2"),
+        Some(worktrees.join("feature-2"))
+    );
+    assert_eq!(cd(&repo_dir, "other"), Some(worktrees.join("feature")));
+    assert_eq!(
+        cd(&repo_dir, "feat/feature"),
+        Some(worktrees.join("feat/feature"))
+    );
+    assert_eq!(
+        cd(&repo_dir, "feature-main"),
+        Some(worktrees.join("feature-main"))
+    );
+
+    // Substrings only resolve when a single worktree contains them
+    assert_eq!(cd(&repo_dir, "2"), Some(worktrees.join("feature-2")));
+    assert_eq!(cd(&repo_dir, "OTH"), Some(worktrees.join("feature")));
+    for name in [
+        "feat",
+        "featur",
+        "feature-",
+        "../test-repo-worktrees/feature-2/..",
+    ] {
+        assert_eq!(cd(&repo_dir, name), None, "{name}");
+    }
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_cd_lists_ambiguous_substring_matches() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let (repo_dir, _) = setup(&temp);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["cd", "ture-"])
+        .env("OFSHT_NO_FZF", "1")
+        .current_dir(&repo_dir)
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(predicate::str::contains("'ture-' matches 2 worktrees:"))
+        .stderr(predicate::str::contains("  feature ("))
+        .stderr(predicate::str::contains("  feature-2 ("))
+        .stderr(predicate::str::contains("Ambiguous worktree name: ture-"));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_rm_removes_the_branch_worktree_not_the_same_named_directory() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let (repo_dir, worktrees) = setup(&temp);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "feature"])
+        .current_dir(&repo_dir)
+        .assert()
+        .success();
+
+    assert!(!worktrees.join("feature-main").exists());
+    assert!(worktrees.join("feature").exists());
+    assert!(worktrees.join("feature-2").exists());
+    assert!(worktrees.join("feat/feature").exists());
+
+    // With the branch gone, `feature` now names the directory of `other`
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "feature"])
+        .current_dir(&repo_dir)
+        .assert()
+        .success();
+    assert!(!worktrees.join("feature").exists());
+    assert!(worktrees.join("feature-2").exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_ls_relative_paths_follow_directories() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let (repo_dir, _) = setup(&temp);
+
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["ls", "--show-path", "--no-truncate", "--color=never"])
+        .current_dir(&repo_dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let row = |branch: &str| {
+        stdout
+            .lines()
+            .find(|line| line.contains(&format!("[{branch}]")))
+            .unwrap_or_else(|| panic!("no row for {branch} in:\n{stdout}"))
+            .split_whitespace()
+            .collect::<Vec<_>>()
+    };
+
+    assert!(row("feature").contains(&"feature-main"), "{stdout}");
+    assert!(row("other").contains(&"feature"), "{stdout}");
+    assert!(row("feature-2").contains(&"feature-2"), "{stdout}");
+    assert!(row("feat/feature").contains(&"feat/feature"), "{stdout}");
+
+    temp.close().unwrap();
+}
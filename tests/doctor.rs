@@ -0,0 +1,50 @@
+#![allow(deprecated)]
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+#[test]
+fn test_doctor_reports_each_integration() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    repo_dir.create_dir_all().unwrap();
+    Command::new("git")
+        .args(["init"])
+        .current_dir(repo_dir.path())
+        .output()
+        .unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .arg("doctor")
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("gh:"))
+        .stderr(predicate::str::contains("zoxide:"))
+        .stderr(predicate::str::contains("fzf:"))
+        .stderr(predicate::str::contains("tmux:"))
+        .stderr(predicate::str::contains("Git repository:"))
+        .stderr(predicate::str::contains("worktree.dir:"));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_doctor_succeeds_outside_git_repository() {
+    let temp = assert_fs::TempDir::new().unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .arg("doctor")
+        .current_dir(temp.path())
+        .env("GIT_CEILING_DIRECTORIES", temp.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Not inside a git repository"));
+
+    temp.close().unwrap();
+}
@@ -0,0 +1,110 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use predicates::prelude::*;
+use std::process::Command;
+
+#[test]
+fn test_no_tmux_env_beats_tmux_flag() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    // Without the override, --tmux outside a tmux session would fail detection
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "--tmux", "feature-ci"])
+        .env("OFSHT_NO_TMUX", "1")
+        .env_remove("TMUX")
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Ignoring --tmux"));
+
+    assert!(temp.path().join("test-repo-worktrees/feature-ci").exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_no_integrations_env_disables_open() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .arg("open")
+        .env("OFSHT_NO_INTEGRATIONS", "1")
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("disabled by the environment"));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_create_accepts_tmux_flags_without_printing_path() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "--tmux", "feature-tmux"])
+        .env("OFSHT_NO_TMUX", "1")
+        .env_remove("TMUX")
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("Ignoring --tmux"));
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "--no-tmux", "feature-plain"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    assert!(temp
+        .path()
+        .join("test-repo-worktrees/feature-tmux")
+        .exists());
+    assert!(temp
+        .path()
+        .join("test-repo-worktrees/feature-plain")
+        .exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_create_tmux_outside_tmux_fails_before_creating() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "--tmux", "feature-tmux"])
+        .env_remove("TMUX")
+        .env_remove("OFSHT_NO_TMUX")
+        .env_remove("OFSHT_NO_INTEGRATIONS")
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure();
+
+    assert!(!temp
+        .path()
+        .join("test-repo-worktrees/feature-tmux")
+        .exists());
+
+    temp.close().unwrap();
+}
@@ -0,0 +1,184 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use predicates::prelude::*;
+use std::process::Command;
+
+fn create_worktrees(repo_dir: &assert_fs::fixture::ChildPath, branches: &[&str]) {
+    for branch in branches {
+        Command::cargo_bin("ofsht")
+            .unwrap()
+            .args(["add", branch])
+            .current_dir(repo_dir.path())
+            .assert()
+            .success();
+    }
+}
+
+#[test]
+fn test_exec_runs_in_named_worktrees() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    create_worktrees(&repo_dir, &["feature-a", "feature-b", "feature-c"]);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["exec", "feature-a", "feature-b", "--", "touch", "marker"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    let worktrees = temp.path().join("test-repo-worktrees");
+    assert!(worktrees.join("feature-a/marker").exists());
+    assert!(worktrees.join("feature-b/marker").exists());
+    assert!(!worktrees.join("feature-c/marker").exists());
+    assert!(!repo_dir.path().join("marker").exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_exec_all_streams_output_to_stderr() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    create_worktrees(&repo_dir, &["feature-a"]);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args([
+            "exec",
+            "--all",
+            "--",
+            "sh",
+            "-c",
+            "echo hello; touch marker",
+        ])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("[@] hello"))
+        .stderr(predicate::str::contains("[feature-a] hello"));
+
+    assert!(repo_dir.path().join("marker").exists());
+    assert!(temp
+        .path()
+        .join("test-repo-worktrees/feature-a/marker")
+        .exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_exec_stops_at_first_failure() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    create_worktrees(&repo_dir, &["feature-a", "feature-b"]);
+
+    // Fails in feature-a (first), so feature-b never runs
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args([
+            "exec",
+            "feature-a",
+            "feature-b",
+            "--",
+            "sh",
+            "-c",
+            "touch marker; exit 3",
+        ])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "feature-a: command failed (exit 3)",
+        ))
+        .stderr(predicate::str::contains("Command failed in 1 worktree(s)"));
+
+    let worktrees = temp.path().join("test-repo-worktrees");
+    assert!(worktrees.join("feature-a/marker").exists());
+    assert!(!worktrees.join("feature-b/marker").exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_exec_keep_going_runs_everywhere_and_fails() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    create_worktrees(&repo_dir, &["feature-a", "feature-b"]);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args([
+            "exec",
+            "--keep-going",
+            "feature-a",
+            "feature-b",
+            "--",
+            "sh",
+            "-c",
+            "touch marker; exit 1",
+        ])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Command failed in 2 worktree(s): feature-a, feature-b",
+        ));
+
+    let worktrees = temp.path().join("test-repo-worktrees");
+    assert!(worktrees.join("feature-a/marker").exists());
+    assert!(worktrees.join("feature-b/marker").exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_exec_keep_going_survives_missing_worktree_directory() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    create_worktrees(&repo_dir, &["feature-a", "feature-b"]);
+
+    // feature-a becomes prunable: git still lists it, but nothing can run there
+    let worktrees = temp.path().join("test-repo-worktrees");
+    std::fs::remove_dir_all(worktrees.join("feature-a")).unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["exec", "--all", "--keep-going", "--", "touch", "marker"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "feature-a: Failed to execute command",
+        ))
+        .stderr(predicate::str::contains(
+            "Command failed in 1 worktree(s): feature-a",
+        ));
+
+    assert!(repo_dir.path().join("marker").exists());
+    assert!(worktrees.join("feature-b/marker").exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_exec_requires_target_or_all() {
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["exec", "--", "true"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("<TARGETS>"));
+}
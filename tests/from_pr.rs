@@ -0,0 +1,59 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use predicates::prelude::*;
+use std::process::Command;
+
+#[test]
+fn test_from_pr_rejects_values_that_are_not_references() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "--from-pr", "feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid --from-pr value 'feature': expected #123, 123 or a GitHub pull request URL",
+        ));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_from_pr_conflicts_with_branch_argument() {
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "--from-pr", "12", "feature", "main"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn test_pr_url_is_literal_when_github_integration_is_disabled() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    // Treated as a branch name, which git refuses
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "https://github.com/org/repo/pull/12"])
+        .env("OFSHT_NO_GH", "1")
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Treating 'https://github.com/org/repo/pull/12' as a literal branch name",
+        ));
+
+    temp.close().unwrap();
+}
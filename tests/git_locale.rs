@@ -0,0 +1,118 @@
+#![allow(deprecated)]
+#![cfg(unix)]
+
+//! ofsht parses some of git's human-readable messages (`Deleted branch ...`,
+//! `error: branch '...' not found.`). These tests run ofsht with a German
+//! locale and a `git` shim that translates those messages the way a localized
+//! git does, unless `LC_ALL=C` is set.
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use predicates::prelude::*;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::Command;
+
+/// Install a `git` shim that translates `git branch` messages to German
+/// unless `LC_ALL=C`, and return a `PATH` that finds it first
+fn install_german_git(bin_dir: &Path) -> String {
+    let real_git = Command::new("sh")
+        .args(["-c", "command -v git"])
+        .output()
+        .unwrap();
+    let real_git = String::from_utf8(real_git.stdout).unwrap();
+    std::fs::create_dir_all(bin_dir).unwrap();
+    let shim = bin_dir.join("git");
+    std::fs::write(
+        &shim,
+        format!(
+            r#"#!/bin/sh
+if [ "$LC_ALL" = C ] || [ "$1" != branch ]; then exec '{git}' "$@"; fi
+out=$(mktemp) err=$(mktemp)
+'{git}' "$@" >"$out" 2>"$err"
+status=$?
+sed 's/^Deleted branch \(.*\) (was \(.*\))\.$/Branch \1 entfernt (war \2)./' "$out"
+sed "s/^error: branch '\(.*\)' not found\.$/Fehler: Branch '\1' nicht gefunden./" "$err" >&2
+rm -f "$out" "$err"
+exit $status
+"#,
+            git = real_git.trim()
+        ),
+    )
+    .unwrap();
+    std::fs::set_permissions(&shim, std::fs::Permissions::from_mode(0o755)).unwrap();
+    format!(
+        "{}:{}",
+        bin_dir.display(),
+        std::env::var("PATH").unwrap_or_default()
+    )
+}
+
+fn ofsht_in_german(repo_dir: &Path, path: &str) -> Command {
+    let mut cmd = Command::cargo_bin("ofsht").unwrap();
+    cmd.current_dir(repo_dir)
+        .env("PATH", path)
+        .env("LANG", "de_DE.UTF-8")
+        .env("LC_ALL", "de_DE.UTF-8")
+        .env("LANGUAGE", "de");
+    cmd
+}
+
+#[test]
+fn test_rm_assume_clean_counts_deleted_branches_in_any_locale() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    for branch in ["feature-a", "feature-b"] {
+        Command::cargo_bin("ofsht")
+            .unwrap()
+            .args(["create", branch])
+            .current_dir(repo_dir.path())
+            .assert()
+            .success();
+    }
+    let path = install_german_git(&temp.path().join("bin"));
+
+    ofsht_in_german(repo_dir.path(), &path)
+        .args(["rm", "--assume-clean", "feature-a", "feature-b"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Removed 2 worktree(s), deleted 2 branch(es)",
+        ));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_rm_treats_missing_branch_as_not_found_in_any_locale() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+    // Drop the branch behind the worktree's back, so `git branch -D` reports it missing
+    Command::new("git")
+        .args(["update-ref", "-d", "refs/heads/feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+    let path = install_german_git(&temp.path().join("bin"));
+
+    ofsht_in_german(repo_dir.path(), &path)
+        .args(["rm", "--force", "feature"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("nicht gefunden").not())
+        .stderr(predicate::str::contains("not found").not());
+    assert!(!temp.path().join("test-repo-worktrees/feature").exists());
+
+    temp.close().unwrap();
+}
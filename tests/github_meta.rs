@@ -0,0 +1,70 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use std::process::Command;
+
+const META: &str = "kind = \"pr\"\n\
+                    number = 456\n\
+                    title = \"Add feature\"\n\
+                    url = \"https://github.com/owner/repo/pull/456\"\n";
+
+#[test]
+fn test_ls_shows_recorded_pr_number_and_rm_drops_it() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    for branch in ["feature", "plain"] {
+        Command::cargo_bin("ofsht")
+            .unwrap()
+            .args(["create", branch])
+            .current_dir(repo_dir.path())
+            .assert()
+            .success();
+    }
+    let meta_file = repo_dir.path().join(".git/worktrees/feature/ofsht.toml");
+    std::fs::write(&meta_file, META).unwrap();
+
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["ls", "--show-path", "--no-truncate", "--color=never"])
+        .current_dir(repo_dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let row = |name: &str| {
+        stdout
+            .lines()
+            .find(|line| line.contains(&format!("[{name}]")))
+            .unwrap_or_else(|| panic!("no row for {name} in:\n{stdout}"))
+            .to_string()
+    };
+    assert!(row("feature").contains("[feature] #456"), "{stdout}");
+    assert!(!row("plain").contains('#'), "{stdout}");
+
+    // Pipe-mode names stay actionable: no decoration
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .arg("ls")
+        .current_dir(repo_dir.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.lines().any(|line| line == "feature"), "{stdout}");
+
+    // The metadata lives in the worktree's git directory and goes away with it
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+    assert!(!meta_file.exists());
+
+    temp.close().unwrap();
+}
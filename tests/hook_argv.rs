@@ -0,0 +1,75 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use predicates::prelude::*;
+use std::process::Command;
+
+#[test]
+fn test_argv_run_entries_are_not_shell_split() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str(
+            r#"
+[hooks.create]
+run = [
+    ["touch", "notes from hook.txt"],
+    { argv = ["sh", "-c", "echo \"$1\" > arg.txt", "sh", "a  b; c"] },
+    "echo shell-form > shell.txt",
+]
+"#,
+        )
+        .unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature-argv"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("touch 'notes from hook.txt'"));
+
+    let worktree = temp.path().join("test-repo-worktrees/feature-argv");
+    assert!(worktree.join("notes from hook.txt").exists());
+    assert!(!worktree.join("notes").exists());
+    assert_eq!(
+        std::fs::read_to_string(worktree.join("arg.txt")).unwrap(),
+        "a  b; c\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(worktree.join("shell.txt")).unwrap(),
+        "shell-form\n"
+    );
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_invalid_run_entry_reports_config_error() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str("[hooks.create]\nrun = [[]]\n")
+        .unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature-argv"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Failed to parse config file"))
+        .stderr(predicate::str::contains("argv must not be empty"));
+
+    temp.close().unwrap();
+}
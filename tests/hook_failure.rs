@@ -0,0 +1,220 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use predicates::prelude::*;
+use std::process::Command;
+
+fn write_failing_hook(repo_dir: &assert_fs::fixture::ChildPath, on_create_failure: &str) {
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str(&format!(
+            r#"
+[hooks]
+on_create_failure = "{on_create_failure}"
+
+[hooks.create]
+run = ["echo network-blip >&2; exit 1"]
+"#
+        ))
+        .unwrap();
+}
+
+fn branch_exists(repo_dir: &assert_fs::fixture::ChildPath, branch: &str) -> bool {
+    Command::new("git")
+        .args(["show-ref", "--verify", &format!("refs/heads/{branch}")])
+        .current_dir(repo_dir.path())
+        .output()
+        .unwrap()
+        .status
+        .success()
+}
+
+#[test]
+fn test_add_hook_failure_keep_leaves_worktree() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    write_failing_hook(&repo_dir, "keep");
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "feature-keep"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("network-blip"))
+        .stderr(predicate::str::contains("ofsht rm feature-keep"));
+
+    assert!(temp
+        .path()
+        .join("test-repo-worktrees/feature-keep")
+        .exists());
+    assert!(branch_exists(&repo_dir, "feature-keep"));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_add_hook_failure_remove_rolls_back() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    write_failing_hook(&repo_dir, "remove");
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "feature-remove"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("network-blip"))
+        .stderr(predicate::str::contains("after hook failure"))
+        // The log was inside the removed worktree
+        .stderr(predicate::str::contains("Full output").not());
+
+    assert!(!temp
+        .path()
+        .join("test-repo-worktrees/feature-remove")
+        .exists());
+    assert!(!branch_exists(&repo_dir, "feature-remove"));
+
+    // The path is free again, so a retry gets as far as the hook
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "feature-remove"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("network-blip"));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_add_hook_failure_remove_keeps_existing_branch() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    write_failing_hook(&repo_dir, "remove");
+
+    Command::new("git")
+        .args(["branch", "existing"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "existing"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure();
+
+    assert!(!temp.path().join("test-repo-worktrees/existing").exists());
+    assert!(branch_exists(&repo_dir, "existing"));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_add_hook_failure_prompt_without_tty_keeps_worktree() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    write_failing_hook(&repo_dir, "prompt");
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "feature-prompt"])
+        .current_dir(repo_dir.path())
+        .stdin(std::process::Stdio::null())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("ofsht rm feature-prompt"));
+
+    assert!(temp
+        .path()
+        .join("test-repo-worktrees/feature-prompt")
+        .exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_add_fail_on_error_prints_path_and_fails() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str(
+            r#"
+[hooks.create]
+fail_on_error = true
+run = ["echo broken >&2; exit 1", "touch hook-ran"]
+"#,
+        )
+        .unwrap();
+
+    let worktree_path = temp.path().join("test-repo-worktrees/feature-partial");
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "feature-partial"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("feature-partial"))
+        .stderr(predicate::str::contains("Hook error"))
+        .stderr(predicate::str::contains("some create hooks failed"));
+
+    assert!(worktree_path.exists());
+    // The succeeding hook after the failing one still ran
+    assert!(worktree_path.join("hook-ran").exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_failed_hook_leaves_error_log_in_worktree() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    write_failing_hook(&repo_dir, "keep");
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature-log"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(".ofsht/last-hook-error.log"));
+
+    let worktree = temp.child("test-repo-worktrees/feature-log");
+    let log = std::fs::read_to_string(worktree.child(".ofsht/last-hook-error.log").path()).unwrap();
+    assert!(
+        log.contains("command: echo network-blip >&2; exit 1"),
+        "{log}"
+    );
+    assert!(log.contains("status: exit 1"), "{log}");
+    assert!(log.contains("network-blip"), "{log}");
+
+    // The log stays out of git status and does not block removal
+    let status = Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(worktree.path())
+        .output()
+        .unwrap();
+    assert!(status.stdout.is_empty());
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "feature-log"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+}
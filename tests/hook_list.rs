@@ -0,0 +1,192 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use predicates::prelude::*;
+use std::process::Command;
+
+fn write_global_config(xdg: &assert_fs::fixture::ChildPath) {
+    xdg.child("ofsht/config.toml")
+        .write_str(
+            r#"
+[hooks.create]
+run = ["echo global"]
+"#,
+        )
+        .unwrap();
+}
+
+#[test]
+fn test_hook_list_labels_global_source() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    let xdg = temp.child("xdg");
+    write_global_config(&xdg);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["hook", "list"])
+        .env("XDG_CONFIG_HOME", xdg.path())
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("echo global"))
+        .stderr(predicate::str::contains("ofsht/config.toml"))
+        .stderr(predicate::str::contains("delete:\n  (none)"))
+        .stderr(predicate::str::contains("replaces").not());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_hook_list_reports_global_config_replaced_by_local() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    let xdg = temp.child("xdg");
+    xdg.child("ofsht/config.toml")
+        .write_str("[hooks.create]\nrun = [\"echo global\"]\n\n[hooks.delete]\nrun = [\"echo global bye\"]\n")
+        .unwrap();
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str("[hooks.create]\nrun = [\"echo local\"]\ncopy = [\".env\"]\n")
+        .unwrap();
+
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["hook", "list", "--color=never"])
+        .env("XDG_CONFIG_HOME", xdg.path())
+        .current_dir(repo_dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    // Local replaces global: none of the global hooks run, and the listing says so
+    let note = stderr
+        .lines()
+        .find(|line| line.contains("replaces"))
+        .unwrap_or_else(|| panic!("no replacement note in:\n{stderr}"));
+    assert!(note.contains(".ofsht.toml replaces"), "{note}");
+    assert!(
+        note.contains("ofsht/config.toml: its hooks are not used"),
+        "{note}"
+    );
+    assert!(!stderr.contains("echo global"), "{stderr}");
+
+    let rows: Vec<&str> = stderr
+        .lines()
+        .filter(|line| line.starts_with("  "))
+        .collect();
+    assert_eq!(rows.len(), 3, "{stderr}");
+    assert!(rows[0].starts_with("  run") && rows[0].contains("echo local"));
+    assert!(rows[1].starts_with("  copy") && rows[1].contains(".env"));
+    assert_eq!(rows[2], "  (none)");
+    for row in &rows[..2] {
+        assert!(row.trim_end().ends_with("test-repo/.ofsht.toml"), "{row}");
+    }
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_hook_list_local_config_takes_over_in_order() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    let xdg = temp.child("xdg");
+    write_global_config(&xdg);
+
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str(
+            r#"
+[hooks.create]
+run = ["pnpm install", "echo done"]
+copy = [".env", "*.local"]
+
+[hooks.delete]
+run = ["echo bye"]
+"#,
+        )
+        .unwrap();
+    repo_dir.child(".env").write_str("").unwrap();
+
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["hook", "list", "--json", "--expand"])
+        .env("XDG_CONFIG_HOME", xdg.path())
+        .current_dir(repo_dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let entries = entries.as_array().unwrap();
+    let summary: Vec<(String, String, String)> = entries
+        .iter()
+        .map(|e| {
+            (
+                e["event"].as_str().unwrap().to_string(),
+                e["kind"].as_str().unwrap().to_string(),
+                e["value"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    let expected = [
+        ("create", "run", "pnpm install"),
+        ("create", "run", "echo done"),
+        ("create", "copy", ".env"),
+        ("create", "copy", "*.local"),
+        ("delete", "run", "echo bye"),
+    ];
+    assert_eq!(
+        summary,
+        expected
+            .iter()
+            .map(|(a, b, c)| ((*a).to_string(), (*b).to_string(), (*c).to_string()))
+            .collect::<Vec<_>>()
+    );
+    for entry in entries {
+        assert!(
+            entry["source"].as_str().unwrap().ends_with(".ofsht.toml"),
+            "unexpected source: {entry}"
+        );
+    }
+    assert_eq!(entries[2]["matches"], 1);
+    assert_eq!(entries[3]["matches"], 0);
+    assert!(entries[0].get("matches").is_none());
+
+    // --event narrows the listing
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["hook", "list", "--event", "delete"])
+        .env("XDG_CONFIG_HOME", xdg.path())
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("echo bye"))
+        .stderr(predicate::str::contains("pnpm install").not());
+
+    // --branch fills the placeholders of run commands
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str("[hooks.create]\nrun = [\"createdb app_{branch}\", \"echo {repo}\"]\n")
+        .unwrap();
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["hook", "list", "--branch", "feat/login"])
+        .env("XDG_CONFIG_HOME", xdg.path())
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("createdb app_feat/login"))
+        .stderr(predicate::str::contains("echo test-repo"));
+
+    temp.close().unwrap();
+}
@@ -0,0 +1,106 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use predicates::prelude::*;
+use std::process::Command;
+
+#[test]
+fn test_hook_output_streams_to_stderr_only() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str(
+            r#"
+[hooks.create]
+run = ["echo line-one; sleep 0.2; echo line-two >&2; sleep 0.2; echo line-three"]
+"#,
+        )
+        .unwrap();
+
+    let worktree_path = temp.path().join("test-repo-worktrees/feature-stream");
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature-stream"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("line-one"))
+        .stderr(predicate::str::contains("line-two"))
+        .stderr(predicate::str::contains("line-three"))
+        .stdout(predicate::str::contains("line-").not());
+
+    assert!(worktree_path.exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_hook_failure_reports_captured_output() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str(
+            r#"
+[hooks.create]
+run = ["echo before-failure; echo reason-for-failure >&2; exit 2"]
+"#,
+        )
+        .unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature-fail"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Hook command failed"))
+        .stderr(predicate::str::contains("reason-for-failure"))
+        .stdout(predicate::str::contains("failure").not());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_add_prints_hook_summary() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    repo_dir.child(".env").write_str("A=1").unwrap();
+    repo_dir.child(".env.local").write_str("B=2").unwrap();
+    repo_dir.child("shared").create_dir_all().unwrap();
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str(
+            r#"
+[hooks.create]
+run = ["true", "true", "true"]
+copy = [".env*"]
+link = ["shared"]
+"#,
+        )
+        .unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "feature-summary"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Created feature-summary (2 copied, 1 linked, 3 commands run)",
+        ));
+
+    temp.close().unwrap();
+}
@@ -0,0 +1,101 @@
+#![allow(deprecated)]
+// Hook configs below contain `{placeholder}`s, not format arguments
+#![allow(clippy::literal_string_with_formatting_args)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo_with_config;
+use std::process::Command;
+
+#[test]
+fn test_create_hook_substitutes_branch_and_escaped_braces() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_config(
+        &repo_dir,
+        r#"[hooks.create]
+run = [
+    "echo {branch} > branch.txt",
+    "echo {repo} {{literal}} > misc.txt",
+    ["sh", "-c", "pwd > worktree.txt; echo {worktree} >> worktree.txt"],
+]
+"#,
+    );
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feat/x"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    let worktree = temp.child("test-repo-worktrees/feat/x");
+    let read = |name: &str| std::fs::read_to_string(worktree.child(name).path()).unwrap();
+    assert_eq!(read("branch.txt"), "feat/x\n");
+    assert_eq!(read("misc.txt"), "test-repo {literal}\n");
+    let lines: Vec<String> = read("worktree.txt").lines().map(String::from).collect();
+    assert!(
+        lines[1].ends_with("test-repo-worktrees/feat/x"),
+        "{lines:?}"
+    );
+}
+
+#[test]
+fn test_delete_hook_substitutes_placeholders() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_config(
+        &repo_dir,
+        "[hooks.delete]\nrun = [\"echo {branch} > {repo_root}/deleted.txt\"]\n",
+    );
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    repo_dir
+        .child("deleted.txt")
+        .assert(predicates::str::diff("feature\n"));
+}
+
+#[test]
+fn test_create_hook_quotes_hostile_branch_name() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_config(
+        &repo_dir,
+        "[hooks.create]\nrun = [\"printf %s {branch} > {repo_root}/branch.txt\"]\n",
+    );
+    let branch = "x;touch${IFS}pwned|cat`touch${IFS}tick`$(touch${IFS}sub)";
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", branch])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    repo_dir
+        .child("branch.txt")
+        .assert(predicates::str::diff(branch));
+    // Hooks run inside the new worktree, so look for the markers everywhere
+    let created = Command::new("find")
+        .args([
+            ".", "-name", "pwned", "-o", "-name", "tick", "-o", "-name", "sub",
+        ])
+        .current_dir(temp.path())
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&created.stdout), "");
+}
@@ -35,9 +35,10 @@ fn test_init_skip_existing_without_force() {
         .arg("--local")
         .current_dir(&temp)
         .assert()
-        .success()
-        .stderr(predicate::str::contains("already exists"))
-        .stderr(predicate::str::contains("--force"));
+        .failure()
+        .stderr(predicate::str::contains("local: exists at"))
+        .stderr(predicate::str::contains("skipping (use --force)"))
+        .stderr(predicate::str::contains("Nothing was written"));
 
     // Original content should be preserved
     config.assert("# existing content");
@@ -69,29 +70,54 @@ fn test_init_force_overwrite() {
 
 #[test]
 fn test_init_global_creates_global_config() {
-    // This test may encounter an existing global config, which is fine
-    // We just verify the command succeeds and mentions global config
+    let temp = assert_fs::TempDir::new().unwrap();
+    let xdg = temp.child("xdg");
+
     Command::cargo_bin("ofsht")
         .unwrap()
         .arg("init")
         .arg("--global")
+        .env("XDG_CONFIG_HOME", xdg.path())
+        .current_dir(&temp)
         .assert()
         .success()
-        .stderr(predicate::str::contains("Global config"));
+        .stderr(predicate::str::contains("Created Global config"));
+
+    xdg.child("ofsht/config.toml")
+        .assert(predicate::path::exists());
+    temp.child(".ofsht.toml").assert(predicate::path::missing());
+
+    // Asking for the existing global config again writes nothing and fails
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .arg("init")
+        .arg("--global")
+        .env("XDG_CONFIG_HOME", xdg.path())
+        .current_dir(&temp)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("global: exists at"));
+
+    temp.close().unwrap();
 }
 
 #[test]
 fn test_init_without_flags_creates_both() {
     let temp = assert_fs::TempDir::new().unwrap();
+    let xdg = temp.child("xdg");
 
     Command::cargo_bin("ofsht")
         .unwrap()
         .arg("init")
+        .env("XDG_CONFIG_HOME", xdg.path())
         .current_dir(&temp)
         .assert()
         .success()
-        .stderr(predicate::str::contains("Global config"))
-        .stderr(predicate::str::contains("Local config"));
+        .stderr(predicate::str::contains("Created Global config"))
+        .stderr(predicate::str::contains("Created Local config"));
+
+    xdg.child("ofsht/config.toml")
+        .assert(predicate::path::exists());
 
     // Local config should be created
     temp.child(".ofsht.toml").assert(predicate::path::exists());
@@ -110,3 +136,244 @@ fn test_init_global_and_local_flags_conflict() {
         .failure()
         .stderr(predicate::str::contains("cannot be used with"));
 }
+
+#[test]
+fn test_init_stdout_prints_global_template_without_writing() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let home = temp.child("home");
+    let xdg = temp.child("xdg");
+    home.create_dir_all().unwrap();
+
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["init", "--stdout", "--no-detect"])
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", xdg.path())
+        .current_dir(&temp)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("[worktree]"));
+    assert!(stdout.contains("[integration.gh]"));
+
+    // Deterministic: the same template comes out every time
+    let again = Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["init", "--stdout", "--no-detect"])
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", xdg.path())
+        .current_dir(&temp)
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8(again.stdout).unwrap(), stdout);
+
+    xdg.assert(predicate::path::missing());
+    home.child(".config").assert(predicate::path::missing());
+    temp.child(".ofsht.toml").assert(predicate::path::missing());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_init_stdout_local_template() {
+    let temp = assert_fs::TempDir::new().unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["init", "--stdout", "--local"])
+        .current_dir(&temp)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ofsht project configuration"))
+        .stdout(predicate::str::contains("[hooks.create]"));
+
+    temp.child(".ofsht.toml").assert(predicate::path::missing());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_init_stdout_conflicts_with_force() {
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["init", "--stdout", "--force"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+/// Paths of `<name>.bak.*` files next to `dir/name`
+fn backups(dir: &std::path::Path, name: &str) -> Vec<std::path::PathBuf> {
+    let prefix = format!("{name}.bak.");
+    let mut found: Vec<_> = std::fs::read_dir(dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.unwrap().path();
+            path.file_name()?
+                .to_str()?
+                .starts_with(&prefix)
+                .then_some(path)
+        })
+        .collect();
+    found.sort();
+    found
+}
+
+#[test]
+fn test_init_force_backs_up_global_and_local_configs() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let xdg = temp.child("xdg");
+    let global = xdg.child("ofsht/config.toml");
+    global.write_str("# my global tweaks").unwrap();
+    let local = temp.child(".ofsht.toml");
+    local.write_str("# my local tweaks").unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["init", "--force"])
+        .env("XDG_CONFIG_HOME", xdg.path())
+        .current_dir(&temp)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Backed up existing Global config"))
+        .stderr(predicate::str::contains("Backed up existing Local config"));
+
+    let global_backups = backups(&xdg.path().join("ofsht"), "config.toml");
+    assert_eq!(global_backups.len(), 1);
+    assert_eq!(
+        std::fs::read_to_string(&global_backups[0]).unwrap(),
+        "# my global tweaks"
+    );
+    global.assert(predicate::str::contains("[integration"));
+
+    let local_backups = backups(temp.path(), ".ofsht.toml");
+    assert_eq!(local_backups.len(), 1);
+    assert_eq!(
+        std::fs::read_to_string(&local_backups[0]).unwrap(),
+        "# my local tweaks"
+    );
+    local.assert(predicate::str::contains("ofsht project configuration"));
+
+    // A second run in the same second gets a numbered backup instead of clobbering the first
+    local.write_str("# edited again").unwrap();
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["init", "--local", "--force"])
+        .current_dir(&temp)
+        .assert()
+        .success();
+    let contents: Vec<String> = backups(temp.path(), ".ofsht.toml")
+        .iter()
+        .map(|path| std::fs::read_to_string(path).unwrap())
+        .collect();
+    assert_eq!(contents.len(), 2);
+    assert!(contents.contains(&"# my local tweaks".to_string()));
+    assert!(contents.contains(&"# edited again".to_string()));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_init_force_no_backup_overwrites() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let local = temp.child(".ofsht.toml");
+    local.write_str("# existing content").unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["init", "--local", "--force", "--no-backup"])
+        .current_dir(&temp)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Backed up").not());
+
+    assert!(backups(temp.path(), ".ofsht.toml").is_empty());
+    local.assert(predicate::str::contains("ofsht project configuration"));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_init_no_backup_requires_force() {
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["init", "--no-backup"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_init_prints_plan_for_partial_existence() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let xdg = temp.child("xdg");
+    let global = xdg.child("ofsht/config.toml");
+    global.write_str("# my global").unwrap();
+
+    // Global exists, local doesn't: default mode writes local and exits 0
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .arg("init")
+        .env("XDG_CONFIG_HOME", xdg.path())
+        .current_dir(&temp)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "global: exists at ".to_string() + &global.path().display().to_string(),
+        ))
+        .stderr(predicate::str::contains("skipping (use --force)"))
+        .stderr(predicate::str::contains("local: will create at"))
+        .stderr(predicate::str::contains("Created Local config"));
+
+    global.assert("# my global");
+    temp.child(".ofsht.toml").assert(predicate::path::exists());
+
+    // Both exist: still exit 0 in default mode, nothing written
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .arg("init")
+        .env("XDG_CONFIG_HOME", xdg.path())
+        .current_dir(&temp)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("local: exists at"))
+        .stderr(predicate::str::contains("Created").not());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_init_dry_run_writes_nothing() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let xdg = temp.child("xdg");
+    let local = temp.child(".ofsht.toml");
+    local.write_str("# existing").unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["init", "--dry-run", "--force"])
+        .env("XDG_CONFIG_HOME", xdg.path())
+        .current_dir(&temp)
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("global: will create at"))
+        .stderr(predicate::str::contains(
+            "local: exists at .ofsht.toml, will overwrite (keeping a backup)",
+        ))
+        .stderr(predicate::str::contains("Created").not());
+
+    assert!(!xdg.path().join("ofsht/config.toml").exists());
+    local.assert("# existing");
+
+    // --dry-run never fails, even when the requested file would be skipped
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["init", "--local", "--dry-run"])
+        .current_dir(&temp)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("local: exists at"));
+
+    temp.close().unwrap();
+}
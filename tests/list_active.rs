@@ -0,0 +1,78 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use std::process::Command;
+
+#[test]
+fn test_ls_marks_worktree_active_from_subdirectory() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("repo");
+    init_repo(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    let subdir = temp.child("repo-worktrees/feature/src/deep/dir");
+    subdir.create_dir_all().unwrap();
+
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["ls", "--show-path"])
+        .current_dir(subdir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    let active: Vec<&str> = stdout.lines().filter(|l| l.starts_with('*')).collect();
+    assert_eq!(active.len(), 1, "expected one active row:\n{stdout}");
+    assert!(
+        active[0].contains("repo-worktrees/feature"),
+        "wrong active row:\n{stdout}"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_ls_marks_worktree_active_through_symlinked_path() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let real = temp.child("real");
+    let repo_dir = real.child("repo");
+    init_repo(&repo_dir);
+    let link = temp.path().join("link");
+    std::os::unix::fs::symlink(real.path(), &link).unwrap();
+
+    // Create and inspect the worktree only through the symlinked ancestor
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature"])
+        .current_dir(link.join("repo"))
+        .assert()
+        .success();
+    let worktree = link.join("repo-worktrees/feature");
+
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["ls", "--show-path"])
+        .current_dir(&worktree)
+        .env("PWD", &worktree)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+
+    let active: Vec<&str> = stdout.lines().filter(|l| l.starts_with('*')).collect();
+    assert_eq!(active.len(), 1, "expected one active row:\n{stdout}");
+    assert!(
+        active[0].contains("repo-worktrees/feature"),
+        "wrong active row:\n{stdout}"
+    );
+}
@@ -0,0 +1,66 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use std::path::Path;
+use std::process::Command;
+
+fn ofsht(repo_dir: &Path, state_home: &Path, args: &[&str]) -> String {
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(args)
+        .env("XDG_STATE_HOME", state_home)
+        .current_dir(repo_dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "ofsht {args:?} failed");
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// The row mentioning `name`, or panic
+fn row<'a>(stdout: &'a str, name: &str) -> &'a str {
+    stdout
+        .lines()
+        .find(|line| line.contains(name))
+        .unwrap_or_else(|| panic!("no row for {name} in:\n{stdout}"))
+}
+
+#[test]
+fn test_ls_diff_marks_changes_since_previous_run() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    let state = temp.child("state");
+    let ls = ["ls", "--show-path", "--no-truncate", "--diff"];
+
+    ofsht(repo_dir.path(), state.path(), &["create", "feature-gone"]);
+    ofsht(repo_dir.path(), state.path(), &["create", "feature-moved"]);
+
+    // First run: nothing to compare against
+    let stdout = ofsht(repo_dir.path(), state.path(), &ls);
+    assert!(stdout.lines().all(|line| line.starts_with(' ')), "{stdout}");
+
+    ofsht(repo_dir.path(), state.path(), &["create", "feature-new"]);
+    ofsht(repo_dir.path(), state.path(), &["rm", "feature-gone"]);
+    Command::new("git")
+        .args(["commit", "--allow-empty", "-m", "Move HEAD"])
+        .current_dir(temp.path().join("test-repo-worktrees/feature-moved"))
+        .assert()
+        .success();
+
+    let stdout = ofsht(repo_dir.path(), state.path(), &ls);
+    assert!(row(&stdout, "feature-new").starts_with("+ "), "{stdout}");
+    assert!(row(&stdout, "feature-gone").starts_with("- "), "{stdout}");
+    assert!(row(&stdout, "feature-moved").starts_with("~ "), "{stdout}");
+    assert!(stdout.lines().next().unwrap().starts_with("  "), "{stdout}");
+
+    // The snapshot was updated: a third run reports no changes
+    let stdout = ofsht(repo_dir.path(), state.path(), &ls);
+    assert!(!stdout.contains("feature-gone"), "{stdout}");
+    assert!(stdout.lines().all(|line| line.starts_with(' ')), "{stdout}");
+
+    temp.close().unwrap();
+}
@@ -0,0 +1,74 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use predicates::prelude::*;
+use std::path::Path;
+use std::process::Command;
+
+fn ls(repo_dir: &Path, args: &[&str]) -> String {
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .arg("ls")
+        .args(args)
+        .current_dir(repo_dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_ls_hides_excluded_worktrees_unless_all() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str("[worktree]\nexclude = [\"release\", \"glob:archive/*\"]\n")
+        .unwrap();
+
+    for branch in ["release", "feature", "archive/old"] {
+        Command::cargo_bin("ofsht")
+            .unwrap()
+            .args(["create", branch])
+            .current_dir(repo_dir.path())
+            .assert()
+            .success();
+    }
+
+    assert_eq!(ls(repo_dir.path(), &[]), "@\nfeature\n");
+
+    let all = ls(repo_dir.path(), &["--all"]);
+    let mut names: Vec<&str> = all.lines().collect();
+    names.sort_unstable();
+    assert_eq!(names, vec!["@", "archive/old", "feature", "release"]);
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_ls_invalid_exclude_pattern_fails() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str("[worktree]\nexclude = [\"glob:[\"]\n")
+        .unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .arg("ls")
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid worktree.exclude pattern: glob:[",
+        ));
+
+    temp.close().unwrap();
+}
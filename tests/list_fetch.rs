@@ -0,0 +1,143 @@
+#![allow(deprecated)]
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+use std::path::Path;
+use std::process::Command;
+
+fn git(dir: &Path, args: &[&str]) {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {args:?} failed: {output:?}");
+}
+
+/// Repository with a bare `origin` and a `feature` worktree tracking `origin/feature`
+fn init_repo_with_remote(temp: &assert_fs::TempDir) -> assert_fs::fixture::ChildPath {
+    let remote_dir = temp.child("origin.git");
+    remote_dir.create_dir_all().unwrap();
+    git(remote_dir.path(), &["init", "--bare"]);
+
+    let repo_dir = temp.child("test-repo");
+    repo_dir.create_dir_all().unwrap();
+    for args in [
+        &["init"][..],
+        &["config", "user.name", "Test User"],
+        &["config", "user.email", "test@example.com"],
+        &["commit", "--allow-empty", "-m", "Initial commit"],
+        &[
+            "remote",
+            "add",
+            "origin",
+            remote_dir.path().to_str().unwrap(),
+        ],
+        &["branch", "feature"],
+        &["push", "--quiet", "-u", "origin", "HEAD", "feature"],
+    ] {
+        git(repo_dir.path(), args);
+    }
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+    repo_dir
+}
+
+fn ls(repo_dir: &Path, args: &[&str]) -> assert_cmd::assert::Assert {
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["ls", "--show-path", "--no-truncate"])
+        .args(args)
+        .current_dir(repo_dir)
+        .assert()
+}
+
+#[test]
+fn test_ls_fetch_picks_up_remote_branch_deletion() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = init_repo_with_remote(&temp);
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str("[worktree]\nlist_columns = [\"branch\", \"upstream\"]\n")
+        .unwrap();
+
+    // Deleted on the remote side only
+    git(
+        temp.child("origin.git").path(),
+        &["branch", "-D", "feature"],
+    );
+
+    // Without a fetch, the remote-tracking branch still looks in sync
+    ls(repo_dir.path(), &[])
+        .success()
+        .stdout(predicate::str::contains("[feature]  ="))
+        .stdout(predicate::str::contains("gone").not());
+
+    ls(repo_dir.path(), &["--fetch"])
+        .success()
+        .stdout(predicate::str::contains("[feature]  gone"));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_ls_fetch_adds_upstream_column() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = init_repo_with_remote(&temp);
+
+    ls(repo_dir.path(), &[])
+        .success()
+        .stdout(predicate::str::contains(" =").not());
+    ls(repo_dir.path(), &["--fetch=all"])
+        .success()
+        .stdout(predicate::str::contains("[feature]").and(predicate::str::contains("=")));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_ls_auto_fetch_if_stale() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = init_repo_with_remote(&temp);
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str(
+            "[worktree]\nlist_columns = [\"branch\", \"upstream\"]\n\n[ls]\nauto_fetch = \"if-stale\"\n",
+        )
+        .unwrap();
+    git(
+        temp.child("origin.git").path(),
+        &["branch", "-D", "feature"],
+    );
+
+    // Never fetched: FETCH_HEAD is missing, so the listing fetches on its own
+    ls(repo_dir.path(), &[])
+        .success()
+        .stdout(predicate::str::contains("[feature]  gone"));
+    assert!(repo_dir.child(".git/FETCH_HEAD").exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_ls_fetch_failure_only_warns() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = init_repo_with_remote(&temp);
+    git(
+        repo_dir.path(),
+        &["remote", "set-url", "origin", "/nonexistent/origin.git"],
+    );
+
+    ls(repo_dir.path(), &["--fetch"])
+        .success()
+        .stderr(predicate::str::contains("Failed to fetch origin"))
+        .stdout(predicate::str::contains("[feature]"));
+
+    temp.close().unwrap();
+}
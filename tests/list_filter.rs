@@ -0,0 +1,49 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use std::process::Command;
+
+#[test]
+fn test_ls_prunable_lists_only_missing_worktrees() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    for branch in ["feature-kept", "feature-deleted"] {
+        Command::cargo_bin("ofsht")
+            .unwrap()
+            .args(["create", branch])
+            .current_dir(repo_dir.path())
+            .assert()
+            .success();
+    }
+    std::fs::remove_dir_all(temp.path().join("test-repo-worktrees/feature-deleted")).unwrap();
+
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["ls", "--prunable"])
+        .current_dir(repo_dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "feature-deleted\n"
+    );
+
+    // Nothing is older than a year
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["ls", "--stale", "365"])
+        .current_dir(repo_dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+
+    temp.close().unwrap();
+}
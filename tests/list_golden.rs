@@ -0,0 +1,55 @@
+#![allow(deprecated)]
+
+//! Golden tests for the `ofsht ls` table, rendered from porcelain fixtures via
+//! `--stdin-porcelain` (no git repository involved)
+//!
+//! Each `tests/fixtures/ls/<name>.porcelain` input is compared byte-for-byte
+//! with `<name>.expected`.
+
+use std::path::Path;
+
+fn assert_golden(name: &str) {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/ls");
+    let input = std::fs::read(fixtures.join(format!("{name}.porcelain"))).unwrap();
+    let expected = std::fs::read_to_string(fixtures.join(format!("{name}.expected"))).unwrap();
+    let temp = assert_fs::TempDir::new().unwrap();
+
+    let output = assert_cmd::Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["ls", "--stdin-porcelain", "--color=never", "--no-truncate"])
+        .current_dir(temp.path())
+        .write_stdin(input)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        expected,
+        "{name}"
+    );
+}
+
+#[test]
+fn test_ls_golden_simple() {
+    assert_golden("simple");
+}
+
+#[test]
+fn test_ls_golden_nested_branches() {
+    assert_golden("nested");
+}
+
+#[test]
+fn test_ls_golden_detached_and_prunable() {
+    assert_golden("detached_prunable");
+}
+
+#[test]
+fn test_ls_golden_natural_order() {
+    assert_golden("natural_order");
+}
@@ -0,0 +1,105 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use std::process::Command;
+
+fn split_records(stdout: &[u8]) -> Vec<String> {
+    let text = String::from_utf8(stdout.to_vec()).unwrap();
+    assert!(
+        text.ends_with('\0'),
+        "records must be NUL-terminated: {text:?}"
+    );
+    assert!(
+        !text.contains('\n'),
+        "no newline separators expected: {text:?}"
+    );
+    text.trim_end_matches('\0')
+        .split('\0')
+        .map(String::from)
+        .collect()
+}
+
+#[test]
+fn test_ls_print0_round_trips_paths_with_spaces() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str(
+            r#"
+[worktree]
+dir = "../my worktrees/{branch}"
+"#,
+        )
+        .unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature-a"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    let worktree_path = temp
+        .path()
+        .join("my worktrees/feature-a")
+        .canonicalize()
+        .unwrap();
+
+    // Names only
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["ls", "-0"])
+        .current_dir(repo_dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(split_records(&output.stdout), vec!["@", "feature-a"]);
+
+    // path<TAB>name records
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["ls", "--print0", "--show-path"])
+        .current_dir(repo_dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let records = split_records(&output.stdout);
+    assert_eq!(records.len(), 2);
+    let (path, name) = records[1].split_once('\t').unwrap();
+    assert_eq!(name, "feature-a");
+    assert_eq!(std::path::Path::new(path), worktree_path);
+    assert!(std::path::Path::new(path).is_dir());
+    assert!(records[0].ends_with("\t@"));
+
+    // The records survive a NUL-aware shell loop untouched
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["ls", "-0", "--show-path"])
+        .current_dir(repo_dir.path())
+        .output()
+        .unwrap();
+    let looped = Command::new("sh")
+        .args([
+            "-c",
+            r#"xargs -0 -n1 sh -c 'p=$(printf "%s" "$1" | cut -f1); [ -d "$p" ] && echo ok' _"#,
+        ])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            use std::io::Write;
+            child.stdin.take().unwrap().write_all(&output.stdout)?;
+            child.wait_with_output()
+        })
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&looped.stdout), "ok\nok\n");
+
+    temp.close().unwrap();
+}
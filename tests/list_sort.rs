@@ -0,0 +1,62 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use std::process::Command;
+
+fn ls_lines(repo_dir: &assert_fs::fixture::ChildPath, sort: &str) -> Vec<String> {
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["ls", "--sort", sort])
+        .current_dir(repo_dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn test_ls_sort_branch_and_time_keep_main_first() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    for branch in ["zeta", "alpha"] {
+        Command::cargo_bin("ofsht")
+            .unwrap()
+            .args(["create", branch])
+            .current_dir(repo_dir.path())
+            .assert()
+            .success();
+    }
+
+    // Give `zeta` the newest commit
+    let zeta_path = temp.path().join("test-repo-worktrees/zeta");
+    Command::new("git")
+        .args(["commit", "--allow-empty", "-m", "newer"])
+        .env("GIT_COMMITTER_DATE", "2090-01-01T00:00:00Z")
+        .current_dir(&zeta_path)
+        .assert()
+        .success();
+
+    assert_eq!(ls_lines(&repo_dir, "branch"), ["@", "alpha", "zeta"]);
+    assert_eq!(ls_lines(&repo_dir, "time"), ["@", "zeta", "alpha"]);
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_ls_sort_rejects_unknown_key() {
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["ls", "--sort", "size"])
+        .assert()
+        .failure();
+}
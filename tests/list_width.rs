@@ -0,0 +1,49 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use std::process::Command;
+
+fn ls_show_path(repo_dir: &assert_fs::fixture::ChildPath, extra: &[&str]) -> String {
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["ls", "--show-path"])
+        .args(extra)
+        .env("COLUMNS", "60")
+        .current_dir(repo_dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_ls_show_path_fits_narrow_columns() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("a-repository-with-a-rather-long-directory-name");
+    init_repo(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature/login"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    let stdout = ls_show_path(&repo_dir, &[]);
+    assert_eq!(stdout.lines().count(), 2);
+    for line in stdout.lines() {
+        assert!(line.chars().count() <= 60, "row too wide: {line:?}");
+    }
+    assert!(stdout.contains('…'));
+
+    // --no-truncate keeps full paths
+    let stdout = ls_show_path(&repo_dir, &["--no-truncate"]);
+    assert!(!stdout.contains('…'));
+    assert!(stdout.contains("feature/login"));
+
+    temp.close().unwrap();
+}
@@ -102,3 +102,41 @@ dir = "{}/worktree-{{branch}}"
         wrong_feature2_path.display()
     );
 }
+
+#[test]
+fn test_add_rejects_template_with_mid_path_branch() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    repo_dir.create_dir_all().unwrap();
+    for args in [
+        &["init"][..],
+        &["config", "user.name", "Test User"],
+        &["config", "user.email", "test@example.com"],
+        &["commit", "--allow-empty", "-m", "Initial commit"],
+    ] {
+        Command::new("git")
+            .args(args)
+            .current_dir(repo_dir.path())
+            .output()
+            .unwrap();
+    }
+
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str("[worktree]\ndir = \"../{repo}-wt/{branch}/checkout\"\n")
+        .unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "{branch} must be the last path component",
+        ));
+
+    assert!(!temp.path().join("test-repo-wt").exists());
+
+    temp.close().unwrap();
+}
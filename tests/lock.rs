@@ -0,0 +1,185 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use ofsht::report;
+use predicates::prelude::*;
+use std::process::Command;
+
+#[test]
+fn test_lock_shows_in_ls_and_blocks_rm_until_unlocked() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature-lock"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    let worktree_path = temp.path().join("test-repo-worktrees/feature-lock");
+    assert!(worktree_path.exists());
+
+    // Lock with a reason
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["lock", "feature-lock", "--reason", "external drive"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Locked feature-lock"));
+
+    // ls shows the lock indicator
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["--color=never", "ls", "--show-path"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[feature-lock]").and(predicate::str::contains("🔒")));
+
+    // rm refuses with a helpful message
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "feature-lock"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is locked (external drive)"))
+        .stderr(predicate::str::contains("ofsht unlock feature-lock"));
+    assert!(worktree_path.exists());
+
+    // --force removes a locked worktree without unlocking it first
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature-forced"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["lock", "feature-forced"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "--force", "feature-forced"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Removing locked worktree"))
+        .stderr(predicate::str::contains(report::removed_worktree(
+            "feature-forced",
+        )));
+    assert!(!temp
+        .path()
+        .join("test-repo-worktrees/feature-forced")
+        .exists());
+
+    // Unlock, then remove
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["unlock", "feature-lock"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Unlocked feature-lock"));
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "feature-lock"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(report::removed_worktree(
+            "feature-lock",
+        )));
+    assert!(!worktree_path.exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_lock_unknown_target_fails() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["lock", "does-not-exist"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Worktree not found: does-not-exist",
+        ));
+
+    temp.close().unwrap();
+}
+
+fn porcelain(repo_dir: &assert_fs::fixture::ChildPath) -> String {
+    let output = Command::new("git")
+        .args(["worktree", "list", "--porcelain"])
+        .current_dir(repo_dir.path())
+        .output()
+        .unwrap();
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_lock_then_unlock_updates_git_porcelain() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature-porcelain"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+    assert!(!porcelain(&repo_dir).contains("locked"));
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["lock", "feature-porcelain", "--reason", "usb stick"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+    assert!(porcelain(&repo_dir).contains("locked usb stick"));
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["unlock", "feature-porcelain"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+    assert!(!porcelain(&repo_dir).contains("locked"));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_lock_refuses_main_worktree() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["lock", "@"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Cannot lock the main worktree"));
+    assert!(!porcelain(&repo_dir).contains("locked"));
+
+    temp.close().unwrap();
+}
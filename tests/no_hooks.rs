@@ -0,0 +1,206 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo_with_config;
+use predicates::prelude::*;
+use std::process::Command;
+
+/// Initialize a git repository with a marker create hook
+fn init_repo_with_hook(repo_dir: &assert_fs::fixture::ChildPath) {
+    init_repo_with_config(repo_dir, "[hooks.create]\nrun = [\"touch marker\"]\n");
+}
+
+#[test]
+fn test_add_no_hooks_skips_create_hooks() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_hook(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "feature", "--no-hooks", "--no-tmux"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Skipped create hooks (--no-hooks)",
+        ));
+
+    let worktree = temp.child("test-repo-worktrees/feature");
+    worktree.assert(predicate::path::is_dir());
+    worktree.child("marker").assert(predicate::path::missing());
+}
+
+#[test]
+fn test_create_no_hooks_skips_create_hooks() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_hook(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature", "--no-hooks"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Skipped create hooks"));
+
+    let worktree = temp.child("test-repo-worktrees/feature");
+    worktree.assert(predicate::path::is_dir());
+    worktree.child("marker").assert(predicate::path::missing());
+}
+
+#[test]
+fn test_create_runs_hooks_without_flag() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_hook(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    temp.child("test-repo-worktrees/feature/marker")
+        .assert(predicate::path::exists());
+}
+
+#[test]
+fn test_create_prints_hook_timing_summary() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_hook(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::is_match(r"Hooks completed in \d+\.\ds \(1 run\)").unwrap());
+}
+
+#[test]
+fn test_rm_no_hooks_skips_delete_hooks() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_hook(&repo_dir);
+    // The delete hook runs inside the worktree; leave its marker next to the repo
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str("[hooks.delete]\nrun = [\"touch ../../delete-marker\"]\n")
+        .unwrap();
+
+    for branch in ["skipped", "hooked"] {
+        Command::cargo_bin("ofsht")
+            .unwrap()
+            .args(["create", branch])
+            .current_dir(repo_dir.path())
+            .assert()
+            .success();
+    }
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "skipped", "--no-hooks"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Skipped delete hooks (--no-hooks)",
+        ));
+    temp.child("delete-marker")
+        .assert(predicate::path::missing());
+    temp.child("test-repo-worktrees/skipped")
+        .assert(predicate::path::missing());
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "hooked"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Hooks completed in"));
+    temp.child("delete-marker")
+        .assert(predicate::path::exists());
+}
+
+#[test]
+fn test_add_link_flag_symlinks_file_not_in_config() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_hook(&repo_dir);
+    repo_dir.child("big-data").create_dir_all().unwrap();
+    repo_dir
+        .child("big-data/blob.bin")
+        .write_str("data")
+        .unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "feature", "--no-tmux", "--link", "big-data"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    let worktree = temp.child("test-repo-worktrees/feature");
+    let link = worktree.child("big-data");
+    assert!(link
+        .path()
+        .symlink_metadata()
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    link.child("blob.bin").assert("data");
+    // Configured hooks still run alongside the CLI action
+    worktree.child("marker").assert(predicate::path::exists());
+}
+
+#[test]
+fn test_create_no_hooks_still_runs_cli_copy() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_hook(&repo_dir);
+    repo_dir.child("extra.txt").write_str("extra").unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature", "--no-hooks", "--copy", "extra.txt"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Skipped create hooks"));
+
+    let worktree = temp.child("test-repo-worktrees/feature");
+    worktree.child("extra.txt").assert("extra");
+    worktree.child("marker").assert(predicate::path::missing());
+}
+
+#[test]
+fn test_create_skip_pattern_drops_configured_copy() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_hook(&repo_dir);
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str("[hooks.create]\ncopy = [\"a.txt\", \"b.txt\"]\n")
+        .unwrap();
+    repo_dir.child("a.txt").write_str("a").unwrap();
+    repo_dir.child("b.txt").write_str("b").unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature", "--skip-pattern", "a.txt"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    let worktree = temp.child("test-repo-worktrees/feature");
+    worktree.child("a.txt").assert(predicate::path::missing());
+    worktree.child("b.txt").assert("b");
+}
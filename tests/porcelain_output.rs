@@ -0,0 +1,92 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use std::collections::HashMap;
+use std::process::Command;
+
+fn run_porcelain(
+    repo_dir: &assert_fs::fixture::ChildPath,
+    args: &[&str],
+) -> HashMap<String, String> {
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(args)
+        .env("OFSHT_NO_TMUX", "1")
+        .current_dir(repo_dir.path())
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| {
+            let (key, value) = line.split_once('=').expect("key=value line");
+            (key.to_string(), value.to_string())
+        })
+        .collect()
+}
+
+#[test]
+fn test_add_porcelain_prints_key_value_lines() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    let fields = run_porcelain(&repo_dir, &["add", "feature", "--porcelain"]);
+
+    assert_eq!(fields.len(), 4);
+    assert_eq!(fields["branch"], "feature");
+    assert_eq!(fields["created"], "true");
+    assert_eq!(fields["start_point"], "");
+    assert!(fields["path"].ends_with("test-repo-worktrees/feature"));
+    assert!(std::path::Path::new(&fields["path"]).is_dir());
+}
+
+#[test]
+fn test_create_porcelain_with_start_point() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    let fields = run_porcelain(&repo_dir, &["create", "feat/x", "HEAD", "--porcelain"]);
+
+    assert_eq!(fields["branch"], "feat/x");
+    assert_eq!(fields["start_point"], "HEAD");
+    assert!(fields["path"].ends_with("test-repo-worktrees/feat/x"));
+}
+
+#[test]
+fn test_create_porcelain_detached_has_empty_branch() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    let fields = run_porcelain(&repo_dir, &["create", "--detach", "HEAD", "--porcelain"]);
+
+    assert_eq!(fields["branch"], "");
+    assert_eq!(fields["start_point"], "HEAD");
+}
+
+#[test]
+fn test_create_without_porcelain_prints_nothing() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature"])
+        .current_dir(repo_dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+}
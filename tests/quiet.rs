@@ -0,0 +1,106 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use predicates::prelude::*;
+use std::process::Command;
+
+#[test]
+fn test_quiet_suppresses_status_output_for_add_and_rm() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str(
+            r#"
+[hooks.create]
+run = ["echo hook-output"]
+"#,
+        )
+        .unwrap();
+
+    let worktree_path = temp.path().join("test-repo-worktrees/feature-quiet");
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["--quiet", "add", "feature-quiet"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("feature-quiet"))
+        .stderr(predicate::str::contains("✓").not())
+        .stderr(predicate::str::contains("ℹ").not())
+        .stderr(predicate::str::contains("hook-output").not());
+
+    assert!(worktree_path.exists());
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "-q", "feature-quiet"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("✓").not())
+        .stderr(predicate::str::contains("ℹ").not());
+
+    assert!(!worktree_path.exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_quiet_keeps_warnings() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature-dirty"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    let worktree_path = temp.path().join("test-repo-worktrees/feature-dirty");
+    std::fs::write(worktree_path.join("scratch.txt"), "wip").unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "--quiet", "feature-dirty"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("⚠ Skipped feature-dirty"));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_quiet_suppresses_nothing_to_do_notices() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["--quiet", "sync"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::is_empty());
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .arg("sync")
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Nothing to sync"));
+
+    temp.close().unwrap();
+}
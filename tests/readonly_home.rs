@@ -0,0 +1,116 @@
+#![allow(deprecated)]
+#![cfg(unix)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Run ofsht with `home` as HOME and no XDG overrides, so state and config
+/// lookups fall back to the home directory
+fn ofsht_with_home(repo_dir: &Path, home: &Path, args: &[&str]) -> Output {
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(args)
+        .env("HOME", home)
+        .env_remove("XDG_CONFIG_HOME")
+        .env_remove("XDG_STATE_HOME")
+        .env("OFSHT_NO_ZOXIDE", "1")
+        .current_dir(repo_dir)
+        .output()
+        .unwrap()
+}
+
+/// Make `dir` read-only; `false` when the process can write to it anyway (e.g. root)
+fn make_read_only(dir: &Path) -> bool {
+    std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o555)).unwrap();
+    let probe = dir.join(".probe");
+    if std::fs::write(&probe, "").is_ok() {
+        std::fs::remove_file(probe).unwrap();
+        eprintln!("skipping: permissions are not enforced for this user");
+        return false;
+    }
+    true
+}
+
+#[test]
+fn test_add_and_ls_succeed_with_read_only_home() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    let home = temp.child("home");
+    home.create_dir_all().unwrap();
+    if !make_read_only(home.path()) {
+        return;
+    }
+
+    let output = ofsht_with_home(repo_dir.path(), home.path(), &["add", "feature"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(temp.path().join("test-repo-worktrees/feature").exists());
+
+    let output = ofsht_with_home(
+        repo_dir.path(),
+        home.path(),
+        &["ls", "--show-path", "--diff"],
+    );
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(output.status.success(), "{stderr}");
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .contains("feature"));
+    assert_eq!(
+        stderr.matches("Not saving ofsht state").count(),
+        1,
+        "{stderr}"
+    );
+
+    std::fs::set_permissions(home.path(), std::fs::Permissions::from_mode(0o755)).unwrap();
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_unreadable_global_config_is_ignored_with_warning() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    // A directory where the config file should be cannot be read as a file
+    let home = temp.child("home");
+    home.child(".config/ofsht/config.toml")
+        .create_dir_all()
+        .unwrap();
+
+    let output = ofsht_with_home(repo_dir.path(), home.path(), &["add", "feature"]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(output.status.success(), "{stderr}");
+    assert_eq!(
+        stderr.matches("Ignoring global config").count(),
+        1,
+        "{stderr}"
+    );
+    assert!(temp.path().join("test-repo-worktrees/feature").exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_invalid_global_config_still_fails() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    let home = temp.child("home");
+    home.child(".config/ofsht/config.toml")
+        .write_str("[worktree\n")
+        .unwrap();
+
+    let output = ofsht_with_home(repo_dir.path(), home.path(), &["add", "feature"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Failed to parse config file"));
+
+    temp.close().unwrap();
+}
@@ -0,0 +1,186 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use predicates::prelude::*;
+use std::process::Command;
+
+#[test]
+fn test_repo_flag_lists_other_repository() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    let elsewhere = temp.child("elsewhere");
+    elsewhere.create_dir_all().unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "feature-a"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .arg("--repo")
+        .arg(repo_dir.path())
+        .arg("ls")
+        .current_dir(elsewhere.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("feature-a"));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_repo_flag_adds_and_removes_in_other_repository() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    let elsewhere = temp.child("elsewhere");
+    elsewhere.create_dir_all().unwrap();
+
+    let worktree_path = temp.path().join("test-repo-worktrees/feature-b");
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "feature-b", "--repo"])
+        .arg(repo_dir.path())
+        .current_dir(elsewhere.path())
+        .assert()
+        .success();
+    assert!(worktree_path.exists());
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .arg("--repo")
+        .arg(repo_dir.path())
+        .args(["rm", "feature-b"])
+        .current_dir(elsewhere.path())
+        .assert()
+        .success();
+    assert!(!worktree_path.exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_repo_flag_rejects_non_repository() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let elsewhere = temp.child("elsewhere");
+    elsewhere.create_dir_all().unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .arg("--repo")
+        .arg(elsewhere.path())
+        .args(["add", "feature-c"])
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Not a git repository"));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_directory_flag_adds_next_to_repository() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    let elsewhere = temp.child("elsewhere");
+    elsewhere.create_dir_all().unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .arg("-C")
+        .arg(repo_dir.path())
+        .args(["add", "x"])
+        .current_dir(elsewhere.path())
+        .assert()
+        .success();
+    assert!(temp.path().join("test-repo-worktrees/x").exists());
+    assert!(!elsewhere.path().join("x").exists());
+
+    // Relative to the current directory, after the subcommand like any global option
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["ls", "-C", "../test-repo"])
+        .current_dir(elsewhere.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("x"));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_relative_repo_flag_resolves_after_directory_flag() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    let elsewhere = temp.child("elsewhere");
+    elsewhere.create_dir_all().unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .arg("-C")
+        .arg(temp.path())
+        .args(["--repo", "test-repo", "create", "feature-c"])
+        .current_dir(elsewhere.path())
+        .assert()
+        .success();
+    assert!(temp.path().join("test-repo-worktrees/feature-c").exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_directory_flag_rejects_missing_directory() {
+    let temp = assert_fs::TempDir::new().unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["-C", "does-not-exist", "ls"])
+        .current_dir(temp.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Cannot change to directory does-not-exist",
+        ));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_directory_flag_applies_to_completions() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    Command::new("git")
+        .args(["branch", "develop"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+    let elsewhere = temp.child("elsewhere");
+    elsewhere.create_dir_all().unwrap();
+    let repo = repo_dir.path().to_str().unwrap();
+
+    // Completing the start point of `ofsht -C <repo> add feature <TAB>`
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .env("COMPLETE", "bash")
+        .env("_CLAP_COMPLETE_INDEX", "5")
+        .env("_CLAP_IFS", "\n")
+        .args(["--", "ofsht", "-C", repo, "add", "feature", ""])
+        .current_dir(elsewhere.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("develop"));
+
+    temp.close().unwrap();
+}
@@ -233,3 +233,103 @@ fn test_cd_command() {
 
     temp.close().unwrap();
 }
+
+/// Run git in `dir`, panicking on failure
+fn git(dir: &std::path::Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args([
+            "-c",
+            "user.name=Test User",
+            "-c",
+            "user.email=test@example.com",
+        ])
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {args:?} failed");
+}
+
+/// Bare clone `repo.git` of a repository with one commit, plus a linked worktree `linked`
+fn init_bare_repo(temp: &assert_fs::TempDir) {
+    let source = temp.child("source");
+    source.create_dir_all().unwrap();
+    git(source.path(), &["init", "-q"]);
+    git(
+        source.path(),
+        &["commit", "-q", "--allow-empty", "-m", "Initial commit"],
+    );
+    git(
+        temp.path(),
+        &["clone", "-q", "--bare", "source", "repo.git"],
+    );
+    git(
+        &temp.path().join("repo.git"),
+        &["worktree", "add", "-q", "../linked", "-b", "linked"],
+    );
+}
+
+#[test]
+fn test_ls_in_bare_repository() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    init_bare_repo(&temp);
+
+    for dir in ["repo.git", "linked"] {
+        Command::cargo_bin("ofsht")
+            .unwrap()
+            .args(["ls", "--show-path"])
+            .current_dir(temp.path().join(dir))
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("repo.git"))
+            .stdout(predicate::str::contains("[linked]"));
+    }
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_bare_repository_is_its_own_root() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    init_bare_repo(&temp);
+
+    // Same worktree root whether run from the bare repository or a linked worktree
+    for (dir, branch) in [("repo.git", "from-bare"), ("linked", "from-linked")] {
+        Command::cargo_bin("ofsht")
+            .unwrap()
+            .args(["create", branch])
+            .current_dir(temp.path().join(dir))
+            .assert()
+            .success();
+        assert!(temp.path().join("repo.git-worktrees").join(branch).exists());
+    }
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_git_dir_and_work_tree_from_environment() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    repo_dir.create_dir_all().unwrap();
+    git(repo_dir.path(), &["init", "-q"]);
+    git(
+        repo_dir.path(),
+        &["commit", "-q", "--allow-empty", "-m", "Initial commit"],
+    );
+    let elsewhere = temp.child("elsewhere");
+    elsewhere.create_dir_all().unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature"])
+        .env("GIT_DIR", repo_dir.path().join(".git"))
+        .env("GIT_WORK_TREE", repo_dir.path())
+        .current_dir(elsewhere.path())
+        .assert()
+        .success();
+
+    assert!(temp.path().join("test-repo-worktrees/feature").exists());
+
+    temp.close().unwrap();
+}
@@ -0,0 +1,69 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use ofsht::report;
+use predicates::prelude::*;
+use std::process::Command;
+
+#[test]
+fn test_add_and_create_report_created_worktree() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    for (command, branch) in [("add", "via-add"), ("create", "via-create")] {
+        Command::cargo_bin("ofsht")
+            .unwrap()
+            .args([command, branch])
+            .env("OFSHT_NO_TMUX", "1")
+            .current_dir(repo_dir.path())
+            .assert()
+            .success()
+            .stderr(predicate::str::contains(format!(
+                "{} {branch} at ",
+                report::CREATED_WORKTREE
+            )))
+            .stderr(predicate::str::contains(format!(
+                "test-repo-worktrees/{branch}"
+            )));
+    }
+}
+
+#[test]
+fn test_rm_reports_removed_worktree_and_deleted_branch() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(report::removed_worktree(
+            "feature",
+        )))
+        .stderr(predicate::str::contains(report::deleted_branch("feature")));
+}
+
+#[test]
+fn test_help_lists_vocabulary() {
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(report::vocabulary_help()));
+}
@@ -0,0 +1,86 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use predicates::prelude::*;
+use std::process::Command;
+
+/// Write a global config with a resolver running `command`
+fn write_resolver_config(xdg: &assert_fs::fixture::ChildPath, command: &str) {
+    xdg.child("ofsht/config.toml")
+        .write_str(&format!(
+            r"
+[integration.resolver]
+pattern = '^[A-Z]+-\d+$'
+command = '''{command}'''
+"
+        ))
+        .unwrap();
+}
+
+#[test]
+fn test_add_resolves_ticket_id_into_branch() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    let xdg = temp.child("xdg");
+    write_resolver_config(&xdg, r#"printf '{"title":"Short title"}'"#);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "ENG-1423"])
+        .current_dir(repo_dir.path())
+        .env("XDG_CONFIG_HOME", xdg.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("eng-1423-short-title"))
+        .stderr(predicate::str::contains("Resolved ENG-1423: Short title"));
+
+    assert!(temp
+        .path()
+        .join("test-repo-worktrees/eng-1423-short-title")
+        .exists());
+
+    // Non-matching input is used literally without running the resolver
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "feature-plain"])
+        .current_dir(repo_dir.path())
+        .env("XDG_CONFIG_HOME", xdg.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Resolved").not());
+
+    assert!(temp
+        .path()
+        .join("test-repo-worktrees/feature-plain")
+        .exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_add_falls_back_to_literal_when_resolver_fails() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    let xdg = temp.child("xdg");
+    write_resolver_config(&xdg, "echo 'not logged in' >&2; exit 1");
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "ENG-9"])
+        .current_dir(repo_dir.path())
+        .env("XDG_CONFIG_HOME", xdg.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Could not resolve ENG-9"))
+        .stderr(predicate::str::contains("not logged in"));
+
+    assert!(temp.path().join("test-repo-worktrees/ENG-9").exists());
+
+    temp.close().unwrap();
+}
@@ -0,0 +1,173 @@
+#![allow(deprecated)]
+#![cfg(unix)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use ofsht::report;
+use predicates::prelude::*;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::Command;
+
+fn create_worktrees(repo_dir: &Path, prefix: &str, count: usize) {
+    for i in 0..count {
+        Command::cargo_bin("ofsht")
+            .unwrap()
+            .args(["create", &format!("{prefix}-{i}")])
+            .current_dir(repo_dir)
+            .assert()
+            .success();
+    }
+}
+
+/// Install a `git` shim that appends one line per invocation to `log`
+/// before delegating to the real git
+fn install_git_counter(bin_dir: &Path, log: &Path) -> String {
+    let real_git = Command::new("sh")
+        .args(["-c", "command -v git"])
+        .output()
+        .unwrap();
+    let real_git = String::from_utf8(real_git.stdout).unwrap();
+    std::fs::create_dir_all(bin_dir).unwrap();
+    let shim = bin_dir.join("git");
+    std::fs::write(
+        &shim,
+        format!(
+            "#!/bin/sh\necho \"$*\" >> '{}'\nexec '{}' \"$@\"\n",
+            log.display(),
+            real_git.trim()
+        ),
+    )
+    .unwrap();
+    std::fs::set_permissions(&shim, std::fs::Permissions::from_mode(0o755)).unwrap();
+    format!(
+        "{}:{}",
+        bin_dir.display(),
+        std::env::var("PATH").unwrap_or_default()
+    )
+}
+
+fn count_lines(path: &Path) -> usize {
+    std::fs::read_to_string(path).map_or(0, |s| s.lines().count())
+}
+
+#[test]
+fn test_rm_assume_clean_spawns_fewer_git_processes() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    create_worktrees(repo_dir.path(), "slow", 10);
+    create_worktrees(repo_dir.path(), "fast", 10);
+
+    let slow_log = temp.path().join("slow.log");
+    let fast_log = temp.path().join("fast.log");
+    let path = install_git_counter(&temp.path().join("bin"), &slow_log);
+    let slow_targets: Vec<String> = (0..10).map(|i| format!("slow-{i}")).collect();
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .arg("rm")
+        .args(&slow_targets)
+        .env("PATH", &path)
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    let path = install_git_counter(&temp.path().join("bin"), &fast_log);
+    let fast_targets: Vec<String> = (0..10).map(|i| format!("fast-{i}")).collect();
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "--assume-clean"])
+        .args(&fast_targets)
+        .env("PATH", &path)
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Removed 10 worktree(s), deleted 10 branch(es)",
+        ))
+        .stderr(predicate::str::contains(report::removed_worktree("fast-0")).not());
+
+    let slow = count_lines(&slow_log);
+    let fast = count_lines(&fast_log);
+    assert!(
+        fast * 3 < slow * 2,
+        "expected --assume-clean to spawn substantially fewer git processes (fast: {fast}, default: {slow})"
+    );
+
+    for i in 0..10 {
+        assert!(!temp
+            .path()
+            .join(format!("test-repo-worktrees/fast-{i}"))
+            .exists());
+        Command::new("git")
+            .args(["show-ref", "--verify", &format!("refs/heads/fast-{i}")])
+            .current_dir(repo_dir.path())
+            .assert()
+            .failure();
+    }
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_rm_assume_clean_refuses_current_worktree() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    create_worktrees(repo_dir.path(), "feature", 2);
+
+    let current = temp.path().join("test-repo-worktrees/feature-0");
+
+    for target in [".", "feature-0"] {
+        Command::cargo_bin("ofsht")
+            .unwrap()
+            .args(["rm", "--assume-clean", "feature-1", target])
+            .current_dir(&current)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "--assume-clean cannot remove the current worktree",
+            ));
+    }
+
+    // Refused before anything was removed
+    assert!(current.exists());
+    assert!(temp.path().join("test-repo-worktrees/feature-1").exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_rm_assume_clean_keep_branch() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    create_worktrees(repo_dir.path(), "feature", 2);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args([
+            "rm",
+            "--assume-clean",
+            "--keep-branch",
+            "feature-0",
+            "feature-1",
+        ])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Removed 2 worktree(s), deleted 0 branch(es)",
+        ));
+
+    Command::new("git")
+        .args(["show-ref", "--verify", "refs/heads/feature-0"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    temp.close().unwrap();
+}
@@ -2,6 +2,7 @@
 
 use assert_cmd::prelude::*;
 use assert_fs::prelude::*;
+use ofsht::report;
 use predicates::prelude::*;
 use std::process::Command;
 
@@ -56,7 +57,9 @@ fn test_rm_current_from_worktree() {
         .assert()
         .success()
         .stdout(predicate::str::contains(repo_dir.path().to_str().unwrap()))
-        .stderr(predicate::str::contains("Removed feature-test"));
+        .stderr(predicate::str::contains(report::removed_worktree(
+            "feature-test",
+        )));
 
     // Verify worktree was removed
     assert!(!worktree_path.exists());
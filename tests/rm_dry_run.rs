@@ -0,0 +1,113 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use ofsht::commands::list_display::format_size;
+use ofsht::domain::worktree::directory_size;
+use predicates::prelude::*;
+use std::process::Command;
+
+/// Create worktrees `small` and `padded`, the latter with a 3 MiB file
+fn create_worktrees(temp: &assert_fs::TempDir) -> assert_fs::fixture::ChildPath {
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    for branch in ["small", "padded"] {
+        Command::cargo_bin("ofsht")
+            .unwrap()
+            .args(["create", branch])
+            .current_dir(repo_dir.path())
+            .assert()
+            .success();
+    }
+    std::fs::write(
+        temp.path().join("test-repo-worktrees/padded/blob.bin"),
+        vec![0u8; 3 * 1024 * 1024],
+    )
+    .unwrap();
+    repo_dir
+}
+
+#[test]
+fn test_rm_dry_run_stat_reports_largest_first_and_total() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = create_worktrees(&temp);
+    let worktrees = temp.path().join("test-repo-worktrees");
+
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "--dry-run", "--stat", "small", "padded"])
+        .current_dir(repo_dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let lines: Vec<&str> = stderr.lines().collect();
+
+    assert_eq!(lines.len(), 3, "{stderr}");
+    assert!(
+        lines[0].trim_start().starts_with("3.0 MB  padded"),
+        "{stderr}"
+    );
+    assert!(lines[1].ends_with("  small"), "{stderr}");
+    let total =
+        directory_size(&worktrees.join("small")) + directory_size(&worktrees.join("padded"));
+    assert_eq!(
+        lines[2],
+        format!("Would reclaim ~{} across 2 worktrees", format_size(total))
+    );
+
+    // Nothing was removed
+    assert!(worktrees.join("small").exists());
+    assert!(worktrees.join("padded").exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_rm_dry_run_marks_missing_directories() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = create_worktrees(&temp);
+    std::fs::remove_dir_all(temp.path().join("test-repo-worktrees/small")).unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "--dry-run", "--stat", "small", "padded"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("0 B  small (missing)"));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_rm_dry_run_lists_targets_without_removing() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = create_worktrees(&temp);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "--dry-run", "small"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("Would remove small"))
+        .stderr(predicate::str::contains("Would reclaim").not());
+    assert!(temp.path().join("test-repo-worktrees/small").exists());
+
+    // --stat only makes sense for a dry run
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "--stat", "small"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure();
+    assert!(temp.path().join("test-repo-worktrees/small").exists());
+
+    temp.close().unwrap();
+}
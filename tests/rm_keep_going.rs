@@ -0,0 +1,105 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use ofsht::report;
+use predicates::prelude::*;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Create worktrees `a` and `b`, then break `a` so git can no longer remove it
+fn create_broken_and_healthy(temp: &assert_fs::TempDir) -> (PathBuf, PathBuf) {
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    for branch in ["a", "b"] {
+        Command::cargo_bin("ofsht")
+            .unwrap()
+            .args(["create", branch])
+            .current_dir(repo_dir.path())
+            .assert()
+            .success();
+    }
+    let broken = temp.path().join("test-repo-worktrees/a");
+    let healthy = temp.path().join("test-repo-worktrees/b");
+    std::fs::write(broken.join(".git"), "gitdir: /nonexistent/worktree\n").unwrap();
+    (broken, healthy)
+}
+
+#[test]
+fn test_rm_keep_going_continues_past_failure() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let (broken, healthy) = create_broken_and_healthy(&temp);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "--keep-going", "a", "b"])
+        .current_dir(temp.child("test-repo").path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Failed to remove a:"))
+        .stderr(predicate::str::contains(report::removed_worktree("b")))
+        .stderr(predicate::str::contains("Removed 1, failed 1"))
+        .stderr(predicate::str::contains(
+            "1 worktree(s) could not be removed",
+        ));
+
+    assert!(broken.exists());
+    assert!(!healthy.exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_rm_without_keep_going_lists_unprocessed_targets() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let (broken, healthy) = create_broken_and_healthy(&temp);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "a", "b"])
+        .current_dir(temp.child("test-repo").path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Not processed: b"))
+        .stderr(predicate::str::contains(report::removed_worktree("b")).not())
+        .stderr(predicate::str::contains("Removed 1").not());
+
+    assert!(broken.exists());
+    assert!(healthy.exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_rm_keep_going_counts_dirty_worktrees_as_skipped() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    for branch in ["a", "b"] {
+        Command::cargo_bin("ofsht")
+            .unwrap()
+            .args(["create", branch])
+            .current_dir(repo_dir.path())
+            .assert()
+            .success();
+    }
+    let dirty = temp.path().join("test-repo-worktrees/a");
+    let clean = temp.path().join("test-repo-worktrees/b");
+    std::fs::write(dirty.join("scratch.txt"), "wip").unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "--keep-going", "a", "b"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Removed 1, skipped 1, failed 0"));
+
+    assert!(dirty.exists());
+    assert!(!clean.exists());
+
+    temp.close().unwrap();
+}
@@ -2,6 +2,7 @@
 
 use assert_cmd::prelude::*;
 use assert_fs::prelude::*;
+use ofsht::report;
 use predicates::prelude::*;
 use std::process::Command;
 
@@ -65,7 +66,9 @@ fn test_rm_multiple_branches() {
         .current_dir(repo_dir.path())
         .assert()
         .success()
-        .stderr(predicate::str::contains("Removed feature-").count(2));
+        .stderr(
+            predicate::str::contains(format!("{} feature-", report::REMOVED_WORKTREE)).count(2),
+        );
 
     // Verify both worktrees were removed
     assert!(!worktree_a_path.exists());
@@ -149,7 +152,9 @@ fn test_rm_multiple_branches_from_worktree() {
         .current_dir(&worktree_a_path)
         .assert()
         .success()
-        .stderr(predicate::str::contains("Removed feature-").count(2));
+        .stderr(
+            predicate::str::contains(format!("{} feature-", report::REMOVED_WORKTREE)).count(2),
+        );
 
     // Verify both worktrees were removed
     assert!(!worktree_a_path.exists());
@@ -226,7 +231,7 @@ fn test_rm_duplicate_targets() {
         .assert()
         .success()
         // Should only remove once
-        .stderr(predicate::str::contains("Removed feature-a").count(1))
+        .stderr(predicate::str::contains(report::removed_worktree("feature-a")).count(1))
         // Should warn about duplicate
         .stderr(predicate::str::contains("Duplicate").or(predicate::str::contains("already")));
 
@@ -306,7 +311,9 @@ fn test_rm_current_with_others() {
         .assert()
         .success()
         .stdout(predicate::str::contains(repo_dir.path().to_str().unwrap()))
-        .stderr(predicate::str::contains("Removed feature-").count(2));
+        .stderr(
+            predicate::str::contains(format!("{} feature-", report::REMOVED_WORKTREE)).count(2),
+        );
 
     // Verify all worktrees were removed
     assert!(!worktree_a_path.exists());
@@ -388,7 +395,7 @@ fn test_rm_alias_and_dot() {
         // Should warn about duplicate
         .stderr(predicate::str::contains("Duplicate"))
         // Should only remove once
-        .stderr(predicate::str::contains("Removed feature-a").count(1));
+        .stderr(predicate::str::contains(report::removed_worktree("feature-a")).count(1));
 
     // Verify worktree was removed
     assert!(!worktree_a_path.exists());
@@ -543,7 +550,9 @@ fn test_rm_prunable_worktree_by_branch_name() {
         .current_dir(repo_dir.path())
         .assert()
         .success()
-        .stderr(predicate::str::contains("Removed test-prunable"));
+        .stderr(predicate::str::contains(report::removed_worktree(
+            "test-prunable",
+        )));
 
     // Verify worktree list no longer contains the entry
     let git_output = Command::new("git")
@@ -632,7 +641,9 @@ fn test_rm_prunable_worktree_by_absolute_path() {
         .current_dir(repo_dir.path())
         .assert()
         .success()
-        .stderr(predicate::str::contains("Removed test-prunable-path"));
+        .stderr(predicate::str::contains(report::removed_worktree(
+            "test-prunable-path",
+        )));
 
     // Verify worktree list no longer contains the entry
     let git_output = Command::new("git")
@@ -720,7 +731,9 @@ fn test_rm_prunable_worktree_by_relative_path() {
         .current_dir(repo_dir.path())
         .assert()
         .success()
-        .stderr(predicate::str::contains("Removed test-prunable-rel"));
+        .stderr(predicate::str::contains(report::removed_worktree(
+            "test-prunable-rel",
+        )));
 
     // Verify worktree list no longer contains the entry
     let git_output = Command::new("git")
@@ -742,3 +755,83 @@ fn test_rm_prunable_worktree_by_relative_path() {
 
     temp.close().unwrap();
 }
+
+/// Create a worktree under a config with a delete hook, then delete its directory
+fn setup_prunable_with_delete_hook(
+    temp: &assert_fs::TempDir,
+    run_when_missing: bool,
+) -> assert_fs::fixture::ChildPath {
+    let repo_dir = temp.child("test-repo");
+    repo_dir.create_dir_all().unwrap();
+    for args in [
+        &["init"][..],
+        &["config", "user.name", "Test User"],
+        &["config", "user.email", "test@example.com"],
+        &["commit", "--allow-empty", "-m", "Initial commit"],
+    ] {
+        Command::new("git")
+            .args(args)
+            .current_dir(repo_dir.path())
+            .output()
+            .unwrap();
+    }
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str(&format!(
+            "[hooks]\nrun_delete_hooks_in_repo_root_when_missing = {run_when_missing}\n\n\
+             [hooks.delete]\nrun = [\"printf '%s' \\\"$OFSHT_WORKTREE_PATH\\\" > delete-marker\"]\n\
+             copy = [\".env\"]\n"
+        ))
+        .unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "test-prunable"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+    std::fs::remove_dir_all(temp.path().join("test-repo-worktrees/test-prunable")).unwrap();
+    repo_dir
+}
+
+#[test]
+fn test_rm_prunable_worktree_warns_that_delete_hooks_were_skipped() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = setup_prunable_with_delete_hook(&temp, false);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "test-prunable"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Skipped delete hooks: worktree directory is missing",
+        ));
+
+    assert!(!repo_dir.path().join("delete-marker").exists());
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_rm_prunable_worktree_runs_delete_hooks_in_repo_root() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = setup_prunable_with_delete_hook(&temp, true);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "test-prunable"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Skipped delete hook copy/link actions",
+        ));
+
+    let marker = std::fs::read_to_string(repo_dir.path().join("delete-marker")).unwrap();
+    assert!(
+        marker.ends_with("test-repo-worktrees/test-prunable"),
+        "{marker}"
+    );
+    temp.close().unwrap();
+}
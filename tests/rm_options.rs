@@ -0,0 +1,199 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use ofsht::report;
+use predicates::prelude::*;
+use std::process::Command;
+
+#[test]
+fn test_rm_keep_branch_preserves_branch() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature-keep"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    let worktree_path = temp.path().join("test-repo-worktrees/feature-keep");
+    assert!(worktree_path.exists());
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "--keep-branch", "feature-keep"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(report::removed_worktree(
+            "feature-keep",
+        )))
+        .stderr(predicate::str::contains(report::DELETED_BRANCH).not());
+
+    // Worktree is gone, branch is still there
+    assert!(!worktree_path.exists());
+    Command::new("git")
+        .args(["show-ref", "--verify", "refs/heads/feature-keep"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_rm_refuses_dirty_worktree_without_force() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    for branch in ["feature-dirty", "feature-clean"] {
+        Command::cargo_bin("ofsht")
+            .unwrap()
+            .args(["create", branch])
+            .current_dir(repo_dir.path())
+            .assert()
+            .success();
+    }
+
+    let dirty_path = temp.path().join("test-repo-worktrees/feature-dirty");
+    let clean_path = temp.path().join("test-repo-worktrees/feature-clean");
+    std::fs::write(dirty_path.join("scratch.txt"), "wip").unwrap();
+
+    // Dirty target is skipped, clean target is still removed, exit is non-zero
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "feature-dirty", "feature-clean"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Skipped feature-dirty: 1 uncommitted file",
+        ))
+        .stderr(predicate::str::contains(report::removed_worktree(
+            "feature-clean",
+        )))
+        .stderr(predicate::str::contains("--force"));
+
+    assert!(dirty_path.exists());
+    assert!(!clean_path.exists());
+
+    // --force removes it anyway
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "--force", "feature-dirty"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(report::removed_worktree(
+            "feature-dirty",
+        )));
+
+    assert!(!dirty_path.exists());
+
+    temp.close().unwrap();
+}
+
+/// Create `feature` with ofsht and force a second worktree onto the same branch,
+/// so the branch cannot be deleted after the first worktree is removed
+fn create_shared_branch_worktrees(
+    temp: &assert_fs::TempDir,
+    repo_dir: &assert_fs::fixture::ChildPath,
+) -> std::path::PathBuf {
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+    Command::new("git")
+        .args(["worktree", "add", "--force", "../shared", "feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+    temp.path().join("test-repo-worktrees/feature")
+}
+
+#[test]
+fn test_rm_warns_when_branch_is_checked_out_elsewhere() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    let worktree_path = create_shared_branch_worktrees(&temp, &repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .arg("rm")
+        .arg(&worktree_path)
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(format!(
+            "{} feature",
+            report::FAILED_TO_DELETE_BRANCH
+        )))
+        .stderr(predicate::str::contains("shared"))
+        .stderr(predicate::str::contains(report::DELETED_BRANCH).not());
+
+    assert!(!worktree_path.exists());
+}
+
+#[test]
+fn test_rm_assume_clean_summary_counts_branch_failures() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    let worktree_path = create_shared_branch_worktrees(&temp, &repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "--assume-clean"])
+        .arg(&worktree_path)
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(format!(
+            "{} feature",
+            report::FAILED_TO_DELETE_BRANCH
+        )))
+        .stderr(predicate::str::contains(
+            "deleted 0 branch(es), 1 branch(es) could not be deleted",
+        ));
+}
+
+#[test]
+fn test_rm_missing_branch_stays_quiet() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+    // Drop the branch ref behind ofsht's back; the worktree still names it
+    Command::new("git")
+        .args(["update-ref", "-d", "refs/heads/feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(report::removed_worktree(
+            "feature",
+        )))
+        .stderr(predicate::str::contains(report::FAILED_TO_DELETE_BRANCH).not());
+}
@@ -0,0 +1,158 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use predicates::prelude::*;
+use std::path::Path;
+use std::process::Command;
+
+fn git(repo_dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(args)
+        .current_dir(repo_dir)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {args:?} failed");
+}
+
+fn ref_exists(repo_dir: &Path, refname: &str) -> bool {
+    Command::new("git")
+        .args(["rev-parse", "--verify", "--quiet", refname])
+        .current_dir(repo_dir)
+        .status()
+        .unwrap()
+        .success()
+}
+
+/// Metadata `ofsht add '#<number>'` records for a fork PR worktree
+fn pr_meta(number: u32) -> String {
+    format!(
+        "kind = \"pr\"\n\
+         number = {number}\n\
+         title = \"PR {number}\"\n\
+         url = \"https://github.com/owner/repo/pull/{number}\"\n"
+    )
+}
+
+/// Repository with worktrees `pr-1` and `pr-2` created from fork PRs #1 and #2
+/// (their heads fetched into `refs/ofsht/pr/<N>`) plus unrelated refs
+fn setup(prune_remotes: bool) -> (assert_fs::TempDir, std::path::PathBuf) {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    repo_dir
+        .child(".ofsht.toml")
+        .write_str(&format!("[rm]\nprune_remotes = {prune_remotes}\n"))
+        .unwrap();
+
+    let repo = repo_dir.path().to_path_buf();
+    git(&repo, &["update-ref", "refs/ofsht/pr/1", "HEAD"]);
+    git(&repo, &["update-ref", "refs/ofsht/pr/2", "HEAD"]);
+    git(&repo, &["update-ref", "refs/ofsht/other", "HEAD"]);
+    git(&repo, &["tag", "v1"]);
+    git(&repo, &["branch", "keep-me"]);
+
+    for number in [1, 2] {
+        let branch = format!("pr-{number}");
+        Command::cargo_bin("ofsht")
+            .unwrap()
+            .args(["create", &branch])
+            .current_dir(&repo)
+            .assert()
+            .success();
+        let meta_file = repo.join(format!(".git/worktrees/{branch}/ofsht.toml"));
+        std::fs::write(meta_file, pr_meta(number)).unwrap();
+    }
+    (temp, repo)
+}
+
+#[test]
+fn test_rm_prune_remotes_deletes_only_ofsht_refs() {
+    let (_temp, repo) = setup(true);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "pr-1", "pr-2"])
+        .current_dir(&repo)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Deleted 2 temporary PR ref(s) (#1, #2)",
+        ));
+
+    assert!(!ref_exists(&repo, "refs/ofsht/pr/1"));
+    assert!(!ref_exists(&repo, "refs/ofsht/pr/2"));
+    assert!(ref_exists(&repo, "refs/ofsht/other"));
+    assert!(ref_exists(&repo, "refs/tags/v1"));
+    assert!(ref_exists(&repo, "refs/heads/keep-me"));
+}
+
+#[test]
+fn test_rm_prune_remotes_keeps_refs_of_remaining_prs() {
+    let (_temp, repo) = setup(true);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "pr-1"])
+        .current_dir(&repo)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Deleted 1 temporary PR ref(s) (#1)",
+        ));
+
+    assert!(!ref_exists(&repo, "refs/ofsht/pr/1"));
+    // pr-2 still has a worktree that was checked out from this ref
+    assert!(ref_exists(&repo, "refs/ofsht/pr/2"));
+}
+
+#[test]
+fn test_rm_prune_remotes_keeps_refs_of_skipped_worktrees() {
+    let (_temp, repo) = setup(true);
+    let dirty = repo.join("../test-repo-worktrees/pr-2/wip.txt");
+    std::fs::write(dirty, "wip").unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "--keep-going", "pr-1", "pr-2"])
+        .current_dir(&repo)
+        .assert()
+        .failure();
+
+    assert!(!ref_exists(&repo, "refs/ofsht/pr/1"));
+    assert!(ref_exists(&repo, "refs/ofsht/pr/2"));
+}
+
+#[test]
+fn test_rm_prune_remotes_with_assume_clean() {
+    let (_temp, repo) = setup(true);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "--assume-clean", "pr-1"])
+        .current_dir(&repo)
+        .assert()
+        .success();
+
+    assert!(!ref_exists(&repo, "refs/ofsht/pr/1"));
+    assert!(ref_exists(&repo, "refs/ofsht/pr/2"));
+    assert!(ref_exists(&repo, "refs/heads/keep-me"));
+}
+
+#[test]
+fn test_rm_keeps_ofsht_refs_by_default() {
+    let (_temp, repo) = setup(false);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["rm", "pr-1"])
+        .current_dir(&repo)
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("temporary PR ref").not());
+
+    assert!(ref_exists(&repo, "refs/ofsht/pr/1"));
+}
@@ -0,0 +1,112 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use ofsht::path_utils::normalize_absolute_path;
+use predicates::prelude::*;
+use std::process::Command;
+
+fn root_stdout(dir: &std::path::Path, args: &[&str]) -> String {
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .arg("root")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_root_from_main_repo_and_worktree() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    let expected = format!(
+        "{}\n",
+        normalize_absolute_path(&repo_dir.path().canonicalize().unwrap())
+    );
+    assert_eq!(root_stdout(repo_dir.path(), &[]), expected);
+
+    let worktree = temp.path().join("test-repo-worktrees/feature");
+    assert_eq!(root_stdout(&worktree, &[]), expected);
+    // Subdirectories resolve the same way
+    std::fs::create_dir(worktree.join("src")).unwrap();
+    assert_eq!(root_stdout(&worktree.join("src"), &[]), expected);
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_root_worktrees_with_and_without_worktrees() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    let expected = format!(
+        "{}\n",
+        normalize_absolute_path(
+            &temp
+                .path()
+                .canonicalize()
+                .unwrap()
+                .join("test-repo-worktrees")
+        )
+    );
+
+    // No worktree yet: derived from `worktree.dir`, even though it does not exist
+    assert_eq!(root_stdout(repo_dir.path(), &["--worktrees"]), expected);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "team/feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+    let worktree = temp.path().join("test-repo-worktrees/team/feature");
+    assert_eq!(root_stdout(repo_dir.path(), &["--worktrees"]), expected);
+    assert_eq!(root_stdout(&worktree, &["--worktrees"]), expected);
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_root_outside_git_repository() {
+    let temp = assert_fs::TempDir::new().unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .arg("root")
+        .current_dir(temp.path())
+        .env("GIT_CEILING_DIRECTORIES", temp.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("Not in a git repository"));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_shell_wrapper_does_not_cd_on_root() {
+    for shell in ["bash", "zsh", "fish"] {
+        let output = Command::cargo_bin("ofsht")
+            .unwrap()
+            .args(["shell-init", shell])
+            .output()
+            .unwrap();
+        let script = String::from_utf8(output.stdout).unwrap();
+        assert!(script.contains("\"switch\""), "{shell}: {script}");
+        assert!(!script.contains("\"root\""), "{shell}: {script}");
+    }
+}
@@ -0,0 +1,127 @@
+#![cfg(unix)]
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use predicates::prelude::*;
+use std::process::Command;
+
+fn create_worktree(repo_dir: &assert_fs::fixture::ChildPath, branch: &str) {
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", branch])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_shell_runs_in_worktree_and_propagates_exit_code() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    create_worktree(&repo_dir, "feature-x");
+
+    let worktree = temp
+        .path()
+        .join("test-repo-worktrees/feature-x")
+        .canonicalize()
+        .unwrap();
+
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["shell", "feature-x", "--shell", "sh -c 'pwd -P; exit 7'"])
+        .current_dir(repo_dir.path())
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(7));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.trim(), worktree.to_str().unwrap());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_shell_exports_worktree_env() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    create_worktree(&repo_dir, "feature-x");
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args([
+            "shell",
+            "feature-x",
+            "--shell",
+            "echo \"$OFSHT_BRANCH $OFSHT_WORKTREE_PATH\"",
+        ])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("feature-x "))
+        .stdout(predicate::str::contains("test-repo-worktrees/feature-x"));
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_shell_uses_shell_env_and_prints_nothing_itself() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    create_worktree(&repo_dir, "feature-x");
+
+    // `true` stands in for an interactive shell that exits immediately
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["shell", "feature-x"])
+        .env("SHELL", "true")
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_shell_unknown_target_fails() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["shell", "missing", "--shell", "true"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .failure();
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_shell_tmux_respects_no_tmux_env() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+    create_worktree(&repo_dir, "feature-x");
+
+    // With tmux disabled, --tmux is ignored and the nested shell starts instead
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["shell", "feature-x", "--tmux", "--shell", "echo nested"])
+        .env("OFSHT_NO_TMUX", "1")
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stdout("nested\n")
+        .stderr(predicate::str::contains("Ignoring --tmux"));
+
+    temp.close().unwrap();
+}
@@ -0,0 +1,169 @@
+#![allow(deprecated)]
+#![cfg(unix)]
+
+//! Behavior of the `ofsht shell-init bash` wrapper, sourced into a real bash
+//! with the freshly built binary first on `PATH`
+
+mod common;
+
+use assert_fs::prelude::*;
+use common::init_repo_on_main;
+use std::process::Command;
+
+/// Run `script` in bash with the wrapper loaded, from `dir`; returns
+/// (exit status, stdout)
+fn run_wrapped(dir: &std::path::Path, script: &str) -> (i32, String) {
+    let ofsht = assert_cmd::cargo::cargo_bin("ofsht");
+    let path = format!(
+        "{}:{}",
+        ofsht.parent().unwrap().display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    let output = Command::new("bash")
+        .args([
+            "--norc",
+            "--noprofile",
+            "-c",
+            &format!("eval \"$(ofsht shell-init bash)\"\n{script}"),
+        ])
+        .current_dir(dir)
+        .env("PATH", path)
+        .env("OFSHT_NO_INTEGRATIONS", "1")
+        .output()
+        .unwrap();
+    (
+        output.status.code().unwrap_or(-1),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+#[test]
+fn test_wrapper_cds_into_added_worktree() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_on_main(&repo_dir);
+
+    let (code, stdout) = run_wrapped(repo_dir.path(), "ofsht add feature && pwd");
+    assert_eq!(code, 0, "{stdout}");
+    assert!(
+        stdout.trim_end().ends_with("test-repo-worktrees/feature"),
+        "{stdout}"
+    );
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_wrapper_passes_porcelain_output_through() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_on_main(&repo_dir);
+
+    let (code, stdout) = run_wrapped(repo_dir.path(), "ofsht add --porcelain feature && pwd");
+    assert_eq!(code, 0, "{stdout}");
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(
+        lines.iter().any(|line| line.starts_with("path=")),
+        "{stdout}"
+    );
+    assert!(lines.contains(&"branch=feature"), "{stdout}");
+    // Still in the main repository
+    assert!(lines.last().unwrap().ends_with("test-repo"), "{stdout}");
+
+    temp.close().unwrap();
+}
+
+fn git(dir: &std::path::Path, args: &[&str]) {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {args:?} failed: {output:?}");
+}
+
+#[test]
+fn test_wrapper_cds_into_worktree_with_carry_conflict() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_on_main(&repo_dir);
+    repo_dir.child("a.txt").write_str("base\n").unwrap();
+    git(repo_dir.path(), &["add", "a.txt"]);
+    git(repo_dir.path(), &["commit", "-qm", "Add a.txt"]);
+    git(repo_dir.path(), &["branch", "other"]);
+    git(repo_dir.path(), &["checkout", "-q", "other"]);
+    repo_dir.child("a.txt").write_str("other\n").unwrap();
+    git(repo_dir.path(), &["commit", "-qam", "Other"]);
+    git(repo_dir.path(), &["checkout", "-q", "main"]);
+    repo_dir.child("a.txt").write_str("mine\n").unwrap();
+
+    let (code, stdout) = run_wrapped(
+        repo_dir.path(),
+        "ofsht add feature other --carry-changes\ncode=$?\npwd\nexit $code",
+    );
+    assert_eq!(code, 3, "{stdout}");
+    assert!(
+        stdout.trim_end().ends_with("test-repo-worktrees/feature"),
+        "{stdout}"
+    );
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_wrapper_cds_into_worktree_with_merge_conflict() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_on_main(&repo_dir);
+    repo_dir.child("a.txt").write_str("base\n").unwrap();
+    git(repo_dir.path(), &["add", "a.txt"]);
+    git(repo_dir.path(), &["commit", "-qm", "Add a.txt"]);
+    git(repo_dir.path(), &["checkout", "-q", "-b", "other"]);
+    repo_dir.child("a.txt").write_str("other\n").unwrap();
+    git(repo_dir.path(), &["commit", "-qam", "Other"]);
+    git(repo_dir.path(), &["checkout", "-q", "main"]);
+    repo_dir.child("a.txt").write_str("mine\n").unwrap();
+    git(repo_dir.path(), &["commit", "-qam", "Mine"]);
+
+    let (code, stdout) = run_wrapped(
+        repo_dir.path(),
+        "ofsht add combined --merge main other\ncode=$?\npwd\nexit $code",
+    );
+    assert_eq!(code, 4, "{stdout}");
+    assert!(
+        stdout.trim_end().ends_with("test-repo-worktrees/combined"),
+        "{stdout}"
+    );
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_wrapper_skips_leading_global_options() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_on_main(&repo_dir);
+    let elsewhere = temp.child("elsewhere");
+    elsewhere.create_dir_all().unwrap();
+    let repo = repo_dir.path().display();
+
+    for (prefix, branch) in [
+        (format!("-C '{repo}'"), "feature-c"),
+        (format!("--repo '{repo}' --color never -q"), "feature-repo"),
+        (format!("--color=never -C '{repo}'"), "feature-eq"),
+    ] {
+        let (code, stdout) = run_wrapped(
+            elsewhere.path(),
+            &format!("ofsht {prefix} add {branch} >/dev/null 2>&1 && cd / && ofsht {prefix} cd {branch} && pwd"),
+        );
+        assert_eq!(code, 0, "{prefix}: {stdout}");
+        assert!(
+            stdout
+                .trim_end()
+                .ends_with(&format!("test-repo-worktrees/{branch}")),
+            "{prefix}: {stdout}"
+        );
+    }
+
+    temp.close().unwrap();
+}
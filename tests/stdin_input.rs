@@ -1,33 +1,11 @@
 #![allow(deprecated)]
 
+mod common;
+
 use assert_fs::prelude::*;
+use common::init_repo;
 use std::process::{Command, Stdio};
 
-/// Initialize a git repository with a single empty commit.
-fn init_repo(repo_dir: &assert_fs::fixture::ChildPath) {
-    repo_dir.create_dir_all().unwrap();
-    Command::new("git")
-        .args(["init"])
-        .current_dir(repo_dir.path())
-        .output()
-        .unwrap();
-    Command::new("git")
-        .args(["config", "user.name", "Test User"])
-        .current_dir(repo_dir.path())
-        .output()
-        .unwrap();
-    Command::new("git")
-        .args(["config", "user.email", "test@example.com"])
-        .current_dir(repo_dir.path())
-        .output()
-        .unwrap();
-    Command::new("git")
-        .args(["commit", "--allow-empty", "-m", "Initial commit"])
-        .current_dir(repo_dir.path())
-        .output()
-        .unwrap();
-}
-
 #[test]
 fn add_reads_branch_from_piped_stdin() {
     let temp = assert_fs::TempDir::new().unwrap();
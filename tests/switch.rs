@@ -0,0 +1,55 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use predicates::prelude::*;
+use std::process::Command;
+
+#[test]
+fn test_switch_creates_missing_worktree() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["switch", "feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::ends_with("test-repo-worktrees/feature\n"))
+        .stderr(predicate::str::contains("Created worktree feature"));
+
+    assert!(temp.path().join("test-repo-worktrees/feature").exists());
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_switch_goes_to_existing_worktree() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["switch", "feature", "HEAD"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::ends_with("test-repo-worktrees/feature\n"))
+        .stderr(predicate::str::contains("Created worktree").not())
+        .stderr(predicate::str::contains("Ignoring start point 'HEAD'"));
+
+    temp.close().unwrap();
+}
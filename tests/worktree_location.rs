@@ -0,0 +1,63 @@
+#![allow(deprecated)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo_with_config;
+use predicates::prelude::*;
+use std::process::Command;
+
+#[test]
+fn test_create_warns_when_worktree_is_inside_repo() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_config(&repo_dir, "[worktree]\ndir = \"worktrees/{branch}\"\n");
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("inside the main repository"))
+        .stderr(predicate::str::contains("Warning:").not())
+        .stderr(predicate::str::contains("allow_inside_repo"));
+
+    repo_dir
+        .child("worktrees/feature")
+        .assert(predicate::path::is_dir());
+}
+
+#[test]
+fn test_allow_inside_repo_silences_warning() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_config(
+        &repo_dir,
+        "[worktree]\ndir = \"worktrees/{branch}\"\nallow_inside_repo = true\n",
+    );
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["add", "feature", "--no-tmux"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("inside the main repository").not());
+}
+
+#[test]
+fn test_default_template_does_not_warn() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo_with_config(&repo_dir, "");
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["create", "feature"])
+        .current_dir(repo_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("inside the main repository").not());
+}
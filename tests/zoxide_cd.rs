@@ -0,0 +1,99 @@
+#![allow(deprecated)]
+#![cfg(unix)]
+
+mod common;
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use common::init_repo;
+use std::os::unix::fs::PermissionsExt;
+use std::process::Command;
+
+/// Install a stub `zoxide` that appends each `add` invocation to `log`
+fn install_zoxide_stub(bin_dir: &assert_fs::fixture::ChildPath, log: &std::path::Path) {
+    bin_dir.create_dir_all().unwrap();
+    let stub = bin_dir.child("zoxide");
+    stub.write_str(&format!(
+        "#!/bin/sh\nif [ \"$1\" = \"add\" ]; then echo \"$2\" >> '{}'; fi\nexit 0\n",
+        log.display()
+    ))
+    .unwrap();
+    std::fs::set_permissions(stub.path(), std::fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+fn path_with(bin_dir: &std::path::Path) -> String {
+    format!(
+        "{}:{}",
+        bin_dir.display(),
+        std::env::var("PATH").unwrap_or_default()
+    )
+}
+
+#[test]
+fn test_cd_records_visit_in_zoxide() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    let bin_dir = temp.child("bin");
+    let log = temp.path().join("zoxide.log");
+    install_zoxide_stub(&bin_dir, &log);
+    let xdg = temp.child("xdg");
+    xdg.create_dir_all().unwrap();
+
+    Command::new("git")
+        .args([
+            "worktree",
+            "add",
+            "-b",
+            "feature-z",
+            "../test-repo-worktrees/feature-z",
+        ])
+        .current_dir(repo_dir.path())
+        .output()
+        .unwrap();
+
+    let output = Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["cd", "feature-z"])
+        .current_dir(repo_dir.path())
+        .env("PATH", path_with(bin_dir.path()))
+        .env("XDG_CONFIG_HOME", xdg.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let printed = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let recorded = std::fs::read_to_string(&log).unwrap();
+    assert_eq!(recorded.trim(), printed);
+
+    temp.close().unwrap();
+}
+
+#[test]
+fn test_cd_skips_zoxide_when_disabled() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let repo_dir = temp.child("test-repo");
+    init_repo(&repo_dir);
+
+    let bin_dir = temp.child("bin");
+    let log = temp.path().join("zoxide.log");
+    install_zoxide_stub(&bin_dir, &log);
+    let xdg = temp.child("xdg");
+    xdg.child("ofsht/config.toml")
+        .write_str("[integration.zoxide]\nenabled = false\n")
+        .unwrap();
+
+    Command::cargo_bin("ofsht")
+        .unwrap()
+        .args(["cd", "@"])
+        .current_dir(repo_dir.path())
+        .env("PATH", path_with(bin_dir.path()))
+        .env("XDG_CONFIG_HOME", xdg.path())
+        .assert()
+        .success();
+
+    assert!(!log.exists());
+
+    temp.close().unwrap();
+}